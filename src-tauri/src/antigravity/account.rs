@@ -1,14 +1,196 @@
 use base64::Engine;
 use prost::Message;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// 会话状态中可修改的字段（为 `None` 的字段保持不变）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct SessionPatch {
+    pub access_token: Option<String>,
+    pub id_token: Option<String>,
+    pub expiry_timestamp: Option<i64>,
+    pub email: Option<String>,
+    pub plan_name: Option<String>,
+    pub plan_slug: Option<String>,
+}
+
+/// 账户可用模型
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct AvailableModels {
+    /// 账户（当前登录账户为邮箱，备份为备份名）
+    pub account: String,
+    /// 是否为当前登录的账户
+    pub current: bool,
+    pub plan_name: String,
+    /// `context.models.items` 中的模型名
+    pub models: Vec<String>,
+    /// `context.models.recommended` 中的推荐模型名
+    pub recommended: Vec<String>,
+    /// 解码失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 从登录状态中提取可用模型与推荐模型
+pub fn available_models(account: &str, current: bool, b64: &str) -> AvailableModels {
+    let mut report = AvailableModels {
+        account: account.to_string(),
+        current,
+        plan_name: String::new(),
+        models: Vec::new(),
+        recommended: Vec::new(),
+        error: None,
+    };
+
+    match decode_session_response(b64) {
+        Ok(msg) => {
+            if let Some(context) = msg.context {
+                report.plan_name = context.plan_name;
+                if let Some(models) = context.models {
+                    report.models = models.items.into_iter().map(|item| item.name).collect();
+                    report.recommended = models
+                        .recommended
+                        .map(|recommended| recommended.names)
+                        .unwrap_or_default();
+                }
+            }
+        }
+        Err(e) => report.error = Some(e),
+    }
+    report
+}
+
 /// 将 jetskiStateSync.agentManagerInitState 作为 SessionResponse proto 解码
 pub fn decode_jetski_state_proto(b64: &str) -> Result<Value, String> {
+    Ok(session_response_to_json(&decode_session_response(b64)?))
+}
+
+// SessionResponse 中可修改字段的路径（字段号）
+const ACCESS_TOKEN_PATH: &[u32] = &[6, 1];
+const ID_TOKEN_PATH: &[u32] = &[6, 3];
+const EXPIRY_TIMESTAMP_PATH: &[u32] = &[6, 4, 1];
+const PLAN_NAME_PATH: &[u32] = &[19, 3];
+const EMAIL_PATH: &[u32] = &[19, 7];
+const PLAN_SLUG_PATH: &[u32] = &[19, 36, 1];
+const PLAN_CONFIG_NAME_PATH: &[u32] = &[19, 36, 2];
+
+/// 修改会话状态中的指定字段并重新编码为 Base64
+///
+/// 直接在原始字节上修改，proto 定义之外的字段按原样保留
+pub fn patch_jetski_state_proto(b64: &str, patch: &SessionPatch) -> Result<String, String> {
+    use super::wire::{self, WireValue};
+
+    let mut bytes = session_bytes(b64)?;
+    // 先确认原始数据可以解码，避免在不认识的格式上修改
+    decode_session_bytes(&bytes)?;
+
+    let strings = [
+        (ACCESS_TOKEN_PATH, &patch.access_token),
+        (ID_TOKEN_PATH, &patch.id_token),
+        (EMAIL_PATH, &patch.email),
+        (PLAN_NAME_PATH, &patch.plan_name),
+        (PLAN_CONFIG_NAME_PATH, &patch.plan_name),
+        (PLAN_SLUG_PATH, &patch.plan_slug),
+    ];
+    for (path, value) in strings {
+        if let Some(value) = value {
+            bytes = wire::set(&bytes, path, &WireValue::Bytes(value.as_bytes()))?;
+        }
+    }
+    if let Some(expiry_timestamp) = patch.expiry_timestamp {
+        // int64 按补码编码为 varint
+        bytes = wire::set(
+            &bytes,
+            EXPIRY_TIMESTAMP_PATH,
+            &WireValue::Varint(expiry_timestamp as u64),
+        )?;
+    }
+
+    // 校验修改结果仍可解码且字段已生效
+    let msg = decode_session_bytes(&bytes)?;
+    let auth = msg.auth.unwrap_or_default();
+    let context = msg.context.unwrap_or_default();
+    let applied = patch
+        .access_token
+        .as_ref()
+        .is_none_or(|v| *v == auth.access_token)
+        && patch.id_token.as_ref().is_none_or(|v| *v == auth.id_token)
+        && patch
+            .expiry_timestamp
+            .is_none_or(|v| Some(v) == auth.meta.as_ref().map(|meta| meta.expiry_timestamp))
+        && patch.email.as_ref().is_none_or(|v| *v == context.email)
+        && patch
+            .plan_name
+            .as_ref()
+            .is_none_or(|v| *v == context.plan_name)
+        && patch
+            .plan_slug
+            .as_ref()
+            .is_none_or(|v| Some(v) == context.plan.as_ref().map(|plan| &plan.slug));
+    if !applied {
+        return Err("修改后的会话状态校验失败".to_string());
+    }
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// 令牌未携带到期时间时使用的有效期
+const DEFAULT_TOKEN_LIFETIME_SECS: i64 = 3600;
+
+/// 读取 JWT 载荷中的 `exp` 声明（不校验签名）
+fn jwt_expiry(token: &str) -> Option<i64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice::<Value>(&bytes).ok()?["exp"].as_i64()
+}
+
+/// 用令牌构造最小的会话状态（只含认证信息与邮箱），返回 Base64 编码的 SessionResponse
+///
+/// 到期时间取自 `id_token` 的 `exp` 声明，无法解析时按一小时后计算；
+/// 套餐与模型列表等字段由 Antigravity 在首次使用该账户时重新获取
+pub fn build_session_state(
+    email: &str,
+    access_token: &str,
+    id_token: &str,
+) -> Result<String, String> {
+    use crate::proto::{AuthInfo, AuthMetadata, SessionResponse, UserContext};
+
+    if !email.contains('@') {
+        return Err(format!("邮箱格式无效: {}", email));
+    }
+    if access_token.is_empty() || id_token.is_empty() {
+        return Err("access_token 与 id_token 不能为空".to_string());
+    }
+
+    let expiry_timestamp = jwt_expiry(id_token)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp() + DEFAULT_TOKEN_LIFETIME_SECS);
+    let msg = SessionResponse {
+        auth: Some(AuthInfo {
+            access_token: access_token.to_string(),
+            r#type: "Bearer".to_string(),
+            id_token: id_token.to_string(),
+            meta: Some(AuthMetadata { expiry_timestamp }),
+        }),
+        context: Some(UserContext {
+            email: email.to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let bytes = msg.encode_to_vec();
+    // 确认编码结果可以按原路径解码
+    decode_session_bytes(&bytes)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+fn session_bytes(b64: &str) -> Result<Vec<u8>, String> {
     if b64.trim().is_empty() {
         return Err("jetskiStateSync.agentManagerInitState 为空".to_string());
     }
 
-    let bytes = base64::engine::general_purpose::STANDARD
+    base64::engine::general_purpose::STANDARD
         .decode(b64)
         .map_err(|e| {
             format!(
@@ -16,17 +198,21 @@ pub fn decode_jetski_state_proto(b64: &str) -> Result<Value, String> {
                 b64.len(),
                 e
             )
-        })?;
+        })
+}
 
-    let msg = crate::proto::SessionResponse::decode(bytes.as_slice()).map_err(|e| {
+fn decode_session_bytes(bytes: &[u8]) -> Result<crate::proto::SessionResponse, String> {
+    crate::proto::SessionResponse::decode(bytes).map_err(|e| {
         format!(
             "jetskiStateSync.agentManagerInitState Protobuf 解码失败(len={}): {}",
             bytes.len(),
             e
         )
-    })?;
+    })
+}
 
-    Ok(session_response_to_json(&msg))
+fn decode_session_response(b64: &str) -> Result<crate::proto::SessionResponse, String> {
+    decode_session_bytes(&session_bytes(b64)?)
 }
 
 fn session_response_to_json(msg: &crate::proto::SessionResponse) -> Value {
@@ -126,3 +312,39 @@ fn session_response_to_json(msg: &crate::proto::SessionResponse) -> Value {
         "context": context,
     })
 }
+
+/// 移除字段名以 `_base64` 结尾的原始 protobuf 片段（内容未解析，可能包含凭据）
+pub fn remove_raw_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !key.ends_with("_base64"));
+            map.values_mut().for_each(remove_raw_fields);
+        }
+        Value::Array(items) => items.iter_mut().for_each(remove_raw_fields),
+        _ => {}
+    }
+}
+
+/// 生成可公开分享的会话信息：移除令牌与原始 protobuf 片段，只保留邮箱、套餐、模型等元数据
+pub fn sanitize_session(session: &mut Value) {
+    crate::utils::redacted_json::strip(session);
+    remove_raw_fields(session);
+}
+
+/// 生成可公开分享的备份内容：原始登录状态解码后替换为脱敏的 `session`，其余字段移除凭据
+pub fn sanitize_backup(backup: &Value) -> Value {
+    let key = crate::constants::database::AGENT_STATE;
+    let mut sanitized = backup.clone();
+
+    let session = backup
+        .get(key)
+        .and_then(Value::as_str)
+        .and_then(|state| decode_jetski_state_proto(state).ok());
+    crate::utils::redacted_json::strip(&mut sanitized);
+
+    if let (Some(mut session), Value::Object(map)) = (session, &mut sanitized) {
+        sanitize_session(&mut session);
+        map.insert("session".to_string(), session);
+    }
+    sanitized
+}
@@ -0,0 +1,396 @@
+// 账户配置文件（.enc）导入导出格式
+// 导出时在配置 JSON 中写入格式版本、导出设备的 Ed25519 签名与 HMAC-SHA256 签名（以导出密码为密钥）；
+// 导入时先校验版本、签名与每个备份的内容，全部通过后才会写入配置目录，
+// 未通过校验的文件复制到隔离目录，防止社区中流传的恶意“备份”文件写入任意内容。
+// 密码签名只能发现损坏或密码错误，来源由设备签名确认：缺少设备签名的文件直接拒绝，
+// 签名设备不是本机也不在信任列表中时，需要用户明确确认信任该设备后才能导入；
+// 文件大小、JSON 嵌套深度与元素数量、备份数量均有上限
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::constants::database;
+use crate::utils::network_fs;
+
+/// 当前导出格式版本（带签名）
+pub const ARCHIVE_VERSION: &str = "1.2.0";
+
+/// 旧版格式版本（无签名）
+const LEGACY_VERSION: &str = "1.1.0";
+
+/// 配置 JSON 中的签名字段
+const SIGNATURE_KEY: &str = "signature";
+
+/// 导出设备信息字段与设备签名字段
+const DEVICE_KEY: &str = "device";
+const DEVICE_SIGNATURE_KEY: &str = "device_signature";
+
+/// 旧版未签名文件的错误提示（前端据此询问是否仍要导入）
+pub const UNSIGNED_ERROR: &str = "配置文件未签名（旧版本导出），无法验证来源";
+
+/// 签名设备未受信任的错误提示前缀（前端据此询问是否信任该设备）
+pub const UNTRUSTED_SIGNER_ERROR: &str = "配置文件由未受信任的设备签名";
+
+/// 导入文件大小上限
+const MAX_ARCHIVE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// 单次导入的备份数量上限
+pub const MAX_BACKUPS: usize = 1000;
+
+/// 单个备份内容序列化后的大小上限
+const MAX_BACKUP_BYTES: usize = 4 * 1024 * 1024;
+
+/// JSON 最大嵌套深度
+const MAX_JSON_DEPTH: usize = 32;
+
+/// JSON 最大元素数量（对象字段与数组元素合计）
+const MAX_JSON_ELEMENTS: usize = 100_000;
+
+/// 备份文件名最大长度
+const MAX_FILENAME_LEN: usize = 200;
+
+/// Windows 保留设备名
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 通过设备签名校验的导出设备
+pub(crate) struct Signer {
+    device_id: String,
+    device_name: String,
+    public_key: String,
+}
+
+/// 与密码循环异或（导出文件的混淆层，加解密对称）
+pub fn xor(data: &[u8], password: &[u8]) -> Vec<u8> {
+    data.iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ password[i % password.len()])
+        .collect()
+}
+
+/// 序列化去掉指定字段后的配置 JSON
+fn bytes_without(config: &Value, keys: &[&str]) -> Result<Vec<u8>, String> {
+    let mut unsigned = config.clone();
+    if let Value::Object(map) = &mut unsigned {
+        for key in keys {
+            map.remove(*key);
+        }
+    }
+    serde_json::to_vec(&unsigned).map_err(|e| format!("序列化配置失败: {}", e))
+}
+
+/// 计算不含签名字段的配置 JSON 的 HMAC
+fn mac(config: &Value, password: &str) -> Result<HmacSha256, String> {
+    let bytes = bytes_without(config, &[SIGNATURE_KEY])?;
+
+    let mut mac = HmacSha256::new_from_slice(password.as_bytes())
+        .map_err(|e| format!("初始化签名失败: {}", e))?;
+    mac.update(&bytes);
+    Ok(mac)
+}
+
+/// 写入导出设备的公钥与设备签名（导入时缺少设备签名的文件会被拒绝，设备密钥不可用时导出失败）
+pub(crate) fn sign_with_device(config: &mut Value) -> Result<(), String> {
+    let identity = crate::device_identity::get()
+        .map_err(|e| format!("设备密钥不可用，无法为配置文件签名: {}", e))?;
+    config[DEVICE_KEY] = serde_json::json!({
+        "device_id": identity.device_id,
+        "device_name": identity.device_name,
+        "public_key": identity.public_key,
+    });
+
+    let bytes = bytes_without(config, &[SIGNATURE_KEY, DEVICE_SIGNATURE_KEY])?;
+    config[DEVICE_SIGNATURE_KEY] = Value::String(crate::device_identity::sign(&bytes)?);
+    Ok(())
+}
+
+/// 校验设备签名，返回签名设备（缺少设备签名时拒绝）
+pub(crate) fn verify_device(config: &Value) -> Result<Signer, String> {
+    let signature = config
+        .get(DEVICE_SIGNATURE_KEY)
+        .and_then(Value::as_str)
+        .ok_or_else(|| "文件缺少设备签名，无法验证来源".to_string())?;
+    let device = config.get(DEVICE_KEY);
+    let public_key = device
+        .and_then(|device| device.get("public_key"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| "文件缺少导出设备公钥".to_string())?;
+
+    let bytes = bytes_without(config, &[SIGNATURE_KEY, DEVICE_SIGNATURE_KEY])?;
+    let device_id = crate::device_identity::verify(public_key, &bytes, signature)
+        .map_err(|e| format!("{}，文件可能被篡改", e))?;
+    tracing::info!(target: "backup::archive", device_id = %device_id, "🔏 设备签名校验通过");
+    Ok(Signer {
+        device_id,
+        device_name: device
+            .and_then(|device| device.get("device_name"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string(),
+        public_key: public_key.to_string(),
+    })
+}
+
+/// 确认签名设备可信：本机或信任列表中的设备直接通过；
+/// 其他设备只有在 `trust_signer` 为 true（用户已确认）时才加入信任列表并通过
+pub(crate) fn ensure_trusted(signer: &Signer, trust_signer: bool) -> Result<(), String> {
+    if crate::device_identity::is_trusted(&signer.public_key) {
+        return Ok(());
+    }
+    if !trust_signer {
+        return Err(format!(
+            "{}：{}（设备 ID {}）",
+            UNTRUSTED_SIGNER_ERROR, signer.device_name, signer.device_id
+        ));
+    }
+    crate::device_identity::trust(&signer.public_key, &signer.device_name)?;
+    Ok(())
+}
+
+/// 为导出的配置 JSON 写入版本、设备签名与密码签名
+pub fn sign(json_data: &str, password: &str) -> Result<String, String> {
+    let mut config: Value =
+        serde_json::from_str(json_data).map_err(|e| format!("配置数据不是有效的 JSON: {}", e))?;
+    let Value::Object(map) = &mut config else {
+        return Err("配置数据格式无效".to_string());
+    };
+    map.insert(
+        "version".to_string(),
+        Value::String(ARCHIVE_VERSION.to_string()),
+    );
+    sign_with_device(&mut config)?;
+
+    let signature = mac(&config, password)?.finalize().into_bytes();
+    config[SIGNATURE_KEY] = Value::String(BASE64.encode(signature));
+    serde_json::to_string(&config).map_err(|e| format!("序列化配置失败: {}", e))
+}
+
+/// 校验版本与签名，返回签名设备（允许导入的旧版未签名文件返回 `None`）
+fn verify(config: &Value, password: &str, allow_unsigned: bool) -> Result<Option<Signer>, String> {
+    let version = config
+        .get("version")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "配置文件缺少版本号".to_string())?;
+
+    match version {
+        ARCHIVE_VERSION => {}
+        LEGACY_VERSION if allow_unsigned => return Ok(None),
+        LEGACY_VERSION => return Err(UNSIGNED_ERROR.to_string()),
+        other => return Err(format!("不支持的配置文件版本: {}", other)),
+    }
+
+    let signature = config
+        .get(SIGNATURE_KEY)
+        .and_then(Value::as_str)
+        .ok_or_else(|| "配置文件缺少签名".to_string())?;
+    let signature = BASE64
+        .decode(signature)
+        .map_err(|_| "配置文件签名格式无效".to_string())?;
+
+    mac(config, password)?
+        .verify_slice(&signature)
+        .map_err(|_| "配置文件签名校验失败，文件可能被篡改".to_string())?;
+    verify_device(config).map(Some)
+}
+
+/// 备份文件名只能是备份目录下的 `<name>.json`，不能包含路径、控制字符或 Windows 保留名
+fn validate_filename(filename: &str) -> Result<(), String> {
+    let is_plain_name = Path::new(filename)
+        .file_name()
+        .is_some_and(|name| name == filename);
+    let stem = filename.strip_suffix(".json").unwrap_or_default();
+    let is_reserved = RESERVED_NAMES.iter().any(|reserved| {
+        stem.split('.')
+            .next()
+            .unwrap_or(stem)
+            .eq_ignore_ascii_case(reserved)
+    });
+
+    if !is_plain_name
+        || stem.is_empty()
+        || filename.len() > MAX_FILENAME_LEN
+        || filename.starts_with('.')
+        || filename.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|'])
+        || filename.chars().any(char::is_control)
+        || stem.ends_with(['.', ' '])
+        || is_reserved
+    {
+        return Err(format!("备份文件名无效: {}", filename));
+    }
+    Ok(())
+}
+
+/// 检查账户备份名（文件名去掉 `.json`）：规则与导入时的备份文件名相同，
+/// 用于外部传入的账户名拼接备份路径之前，防止 `../` 等路径逃出备份目录
+pub fn validate_backup_name(name: &str) -> Result<(), String> {
+    validate_filename(&format!("{}.json", name)).map_err(|_| format!("账户名无效: {}", name))
+}
+
+/// 检查 JSON 的嵌套深度与元素数量
+fn check_json_limits(value: &Value) -> Result<(), String> {
+    let mut stack = vec![(value, 1usize)];
+    let mut elements = 0usize;
+
+    while let Some((value, depth)) = stack.pop() {
+        if depth > MAX_JSON_DEPTH {
+            return Err(format!("JSON 嵌套层级超过 {} 层", MAX_JSON_DEPTH));
+        }
+        let children: Box<dyn Iterator<Item = &Value>> = match value {
+            Value::Array(items) => Box::new(items.iter()),
+            Value::Object(map) => Box::new(map.values()),
+            _ => continue,
+        };
+        for child in children {
+            elements += 1;
+            if elements > MAX_JSON_ELEMENTS {
+                return Err(format!("JSON 元素数量超过 {} 个", MAX_JSON_ELEMENTS));
+            }
+            stack.push((child, depth + 1));
+        }
+    }
+    Ok(())
+}
+
+/// 检查单个待导入备份：文件名合法，内容大小、嵌套深度与元素数量不超限
+pub fn validate_backup(filename: &str, content: &Value) -> Result<(), String> {
+    validate_filename(filename)?;
+    check_json_limits(content).map_err(|e| format!("备份 {} 内容无效: {}", filename, e))?;
+
+    let size = serde_json::to_vec(content)
+        .map_err(|e| format!("序列化备份 {} 失败: {}", filename, e))?
+        .len();
+    if size > MAX_BACKUP_BYTES {
+        return Err(format!(
+            "备份 {} 过大（{} 字节，上限 {} 字节）",
+            filename, size, MAX_BACKUP_BYTES
+        ));
+    }
+    Ok(())
+}
+
+/// 校验配置中的每个备份：文件名合法，登录状态可以解码
+fn validate_backups(config: &Value) -> Result<(), String> {
+    let backups = config
+        .get("backups")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "配置文件缺少备份列表".to_string())?;
+
+    if backups.len() > MAX_BACKUPS {
+        return Err(format!("备份数量超过 {} 个", MAX_BACKUPS));
+    }
+    if let Some(count) = config.get("backupCount").and_then(Value::as_u64) {
+        if count != backups.len() as u64 {
+            return Err("配置文件备份数量不一致".to_string());
+        }
+    }
+
+    for backup in backups {
+        let filename = backup
+            .get("filename")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "备份缺少文件名".to_string())?;
+        let content = backup
+            .get("content")
+            .ok_or_else(|| format!("备份 {} 缺少内容", filename))?;
+        validate_backup(filename, content)?;
+
+        let state = content
+            .get(database::AGENT_STATE)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("备份 {} 缺少登录状态", filename))?;
+        crate::antigravity::account::decode_jetski_state_proto(state)
+            .map_err(|e| format!("备份 {} 的登录状态无效: {}", filename, e))?;
+    }
+    Ok(())
+}
+
+/// 将文件复制到隔离目录，返回隔离后的路径
+fn quarantine(path: &Path) -> Result<PathBuf, String> {
+    let dir = crate::directories::get_quarantine_directory();
+    fs::create_dir_all(&dir).map_err(|e| format!("创建隔离目录失败: {}", e))?;
+
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "archive".to_string());
+    let target = dir.join(format!(
+        "{}-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        name
+    ));
+    fs::copy(path, &target).map_err(|e| format!("隔离文件失败: {}", e))?;
+    Ok(target)
+}
+
+/// 读取并解密配置文件（超过大小上限或 JSON 结构超限时拒绝）
+fn decrypt(path: &Path, password: &str) -> Result<Value, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("读取配置文件失败 {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_ARCHIVE_BYTES {
+        return Err(format!(
+            "配置文件过大（{} 字节，上限 {} 字节）",
+            size, MAX_ARCHIVE_BYTES
+        ));
+    }
+
+    let encoded = network_fs::read_to_string(path)
+        .map_err(|e| format!("读取配置文件失败 {}: {}", path.display(), e))?;
+    let decoded = BASE64
+        .decode(encoded.trim())
+        .map_err(|_| "Base64 解码失败".to_string())?;
+    let config: Value = serde_json::from_slice(&xor(&decoded, password.as_bytes()))
+        .map_err(|_| "解密失败，密码错误或文件已损坏".to_string())?;
+    check_json_limits(&config).map_err(|e| format!("配置文件结构无效: {}", e))?;
+    Ok(config)
+}
+
+/// 读取、解密并校验导入的配置文件，返回配置 JSON
+///
+/// 签名或内容校验失败的文件会被复制到隔离目录；旧版未签名文件在未允许时直接拒绝，
+/// 未受信任设备签名的文件在未确认信任（`trust_signer`）时直接拒绝
+pub fn open(
+    path: &Path,
+    password: &str,
+    allow_unsigned: bool,
+    trust_signer: bool,
+) -> Result<Value, String> {
+    if password.is_empty() {
+        return Err("密码不能为空".to_string());
+    }
+
+    let config = decrypt(path, password)?;
+    let checked = verify(&config, password, allow_unsigned)
+        .and_then(|signer| validate_backups(&config).map(|_| signer));
+    match checked {
+        Ok(Some(signer)) => {
+            ensure_trusted(&signer, trust_signer)?;
+            Ok(config)
+        }
+        Ok(None) => Ok(config),
+        Err(e) if e == UNSIGNED_ERROR => Err(e),
+        Err(e) => Err(reject(path, e)),
+    }
+}
+
+/// 把未通过校验的文件复制到隔离目录，返回附带隔离位置的错误信息
+pub(crate) fn reject(path: &Path, error: String) -> String {
+    match quarantine(path) {
+        Ok(target) => {
+            tracing::warn!(target: "backup::archive", file = %path.display(), quarantined = %target.display(), error = %error, "⛔ 文件未通过校验，已隔离");
+            format!("{}（文件已隔离到 {}）", error, target.display())
+        }
+        Err(quarantine_error) => {
+            tracing::warn!(target: "backup::archive", file = %path.display(), error = %error, quarantine_error = %quarantine_error, "⛔ 文件未通过校验");
+            error
+        }
+    }
+}
@@ -0,0 +1,348 @@
+// Antigravity 账户备份文件模块
+// 负责枚举账户备份目录中的备份文件，以及安全快照与每个账户的历史版本
+
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::{CoreError, CoreResult, ResultExt};
+use crate::utils::db_pool;
+
+/// 保留的安全快照数量
+const SAFETY_SNAPSHOT_KEEP: usize = 5;
+
+/// 每个账户保留的历史版本数量
+const BACKUP_VERSION_KEEP: usize = 10;
+
+/// 备份文件条目
+#[derive(Debug, Clone)]
+pub struct BackupFileEntry {
+    /// 备份名（文件名去掉扩展名，即邮箱）
+    pub name: String,
+    /// 备份文件完整路径
+    pub path: PathBuf,
+    /// 文件修改时间
+    pub modified: SystemTime,
+}
+
+/// 备份目录顶层备份文件的文件名；安全快照、历史版本等其他位置的文件返回 `None`
+pub fn top_level_backup_filename(path: &Path) -> Option<String> {
+    let accounts_dir = crate::directories::get_accounts_directory();
+    if path.parent() != Some(accounts_dir.as_path()) {
+        return None;
+    }
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// 列出账户备份目录中的所有备份文件，按修改时间排序（最新的在前）
+pub fn list_backup_files() -> CoreResult<Vec<BackupFileEntry>> {
+    let accounts_dir = crate::directories::get_accounts_directory();
+
+    if !accounts_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&accounts_dir)
+        .map_err(|e| CoreError::io(&accounts_dir, e))
+        .context("读取备份目录失败")?
+    {
+        let entry = entry
+            .map_err(|e| CoreError::io(&accounts_dir, e))
+            .context("读取目录项失败")?;
+        let path = entry.path();
+
+        if !path.extension().is_some_and(|ext| ext == "json") {
+            continue;
+        }
+
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+
+        let modified = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        entries.push(BackupFileEntry {
+            name,
+            path,
+            modified,
+        });
+    }
+
+    entries.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(entries)
+}
+
+/// 安全快照目录（位于备份目录下的子目录，不会出现在备份列表中）
+fn get_safety_snapshot_directory() -> PathBuf {
+    crate::directories::get_accounts_directory().join("safety-snapshots")
+}
+
+/// 在恢复备份前为当前登录状态创建安全快照
+///
+/// 快照只保留最近 5 份；当前没有登录状态时返回 `Ok(None)`
+pub fn create_safety_snapshot() -> CoreResult<Option<PathBuf>> {
+    let Some(db_path) = crate::platform::get_antigravity_db_path().filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+
+    let jetski_state: Option<String> =
+        crate::slow_operation::measure("db::read_agent_state", || {
+            db_pool::with_read_connection(&db_path, |conn| {
+                conn.query_row(
+                    "SELECT value FROM ItemTable WHERE key = ?",
+                    [crate::constants::database::AGENT_STATE],
+                    |row| row.get(0),
+                )
+                .optional()
+            })
+        })
+        .map_err(|e| CoreError::sqlite(&db_path, e))
+        .context("查询当前登录状态失败")?;
+
+    let Some(jetski_state) = jetski_state else {
+        return Ok(None);
+    };
+
+    let snapshot_dir = get_safety_snapshot_directory();
+    fs::create_dir_all(&snapshot_dir)
+        .map_err(|e| CoreError::io(&snapshot_dir, e))
+        .context("创建安全快照目录失败")?;
+
+    let snapshot_name = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let snapshot_file = snapshot_dir.join(format!("{}.json", snapshot_name));
+    let key = crate::constants::database::AGENT_STATE;
+    let content = serde_json::json!({ key: jetski_state });
+    crate::antigravity::keychain::write_backup(
+        &snapshot_file,
+        &snapshot_keychain_account(&snapshot_name),
+        &content,
+    )
+    .context("写入安全快照失败")?;
+
+    tracing::info!(target: "backup::safety_snapshot", file = %snapshot_file.display(), "🛟 已创建安全快照");
+
+    prune_safety_snapshots();
+    Ok(Some(snapshot_file))
+}
+
+/// 安全快照在钥匙串中的账户名（与账户备份区分）
+fn snapshot_keychain_account(snapshot_name: &str) -> String {
+    format!("safety-snapshot-{}", snapshot_name)
+}
+
+/// 列出所有安全快照文件，按时间从旧到新（文件名即时间戳）
+pub fn list_safety_snapshots() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(get_safety_snapshot_directory()) else {
+        return Vec::new();
+    };
+
+    let mut snapshots: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    snapshots.sort();
+    snapshots
+}
+
+/// 删除多余的旧安全快照
+fn prune_safety_snapshots() {
+    let snapshots = list_safety_snapshots();
+
+    let excess = snapshots.len().saturating_sub(SAFETY_SNAPSHOT_KEEP);
+    for path in snapshots.into_iter().take(excess) {
+        if let Err(e) = crate::utils::secure_delete::remove_file(&path) {
+            tracing::warn!(target: "backup::safety_snapshot", error = %e, "删除旧安全快照失败");
+            continue;
+        }
+        if let Some(stem) = path.file_stem() {
+            crate::antigravity::keychain::delete(&snapshot_keychain_account(
+                &stem.to_string_lossy(),
+            ));
+        }
+    }
+}
+
+/// 账户备份的历史版本
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BackupVersion {
+    /// 版本 ID（创建时间，格式 `YYYYMMDD-HHMMSSmmm`）
+    pub version: String,
+    /// 创建时间（RFC 3339）
+    pub created_at: String,
+    /// 文件大小（字节）
+    pub size: u64,
+}
+
+/// 账户历史版本目录（位于备份目录下的 `versions/<账户>`，不会出现在备份列表中）
+fn get_backup_versions_directory(account: &str) -> CoreResult<PathBuf> {
+    // 账户名直接用作目录名，拒绝路径分隔符与 `..`
+    if Path::new(account)
+        .file_name()
+        .is_none_or(|name| name != account)
+    {
+        return Err(CoreError::Other(format!("账户名无效: {}", account)));
+    }
+    Ok(crate::directories::get_accounts_directory()
+        .join("versions")
+        .join(account))
+}
+
+/// 历史版本在钥匙串中的账户名（与账户备份区分）
+fn version_keychain_account(account: &str, version: &str) -> String {
+    format!("version-{}-{}", account, version)
+}
+
+/// 覆盖账户备份前，把现有备份保存为历史版本
+///
+/// `new_state` 为即将写入的登录状态，与现有备份相同时不产生新版本；
+/// 备份不存在或内容相同时返回 `Ok(None)`
+pub fn archive_backup_version(account: &str, new_state: &str) -> CoreResult<Option<PathBuf>> {
+    let current = crate::directories::get_accounts_directory().join(format!("{}.json", account));
+    if !current.exists() {
+        return Ok(None);
+    }
+
+    // 读取完整内容（钥匙串中的登录状态会被覆盖，版本需要另存一份）
+    let data = crate::antigravity::keychain::read_backup(&current)
+        .map_err(CoreError::Other)
+        .context("读取现有备份失败")?;
+    if data
+        .get(crate::constants::database::AGENT_STATE)
+        .and_then(serde_json::Value::as_str)
+        == Some(new_state)
+    {
+        return Ok(None);
+    }
+
+    let version_dir = get_backup_versions_directory(account)?;
+    fs::create_dir_all(&version_dir)
+        .map_err(|e| CoreError::io(&version_dir, e))
+        .context("创建历史版本目录失败")?;
+
+    let version = chrono::Local::now().format("%Y%m%d-%H%M%S%3f").to_string();
+    let version_file = version_dir.join(format!("{}.json", version));
+    crate::antigravity::keychain::write_backup(
+        &version_file,
+        &version_keychain_account(account, &version),
+        &data,
+    )
+    .map_err(CoreError::Other)
+    .context("写入历史版本失败")?;
+
+    tracing::info!(target: "backup::version", account = %crate::utils::privacy::account(account), version = %version, "🕘 已保存备份历史版本");
+
+    prune_backup_versions(account);
+    Ok(Some(version_file))
+}
+
+/// 列出账户的历史版本文件，按时间从旧到新（文件名即时间戳）
+fn list_backup_version_files(account: &str) -> CoreResult<Vec<PathBuf>> {
+    let version_dir = get_backup_versions_directory(account)?;
+    let Ok(entries) = fs::read_dir(&version_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut versions: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    versions.sort();
+    Ok(versions)
+}
+
+/// 列出所有账户的历史版本文件（`versions/<账户>/<版本>.json`），按路径排序
+///
+/// 启用或关闭加密、钥匙串存储或切换压缩格式时，历史版本与账户备份一起重写
+pub fn list_all_version_files() -> Vec<PathBuf> {
+    let versions_dir = crate::directories::get_accounts_directory().join("versions");
+    let Ok(accounts) = fs::read_dir(&versions_dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = accounts
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.filter_map(|entry| entry.ok().map(|e| e.path())))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// 历史版本文件在钥匙串中的账户名；不是历史版本文件时返回 `None`
+pub fn version_file_keychain_account(path: &Path) -> Option<String> {
+    let account_dir = path.parent()?;
+    let versions_dir = crate::directories::get_accounts_directory().join("versions");
+    if account_dir.parent() != Some(versions_dir.as_path()) {
+        return None;
+    }
+    let account = account_dir.file_name()?.to_string_lossy();
+    let version = path.file_stem()?.to_string_lossy();
+    Some(version_keychain_account(&account, &version))
+}
+
+/// 列出账户的历史版本（最新的在前）
+pub fn list_backup_versions(account: &str) -> CoreResult<Vec<BackupVersion>> {
+    let mut versions: Vec<BackupVersion> = list_backup_version_files(account)?
+        .into_iter()
+        .filter_map(|path| {
+            let version = path.file_stem()?.to_string_lossy().to_string();
+            let metadata = fs::metadata(&path).ok()?;
+            let created_at = metadata
+                .modified()
+                .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339())
+                .unwrap_or_default();
+            Some(BackupVersion {
+                version,
+                created_at,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    versions.reverse();
+    Ok(versions)
+}
+
+/// 历史版本文件路径；版本不存在时返回 [`CoreError::NotFound`]
+pub fn backup_version_path(account: &str, version: &str) -> CoreResult<PathBuf> {
+    if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(CoreError::Other(format!("版本 ID 无效: {}", version)));
+    }
+    let path = get_backup_versions_directory(account)?.join(format!("{}.json", version));
+    if !path.exists() {
+        return Err(CoreError::not_found(format!(
+            "{} 的历史版本 {}",
+            account, version
+        )));
+    }
+    Ok(path)
+}
+
+/// 删除多余的旧历史版本
+fn prune_backup_versions(account: &str) {
+    let Ok(versions) = list_backup_version_files(account) else {
+        return;
+    };
+
+    let excess = versions.len().saturating_sub(BACKUP_VERSION_KEEP);
+    for path in versions.into_iter().take(excess) {
+        if let Err(e) = crate::utils::secure_delete::remove_file(&path) {
+            tracing::warn!(target: "backup::version", error = %e, "删除旧历史版本失败");
+            continue;
+        }
+        if let Some(stem) = path.file_stem() {
+            crate::antigravity::keychain::delete(&version_keychain_account(
+                account,
+                &stem.to_string_lossy(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,558 @@
+// 完整备份归档（.agbak）
+// 把备份目录中的所有账户备份（钥匙串中的登录状态会重新组装）与 Antigravity 路径配置打包为一个 zip，
+// 整体以导出密码经 Argon2id 派生的密钥做 AES-256-GCM 加密，换机时只需要迁移一个文件。
+// 导入时先校验设备签名与签名设备是否可信（未通过校验的文件复制到隔离目录），再逐个校验后合并到
+// 备份目录，与已有备份同名时按冲突策略跳过、覆盖或另存为新名称。归档中的自定义可执行文件路径
+// 默认不导入，只有用户确认且路径有效时才写入。
+//
+// 文件内容为 JSON 加密信封：格式标识、版本、盐值、KDF 参数与密文，以及导出设备的公钥与设备签名
+// （覆盖信封中除设备签名外的全部字段，与配置文件导出使用同一设备密钥）；
+// zip 内账户备份位于 `accounts/<账户>.json`，路径配置位于 `antigravity_path.json`
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use zeroize::Zeroizing;
+use zip::write::SimpleFileOptions;
+
+use super::vault::Sealed;
+use crate::cancellation::CancellationToken;
+use crate::utils::network_fs;
+
+/// 信封中的格式标识
+const FORMAT: &str = "agbak";
+
+/// 当前归档格式版本（版本 1 没有设备签名）
+const ARCHIVE_VERSION: u32 = 2;
+
+/// zip 内账户备份所在目录
+const ACCOUNTS_PREFIX: &str = "accounts/";
+
+/// zip 内路径配置文件名
+const PATH_CONFIG_ENTRY: &str = "antigravity_path.json";
+
+/// 导入文件大小上限
+const MAX_ARCHIVE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// zip 内单个文件解压后的大小上限
+const MAX_ENTRY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// 另存为新名称时尝试的序号上限
+const MAX_KEEP_BOTH_SUFFIX: usize = 100;
+
+/// 不带设备签名的旧版归档版本
+const UNSIGNED_VERSION: u32 = 1;
+
+/// 旧版未签名归档的错误信息（前端据此提示用户确认后重试）
+pub const UNSIGNED_ERROR: &str = "备份归档未签名（旧版本导出），无法验证来源";
+
+/// 加密信封
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    format: String,
+    version: u32,
+    /// 导出时间（RFC 3339）
+    created_at: String,
+    salt: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    sealed: Sealed,
+}
+
+/// 导入选项（除冲突策略外均需用户明确确认，默认全部关闭）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArchiveImportOptions {
+    /// 允许导入没有设备签名的旧版归档
+    pub allow_unsigned: bool,
+    /// 信任未在信任列表中的签名设备（加入信任列表）
+    pub trust_signer: bool,
+    /// 导入归档中的自定义 Antigravity 可执行文件路径
+    pub import_executable_path: bool,
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BackupArchiveSummary {
+    /// 归档文件路径
+    pub path: String,
+    /// 打包的账户备份数
+    pub accounts: usize,
+    /// 是否包含路径配置
+    pub path_config: bool,
+    /// 归档文件大小（字节）
+    pub size: u64,
+}
+
+/// 与已有备份同名时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// 保留已有备份，不导入
+    #[default]
+    Skip,
+    /// 用归档中的备份覆盖已有备份
+    Overwrite,
+    /// 两者都保留，归档中的备份另存为 `<账户> (n).json`
+    KeepBoth,
+}
+
+/// 单个文件的导入结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveImportAction {
+    /// 新增
+    Imported,
+    /// 覆盖了已有文件
+    Overwritten,
+    /// 与已有备份同名，另存为新名称
+    Renamed,
+    /// 已存在，按策略跳过
+    Skipped,
+    Failed,
+}
+
+/// 归档中单个文件的导入结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ArchiveImportItem {
+    /// 归档中的文件名
+    pub filename: String,
+    pub action: ArchiveImportAction,
+    /// 实际写入的文件名（跳过或失败时为 `None`）
+    pub target: Option<String>,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// 导入报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ArchiveImportReport {
+    /// 写入的文件数（新增、覆盖与另存）
+    pub restored_count: usize,
+    pub skipped_count: usize,
+    pub failed_count: usize,
+    /// 每个文件的结果（账户备份在前，路径配置在最后）
+    pub items: Vec<ArchiveImportItem>,
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    settings: &crate::utils::kdf::KdfSettings,
+) -> Result<Zeroizing<[u8; 32]>, String> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    crate::utils::kdf::argon2(settings, Some(32))?
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(|e| format!("派生密钥失败: {}", e))?;
+    Ok(key)
+}
+
+/// 将备份目录与路径配置打包为 zip（在内存中完成，明文不落盘）
+///
+/// 每打包一个备份前检查 `token`，已取消时返回取消错误
+fn build_zip(token: &CancellationToken) -> Result<(Zeroizing<Vec<u8>>, usize, bool), String> {
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+    let mut accounts = 0;
+    for entry in super::backup::list_backup_files()? {
+        token.check()?;
+        let content = super::keychain::read_backup(&entry.path)?;
+        let json = Zeroizing::new(
+            serde_json::to_vec_pretty(&content)
+                .map_err(|e| format!("序列化备份 {} 失败: {}", entry.name, e))?,
+        );
+        writer
+            .start_file(format!("{}{}.json", ACCOUNTS_PREFIX, entry.name), options)
+            .and_then(|()| writer.write_all(&json).map_err(Into::into))
+            .map_err(|e| format!("打包备份 {} 失败: {}", entry.name, e))?;
+        accounts += 1;
+    }
+
+    let path_config_file = crate::directories::get_antigravity_path_file();
+    let path_config = path_config_file.exists();
+    if path_config {
+        let content = network_fs::read_to_string(&path_config_file)
+            .map_err(|e| format!("读取路径配置失败: {}", e))?;
+        writer
+            .start_file(PATH_CONFIG_ENTRY, options)
+            .and_then(|()| writer.write_all(content.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("打包路径配置失败: {}", e))?;
+    }
+
+    let bytes = writer
+        .finish()
+        .map_err(|e| format!("生成归档失败: {}", e))?
+        .into_inner();
+    Ok((Zeroizing::new(bytes), accounts, path_config))
+}
+
+/// 导出所有备份与路径配置到加密归档文件
+///
+/// 备份目录已加密时需要先解锁；启用钥匙串时登录状态从钥匙串读取后写入归档
+pub fn export(
+    path: &Path,
+    password: &str,
+    token: &CancellationToken,
+) -> Result<BackupArchiveSummary, String> {
+    if password.is_empty() {
+        return Err("密码不能为空".to_string());
+    }
+
+    let (plaintext, accounts, path_config) = build_zip(token)?;
+    token.check()?;
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let settings = crate::utils::kdf::params();
+    let key = derive_key(password, &salt, &settings)?;
+    let envelope = Envelope {
+        format: FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        created_at: chrono::Local::now().to_rfc3339(),
+        salt: BASE64.encode(salt),
+        m_cost: settings.m_cost,
+        t_cost: settings.t_cost,
+        p_cost: settings.p_cost,
+        sealed: super::vault::seal(&key, &plaintext)?,
+    };
+    // 设备签名覆盖信封头与密文，导入时据此校验来源
+    let mut envelope =
+        serde_json::to_value(&envelope).map_err(|e| format!("序列化加密归档失败: {}", e))?;
+    super::archive::sign_with_device(&mut envelope)?;
+    let content =
+        serde_json::to_vec(&envelope).map_err(|e| format!("序列化加密归档失败: {}", e))?;
+    network_fs::write(path, &content)
+        .map_err(|e| format!("写入归档文件失败 {}: {}", path.display(), e))?;
+
+    tracing::info!(
+        target: "backup::archive",
+        accounts = accounts,
+        path_config = path_config,
+        size = content.len(),
+        "📦 已导出完整备份归档"
+    );
+    Ok(BackupArchiveSummary {
+        path: path.display().to_string(),
+        accounts,
+        path_config,
+        size: content.len() as u64,
+    })
+}
+
+/// 校验信封：KDF 参数在允许范围内、设备签名有效，返回签名设备（允许导入的旧版未签名归档返回 `None`）
+///
+/// 在派生密钥之前完成，篡改过的归档不会进入耗时的密钥派生
+fn verify_envelope(
+    value: &serde_json::Value,
+    envelope: &Envelope,
+    allow_unsigned: bool,
+) -> Result<Option<super::archive::Signer>, String> {
+    crate::utils::kdf::check_limits(&kdf_settings(envelope))
+        .map_err(|e| format!("归档加密参数无效: {}", e))?;
+    match envelope.version {
+        UNSIGNED_VERSION if allow_unsigned => Ok(None),
+        UNSIGNED_VERSION => Err(UNSIGNED_ERROR.to_string()),
+        _ => super::archive::verify_device(value).map(Some),
+    }
+}
+
+fn kdf_settings(envelope: &Envelope) -> crate::utils::kdf::KdfSettings {
+    crate::utils::kdf::KdfSettings {
+        m_cost: envelope.m_cost,
+        t_cost: envelope.t_cost,
+        p_cost: envelope.p_cost,
+    }
+}
+
+/// 读取、校验并解密归档文件，返回 zip 内容
+///
+/// 签名或加密参数校验失败的文件会被复制到隔离目录；旧版未签名归档在未允许时直接拒绝，
+/// 未受信任设备签名的归档在未确认信任时直接拒绝
+fn decrypt(
+    path: &Path,
+    password: &str,
+    options: &ArchiveImportOptions,
+) -> Result<Zeroizing<Vec<u8>>, String> {
+    if password.is_empty() {
+        return Err("密码不能为空".to_string());
+    }
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("读取归档文件失败 {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_ARCHIVE_BYTES {
+        return Err(format!(
+            "归档文件过大（{} 字节，上限 {} 字节）",
+            size, MAX_ARCHIVE_BYTES
+        ));
+    }
+
+    let content = network_fs::read_to_string(path)
+        .map_err(|e| format!("读取归档文件失败 {}: {}", path.display(), e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|_| "不是有效的备份归档文件".to_string())?;
+    let envelope: Envelope =
+        serde_json::from_value(value.clone()).map_err(|_| "不是有效的备份归档文件".to_string())?;
+    if envelope.format != FORMAT {
+        return Err("不是有效的备份归档文件".to_string());
+    }
+    if envelope.version > ARCHIVE_VERSION {
+        return Err(format!("不支持的归档版本: {}", envelope.version));
+    }
+
+    match verify_envelope(&value, &envelope, options.allow_unsigned) {
+        Ok(Some(signer)) => super::archive::ensure_trusted(&signer, options.trust_signer)?,
+        Ok(None) => {
+            tracing::warn!(target: "backup::archive", file = %path.display(), "导入未签名的旧版备份归档（用户已确认）");
+        }
+        Err(e) if e == UNSIGNED_ERROR => return Err(e),
+        Err(e) => return Err(super::archive::reject(path, e)),
+    }
+
+    let salt = BASE64
+        .decode(&envelope.salt)
+        .map_err(|e| format!("盐值解码失败: {}", e))?;
+    let key = derive_key(password, &salt, &kdf_settings(&envelope))?;
+    super::vault::unseal(&key, &envelope.sealed)
+        .map_err(|_| "解密失败，密码错误或文件已损坏".to_string())
+}
+
+/// 读取 zip 中的单个文件（超过大小上限时拒绝）
+fn read_entry(file: impl Read) -> Result<Zeroizing<Vec<u8>>, String> {
+    let mut bytes = Zeroizing::new(Vec::new());
+    file.take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("解压失败: {}", e))?;
+    if bytes.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(format!("解压后超过 {} 字节", MAX_ENTRY_BYTES));
+    }
+    Ok(bytes)
+}
+
+/// 解析并校验归档中的账户备份：文件名合法、结构不超限、登录状态可以解码
+fn parse_backup(filename: &str, bytes: &[u8]) -> Result<serde_json::Value, String> {
+    let content: serde_json::Value =
+        serde_json::from_slice(bytes).map_err(|e| format!("备份内容不是有效的 JSON: {}", e))?;
+    super::archive::validate_backup(filename, &content)?;
+    let state = content
+        .get(crate::constants::database::AGENT_STATE)
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| "备份缺少登录状态".to_string())?;
+    super::account::decode_jetski_state_proto(state).map_err(|e| format!("登录状态无效: {}", e))?;
+    Ok(content)
+}
+
+/// 为另存的备份选一个不冲突的文件名：`<账户> (2).json`、`<账户> (3).json`……
+fn keep_both_name(accounts_dir: &Path, filename: &str) -> Result<String, String> {
+    let stem = filename.strip_suffix(".json").unwrap_or(filename);
+    (2..=MAX_KEEP_BOTH_SUFFIX)
+        .map(|index| format!("{} ({}).json", stem, index))
+        .find(|candidate| !accounts_dir.join(candidate).exists())
+        .ok_or_else(|| format!("{} 的同名备份过多", stem))
+}
+
+/// 按冲突策略写入单个账户备份（`bytes` 为备份 JSON 明文）
+pub(crate) fn import_backup(
+    accounts_dir: &Path,
+    filename: String,
+    bytes: &[u8],
+    strategy: ConflictStrategy,
+) -> ArchiveImportItem {
+    let mut item = ArchiveImportItem {
+        filename,
+        action: ArchiveImportAction::Failed,
+        target: None,
+        error: None,
+    };
+    let result = parse_backup(&item.filename, bytes).and_then(|content| {
+        let exists = accounts_dir.join(&item.filename).exists();
+        let (action, target) = match strategy {
+            _ if !exists => (ArchiveImportAction::Imported, item.filename.clone()),
+            ConflictStrategy::Skip => return Ok((ArchiveImportAction::Skipped, None)),
+            ConflictStrategy::Overwrite => {
+                (ArchiveImportAction::Overwritten, item.filename.clone())
+            }
+            ConflictStrategy::KeepBoth => (
+                ArchiveImportAction::Renamed,
+                keep_both_name(accounts_dir, &item.filename)?,
+            ),
+        };
+        let path = accounts_dir.join(&target);
+        let account = target.strip_suffix(".json").unwrap_or(&target);
+        super::keychain::write_backup(&path, account, &content)?;
+        Ok((action, Some(target)))
+    });
+
+    match result {
+        Ok((action, target)) => {
+            item.action = action;
+            item.target = target;
+        }
+        Err(e) => {
+            tracing::warn!(target: "backup::archive", filename = %item.filename, error = %e, "导入备份失败");
+            item.error = Some(e);
+        }
+    }
+    item
+}
+
+/// 归档中的自定义可执行文件路径必须是本机存在的文件，且路径中包含 Antigravity
+fn check_executable_path(path: &str) -> Result<(), String> {
+    let looks_like_antigravity = Path::new(path).components().any(|component| {
+        component
+            .as_os_str()
+            .to_string_lossy()
+            .to_lowercase()
+            .contains("antigravity")
+    });
+    if !super::path_config::validate_executable_path(path) || !looks_like_antigravity {
+        return Err(format!("归档中的可执行文件路径无效: {}", path));
+    }
+    Ok(())
+}
+
+/// 按冲突策略导入路径配置；两者都保留时没有可另存的位置，保留本机配置
+///
+/// 自定义可执行文件路径会在启动 Antigravity 时直接运行：只有 `import_executable_path`
+/// 为 true（用户已确认）且路径有效时才导入，否则保留本机的设置
+fn import_path_config(
+    bytes: &[u8],
+    strategy: ConflictStrategy,
+    import_executable_path: bool,
+) -> ArchiveImportItem {
+    let mut item = ArchiveImportItem {
+        filename: PATH_CONFIG_ENTRY.to_string(),
+        action: ArchiveImportAction::Failed,
+        target: None,
+        error: None,
+    };
+    let exists = crate::directories::get_antigravity_path_file().exists();
+    let action = match strategy {
+        _ if !exists => ArchiveImportAction::Imported,
+        ConflictStrategy::Overwrite => ArchiveImportAction::Overwritten,
+        ConflictStrategy::Skip | ConflictStrategy::KeepBoth => {
+            item.action = ArchiveImportAction::Skipped;
+            return item;
+        }
+    };
+
+    let result = serde_json::from_slice::<super::path_config::AntigravityPathConfig>(bytes)
+        .map_err(|e| format!("路径配置格式无效: {}", e))
+        .and_then(|mut config| {
+            let local = super::path_config::get_custom_executable_path()
+                .ok()
+                .flatten();
+            config.custom_executable_path = match config.custom_executable_path.take() {
+                Some(executable) if import_executable_path => {
+                    check_executable_path(&executable)?;
+                    Some(executable)
+                }
+                Some(_) => {
+                    tracing::info!(target: "backup::archive", "未导入归档中的自定义可执行文件路径（未确认）");
+                    local
+                }
+                None => local,
+            };
+            super::path_config::replace_config(&config).map_err(String::from)
+        });
+    match result {
+        Ok(()) => {
+            item.action = action;
+            item.target = Some(PATH_CONFIG_ENTRY.to_string());
+        }
+        Err(e) => item.error = Some(e),
+    }
+    item
+}
+
+/// 汇总各文件的导入结果
+pub(crate) fn summarize(items: Vec<ArchiveImportItem>) -> ArchiveImportReport {
+    let count =
+        |action: ArchiveImportAction| items.iter().filter(|item| item.action == action).count();
+    let skipped_count = count(ArchiveImportAction::Skipped);
+    let failed_count = count(ArchiveImportAction::Failed);
+    ArchiveImportReport {
+        restored_count: items.len() - skipped_count - failed_count,
+        skipped_count,
+        failed_count,
+        items,
+    }
+}
+
+/// 解密归档并合并到备份目录，返回每个文件的导入结果
+///
+/// 单个文件失败不影响其他文件；每处理一个文件前检查 `token`，已取消时返回取消错误（已写入的备份保留）
+pub fn import(
+    path: &Path,
+    password: &str,
+    strategy: ConflictStrategy,
+    options: &ArchiveImportOptions,
+    token: &CancellationToken,
+) -> Result<ArchiveImportReport, String> {
+    let plaintext = decrypt(path, password, options)?;
+    let mut zip = zip::ZipArchive::new(Cursor::new(plaintext.as_slice()))
+        .map_err(|e| format!("归档内容已损坏: {}", e))?;
+    if zip.len() > super::archive::MAX_BACKUPS + 1 {
+        return Err(format!("备份数量超过 {} 个", super::archive::MAX_BACKUPS));
+    }
+
+    let accounts_dir = crate::directories::get_accounts_directory();
+    let mut items = Vec::new();
+    let mut path_config = None;
+    for index in 0..zip.len() {
+        token.check()?;
+        let file = zip
+            .by_index(index)
+            .map_err(|e| format!("读取归档内容失败: {}", e))?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let bytes = match read_entry(file) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                items.push(ArchiveImportItem {
+                    filename: name,
+                    action: ArchiveImportAction::Failed,
+                    target: None,
+                    error: Some(e),
+                });
+                continue;
+            }
+        };
+
+        if name == PATH_CONFIG_ENTRY {
+            path_config = Some(bytes);
+        } else if let Some(filename) = name.strip_prefix(ACCOUNTS_PREFIX) {
+            items.push(import_backup(
+                &accounts_dir,
+                filename.to_string(),
+                &bytes,
+                strategy,
+            ));
+        } else {
+            tracing::warn!(target: "backup::archive", entry = %name, "忽略归档中的未知文件");
+        }
+    }
+    if let Some(bytes) = path_config {
+        items.push(import_path_config(
+            &bytes,
+            strategy,
+            options.import_executable_path,
+        ));
+    }
+
+    let report = summarize(items);
+    tracing::info!(
+        target: "backup::archive",
+        strategy = ?strategy,
+        restored = report.restored_count,
+        skipped = report.skipped_count,
+        failed = report.failed_count,
+        "📥 已导入完整备份归档"
+    );
+    Ok(report)
+}
@@ -0,0 +1,351 @@
+// 账户备份增量链模块
+// 账户备份由完整快照（`<账户>.json`）加若干增量记录（`chains/<账户>/<序号>.json`）组成：
+// 每次备份只保存与上一次相比发生变化的键（删除的键记为 null），读取时按序重放得到完整内容。
+// `chains/<账户>/digests.json` 记录最近一次完整内容中每个键的摘要，备份时无需解密整条链即可比较
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::network_fs;
+
+/// 增量记录达到该数量后合并为新的完整快照
+const MAX_CHAIN_DELTAS: usize = 20;
+
+/// 键摘要文件名
+const DIGESTS_FILE: &str = "digests.json";
+
+/// 每个键的 SHA-256 摘要（十六进制）
+pub type Digests = BTreeMap<String, String>;
+
+/// 本次备份的写入方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainWrite {
+    /// 与上一次备份相同，未写入
+    Unchanged,
+    /// 写入了完整快照（首次备份、摘要缺失或增量已满）
+    Snapshot,
+    /// 写入了增量记录，包含变化的键
+    Delta { keys: Vec<String> },
+}
+
+/// 计算备份内容中每个键的摘要
+pub fn digests(data: &Value) -> Digests {
+    data.as_object()
+        .map(|object| {
+            object
+                .iter()
+                .map(|(key, value)| {
+                    let sha256 = Sha256::digest(value.to_string().as_bytes())
+                        .iter()
+                        .map(|byte| format!("{:02x}", byte))
+                        .collect();
+                    (key.clone(), sha256)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 与上一次备份的摘要比较，返回增量：变化或新增的键保存新值，删除的键记为 null
+pub fn diff(previous: &Digests, current: &Value) -> Map<String, Value> {
+    let current_digests = digests(current);
+    let mut delta = Map::new();
+
+    for (key, digest) in &current_digests {
+        if previous.get(key) != Some(digest) {
+            delta.insert(key.clone(), current[key.as_str()].clone());
+        }
+    }
+    for key in previous.keys() {
+        if !current_digests.contains_key(key) {
+            delta.insert(key.clone(), Value::Null);
+        }
+    }
+    delta
+}
+
+/// 把增量按顺序应用到完整快照上
+pub fn replay<'a>(base: &mut Value, deltas: impl IntoIterator<Item = &'a Value>) {
+    let Some(object) = base.as_object_mut() else {
+        return;
+    };
+    for delta in deltas {
+        let Some(changes) = delta.as_object() else {
+            continue;
+        };
+        for (key, value) in changes {
+            if value.is_null() {
+                object.remove(key);
+            } else {
+                object.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// 账户备份的增量链目录；不是账户备份文件时返回 `None`
+fn chain_directory(base_path: &Path) -> Option<PathBuf> {
+    crate::antigravity::backup::top_level_backup_filename(base_path)?;
+    let account = base_path.file_stem()?.to_string_lossy().to_string();
+    Some(
+        crate::directories::get_accounts_directory()
+            .join("chains")
+            .join(account),
+    )
+}
+
+/// 增量记录在钥匙串中的账户名（与账户备份区分）
+fn delta_keychain_account(account: &str, seq: u64) -> String {
+    format!("chain-{}-{}", account, seq)
+}
+
+/// 增量记录的序号（文件名为纯数字）
+fn delta_seq(path: &Path) -> Option<u64> {
+    if path.extension().is_none_or(|ext| ext != "json") {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+/// 列出目录中的增量记录，按序号从旧到新
+fn list_deltas(chain_dir: &Path) -> Vec<(u64, PathBuf)> {
+    let Ok(entries) = fs::read_dir(chain_dir) else {
+        return Vec::new();
+    };
+    let mut deltas: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| Some((delta_seq(&path)?, path)))
+        .collect();
+    deltas.sort();
+    deltas
+}
+
+/// 账户备份是否带有增量记录
+pub fn has_deltas(base_path: &Path) -> bool {
+    chain_directory(base_path).is_some_and(|dir| !list_deltas(&dir).is_empty())
+}
+
+/// 把账户备份的增量链重放到已读取的完整快照上（没有增量链时不做任何修改）
+pub fn apply(base_path: &Path, data: &mut Value) -> Result<(), String> {
+    let Some(chain_dir) = chain_directory(base_path) else {
+        return Ok(());
+    };
+    let deltas = list_deltas(&chain_dir)
+        .into_iter()
+        .map(|(_, path)| crate::antigravity::keychain::read_backup(&path))
+        .collect::<Result<Vec<Value>, String>>()?;
+    replay(data, &deltas);
+    Ok(())
+}
+
+/// 列出所有账户的增量记录文件（`chains/<账户>/<序号>.json`），按路径排序
+///
+/// 启用或关闭加密、钥匙串存储或切换压缩格式时，增量记录与账户备份一起重写
+pub fn list_all_delta_files() -> Vec<PathBuf> {
+    let chains_dir = crate::directories::get_accounts_directory().join("chains");
+    let Ok(accounts) = fs::read_dir(&chains_dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = accounts
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .flat_map(|dir| list_deltas(&dir).into_iter().map(|(_, path)| path))
+        .collect();
+    files.sort();
+    files
+}
+
+fn load_digests(chain_dir: &Path) -> Option<Digests> {
+    let content = network_fs::read_to_string(chain_dir.join(DIGESTS_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_digests(chain_dir: &Path, data: &Value) -> Result<(), String> {
+    let path = chain_dir.join(DIGESTS_FILE);
+    let content =
+        serde_json::to_string(&digests(data)).map_err(|e| format!("序列化备份摘要失败: {}", e))?;
+    network_fs::write(&path, content)
+        .map_err(|e| format!("写入备份摘要失败 {}: {}", path.display(), e))
+}
+
+/// 删除账户备份的增量链（包括钥匙串中的条目）
+///
+/// 写入新的完整快照或删除账户备份时调用，避免旧增量被重放到新快照上
+pub fn reset(base_path: &Path) {
+    let Some(chain_dir) = chain_directory(base_path) else {
+        return;
+    };
+    if !chain_dir.exists() {
+        return;
+    }
+
+    let account = chain_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for (seq, path) in list_deltas(&chain_dir) {
+        if let Err(e) = crate::utils::secure_delete::remove_file(&path) {
+            tracing::warn!(target: "backup::chain", error = %e, "删除增量记录失败");
+            continue;
+        }
+        crate::antigravity::keychain::delete(&delta_keychain_account(&account, seq));
+    }
+    if let Err(e) = fs::remove_dir_all(&chain_dir) {
+        tracing::warn!(target: "backup::chain", error = %e, "删除增量链目录失败");
+    }
+}
+
+/// 快照内容未变时更新修改时间，让“最近备份时间”保持准确
+fn touch(base_path: &Path) {
+    if let Err(e) = fs::File::options()
+        .write(true)
+        .open(base_path)
+        .and_then(|file| file.set_modified(std::time::SystemTime::now()))
+    {
+        tracing::warn!(target: "backup::chain", error = %e, "更新备份修改时间失败");
+    }
+}
+
+/// 保存账户备份：与上一次备份相比只写入变化的键
+///
+/// 首次备份、摘要缺失或增量记录达到上限时写入完整快照（同时清空增量链）
+pub fn save(account: &str, base_path: &Path, data: &Value) -> Result<ChainWrite, String> {
+    let chain_dir = chain_directory(base_path)
+        .ok_or_else(|| format!("不是账户备份文件: {}", base_path.display()))?;
+
+    let previous = if base_path.exists() {
+        load_digests(&chain_dir)
+    } else {
+        None
+    };
+    let deltas = list_deltas(&chain_dir);
+
+    let delta = previous.map(|previous| diff(&previous, data));
+    if delta.as_ref().is_some_and(Map::is_empty) {
+        touch(base_path);
+        crate::antigravity::backup_index::refresh(base_path);
+        return Ok(ChainWrite::Unchanged);
+    }
+
+    let Some(delta) = delta.filter(|_| deltas.len() < MAX_CHAIN_DELTAS) else {
+        crate::antigravity::keychain::write_backup(base_path, account, data)?;
+        fs::create_dir_all(&chain_dir)
+            .map_err(|e| format!("创建增量链目录失败 {}: {}", chain_dir.display(), e))?;
+        save_digests(&chain_dir, data)?;
+        tracing::info!(target: "backup::chain", account = %crate::utils::privacy::account(account), "📦 已写入完整备份快照");
+        return Ok(ChainWrite::Snapshot);
+    };
+
+    let seq = deltas.last().map(|(seq, _)| seq + 1).unwrap_or(1);
+    let delta_path = chain_dir.join(format!("{:06}.json", seq));
+    let keys: Vec<String> = delta.keys().cloned().collect();
+    crate::antigravity::keychain::write_backup(
+        &delta_path,
+        &delta_keychain_account(account, seq),
+        &Value::Object(delta),
+    )?;
+    save_digests(&chain_dir, data)?;
+
+    touch(base_path);
+    crate::antigravity::backup_index::record(base_path, data);
+
+    tracing::info!(target: "backup::chain", account = %crate::utils::privacy::account(account), seq = seq, keys = keys.len(), "🧩 已写入增量备份");
+    Ok(ChainWrite::Delta { keys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 依次备份多个状态，返回完整快照与增量记录
+    fn build_chain(states: &[Value]) -> (Value, Vec<Value>) {
+        let base = states[0].clone();
+        let mut previous = digests(&base);
+        let mut deltas = Vec::new();
+        for state in &states[1..] {
+            let delta = diff(&previous, state);
+            if !delta.is_empty() {
+                deltas.push(Value::Object(delta));
+            }
+            previous = digests(state);
+        }
+        (base, deltas)
+    }
+
+    #[test]
+    fn diff_keeps_only_changed_keys() {
+        let base = json!({ "a": "1", "b": "2" });
+        let current = json!({ "a": "1", "b": "3", "c": "4" });
+
+        let delta = diff(&digests(&base), &current);
+        assert_eq!(Value::Object(delta), json!({ "b": "3", "c": "4" }));
+    }
+
+    #[test]
+    fn diff_marks_removed_keys_as_null() {
+        let base = json!({ "a": "1", "b": "2" });
+        let current = json!({ "a": "1" });
+
+        let delta = diff(&digests(&base), &current);
+        assert_eq!(Value::Object(delta), json!({ "b": null }));
+    }
+
+    #[test]
+    fn diff_of_identical_state_is_empty() {
+        let state = json!({ "a": "1", "b": "2" });
+        assert!(diff(&digests(&state), &state).is_empty());
+    }
+
+    #[test]
+    fn replay_reconstructs_state_from_chain_with_multiple_deltas() {
+        let states = [
+            json!({ "a": "1", "b": "2", "c": "3" }),
+            json!({ "a": "1", "b": "20", "c": "3" }),
+            json!({ "a": "1", "b": "20", "d": "4" }),
+            json!({ "a": "10", "b": "20", "d": "4" }),
+        ];
+        let (mut base, deltas) = build_chain(&states);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[1], json!({ "c": null, "d": "4" }));
+
+        replay(&mut base, &deltas);
+        assert_eq!(base, states[3]);
+    }
+
+    #[test]
+    fn replay_of_chain_prefix_matches_intermediate_state() {
+        let states = [
+            json!({ "a": "1", "b": "2" }),
+            json!({ "a": "1", "b": "3" }),
+            json!({ "a": "5", "b": "3" }),
+        ];
+        let (base, deltas) = build_chain(&states);
+        assert_eq!(deltas.len(), 2);
+
+        for (applied, expected) in states.iter().enumerate() {
+            let mut state = base.clone();
+            replay(&mut state, &deltas[..applied]);
+            assert_eq!(&state, expected);
+        }
+    }
+
+    #[test]
+    fn unchanged_save_produces_no_delta() {
+        let states = [
+            json!({ "a": "1" }),
+            json!({ "a": "2" }),
+            json!({ "a": "2" }),
+            json!({ "a": "3" }),
+        ];
+        let (mut base, deltas) = build_chain(&states);
+        assert_eq!(deltas.len(), 2);
+
+        replay(&mut base, &deltas);
+        assert_eq!(base, json!({ "a": "3" }));
+    }
+}
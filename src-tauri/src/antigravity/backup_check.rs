@@ -0,0 +1,142 @@
+// 备份检查模块
+// 扫描账户备份目录中无法使用的备份：无法解析或解密、钥匙串中的登录状态已丢失、
+// 缺少或无法解码登录状态、登录状态中没有邮箱；并可将其移入回收目录
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cancellation::CancellationToken;
+use crate::constants::database;
+use crate::utils::network_fs;
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupProblemKind {
+    /// 文件无法读取、解析或解密
+    Unreadable,
+    /// 登录状态引用的钥匙串条目不存在
+    MissingSecret,
+    /// 缺少登录状态
+    MissingState,
+    /// 登录状态无法解码
+    InvalidState,
+    /// 登录状态中没有邮箱
+    MissingEmail,
+}
+
+/// 有问题的备份
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BackupProblem {
+    /// 备份名（文件名去掉扩展名）
+    pub name: String,
+    pub path: String,
+    pub kind: BackupProblemKind,
+    pub message: String,
+}
+
+/// 检查单个备份文件
+fn check(path: &std::path::Path) -> Option<(BackupProblemKind, String)> {
+    let content = match network_fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return Some((BackupProblemKind::Unreadable, e.to_string())),
+    };
+    let mut data = match super::vault::open(&content) {
+        Ok(data) => data,
+        Err(e) => return Some((BackupProblemKind::Unreadable, e)),
+    };
+    if let Err(e) = super::keychain::reassemble(&mut data) {
+        return Some((BackupProblemKind::MissingSecret, e));
+    }
+    if let Err(e) = super::backup_chain::apply(path, &mut data) {
+        return Some((BackupProblemKind::Unreadable, e));
+    }
+
+    let Some(state) = data.get(database::AGENT_STATE).and_then(Value::as_str) else {
+        return Some((
+            BackupProblemKind::MissingState,
+            format!("缺少 {}", database::AGENT_STATE),
+        ));
+    };
+    let session = match super::account::decode_jetski_state_proto(state) {
+        Ok(session) => session,
+        Err(e) => return Some((BackupProblemKind::InvalidState, e)),
+    };
+    let has_email = session["context"]["email"]
+        .as_str()
+        .is_some_and(|email| !email.is_empty());
+    if !has_email {
+        return Some((
+            BackupProblemKind::MissingEmail,
+            "登录状态中没有邮箱".to_string(),
+        ));
+    }
+    None
+}
+
+/// 扫描备份目录中有问题的备份
+///
+/// 备份目录已加密但尚未解锁时无法区分损坏与未解锁，直接返回错误；
+/// 每检查一个备份前检查 `token`，已取消时返回取消错误
+pub fn scan(token: &CancellationToken) -> Result<Vec<BackupProblem>, String> {
+    if super::vault::is_enabled() && !super::vault::status()?.unlocked {
+        return Err("备份目录已加密，请先解锁".to_string());
+    }
+
+    let mut problems = Vec::new();
+    for entry in super::backup::list_backup_files()? {
+        token.check()?;
+        if let Some((kind, message)) = check(&entry.path) {
+            problems.push(BackupProblem {
+                name: entry.name,
+                path: entry.path.display().to_string(),
+                kind,
+                message,
+            });
+        }
+    }
+
+    tracing::info!(target: "backup::check", problems = problems.len(), "🩺 备份检查完成");
+    Ok(problems)
+}
+
+/// 将指定备份移入回收目录（文件名加时间戳前缀），返回移动的文件数
+pub fn move_to_trash(names: &[String]) -> Result<usize, String> {
+    let accounts_dir = crate::directories::get_accounts_directory();
+    let trash_dir = crate::directories::get_backup_trash_directory();
+    fs::create_dir_all(&trash_dir).map_err(|e| format!("创建回收目录失败: {}", e))?;
+
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut moved = 0;
+    for name in names {
+        // 只接受备份目录中的文件名，拒绝路径
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(format!("备份名无效: {}", name));
+        }
+        let source = accounts_dir.join(format!("{}.json", name));
+        if !source.exists() {
+            continue;
+        }
+        // 带增量链的备份先合并为完整快照，回收目录中的副本才是完整内容
+        if super::backup_chain::has_deltas(&source) {
+            if let Err(e) = super::keychain::read_backup(&source)
+                .and_then(|data| super::keychain::write_backup(&source, name, &data))
+            {
+                tracing::warn!(target: "backup::check", name = %name, error = %e, "合并增量备份失败，回收的备份不含增量记录");
+            }
+        }
+        let target: PathBuf = trash_dir.join(format!("{}-{}.json", timestamp, name));
+        fs::rename(&source, &target)
+            .or_else(|_| fs::copy(&source, &target).and_then(|_| fs::remove_file(&source)))
+            .map_err(|e| format!("移动 {} 到回收目录失败: {}", name, e))?;
+        super::backup_manifest::forget(&source);
+        super::backup_index::forget(&source);
+        super::backup_chain::reset(&source);
+        moved += 1;
+    }
+
+    tracing::info!(target: "backup::check", moved = moved, trash = %trash_dir.display(), "🗑️ 已将有问题的备份移入回收目录");
+    Ok(moved)
+}
@@ -0,0 +1,238 @@
+// 备份元数据索引
+// 在 `backup_index.json` 中为每个账户备份保存文件名、邮箱、备份时间、文件大小与键摘要，
+// 写入、重写或删除备份时同步更新；列出备份时直接读取索引，不需要逐个读取、解密和解析备份文件。
+// 索引以文件的修改时间与大小判断是否过期：在 Agent 之外增删或修改的备份，列出时只按文件元数据返回，
+// 并在后台（不持有索引锁）重新读取这些文件、补全索引
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// 写入锁，避免并发更新时索引内容交错
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 后台重建是否正在进行（同一时间只运行一个）
+static REBUILDING: AtomicBool = AtomicBool::new(false);
+
+/// 索引中的单个备份
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct BackupIndexEntry {
+    pub filename: String,
+    /// 登录状态中的邮箱（无法解码时为 `None`）
+    pub email: Option<String>,
+    /// 备份时间（文件修改时间，RFC 3339）
+    pub backup_time: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 备份中保存的键（不含值；索引过期时为空）
+    pub keys: Vec<String>,
+    /// 索引中没有或已过期：邮箱与键摘要暂缺，正在后台重建
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// 备份文件名 -> 元数据
+type Index = BTreeMap<String, BackupIndexEntry>;
+
+fn load_index() -> Index {
+    fs::read_to_string(crate::directories::get_backup_index_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &Index) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(index).map_err(|e| format!("序列化备份索引失败: {}", e))?;
+    let path = crate::directories::get_backup_index_file();
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json).map_err(|e| format!("写入备份索引失败: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("替换备份索引失败: {}", e))
+}
+
+/// 更新索引（在写入锁内读取、修改并保存）
+fn update<F>(update_fn: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Index),
+{
+    let _lock = WRITE_LOCK
+        .lock()
+        .map_err(|e| format!("获取索引锁失败: {}", e))?;
+    let mut index = load_index();
+    update_fn(&mut index);
+    save_index(&index)
+}
+
+/// 文件的修改时间（RFC 3339）与大小
+fn file_stat(path: &Path) -> Option<(String, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?;
+    Some((
+        chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+        metadata.len(),
+    ))
+}
+
+/// 从备份内容（组装后的明文）生成索引条目
+fn build_entry(filename: String, path: &Path, data: &Value) -> Option<BackupIndexEntry> {
+    let (backup_time, size) = file_stat(path)?;
+    let email = data
+        .get(crate::constants::database::AGENT_STATE)
+        .and_then(Value::as_str)
+        .and_then(|state| super::account::decode_jetski_state_proto(state).ok())
+        .and_then(|session| session["context"]["email"].as_str().map(str::to_string));
+    let keys = data
+        .as_object()
+        .map(|map| map.keys().cloned().collect())
+        .unwrap_or_default();
+    Some(BackupIndexEntry {
+        filename,
+        email,
+        backup_time,
+        size,
+        keys,
+        stale: false,
+    })
+}
+
+/// 记录刚写入的备份（`data` 为写入前的完整内容；失败只记录警告，不影响备份本身）
+pub fn record(path: &Path, data: &Value) {
+    let Some(filename) = super::backup::top_level_backup_filename(path) else {
+        return;
+    };
+    let Some(entry) = build_entry(filename.clone(), path, data) else {
+        return;
+    };
+    if let Err(e) = update(|index| {
+        index.insert(filename, entry);
+    }) {
+        tracing::warn!(target: "backup::index", file = %path.display(), error = %e, "更新备份索引失败");
+    }
+}
+
+/// 备份只改变了存储形式（压缩、加密）或修改时间时，更新大小与备份时间
+pub fn refresh(path: &Path) {
+    let Some(filename) = super::backup::top_level_backup_filename(path) else {
+        return;
+    };
+    let Some((backup_time, size)) = file_stat(path) else {
+        return;
+    };
+    if let Err(e) = update(|index| {
+        if let Some(entry) = index.get_mut(&filename) {
+            entry.backup_time = backup_time;
+            entry.size = size;
+        }
+    }) {
+        tracing::warn!(target: "backup::index", file = %path.display(), error = %e, "更新备份索引失败");
+    }
+}
+
+/// 备份被删除或移走后移除其条目
+pub fn forget(path: &Path) {
+    let Some(filename) = super::backup::top_level_backup_filename(path) else {
+        return;
+    };
+    if let Err(e) = update(|index| {
+        index.remove(&filename);
+    }) {
+        tracing::warn!(target: "backup::index", file = %path.display(), error = %e, "移除备份索引条目失败");
+    }
+}
+
+/// 清空索引（清空所有备份后调用）
+pub fn clear() {
+    if let Err(e) = update(Index::clear) {
+        tracing::warn!(target: "backup::index", error = %e, "清空备份索引失败");
+    }
+}
+
+/// 按索引列出备份（按修改时间排序，最新的在前）
+///
+/// 只读取索引与文件元数据，不读取或解密备份内容；索引中没有或已过期的备份以 `stale`
+/// 标记列出（没有邮箱与键摘要），并在后台重新读取后写回索引，下次列出时即可得到完整条目
+pub fn list() -> Result<Vec<BackupIndexEntry>, String> {
+    let (entries, stale) = {
+        let _lock = WRITE_LOCK
+            .lock()
+            .map_err(|e| format!("获取索引锁失败: {}", e))?;
+        let index = load_index();
+        // `entries` 是返回的列表，`stored` 是写回的索引（只保留仍然有效的条目）
+        let mut entries = Vec::new();
+        let mut stored = Index::new();
+        let mut stale = Vec::new();
+
+        for backup in super::backup::list_backup_files()? {
+            let Some(filename) = super::backup::top_level_backup_filename(&backup.path) else {
+                continue;
+            };
+            let Some((backup_time, size)) = file_stat(&backup.path) else {
+                continue;
+            };
+            if let Some(entry) = index
+                .get(&filename)
+                .filter(|entry| entry.backup_time == backup_time && entry.size == size)
+            {
+                entries.push(entry.clone());
+                stored.insert(filename, entry.clone());
+                continue;
+            }
+
+            entries.push(BackupIndexEntry {
+                filename,
+                email: None,
+                backup_time,
+                size,
+                keys: Vec::new(),
+                stale: true,
+            });
+            stale.push(backup.path);
+        }
+
+        // 只在内容有变化时写回（包括移除已不存在或已过期的备份）
+        if stored != index {
+            if let Err(e) = save_index(&stored) {
+                tracing::warn!(target: "backup::index", error = %e, "保存备份索引失败");
+            }
+        }
+        (entries, stale)
+    };
+
+    tracing::debug!(target: "backup::index", count = entries.len(), stale = stale.len(), "📇 已按索引列出备份");
+    if !stale.is_empty() {
+        spawn_rebuild(stale);
+    }
+    Ok(entries)
+}
+
+/// 在后台读取过期的备份并写回索引（已有重建在进行时跳过，下次列出时重试）
+fn spawn_rebuild(paths: Vec<PathBuf>) {
+    // 备份目录已加密但未解锁时无法读取，解锁后再列出即可重建
+    if super::vault::is_enabled() && !super::vault::is_unlocked() {
+        return;
+    }
+    if REBUILDING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut rebuilt = 0;
+        for path in paths {
+            // 读取与解密不持有索引锁；`record` 只在写回时短暂加锁
+            match super::keychain::read_backup(&path) {
+                Ok(data) => {
+                    record(&path, &data);
+                    rebuilt += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(target: "backup::index", file = %path.display(), error = %e, "无法读取备份，暂时无法补全索引");
+                }
+            }
+        }
+        REBUILDING.store(false, Ordering::Release);
+        tracing::debug!(target: "backup::index", rebuilt = rebuilt, "📇 已在后台重建过期的索引条目");
+    });
+}
@@ -0,0 +1,254 @@
+// 备份完整性清单模块
+// 每次写入账户备份后把文件的 SHA-256 校验和与大小记录到 `backup_manifest.json`，
+// `verify` 据此找出被截断、损坏或在 Agent 之外被修改的备份，以及清单中有记录但文件已丢失的备份；
+// 清单建立之前就存在的备份标记为未记录，下次备份或重写后开始受保护
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::cancellation::CancellationToken;
+use crate::utils::network_fs;
+
+/// 写入锁，避免并发记录时清单内容交错
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 清单中的单个备份
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    sha256: String,
+    size: u64,
+    /// 记录时间（RFC 3339）
+    recorded_at: String,
+}
+
+/// 备份文件名 -> 校验记录
+type Manifest = BTreeMap<String, ManifestEntry>;
+
+/// 校验结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupIntegrityStatus {
+    /// 与记录一致
+    Ok,
+    /// 清单中没有记录（内容可以解析）
+    Untracked,
+    /// 文件比记录时小
+    Truncated,
+    /// 内容与记录不一致且无法解析
+    Corrupted,
+    /// 内容与记录不一致但仍可解析（在 Agent 之外被修改）
+    Modified,
+    /// 清单中有记录但文件不存在
+    Missing,
+    /// 文件无法读取
+    Unreadable,
+}
+
+/// 单个备份的校验结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BackupIntegrity {
+    /// 备份名（文件名去掉扩展名）
+    pub name: String,
+    pub status: BackupIntegrityStatus,
+    /// 记录的文件大小（字节）
+    pub expected_size: Option<u64>,
+    /// 实际文件大小（字节）
+    pub actual_size: Option<u64>,
+    /// 问题说明
+    pub message: Option<String>,
+}
+
+/// 备份校验报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BackupVerifyReport {
+    /// 检查的备份数（包括已丢失的）
+    pub checked: usize,
+    /// 有问题的备份数（截断、损坏、被修改、丢失或无法读取）
+    pub problems: usize,
+    pub results: Vec<BackupIntegrity>,
+}
+
+impl BackupIntegrityStatus {
+    /// 是否属于需要处理的问题（未记录不算）
+    fn is_problem(self) -> bool {
+        !matches!(
+            self,
+            BackupIntegrityStatus::Ok | BackupIntegrityStatus::Untracked
+        )
+    }
+}
+
+fn checksum(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn load_manifest() -> Manifest {
+    fs::read_to_string(crate::directories::get_backup_manifest_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(manifest: &Manifest) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(manifest).map_err(|e| format!("序列化备份清单失败: {}", e))?;
+    let path = crate::directories::get_backup_manifest_file();
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json).map_err(|e| format!("写入备份清单失败: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("替换备份清单失败: {}", e))
+}
+
+/// 只记录备份目录顶层的备份文件（安全快照与历史版本不在清单中）
+fn manifest_key(path: &Path) -> Option<String> {
+    super::backup::top_level_backup_filename(path)
+}
+
+/// 更新清单（在写入锁内读取、修改并保存）
+fn update<F>(update_fn: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Manifest),
+{
+    let _lock = WRITE_LOCK
+        .lock()
+        .map_err(|e| format!("获取清单锁失败: {}", e))?;
+    let mut manifest = load_manifest();
+    update_fn(&mut manifest);
+    save_manifest(&manifest)
+}
+
+/// 记录刚写入的备份文件的校验和（失败只记录警告，不影响备份本身）
+pub fn record(path: &Path) {
+    let Some(key) = manifest_key(path) else {
+        return;
+    };
+    let result = fs::read(path)
+        .map_err(|e| format!("读取备份失败: {}", e))
+        .and_then(|content| {
+            let entry = ManifestEntry {
+                sha256: checksum(&content),
+                size: content.len() as u64,
+                recorded_at: chrono::Local::now().to_rfc3339(),
+            };
+            update(|manifest| {
+                manifest.insert(key, entry);
+            })
+        });
+    if let Err(e) = result {
+        tracing::warn!(target: "backup::manifest", file = %path.display(), error = %e, "记录备份校验和失败");
+    }
+}
+
+/// 备份被删除或移走后移除其记录
+pub fn forget(path: &Path) {
+    let Some(key) = manifest_key(path) else {
+        return;
+    };
+    if let Err(e) = update(|manifest| {
+        manifest.remove(&key);
+    }) {
+        tracing::warn!(target: "backup::manifest", file = %path.display(), error = %e, "移除备份校验记录失败");
+    }
+}
+
+/// 清空清单（清空所有备份后调用）
+pub fn clear() {
+    if let Err(e) = update(Manifest::clear) {
+        tracing::warn!(target: "backup::manifest", error = %e, "清空备份清单失败");
+    }
+}
+
+/// 校验单个备份文件
+fn check(name: String, path: &Path, expected: Option<&ManifestEntry>) -> BackupIntegrity {
+    let mut result = BackupIntegrity {
+        name,
+        status: BackupIntegrityStatus::Ok,
+        expected_size: expected.map(|entry| entry.size),
+        actual_size: None,
+        message: None,
+    };
+
+    let content = match network_fs::with_retry(path, "read", || fs::read(path)) {
+        Ok(content) => content,
+        Err(e) => {
+            result.status = BackupIntegrityStatus::Unreadable;
+            result.message = Some(e.to_string());
+            return result;
+        }
+    };
+    let size = content.len() as u64;
+    result.actual_size = Some(size);
+    // 只检查 JSON 结构，不解密，未解锁的加密备份也能校验
+    let parse_error = serde_json::from_slice::<Value>(&content)
+        .err()
+        .map(|e| e.to_string());
+
+    result.status = match expected {
+        Some(entry) if entry.sha256 == checksum(&content) => BackupIntegrityStatus::Ok,
+        Some(entry) if size < entry.size => {
+            result.message = Some(format!("文件比记录时小 {} 字节", entry.size - size));
+            BackupIntegrityStatus::Truncated
+        }
+        Some(_) if parse_error.is_some() => {
+            result.message = parse_error;
+            BackupIntegrityStatus::Corrupted
+        }
+        Some(entry) => {
+            result.message = Some(format!("内容在 {} 之后被修改", entry.recorded_at));
+            BackupIntegrityStatus::Modified
+        }
+        None if parse_error.is_some() => {
+            result.message = parse_error;
+            BackupIntegrityStatus::Corrupted
+        }
+        None => BackupIntegrityStatus::Untracked,
+    };
+    result
+}
+
+/// 按清单校验备份目录中的所有备份
+///
+/// 每检查一个备份前检查 `token`，已取消时返回取消错误
+pub fn verify(token: &CancellationToken) -> Result<BackupVerifyReport, String> {
+    let mut manifest = load_manifest();
+    let mut results = Vec::new();
+    for entry in super::backup::list_backup_files()? {
+        token.check()?;
+        let key = entry
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let expected = manifest.remove(&key);
+        results.push(check(entry.name, &entry.path, expected.as_ref()));
+    }
+
+    // 清单中剩下的记录对应的文件已不存在
+    for (key, entry) in manifest {
+        results.push(BackupIntegrity {
+            name: key.strip_suffix(".json").unwrap_or(&key).to_string(),
+            status: BackupIntegrityStatus::Missing,
+            expected_size: Some(entry.size),
+            actual_size: None,
+            message: Some(format!("{} 记录后文件已丢失", entry.recorded_at)),
+        });
+    }
+
+    let problems = results
+        .iter()
+        .filter(|result| result.status.is_problem())
+        .count();
+    tracing::info!(target: "backup::manifest", checked = results.len(), problems = problems, "🔏 备份完整性校验完成");
+    Ok(BackupVerifyReport {
+        checked: results.len(),
+        problems,
+        results,
+    })
+}
@@ -1,15 +1,16 @@
 // Antigravity 用户数据清除模块
 // 负责清除 Antigravity 应用的所有用户认证和设置信息
 
-use rusqlite::{params, Connection};
+use rusqlite::params;
 use std::path::Path;
 
 // 导入 platform_utils 模块
 use crate::platform;
+use crate::utils::network_fs;
 
 fn clear_database(db_path: &Path, db_name: &str) -> Result<usize, String> {
     tracing::info!(target: "cleanup::database", db_name = %db_name, "开始清理数据库");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = network_fs::open_connection(db_path).map_err(|e| e.to_string())?;
 
     // 仅删除 jetskiStateSync.agentManagerInitState
     let key = "jetskiStateSync.agentManagerInitState";
@@ -0,0 +1,191 @@
+// 备份压缩模块
+// 开启后备份文件内容以 zstd 或 gzip 压缩，压缩数据以 base64 保存在 `$compressed` 标记键下，
+// 文件仍是 `<账户>.json`，列表、校验与导出等按文件名工作的逻辑不受影响；
+// 读取时由 `vault::open` 透明解压，压缩与未压缩的备份可以混用。
+// 同时启用加密时先压缩再加密
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::utils::network_fs;
+
+/// 压缩备份中的标记键
+const COMPRESSED_KEY: &str = "$compressed";
+
+/// zstd 压缩级别（兼顾速度与压缩率）
+const ZSTD_LEVEL: i32 = 9;
+
+/// 解压后大小上限，防止异常文件耗尽内存
+const MAX_DECOMPRESSED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// 备份压缩格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupCompression {
+    /// 不压缩（普通 JSON）
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl BackupCompression {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => BackupCompression::Gzip,
+            2 => BackupCompression::Zstd,
+            _ => BackupCompression::None,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            BackupCompression::None => 0,
+            BackupCompression::Gzip => 1,
+            BackupCompression::Zstd => 2,
+        }
+    }
+}
+
+/// 压缩数据封装
+#[derive(Debug, Serialize, Deserialize)]
+struct Compressed {
+    algorithm: BackupCompression,
+    /// 压缩后的 JSON（base64）
+    data: String,
+}
+
+/// 新写入的备份使用的压缩格式（默认不压缩，由设置决定）
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// 更新压缩格式
+pub fn set_mode(mode: BackupCompression) {
+    MODE.store(mode.as_u8(), Ordering::Relaxed);
+}
+
+/// 当前压缩格式
+pub fn mode() -> BackupCompression {
+    BackupCompression::from_u8(MODE.load(Ordering::Relaxed))
+}
+
+fn compress_bytes(algorithm: BackupCompression, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    match algorithm {
+        BackupCompression::None => Ok(bytes.to_vec()),
+        BackupCompression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(bytes)
+                .and_then(|()| encoder.finish())
+                .map_err(|e| format!("gzip 压缩失败: {}", e))
+        }
+        BackupCompression::Zstd => {
+            zstd::encode_all(bytes, ZSTD_LEVEL).map_err(|e| format!("zstd 压缩失败: {}", e))
+        }
+    }
+}
+
+fn decompress_bytes(algorithm: BackupCompression, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    let result = match algorithm {
+        BackupCompression::None => return Ok(bytes.to_vec()),
+        BackupCompression::Gzip => GzDecoder::new(bytes)
+            .take(MAX_DECOMPRESSED_BYTES + 1)
+            .read_to_end(&mut output),
+        BackupCompression::Zstd => zstd::Decoder::new(bytes).and_then(|decoder| {
+            decoder
+                .take(MAX_DECOMPRESSED_BYTES + 1)
+                .read_to_end(&mut output)
+        }),
+    };
+    result.map_err(|e| format!("解压备份失败: {}", e))?;
+    if output.len() as u64 > MAX_DECOMPRESSED_BYTES {
+        return Err("解压后的备份过大".to_string());
+    }
+    Ok(output)
+}
+
+/// 按当前压缩格式封装备份内容；不压缩时原样返回
+pub fn wrap(data: &Value) -> Result<Value, String> {
+    let algorithm = mode();
+    if algorithm == BackupCompression::None {
+        return Ok(data.clone());
+    }
+
+    let plaintext = serde_json::to_vec(data).map_err(|e| format!("序列化备份失败: {}", e))?;
+    let compressed = compress_bytes(algorithm, &plaintext)?;
+    Ok(json!({
+        COMPRESSED_KEY: Compressed {
+            algorithm,
+            data: BASE64.encode(compressed),
+        }
+    }))
+}
+
+/// 解开压缩封装；未压缩的内容原样返回
+pub fn unwrap(value: Value) -> Result<Value, String> {
+    let Some(compressed) = value.get(COMPRESSED_KEY) else {
+        return Ok(value);
+    };
+
+    let compressed: Compressed = serde_json::from_value(compressed.clone())
+        .map_err(|e| format!("压缩备份格式无效: {}", e))?;
+    let bytes = BASE64
+        .decode(&compressed.data)
+        .map_err(|e| format!("压缩备份数据无效: {}", e))?;
+    let plaintext = decompress_bytes(compressed.algorithm, &bytes)?;
+    serde_json::from_slice(&plaintext).map_err(|e| format!("解析解压后的备份失败: {}", e))
+}
+
+/// 备份文件内容当前的压缩格式；已加密（无法直接判断）或无法解析时返回 `None`
+fn stored_mode(content: &str) -> Option<BackupCompression> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    if crate::antigravity::vault::is_sealed(&value) {
+        return None;
+    }
+    match value.get(COMPRESSED_KEY) {
+        Some(compressed) => serde_json::from_value::<Compressed>(compressed.clone())
+            .ok()
+            .map(|compressed| compressed.algorithm),
+        None => Some(BackupCompression::None),
+    }
+}
+
+/// 按当前压缩格式重写备份目录中格式不同的备份，返回处理的文件数
+///
+/// 只改变文件的存储形式，钥匙串引用与加密状态保持不变（加密备份需要已解锁）；
+/// 账户的历史版本与增量记录一并处理
+pub fn migrate_backups() -> Result<usize, String> {
+    let target = mode();
+    let mut paths: Vec<PathBuf> = crate::antigravity::backup::list_backup_files()?
+        .into_iter()
+        .map(|backup| backup.path)
+        .collect();
+    paths.extend(crate::antigravity::backup::list_all_version_files());
+    paths.extend(crate::antigravity::backup_chain::list_all_delta_files());
+
+    let mut migrated = 0;
+    for path in paths {
+        let content = network_fs::read_to_string(&path)
+            .map_err(|e| format!("读取备份文件失败 {}: {}", path.display(), e))?;
+        if stored_mode(&content) == Some(target) {
+            continue;
+        }
+
+        let data = crate::antigravity::vault::open(&content)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        network_fs::write(&path, crate::antigravity::vault::seal_backup(&data)?)
+            .map_err(|e| format!("写入备份文件失败 {}: {}", path.display(), e))?;
+        super::backup_manifest::record(&path);
+        super::backup_index::refresh(&path);
+        migrated += 1;
+    }
+
+    tracing::info!(target: "backup::compression", mode = ?target, migrated = migrated, "🗜️ 备份压缩格式迁移完成");
+    Ok(migrated)
+}
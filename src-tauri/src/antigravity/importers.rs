@@ -0,0 +1,318 @@
+// 其他账户切换工具的导入
+// Cursor / VSCode 类账户切换工具大多通过快照编辑器的全局状态（`state.vscdb` 中的 ItemTable）
+// 来保存账户，这里识别它们常见的导出形态，从中取出 Antigravity 登录状态并转换为本应用的备份格式：
+// - `state_database`：整份复制的 `state.vscdb`（SQLite）
+// - `item_table_rows`：ItemTable 行转储，`[{"key": ..., "value": ...}]`
+// - `profile_list`：按账户保存键值快照的配置列表，
+//   `{"accounts"|"profiles": [{"email"|"name"|"label": ..., "storage"|"state"|"items"|"data": {...}}]}`
+// - `agent_export`：本应用导出的未加密备份列表，`[{"filename": ..., "content": {...}}]`
+// - `agent_backup`：单个备份文件，`{"jetskiStateSync.agentManagerInitState": ...}`
+
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::constants::database;
+
+/// 导入文件大小上限（状态数据库可能较大）
+const MAX_IMPORT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// SQLite 文件头
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// 配置列表中可能保存账户列表的字段
+const PROFILE_LIST_KEYS: &[&str] = &["accounts", "profiles"];
+
+/// 配置项中可能保存键值快照的字段
+const PROFILE_STORAGE_KEYS: &[&str] = &["storage", "state", "items", "data"];
+
+/// 配置项中可能保存账户名的字段
+const PROFILE_NAME_KEYS: &[&str] = &["email", "name", "label"];
+
+/// 识别出的导入格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    StateDatabase,
+    ItemTableRows,
+    ProfileList,
+    AgentExport,
+    AgentBackup,
+}
+
+/// 格式识别结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ImportDetection {
+    pub format: ImportFormat,
+    /// 文件中的账户记录数
+    pub records: usize,
+}
+
+/// 单条记录的导入结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ImportRecordResult {
+    /// 记录在源文件中的位置或名称
+    pub source: String,
+    /// 导入后的备份名（无法确定时为 `None`）
+    pub account: Option<String>,
+    pub imported: bool,
+    pub error: Option<String>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ImportReport {
+    pub format: ImportFormat,
+    pub imported: usize,
+    pub failed: usize,
+    pub records: Vec<ImportRecordResult>,
+}
+
+/// 源文件中的一条账户记录
+struct Candidate {
+    source: String,
+    /// 源文件中记录的账户名（登录状态中没有邮箱时使用）
+    name: Option<String>,
+    state: Result<String, String>,
+}
+
+/// 从键值快照（对象或 ItemTable 行数组）中取出登录状态
+fn state_from_storage(storage: &Value) -> Result<String, String> {
+    let value = match storage {
+        Value::Array(rows) => rows
+            .iter()
+            .find(|row| row["key"].as_str() == Some(database::AGENT_STATE))
+            .map(|row| &row["value"]),
+        Value::Object(items) => items.get(database::AGENT_STATE),
+        _ => None,
+    };
+    value
+        .and_then(Value::as_str)
+        .filter(|state| !state.trim().is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| format!("记录中没有 {}", database::AGENT_STATE))
+}
+
+fn is_rows(items: &[Value]) -> bool {
+    items
+        .iter()
+        .all(|item| item.get("key").is_some() && item.get("value").is_some())
+}
+
+fn is_export(items: &[Value]) -> bool {
+    items
+        .iter()
+        .all(|item| item["filename"].is_string() && item["content"].is_object())
+}
+
+fn is_profile(item: &Value) -> bool {
+    PROFILE_STORAGE_KEYS
+        .iter()
+        .any(|key| item.get(key).is_some())
+}
+
+fn profile_candidates(profiles: &[Value]) -> Vec<Candidate> {
+    profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| {
+            let name = PROFILE_NAME_KEYS
+                .iter()
+                .find_map(|key| profile[*key].as_str())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string);
+            let storage = PROFILE_STORAGE_KEYS
+                .iter()
+                .find_map(|key| profile.get(*key))
+                .unwrap_or(&Value::Null);
+            Candidate {
+                source: name.clone().unwrap_or_else(|| format!("#{}", index + 1)),
+                name,
+                state: state_from_storage(storage),
+            }
+        })
+        .collect()
+}
+
+/// 从 JSON 内容识别格式并取出记录
+fn parse_json(value: &Value) -> Option<(ImportFormat, Vec<Candidate>)> {
+    if let Some(items) = value.as_array().filter(|items| !items.is_empty()) {
+        if is_rows(items) {
+            return Some((
+                ImportFormat::ItemTableRows,
+                vec![Candidate {
+                    source: "#1".to_string(),
+                    name: None,
+                    state: state_from_storage(value),
+                }],
+            ));
+        }
+        if is_export(items) {
+            let candidates = items
+                .iter()
+                .map(|item| {
+                    let filename = item["filename"].as_str().unwrap_or_default();
+                    Candidate {
+                        source: filename.to_string(),
+                        name: Some(filename.trim_end_matches(".json").to_string()),
+                        state: state_from_storage(&item["content"]),
+                    }
+                })
+                .collect();
+            return Some((ImportFormat::AgentExport, candidates));
+        }
+        if items.iter().all(is_profile) {
+            return Some((ImportFormat::ProfileList, profile_candidates(items)));
+        }
+        return None;
+    }
+
+    if value.get(database::AGENT_STATE).is_some() {
+        return Some((
+            ImportFormat::AgentBackup,
+            vec![Candidate {
+                source: "#1".to_string(),
+                name: None,
+                state: state_from_storage(value),
+            }],
+        ));
+    }
+
+    PROFILE_LIST_KEYS
+        .iter()
+        .find_map(|key| value[*key].as_array())
+        .filter(|profiles| !profiles.is_empty() && profiles.iter().all(is_profile))
+        .map(|profiles| (ImportFormat::ProfileList, profile_candidates(profiles)))
+}
+
+/// 从状态数据库副本中读取登录状态
+fn read_database(path: &Path) -> Result<Vec<Candidate>, String> {
+    let conn = crate::utils::network_fs::open_connection(path)
+        .map_err(|e| format!("打开状态数据库失败 ({}): {}", path.display(), e))?;
+    let state: Option<String> = conn
+        .query_row(
+            "SELECT value FROM ItemTable WHERE key = ?",
+            [database::AGENT_STATE],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("读取状态数据库失败: {}", e))?;
+    Ok(vec![Candidate {
+        source: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "#1".to_string()),
+        name: None,
+        state: state.ok_or_else(|| format!("数据库中没有 {}", database::AGENT_STATE)),
+    }])
+}
+
+/// 识别文件格式并取出所有记录
+fn read_candidates(path: &Path) -> Result<(ImportFormat, Vec<Candidate>), String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("读取导入文件失败 {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_IMPORT_BYTES {
+        return Err(format!(
+            "导入文件过大（{} 字节，上限 {} 字节）",
+            size, MAX_IMPORT_BYTES
+        ));
+    }
+
+    let bytes =
+        fs::read(path).map_err(|e| format!("读取导入文件失败 {}: {}", path.display(), e))?;
+    if bytes.starts_with(SQLITE_HEADER) {
+        return Ok((ImportFormat::StateDatabase, read_database(path)?));
+    }
+
+    let value: Value = serde_json::from_slice(&bytes)
+        .map_err(|_| "无法识别的导入文件：既不是状态数据库也不是 JSON".to_string())?;
+    parse_json(&value).ok_or_else(|| "无法识别的导入文件格式".to_string())
+}
+
+/// 识别导入文件的格式与记录数
+pub fn detect(path: &Path) -> Result<ImportDetection, String> {
+    let (format, candidates) = read_candidates(path)?;
+    Ok(ImportDetection {
+        format,
+        records: candidates.len(),
+    })
+}
+
+/// 将一条记录转换为备份名与备份内容
+fn convert(candidate: Candidate) -> Result<(String, Value), String> {
+    let state = candidate.state?;
+    let session = super::account::decode_jetski_state_proto(&state)
+        .map_err(|e| format!("登录状态无效: {}", e))?;
+    let account = session["context"]["email"]
+        .as_str()
+        .filter(|email| !email.is_empty())
+        .map(str::to_string)
+        .or(candidate.name)
+        .ok_or_else(|| "无法确定账户名：登录状态中没有邮箱".to_string())?;
+
+    let content = json!({ database::AGENT_STATE: state });
+    super::archive::validate_backup(&format!("{}.json", account), &content)?;
+    Ok((account, content))
+}
+
+/// 导入文件中的所有账户，`expected` 指定格式时与识别结果不一致则拒绝
+pub fn import(path: &Path, expected: Option<ImportFormat>) -> Result<ImportReport, String> {
+    let (format, candidates) = read_candidates(path)?;
+    if let Some(expected) = expected.filter(|expected| *expected != format) {
+        return Err(format!(
+            "导入文件格式为 {:?}，与指定的 {:?} 不一致",
+            format, expected
+        ));
+    }
+    if candidates.len() > super::archive::MAX_BACKUPS {
+        return Err(format!("账户记录超过 {} 个", super::archive::MAX_BACKUPS));
+    }
+
+    let accounts_dir = crate::directories::get_accounts_directory();
+    fs::create_dir_all(&accounts_dir).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let mut report = ImportReport {
+        format,
+        imported: 0,
+        failed: 0,
+        records: Vec::with_capacity(candidates.len()),
+    };
+    for candidate in candidates {
+        let source = candidate.source.clone();
+        let result = convert(candidate).and_then(|(account, content)| {
+            super::keychain::write_backup(
+                &accounts_dir.join(format!("{}.json", account)),
+                &account,
+                &content,
+            )
+            .map(|_| account)
+        });
+        match result {
+            Ok(account) => {
+                report.imported += 1;
+                report.records.push(ImportRecordResult {
+                    source,
+                    account: Some(account),
+                    imported: true,
+                    error: None,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(target: "account::import", source = %source, error = %e, "跳过无法导入的记录");
+                report.failed += 1;
+                report.records.push(ImportRecordResult {
+                    source,
+                    account: None,
+                    imported: false,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    tracing::info!(target: "account::import", format = ?format, imported = report.imported, failed = report.failed, "📥 已从其他工具导入账户");
+    Ok(report)
+}
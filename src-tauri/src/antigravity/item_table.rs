@@ -0,0 +1,124 @@
+// Antigravity ItemTable 浏览模块
+// 按键名列出、读取和修改状态数据库（state.vscdb）中的任意条目，供高级用户排查问题
+
+use rusqlite::types::ValueRef;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::platform;
+use crate::utils::network_fs;
+
+/// 单次列出的最大键数量
+const MAX_KEYS: usize = 5000;
+
+/// ItemTable 中的键
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ItemTableKey {
+    pub key: String,
+    /// 值的字节数
+    pub size: u32,
+}
+
+/// ItemTable 中的条目
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ItemTableValue {
+    pub key: String,
+    /// 值（BLOB 按 UTF-8 解码，无效字节替换为 U+FFFD）
+    pub value: String,
+    /// 原始值是否为 BLOB
+    pub binary: bool,
+    /// 值的字节数
+    pub size: u32,
+}
+
+fn database_path() -> Result<PathBuf, String> {
+    platform::get_antigravity_db_path()
+        .filter(|path| path.exists())
+        .ok_or_else(|| "未找到 Antigravity 状态数据库".to_string())
+}
+
+/// 将搜索模式转换为 SQLite GLOB 模式：包含 `*` / `?` 时按通配符匹配，否则按包含匹配
+fn glob_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        "*".to_string()
+    } else if pattern.contains(['*', '?']) {
+        pattern.to_string()
+    } else {
+        // 转义 GLOB 的字符集语法，按字面量匹配
+        format!("*{}*", pattern.replace('[', "[[]"))
+    }
+}
+
+/// 列出匹配模式的键（按键名排序，最多 5000 个）
+pub fn list_keys(pattern: Option<&str>) -> Result<Vec<ItemTableKey>, String> {
+    let conn = network_fs::open_connection(&database_path()?).map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT key, length(CAST(value AS BLOB)) FROM ItemTable WHERE key GLOB ? ORDER BY key LIMIT ?",
+        )
+        .map_err(|e| format!("查询 ItemTable 失败: {}", e))?;
+    let keys = stmt
+        .query_map(
+            params![glob_pattern(pattern.unwrap_or_default()), MAX_KEYS],
+            |row| {
+                Ok(ItemTableKey {
+                    key: row.get(0)?,
+                    size: row.get::<_, Option<u32>>(1)?.unwrap_or(0),
+                })
+            },
+        )
+        .map_err(|e| format!("查询 ItemTable 失败: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("读取 ItemTable 失败: {}", e))?;
+    Ok(keys)
+}
+
+/// 读取指定键的值，键不存在时返回 `None`
+pub fn get_value(key: &str) -> Result<Option<ItemTableValue>, String> {
+    let conn = network_fs::open_connection(&database_path()?).map_err(|e| e.to_string())?;
+    conn.query_row("SELECT value FROM ItemTable WHERE key = ?", [key], |row| {
+        let value = row.get_ref(0)?;
+        let (text, binary) = match value {
+            ValueRef::Null => (String::new(), false),
+            ValueRef::Text(text) => (String::from_utf8_lossy(text).into_owned(), false),
+            ValueRef::Blob(blob) => (String::from_utf8_lossy(blob).into_owned(), true),
+            ValueRef::Integer(i) => (i.to_string(), false),
+            ValueRef::Real(f) => (f.to_string(), false),
+        };
+        let size = match value {
+            ValueRef::Text(bytes) | ValueRef::Blob(bytes) => bytes.len(),
+            _ => text.len(),
+        };
+        Ok(ItemTableValue {
+            key: key.to_string(),
+            value: text,
+            binary,
+            size: size as u32,
+        })
+    })
+    .optional()
+    .map_err(|e| format!("读取 {} 失败: {}", key, e))
+}
+
+/// 写入指定键的值（不存在时新建），写入前先创建安全快照
+///
+/// Antigravity 运行时会在内存中保留状态并在退出时写回，修改应在其关闭后进行
+pub fn set_value(key: &str, value: &str) -> Result<String, String> {
+    if key.trim().is_empty() {
+        return Err("键名不能为空".to_string());
+    }
+    let db_path = database_path()?;
+    crate::antigravity::backup::create_safety_snapshot()?;
+
+    let conn = network_fs::open_connection(&db_path).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+        params![key, value],
+    )
+    .map_err(|e| format!("写入 {} 失败: {}", key, e))?;
+
+    tracing::info!(target: "item_table::set", key = %key, size = value.len(), "✏️ 已修改 ItemTable 条目");
+    Ok(format!("已写入 {}（{} 字节）", key, value.len()))
+}
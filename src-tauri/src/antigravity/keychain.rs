@@ -0,0 +1,189 @@
+// 系统钥匙串存储模块
+// 开启后备份文件中的登录状态（含 OAuth 令牌）保存到系统钥匙串（macOS Keychain /
+// Windows 凭据管理器 / Secret Service），JSON 中只保留引用，恢复时再重新组装
+
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::constants::database;
+use crate::utils::network_fs;
+
+/// 钥匙串服务名
+const SERVICE: &str = "antigravity-agent";
+
+/// 备份 JSON 中表示钥匙串引用的键
+const REFERENCE_KEY: &str = "$keychain";
+
+/// 单个钥匙串条目保存的最大字符数（Windows 凭据上限为 2560 字节）
+const CHUNK_SIZE: usize = 2048;
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 更新启用状态
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn entry(account: &str, index: usize) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, &format!("{}#{}", account, index))
+        .map_err(|e| format!("打开钥匙串条目失败: {}", e))
+}
+
+/// 把值分段写入钥匙串，返回段数
+fn store(account: &str, secret: &str) -> Result<usize, String> {
+    let chars: Vec<char> = secret.chars().collect();
+    let chunks: Vec<String> = chars
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| chunk.iter().collect())
+        .collect();
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        entry(account, index)?
+            .set_password(chunk)
+            .map_err(|e| format!("写入钥匙串失败: {}", e))?;
+    }
+    Ok(chunks.len())
+}
+
+/// 读取并拼接分段
+fn load(account: &str, chunks: usize) -> Result<String, String> {
+    let mut secret = String::new();
+    for index in 0..chunks {
+        let chunk = entry(account, index)?
+            .get_password()
+            .map_err(|e| format!("读取钥匙串失败 ({}): {}", account, e))?;
+        secret.push_str(&chunk);
+    }
+    Ok(secret)
+}
+
+/// 删除账户在钥匙串中的所有分段（条目不存在时忽略）
+pub fn delete(account: &str) {
+    let mut index = 0;
+    while let Ok(credential) = entry(account, index) {
+        match credential.delete_credential() {
+            Ok(()) => index += 1,
+            Err(keyring::Error::NoEntry) => break,
+            Err(e) => {
+                tracing::warn!(target: "backup::keychain", account = %account, error = %e, "删除钥匙串条目失败");
+                break;
+            }
+        }
+    }
+}
+
+/// 备份数据中的登录状态是否为钥匙串引用
+fn reference(data: &Value) -> Option<(String, usize)> {
+    let reference = data.get(database::AGENT_STATE)?.get(REFERENCE_KEY)?;
+    let account = reference.get("account")?.as_str()?.to_string();
+    let chunks = reference.get("chunks")?.as_u64()? as usize;
+    Some((account, chunks))
+}
+
+/// 把登录状态移入钥匙串，JSON 中替换为引用
+fn split_secrets(account: &str, data: &mut Value) -> Result<(), String> {
+    let Some(secret) = data.get(database::AGENT_STATE).and_then(Value::as_str) else {
+        return Ok(());
+    };
+
+    // 先清除旧分段，避免新值段数更少时残留
+    delete(account);
+    let chunks = store(account, secret)?;
+    data[database::AGENT_STATE] = json!({
+        REFERENCE_KEY: { "account": account, "chunks": chunks }
+    });
+    Ok(())
+}
+
+/// 将钥匙串引用替换回原始登录状态（不是引用时原样返回）
+pub fn reassemble(data: &mut Value) -> Result<(), String> {
+    let Some((account, chunks)) = reference(data) else {
+        return Ok(());
+    };
+
+    data[database::AGENT_STATE] = Value::String(load(&account, chunks)?);
+    Ok(())
+}
+
+/// 读取备份文件并组装完整内容
+pub fn read_backup(path: &Path) -> Result<Value, String> {
+    let content = network_fs::read_to_string(path)
+        .map_err(|e| format!("读取备份文件失败 {}: {}", path.display(), e))?;
+    let mut data = crate::antigravity::vault::open(&content)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    reassemble(&mut data)?;
+    crate::antigravity::backup_chain::apply(path, &mut data)?;
+    Ok(data)
+}
+
+/// 写入备份文件；启用钥匙串时登录状态保存到钥匙串，文件中只保留引用；
+/// 备份目录已加密时写入密文。写入账户备份时视为新的完整快照，清空其增量链
+pub fn write_backup(path: &Path, account: &str, data: &Value) -> Result<(), String> {
+    let mut stored = data.clone();
+    if is_enabled() {
+        split_secrets(account, &mut stored)?;
+    }
+
+    let content = crate::antigravity::vault::seal_backup(&stored)?;
+    network_fs::write(path, content)
+        .map_err(|e| format!("写入备份文件失败 {}: {}", path.display(), e))?;
+    crate::antigravity::backup_chain::reset(path);
+    crate::antigravity::backup_manifest::record(path);
+    crate::antigravity::backup_index::record(path, data);
+    Ok(())
+}
+
+/// 按当前开关重写备份目录中的所有备份，返回处理的文件数
+///
+/// 开启时把明文登录状态移入钥匙串；关闭时写回明文并删除钥匙串条目。
+/// 账户的历史版本一并处理（各自使用独立的钥匙串条目）；带增量链的账户备份
+/// 重放后合并为完整快照
+pub fn migrate_backups() -> Result<usize, String> {
+    // (备份文件, 钥匙串账户名)
+    let mut backups: Vec<(PathBuf, String)> = crate::antigravity::backup::list_backup_files()?
+        .into_iter()
+        .map(|backup| (backup.path, backup.name))
+        .collect();
+    backups.extend(
+        crate::antigravity::backup::list_all_version_files()
+            .into_iter()
+            .filter_map(|path| {
+                let account = crate::antigravity::backup::version_file_keychain_account(&path)?;
+                Some((path, account))
+            }),
+    );
+
+    let mut migrated = 0;
+    for (path, account) in backups {
+        let content = network_fs::read_to_string(&path)
+            .map_err(|e| format!("读取备份文件失败 {}: {}", path.display(), e))?;
+        let mut data = crate::antigravity::vault::open(&content)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        // 已经是目标形式且没有增量链的备份无需处理（增量记录在合并为完整快照时删除）
+        let has_deltas = crate::antigravity::backup_chain::has_deltas(&path);
+        if reference(&data).is_some() == is_enabled() && !has_deltas {
+            continue;
+        }
+
+        reassemble(&mut data)?;
+        crate::antigravity::backup_chain::apply(&path, &mut data)?;
+        write_backup(&path, &account, &data)?;
+        if !is_enabled() {
+            delete(&account);
+        }
+        migrated += 1;
+    }
+    // 变更日志中凭据的保存形式同样取决于钥匙串开关
+    crate::state_journal::reseal()?;
+
+    tracing::info!(target: "backup::keychain", enabled = is_enabled(), migrated = migrated, "🔐 备份钥匙串迁移完成");
+    Ok(migrated)
+}
@@ -0,0 +1,183 @@
+// Antigravity 设备标识模块
+// 读取并重新生成 Antigravity 的遥测设备标识（storage.json 中的 telemetry.* 键、
+// 状态数据库中的 storage.serviceMachineId 以及 machineid 文件），与账户清理相互独立
+
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::platform;
+use crate::utils::network_fs;
+
+/// storage.json 中的标识键
+const MACHINE_ID_KEY: &str = "telemetry.machineId";
+const MAC_MACHINE_ID_KEY: &str = "telemetry.macMachineId";
+const DEV_DEVICE_ID_KEY: &str = "telemetry.devDeviceId";
+const SQM_ID_KEY: &str = "telemetry.sqmId";
+
+/// 状态数据库中的标识键
+const SERVICE_MACHINE_ID_KEY: &str = "storage.serviceMachineId";
+
+/// Antigravity 的设备标识（未找到的为 `None`）
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+pub struct MachineIds {
+    pub machine_id: Option<String>,
+    pub mac_machine_id: Option<String>,
+    pub dev_device_id: Option<String>,
+    pub sqm_id: Option<String>,
+    pub service_machine_id: Option<String>,
+    /// machineid 文件内容
+    pub machine_id_file: Option<String>,
+}
+
+fn data_dir() -> Result<PathBuf, String> {
+    platform::get_antigravity_data_dir()
+        .filter(|dir| dir.exists())
+        .ok_or_else(|| "未找到 Antigravity 数据目录".to_string())
+}
+
+/// `User/globalStorage/storage.json`
+fn storage_json_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("storage.json")
+}
+
+/// Antigravity 配置根目录下的 `machineid` 文件（位于 `User/globalStorage` 的上两级）
+fn machine_id_file_path(data_dir: &Path) -> Option<PathBuf> {
+    data_dir
+        .parent()?
+        .parent()
+        .map(|root| root.join("machineid"))
+}
+
+fn read_storage_json(path: &Path) -> Result<Map<String, Value>, String> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} 不是 JSON 对象", path.display())),
+        Err(e) => Err(format!("解析 {} 失败: {}", path.display(), e)),
+    }
+}
+
+fn read_service_machine_id(db_path: &Path) -> Result<Option<String>, String> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let conn = network_fs::open_connection(db_path).map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT value FROM ItemTable WHERE key = ?",
+        [SERVICE_MACHINE_ID_KEY],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| format!("读取 {} 失败: {}", SERVICE_MACHINE_ID_KEY, e))
+}
+
+/// 读取当前的设备标识
+pub fn read() -> Result<MachineIds, String> {
+    let data_dir = data_dir()?;
+    let storage = read_storage_json(&storage_json_path(&data_dir))?;
+    let field = |key: &str| storage.get(key).and_then(Value::as_str).map(str::to_string);
+
+    Ok(MachineIds {
+        machine_id: field(MACHINE_ID_KEY),
+        mac_machine_id: field(MAC_MACHINE_ID_KEY),
+        dev_device_id: field(DEV_DEVICE_ID_KEY),
+        sqm_id: field(SQM_ID_KEY),
+        service_machine_id: read_service_machine_id(&data_dir.join("state.vscdb"))?,
+        machine_id_file: machine_id_file_path(&data_dir)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| content.trim().to_string()),
+    })
+}
+
+/// 与 VS Code 相同格式的 64 位十六进制标识
+fn new_hex_id() -> String {
+    format!("{:x}", Sha256::digest(uuid::Uuid::new_v4().as_bytes()))
+}
+
+fn new_uuid() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 旧标识的备份目录
+fn backup_directory() -> PathBuf {
+    crate::directories::get_config_directory().join("machine-id-backups")
+}
+
+/// 把旧标识写入备份目录，返回备份文件路径
+fn backup_previous(previous: &MachineIds) -> Result<PathBuf, String> {
+    let dir = backup_directory();
+    fs::create_dir_all(&dir).map_err(|e| format!("创建设备标识备份目录失败: {}", e))?;
+    let path = dir.join(format!(
+        "{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let content = serde_json::to_string_pretty(previous).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("写入设备标识备份失败: {}", e))?;
+    Ok(path)
+}
+
+/// 先写临时文件再替换，避免写入中断留下半个 storage.json
+fn write_storage_json(path: &Path, storage: &Map<String, Value>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(storage).map_err(|e| e.to_string())?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("写入 {} 失败: {}", temp.display(), e))?;
+    fs::rename(&temp, path).map_err(|e| format!("替换 {} 失败: {}", path.display(), e))
+}
+
+/// 重新生成设备标识
+///
+/// storage.json 中的 machineId / macMachineId / devDeviceId 总是写入，sqmId、serviceMachineId 与
+/// machineid 文件只在原本存在时替换；旧值保存到配置目录的 `machine-id-backups` 中，写入前创建登录状态
+/// 安全快照。Antigravity 运行时会在退出时写回 storage.json，因此必须在其关闭后调用
+pub fn regenerate() -> Result<MachineIds, String> {
+    if platform::is_antigravity_running() {
+        return Err("请先关闭 Antigravity 再重新生成设备标识".to_string());
+    }
+
+    let data_dir = data_dir()?;
+    let previous = read()?;
+    let backup = backup_previous(&previous)?;
+    crate::antigravity::backup::create_safety_snapshot()?;
+
+    // storage.json
+    let storage_path = storage_json_path(&data_dir);
+    let mut storage = read_storage_json(&storage_path)?;
+    storage.insert(MACHINE_ID_KEY.to_string(), Value::String(new_hex_id()));
+    storage.insert(MAC_MACHINE_ID_KEY.to_string(), Value::String(new_hex_id()));
+    storage.insert(DEV_DEVICE_ID_KEY.to_string(), Value::String(new_uuid()));
+    if previous.sqm_id.as_deref().is_some_and(|id| !id.is_empty()) {
+        let sqm_id = format!("{{{}}}", new_uuid().to_uppercase());
+        storage.insert(SQM_ID_KEY.to_string(), Value::String(sqm_id));
+    }
+    write_storage_json(&storage_path, &storage)?;
+
+    // 状态数据库
+    let db_path = data_dir.join("state.vscdb");
+    if previous.service_machine_id.is_some() {
+        let conn = network_fs::open_connection(&db_path).map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE ItemTable SET value = ? WHERE key = ?",
+            params![new_uuid(), SERVICE_MACHINE_ID_KEY],
+        )
+        .map_err(|e| format!("写入 {} 失败: {}", SERVICE_MACHINE_ID_KEY, e))?;
+    }
+
+    // machineid 文件
+    if previous.machine_id_file.is_some() {
+        if let Some(path) = machine_id_file_path(&data_dir) {
+            fs::write(&path, new_uuid())
+                .map_err(|e| format!("写入 {} 失败: {}", path.display(), e))?;
+        }
+    }
+
+    tracing::info!(target: "machine_id::regenerate", backup = %backup.display(), "🆔 已重新生成 Antigravity 设备标识");
+    read()
+}
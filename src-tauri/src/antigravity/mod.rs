@@ -1,5 +1,22 @@
 pub mod account;
+pub mod archive;
+pub mod backup;
+pub mod backup_archive;
+pub mod backup_chain;
+pub mod backup_check;
+pub mod backup_index;
+pub mod backup_manifest;
 pub mod cleanup;
+pub mod compression;
+pub mod importers;
+pub mod item_table;
+pub mod keychain;
+pub mod machine_id;
 pub mod path_config;
+pub mod report;
 pub mod restore;
 pub mod starter;
+pub mod transfer;
+pub mod vault;
+pub mod wire;
+pub mod workspace_storage;
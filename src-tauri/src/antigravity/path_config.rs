@@ -2,15 +2,19 @@
 //! 负责保存和读取用户自定义的 Antigravity 可执行文件路径
 
 use crate::directories;
+use crate::error::{CoreError, CoreResult};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
 /// Antigravity 路径配置
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct AntigravityPathConfig {
     /// 用户自定义的 Antigravity 可执行文件路径
     pub custom_executable_path: Option<String>,
+    /// 首次运行引导检测到的 Antigravity 数据目录
+    pub data_dir: Option<String>,
 }
 
 /// 获取配置文件路径
@@ -19,7 +23,7 @@ fn get_config_file_path() -> PathBuf {
 }
 
 /// 保存用户自定义可执行文件路径
-pub fn save_custom_executable_path(path: String) -> Result<(), String> {
+pub fn save_custom_executable_path(path: String) -> CoreResult<()> {
     let config_file = get_config_file_path();
     let mut config = read_config().unwrap_or_default();
 
@@ -30,47 +34,68 @@ pub fn save_custom_executable_path(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 保存首次运行引导的检测结果（只写入检测成功的项）
+pub fn save_detection(executable_path: Option<String>, data_dir: Option<String>) -> CoreResult<()> {
+    let config_file = get_config_file_path();
+    let mut config = read_config().unwrap_or_default();
+
+    if let Some(path) = executable_path {
+        config.custom_executable_path = Some(path);
+    }
+    if let Some(dir) = data_dir {
+        config.data_dir = Some(dir);
+    }
+    write_config(&config_file, &config)?;
+
+    tracing::info!("✅ 已保存首次运行检测结果");
+    Ok(())
+}
+
+/// 用导入的配置整体替换当前配置（完整备份归档导入时使用）
+pub fn replace_config(config: &AntigravityPathConfig) -> CoreResult<()> {
+    write_config(&get_config_file_path(), config)?;
+    tracing::info!("✅ 已导入 Antigravity 路径配置");
+    Ok(())
+}
+
 /// 写入配置到文件
-fn write_config(
-    config_file: &std::path::Path,
-    config: &AntigravityPathConfig,
-) -> Result<(), String> {
-    let json =
-        serde_json::to_string_pretty(config).map_err(|e| format!("序列化配置失败: {}", e))?;
-    fs::write(config_file, json).map_err(|e| format!("写入配置文件失败: {}", e))?;
+fn write_config(config_file: &std::path::Path, config: &AntigravityPathConfig) -> CoreResult<()> {
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| CoreError::Other(format!("序列化配置失败: {}", e)))?;
+    fs::write(config_file, json).map_err(|e| CoreError::io(config_file, e))?;
+    crate::config_integrity::record(config_file);
     Ok(())
 }
 
 /// 读取配置文件
-fn read_config() -> Result<AntigravityPathConfig, String> {
+fn read_config() -> CoreResult<AntigravityPathConfig> {
     let config_file = get_config_file_path();
 
     if !config_file.exists() {
         return Ok(AntigravityPathConfig::default());
     }
 
-    let content =
-        fs::read_to_string(&config_file).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    let content = fs::read_to_string(&config_file).map_err(|e| CoreError::io(&config_file, e))?;
 
     let config: AntigravityPathConfig =
-        serde_json::from_str(&content).map_err(|e| format!("解析配置文件失败: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| CoreError::parse("路径配置文件", e))?;
 
     Ok(config)
 }
 
 /// 从配置文件读取自定义可执行文件路径
-pub fn get_custom_executable_path() -> Result<Option<String>, String> {
+pub fn get_custom_executable_path() -> CoreResult<Option<String>> {
     let config = read_config()?;
     Ok(config.custom_executable_path)
 }
 
 /// 清除自定义路径配置
 #[allow(dead_code)]
-pub fn clear_custom_path() -> Result<(), String> {
+pub fn clear_custom_path() -> CoreResult<()> {
     let config_file = get_config_file_path();
 
     if config_file.exists() {
-        fs::remove_file(&config_file).map_err(|e| format!("删除配置文件失败: {}", e))?;
+        fs::remove_file(&config_file).map_err(|e| CoreError::io(&config_file, e))?;
         tracing::info!("✅ 已清除自定义 Antigravity 路径");
     }
 
@@ -0,0 +1,153 @@
+// 账户报表
+// 汇总每个备份账户的邮箱、套餐、令牌到期时间、最近备份时间、最近切换时间与备份大小，
+// 可导出为 CSV 或 JSON，便于团队管理账户池
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+/// 报表中的一行
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountReportRow {
+    /// 备份名
+    pub account: String,
+    pub email: String,
+    pub plan: String,
+    /// 登录令牌到期时间（RFC 3339）
+    pub token_expiry: Option<String>,
+    /// 最近备份时间（备份文件修改时间，RFC 3339）
+    pub last_backup: String,
+    /// 最近一次成功切换到该账户的时间（来自审计日志，RFC 3339）
+    pub last_switched: Option<String>,
+    /// 备份文件大小（字节）
+    pub backup_size: u64,
+}
+
+/// CSV 表头（与 `AccountReportRow` 字段顺序一致）
+const CSV_HEADER: &str = "account,email,plan,token_expiry,last_backup,last_switched,backup_size";
+
+/// 每个账户最近一次成功切换的时间
+fn last_switches() -> HashMap<String, String> {
+    let mut switches = HashMap::new();
+    // 审计日志最新在前，保留每个账户第一次出现的记录
+    for entry in crate::audit::load(Some(usize::MAX)).unwrap_or_default() {
+        if entry.operation == crate::audit::AuditOperation::Switch
+            && entry.outcome == crate::audit::AuditOutcome::Success
+        {
+            if let Some(subject) = entry.subject {
+                switches.entry(subject).or_insert(entry.timestamp);
+            }
+        }
+    }
+    switches
+}
+
+/// 生成报表（按最近备份时间排序，最新在前）
+pub fn build() -> Result<Vec<AccountReportRow>, String> {
+    let switches = last_switches();
+    let mut rows = Vec::new();
+
+    for entry in super::backup::list_backup_files()? {
+        let session = super::keychain::read_backup(&entry.path)
+            .ok()
+            .and_then(|backup| {
+                backup
+                    .get(crate::constants::database::AGENT_STATE)
+                    .and_then(Value::as_str)
+                    .and_then(|state| super::account::decode_jetski_state_proto(state).ok())
+            })
+            .unwrap_or(Value::Null);
+
+        let token_expiry = session["auth"]["meta"]["expiry_timestamp"]
+            .as_i64()
+            .filter(|timestamp| *timestamp > 0)
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .map(|expiry| expiry.with_timezone(&chrono::Local).to_rfc3339());
+
+        // 隐私模式下写入的审计记录以哈希作为账户名
+        let last_switched = switches
+            .get(&entry.name)
+            .or_else(|| switches.get(&crate::utils::privacy::hash(&entry.name)))
+            .cloned();
+
+        rows.push(AccountReportRow {
+            email: session["context"]["email"]
+                .as_str()
+                .filter(|email| !email.is_empty())
+                .unwrap_or(&entry.name)
+                .to_string(),
+            plan: session["context"]["plan_name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            token_expiry,
+            last_backup: chrono::DateTime::<chrono::Local>::from(entry.modified).to_rfc3339(),
+            last_switched,
+            backup_size: fs::metadata(&entry.path).map(|m| m.len()).unwrap_or(0),
+            account: entry.name,
+        });
+    }
+    Ok(rows)
+}
+
+/// 转义 CSV 字段；以公式字符开头的值加前缀，避免在表格软件中被当作公式执行
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn to_csv(rows: &[AccountReportRow]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for row in rows {
+        let fields = [
+            csv_field(&row.account),
+            csv_field(&row.email),
+            csv_field(&row.plan),
+            csv_field(row.token_expiry.as_deref().unwrap_or_default()),
+            csv_field(&row.last_backup),
+            csv_field(row.last_switched.as_deref().unwrap_or_default()),
+            row.backup_size.to_string(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// 导出报表到指定文件，返回账户数
+pub fn export(format: ReportFormat, path: &Path) -> Result<usize, String> {
+    let rows = build()?;
+    let content = match format {
+        ReportFormat::Csv => to_csv(&rows),
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(&rows).map_err(|e| format!("序列化账户报表失败: {}", e))?
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("写入账户报表失败: {}", e))?;
+
+    tracing::info!(target: "account::report", accounts = rows.len(), format = ?format, "📊 账户报表已导出");
+    Ok(rows.len())
+}
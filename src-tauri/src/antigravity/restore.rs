@@ -1,14 +1,85 @@
 // Antigravity 用户数据恢复模块
 // 负责将备份数据恢复到 Antigravity 应用数据库
 
-use rusqlite::{params, Connection};
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
 use serde_json::Value;
-use std::fs;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 // 导入相关模块
 use crate::constants::database;
+use crate::error::{CoreError, CoreResult, ResultExt};
 use crate::platform;
+use crate::utils::{blocking, network_fs};
+
+/// 数据库被占用时的重试次数
+const BUSY_RETRIES: u32 = 3;
+
+/// 数据库被占用时的重试间隔
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// 恢复时单个键的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreChangeKind {
+    /// 数据库中没有该键，将新增
+    Insert,
+    /// 将替换为备份中的值
+    Replace,
+    /// 将被删除
+    Delete,
+    /// 与备份中的值相同
+    Unchanged,
+}
+
+/// 恢复预览中的一项（只返回值的长度，不返回内容）
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RestoreChange {
+    /// 数据库名（`state.vscdb` 或 `state.vscdb.backup`）
+    pub database: String,
+    pub key: String,
+    pub kind: RestoreChangeKind,
+    /// 当前值的长度（字符数）
+    pub current_length: Option<usize>,
+    /// 恢复后值的长度（字符数）
+    pub new_length: Option<usize>,
+}
+
+/// 恢复预览
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RestorePreview {
+    pub changes: Vec<RestoreChange>,
+}
+
+/// 读取账户备份并组装钥匙串中的登录状态
+async fn read_account_data(account_file_path: PathBuf) -> CoreResult<Value> {
+    let content = blocking::read_to_string(account_file_path.clone()).await?;
+    blocking::run("解析账户备份", move || -> CoreResult<Value> {
+        let mut account_data: Value =
+            crate::antigravity::vault::open(&content).context("读取账户备份失败")?;
+        crate::antigravity::keychain::reassemble(&mut account_data)
+            .context("读取钥匙串中的登录状态失败")?;
+        crate::antigravity::backup_chain::apply(&account_file_path, &mut account_data)
+            .map_err(CoreError::Other)
+            .context("重放增量备份失败")?;
+        Ok(account_data)
+    })
+    .await
+}
+
+/// 恢复写入的主库路径（默认位置不存在时使用检测到的第一个位置）
+fn target_db_path() -> CoreResult<PathBuf> {
+    match platform::get_antigravity_db_path() {
+        Some(p) => Ok(p),
+        None => platform::get_all_antigravity_db_paths()
+            .into_iter()
+            .next()
+            .ok_or_else(|| CoreError::not_found("Antigravity 安装位置")),
+    }
+}
 
 /// 恢复 Antigravity 状态（精简版）
 ///
@@ -19,102 +90,301 @@ use crate::platform;
 ///
 /// # 返回
 /// - `Ok(message)`: 成功消息
-/// - `Err(message)`: 错误信息
-pub async fn save_antigravity_account_to_file(
-    account_file_path: PathBuf,
-) -> Result<String, String> {
-    println!("📂 账户文件: {}", account_file_path.display());
+/// - `Err(error)`: 错误（主库被占用时会先重试几次）
+pub async fn save_antigravity_account_to_file(account_file_path: PathBuf) -> CoreResult<String> {
+    tracing::info!(target: "restore::account", "📂 账户文件: {}", account_file_path.display());
 
     if !account_file_path.exists() {
-        return Err(format!("账户文件不存在: {}", account_file_path.display()));
+        return Err(CoreError::not_found(format!(
+            "账户文件 {}",
+            account_file_path.display()
+        )));
     }
 
-    let content = fs::read_to_string(&account_file_path).map_err(|e| e.to_string())?;
-    let account_data: Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let account_data = Arc::new(read_account_data(account_file_path).await?);
 
-    println!("✅ 账户文件读取成功");
+    tracing::info!(target: "restore::account", "✅ 账户文件读取成功");
 
-    let app_data = match platform::get_antigravity_db_path() {
-        Some(p) => p,
-        None => {
-            let possible_paths = platform::get_all_antigravity_db_paths();
-            if possible_paths.is_empty() {
-                return Err("未找到 Antigravity 安装位置".to_string());
-            }
-            possible_paths[0].clone()
-        }
-    };
+    let app_data = target_db_path()?;
 
     // 确保数据库目录存在
     if let Some(parent) = app_data.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建数据库目录失败: {}", e))?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| CoreError::io(parent, e))
+            .context("创建数据库目录失败")?;
     }
 
     let mut msg = String::new();
 
-    // 内联恢复逻辑：仅写回 AGENT_STATE 并删除 AUTH_STATUS
-    let restore_db = |db_path: &PathBuf, db_name: &str| -> Result<usize, String> {
-        tracing::info!(target: "restore::database", db_name = %db_name, "开始恢复数据库（仅 jetskiStateSync.agentManagerInitState，移除 antigravityAuthStatus）");
-        let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-
-        let mut restored_count = 0;
+    // 恢复主库（Antigravity 刚退出时数据库可能仍被占用，稍后重试）
+    tracing::info!(target: "restore::account", "📊 步骤1: 恢复 state.vscdb 数据库");
+    let mut attempt = 0;
+    let count = loop {
+        let (db_path, data) = (app_data.clone(), Arc::clone(&account_data));
+        let result = blocking::run("恢复主库", move || {
+            restore_database(&db_path, "state.vscdb", &data)
+        })
+        .await;
+        match result {
+            Err(e) if e.is_database_busy() && attempt < BUSY_RETRIES => {
+                attempt += 1;
+                tracing::warn!(target: "restore::account", attempt, "数据库被占用，稍后重试");
+                tokio::time::sleep(BUSY_RETRY_DELAY).await;
+            }
+            result => break result.context("恢复主库失败")?,
+        }
+    };
+    let status = format!("主库恢复 {} 项", count);
+    tracing::info!(target: "restore::account", "✅ {}", status);
+    msg.push_str(&status);
 
-        if let Some(val) = account_data.get(database::AGENT_STATE) {
-            if let Some(val_str) = val.as_str() {
-                match conn.execute(
-                    "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
-                    params![database::AGENT_STATE, val_str],
-                ) {
-                    Ok(_) => {
-                        tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "注入数据成功");
-                        restored_count += 1;
-                    }
-                    Err(e) => {
-                        tracing::error!(target: "restore::database", key = %database::AGENT_STATE, error = %e, "写入数据失败");
-                    }
-                }
-            } else {
-                tracing::warn!(target: "restore::database", key = %database::AGENT_STATE, "字段不是字符串类型，跳过");
+    // 恢复账户库（如果有）
+    tracing::info!(target: "restore::account", "💾 步骤2: 恢复 state.vscdb.backup");
+    let backup_db = app_data.with_extension("vscdb.backup");
+    if backup_db.exists() {
+        let result = blocking::run("恢复账户库", move || {
+            restore_database(&backup_db, "state.vscdb.backup", &account_data)
+        })
+        .await;
+        match result {
+            Ok(count) => {
+                let status = format!("; 账户库恢复 {} 项", count);
+                tracing::info!(target: "restore::account", "✅ {}", status);
+                msg.push_str(&status);
+            }
+            // 主库已恢复，账户库失败不影响登录，只在结果中说明
+            Err(e) => {
+                tracing::warn!(target: "restore::account", error = %e, "恢复 state.vscdb.backup 失败");
+                msg.push_str(&format!("; 账户库恢复失败: {}", e));
             }
-        } else {
-            tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "备份中未找到字段，跳过");
         }
+    } else {
+        tracing::info!(target: "restore::account", "ℹ️ 账户数据库不存在，跳过");
+    }
 
-        if let Err(e) = conn.execute(
-            "DELETE FROM ItemTable WHERE key = ?",
-            [database::AUTH_STATUS],
-        ) {
-            tracing::warn!(target: "restore::database", error = %e, "删除 antigravityAuthStatus 失败（忽略）");
+    Ok(format!("✅ 恢复成功! {}", msg))
+}
+
+/// 将备份中的登录状态写回单个数据库（仅 jetskiStateSync.agentManagerInitState，并删除 antigravityAuthStatus）
+fn restore_database(db_path: &Path, db_name: &str, account_data: &Value) -> CoreResult<usize> {
+    tracing::info!(target: "restore::database", db_name = %db_name, "开始恢复数据库（仅 jetskiStateSync.agentManagerInitState，移除 antigravityAuthStatus）");
+    let conn = network_fs::open_connection(db_path).map_err(|e| CoreError::sqlite(db_path, e))?;
+
+    let mut restored_count = 0;
+
+    if let Some(val) = account_data.get(database::AGENT_STATE) {
+        if let Some(val_str) = val.as_str() {
+            // 写入失败（包括数据库被占用）直接返回，由调用方决定重试或报告失败
+            conn.execute(
+                "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                params![database::AGENT_STATE, val_str],
+            )
+            .map_err(|e| CoreError::sqlite(db_path, e))
+            .context(format!("写入 {} 失败", database::AGENT_STATE))?;
+            tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "注入数据成功");
+            restored_count += 1;
         } else {
-            tracing::debug!(target: "restore::database", "已删除 antigravityAuthStatus");
+            tracing::warn!(target: "restore::database", key = %database::AGENT_STATE, "字段不是字符串类型，跳过");
         }
+    } else {
+        tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "备份中未找到字段，跳过");
+    }
 
-        Ok(restored_count)
-    };
+    if let Err(e) = conn.execute(
+        "DELETE FROM ItemTable WHERE key = ?",
+        [database::AUTH_STATUS],
+    ) {
+        tracing::warn!(target: "restore::database", error = %e, "删除 antigravityAuthStatus 失败（忽略）");
+    } else {
+        tracing::debug!(target: "restore::database", "已删除 antigravityAuthStatus");
+    }
+
+    Ok(restored_count)
+}
+
+/// 预览恢复：对比备份与主库、账户库，列出恢复时将写入、替换或删除的键，不修改数据库
+///
+/// 与 [`save_antigravity_account_to_file`] 的写入规则一致：写入登录状态，删除 antigravityAuthStatus
+pub async fn preview_restore(account_file_path: PathBuf) -> CoreResult<RestorePreview> {
+    if !account_file_path.exists() {
+        return Err(CoreError::not_found(format!(
+            "账户文件 {}",
+            account_file_path.display()
+        )));
+    }
 
-    // 恢复主库
-    println!("📊 步骤1: 恢复 state.vscdb 数据库");
-    match restore_db(&app_data, "state.vscdb") {
-        Ok(count) => {
-            let status = format!("主库恢复 {} 项", count);
-            println!("  ✅ {}", status);
-            msg.push_str(&status);
+    let account_data = read_account_data(account_file_path).await?;
+    let app_data = target_db_path()?;
+    if !app_data.exists() {
+        return Err(CoreError::not_found("Antigravity 状态数据库"));
+    }
+
+    blocking::run("预览恢复", move || {
+        let mut changes = preview_database(&app_data, "state.vscdb", &account_data)?;
+        let backup_db = app_data.with_extension("vscdb.backup");
+        if backup_db.exists() {
+            changes.extend(preview_database(
+                &backup_db,
+                "state.vscdb.backup",
+                &account_data,
+            )?);
         }
-        Err(e) => return Err(e),
+        tracing::info!(target: "restore::preview", changes = changes.len(), "🔍 已生成恢复预览");
+        Ok(RestorePreview { changes })
+    })
+    .await
+}
+
+/// 对比单个数据库（只读打开）
+fn preview_database(
+    db_path: &Path,
+    db_name: &str,
+    account_data: &Value,
+) -> CoreResult<Vec<RestoreChange>> {
+    let conn = network_fs::open_read_only_connection(db_path)
+        .map_err(|e| CoreError::sqlite(db_path, e))?;
+    let current = |key: &str| -> CoreResult<Option<String>> {
+        conn.query_row("SELECT value FROM ItemTable WHERE key = ?", [key], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(|e| CoreError::sqlite(db_path, e))
+        .context(format!("查询 {} 失败", key))
+    };
+
+    let mut changes = Vec::new();
+    if let Some(new_value) = account_data
+        .get(database::AGENT_STATE)
+        .and_then(Value::as_str)
+    {
+        let existing = current(database::AGENT_STATE)?;
+        let kind = match existing.as_deref() {
+            None => RestoreChangeKind::Insert,
+            Some(value) if value == new_value => RestoreChangeKind::Unchanged,
+            Some(_) => RestoreChangeKind::Replace,
+        };
+        changes.push(RestoreChange {
+            database: db_name.to_string(),
+            key: database::AGENT_STATE.to_string(),
+            kind,
+            current_length: existing.map(|value| value.chars().count()),
+            new_length: Some(new_value.chars().count()),
+        });
+    }
+    if let Some(existing) = current(database::AUTH_STATUS)? {
+        changes.push(RestoreChange {
+            database: db_name.to_string(),
+            key: database::AUTH_STATUS.to_string(),
+            kind: RestoreChangeKind::Delete,
+            current_length: Some(existing.chars().count()),
+            new_length: None,
+        });
     }
+    Ok(changes)
+}
+
+/// 就地修改当前登录状态中的指定字段（主库与账户库），而不是整体替换登录状态
+///
+/// 修改前先创建安全快照，返回修改结果说明
+pub fn patch_session_state(
+    patch: &crate::antigravity::account::SessionPatch,
+) -> CoreResult<String> {
+    let app_data = platform::get_antigravity_db_path()
+        .filter(|path| path.exists())
+        .ok_or_else(|| CoreError::not_found("Antigravity 状态数据库"))?;
+
+    crate::antigravity::backup::create_safety_snapshot().context("创建安全快照失败")?;
+
+    let patch_db = |db_path: &PathBuf| -> CoreResult<bool> {
+        let conn =
+            network_fs::open_connection(db_path).map_err(|e| CoreError::sqlite(db_path, e))?;
+        let state: Option<String> = conn
+            .query_row(
+                "SELECT value FROM ItemTable WHERE key = ?",
+                [database::AGENT_STATE],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| CoreError::sqlite(db_path, e))
+            .context("查询登录状态失败")?;
+        let Some(state) = state else {
+            return Ok(false);
+        };
+
+        let patched = crate::antigravity::account::patch_jetski_state_proto(&state, patch)
+            .map_err(|e| CoreError::parse("登录状态", e))?;
+        conn.execute(
+            "UPDATE ItemTable SET value = ? WHERE key = ?",
+            params![patched, database::AGENT_STATE],
+        )
+        .map_err(|e| CoreError::sqlite(db_path, e))
+        .context("写入登录状态失败")?;
+        Ok(true)
+    };
+
+    if !patch_db(&app_data)? {
+        return Err(CoreError::not_found("可修改的登录状态"));
+    }
+    tracing::info!(target: "restore::patch", "✏️ 已修改主库登录状态");
+    let mut msg = "主库登录状态已修改".to_string();
 
-    // 恢复账户库（如果有）
-    println!("💾 步骤2: 恢复 state.vscdb.backup");
     let backup_db = app_data.with_extension("vscdb.backup");
     if backup_db.exists() {
-        if let Ok(count) = restore_db(&backup_db, "state.vscdb.backup") {
-            let status = format!("; 账户库恢复 {} 项", count);
-            println!("  ✅ {}", status);
-            msg.push_str(&status);
+        match patch_db(&backup_db) {
+            Ok(true) => msg.push_str("; 账户库登录状态已修改"),
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!(target: "restore::patch", error = %e, "修改账户库登录状态失败（忽略）")
+            }
         }
-    } else {
-        println!("  ℹ️ 账户数据库不存在，跳过");
     }
+    Ok(msg)
+}
 
-    Ok(format!("✅ 恢复成功! {}", msg))
+/// 将指定 ItemTable 键写回主库与账户库（值为 `None` 的键被删除），用于按变更日志回滚
+///
+/// 写入前先创建安全快照，返回写入结果说明
+pub fn write_item_values(values: &BTreeMap<String, Option<String>>) -> CoreResult<String> {
+    let app_data = platform::get_antigravity_db_path()
+        .filter(|path| path.exists())
+        .ok_or_else(|| CoreError::not_found("Antigravity 状态数据库"))?;
+
+    crate::antigravity::backup::create_safety_snapshot().context("创建安全快照失败")?;
+
+    let write_db = |db_path: &PathBuf| -> CoreResult<()> {
+        let mut conn =
+            network_fs::open_connection(db_path).map_err(|e| CoreError::sqlite(db_path, e))?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| CoreError::sqlite(db_path, e))?;
+        for (key, value) in values {
+            let result = match value {
+                Some(value) => tx.execute(
+                    "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                    params![key, value],
+                ),
+                None => tx.execute("DELETE FROM ItemTable WHERE key = ?", [key]),
+            };
+            result
+                .map_err(|e| CoreError::sqlite(db_path, e))
+                .context(format!("写入 {} 失败", key))?;
+        }
+        tx.commit().map_err(|e| CoreError::sqlite(db_path, e))
+    };
+
+    write_db(&app_data)?;
+    tracing::info!(target: "restore::rollback", keys = values.len(), "⏪ 已按变更日志回滚主库");
+    let mut msg = format!("主库已写回 {} 个键", values.len());
+
+    let backup_db = app_data.with_extension("vscdb.backup");
+    if backup_db.exists() {
+        match write_db(&backup_db) {
+            Ok(()) => msg.push_str("; 账户库已写回"),
+            Err(e) => {
+                tracing::warn!(target: "restore::rollback", error = %e, "写回账户库失败（忽略）")
+            }
+        }
+    }
+    Ok(msg)
 }
@@ -21,6 +21,11 @@ use std::process::{Command, Stdio};
 /// }
 /// ```
 pub fn start_antigravity() -> Result<String, String> {
+    #[cfg(feature = "simulation")]
+    if crate::simulation::is_enabled() {
+        return Ok(crate::simulation::start());
+    }
+
     // 优先使用用户配置的可执行文件路径
     if let Ok(Some(custom_exec)) = crate::antigravity::path_config::get_custom_executable_path() {
         let path = PathBuf::from(&custom_exec);
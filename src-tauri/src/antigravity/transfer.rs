@@ -0,0 +1,233 @@
+// 二维码账户传输
+// 将单个账户备份加密后切分为多个文本分片，由前端渲染为二维码，在另一台（可能离线的）机器上
+// 用手机等设备扫描后重新拼装导入。密钥由随机生成的传输码经 Argon2id 派生，传输码单独显示、
+// 需要在接收端手动输入，只拿到二维码无法解密
+//
+// 分片格式：`AGTX1:<传输 ID>:<序号>:<总数>:<数据>`，数据为加密信封 JSON 的 Base64URL 片段
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::{
+    engine::general_purpose::STANDARD as BASE64, engine::general_purpose::URL_SAFE_NO_PAD,
+    Engine as _,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+use super::vault::Sealed;
+
+/// 分片前缀（含格式版本）
+const CHUNK_PREFIX: &str = "AGTX1";
+
+/// 每个分片携带的数据长度（字符），保证二维码在手机上容易识别
+const CHUNK_DATA_LEN: usize = 800;
+
+/// 分片数上限
+const MAX_CHUNKS: usize = 200;
+
+/// 传输码字符集（去掉易混淆的 0/O、1/I）
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// 传输码长度（16 位，约 80 bit）
+const CODE_LEN: usize = 16;
+
+/// 生成的传输载荷
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct TransferPayload {
+    pub transfer_id: String,
+    /// 传输码（形如 `ABCD-EFGH-JKLM-NPQR`），需要在接收端手动输入
+    pub code: String,
+    /// 按顺序排列的分片，每个渲染为一个二维码
+    pub chunks: Vec<String>,
+}
+
+/// 加密信封
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    salt: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    sealed: Sealed,
+}
+
+fn random_code() -> String {
+    let mut bytes = [0u8; CODE_LEN];
+    OsRng.fill_bytes(&mut bytes);
+    let chars: Vec<char> = bytes
+        .iter()
+        .map(|byte| CODE_ALPHABET[*byte as usize % CODE_ALPHABET.len()] as char)
+        .collect();
+    chars
+        .chunks(4)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 去掉分隔符并转为大写，容忍手动输入的格式差异
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn derive_key(
+    code: &str,
+    salt: &[u8],
+    settings: &crate::utils::kdf::KdfSettings,
+) -> Result<Zeroizing<[u8; 32]>, String> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    crate::utils::kdf::argon2(settings, Some(32))?
+        .hash_password_into(normalize_code(code).as_bytes(), salt, key.as_mut())
+        .map_err(|e| format!("派生密钥失败: {}", e))?;
+    Ok(key)
+}
+
+/// 加密指定账户的备份并切分为分片
+pub fn create(account: &str) -> Result<TransferPayload, String> {
+    let entry = super::backup::list_backup_files()?
+        .into_iter()
+        .find(|entry| entry.name == account)
+        .ok_or_else(|| format!("账户备份不存在: {}", account))?;
+    let content = super::keychain::read_backup(&entry.path)?;
+
+    let plaintext = Zeroizing::new(
+        serde_json::to_vec(&json!({ "account": account, "content": content }))
+            .map_err(|e| format!("序列化备份失败: {}", e))?,
+    );
+
+    let code = random_code();
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let settings = crate::utils::kdf::params();
+    let key = derive_key(&code, &salt, &settings)?;
+
+    let envelope = Envelope {
+        salt: BASE64.encode(salt),
+        m_cost: settings.m_cost,
+        t_cost: settings.t_cost,
+        p_cost: settings.p_cost,
+        sealed: super::vault::seal(&key, &plaintext)?,
+    };
+    let encoded = URL_SAFE_NO_PAD
+        .encode(serde_json::to_vec(&envelope).map_err(|e| format!("序列化加密信封失败: {}", e))?);
+
+    let mut id = [0u8; 4];
+    OsRng.fill_bytes(&mut id);
+    let transfer_id: String = id.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    // Base64URL 只含 ASCII，可按字节切分
+    let parts: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(CHUNK_DATA_LEN)
+        .map(|part| std::str::from_utf8(part).unwrap_or_default())
+        .collect();
+    if parts.len() > MAX_CHUNKS {
+        return Err(format!("备份过大，需要超过 {} 个二维码", MAX_CHUNKS));
+    }
+    let total = parts.len();
+    let chunks = parts
+        .iter()
+        .enumerate()
+        .map(|(index, part)| {
+            format!(
+                "{}:{}:{}:{}:{}",
+                CHUNK_PREFIX,
+                transfer_id,
+                index + 1,
+                total,
+                part
+            )
+        })
+        .collect();
+
+    tracing::info!(target: "account::transfer", transfer_id = %transfer_id, chunks = total, "📤 已生成账户传输二维码");
+    Ok(TransferPayload {
+        transfer_id,
+        code,
+        chunks,
+    })
+}
+
+/// 解析单个分片，返回 (传输 ID, 序号, 总数, 数据)
+fn parse_chunk(chunk: &str) -> Result<(&str, usize, usize, &str), String> {
+    let mut fields = chunk.trim().splitn(5, ':');
+    let (Some(prefix), Some(id), Some(index), Some(total), Some(data)) = (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    ) else {
+        return Err("二维码内容不是账户传输分片".to_string());
+    };
+    if prefix != CHUNK_PREFIX {
+        return Err(format!("不支持的传输格式: {}", prefix));
+    }
+    let index: usize = index.parse().map_err(|_| "分片序号无效".to_string())?;
+    let total: usize = total.parse().map_err(|_| "分片总数无效".to_string())?;
+    if total == 0 || total > MAX_CHUNKS || index == 0 || index > total {
+        return Err("分片序号超出范围".to_string());
+    }
+    Ok((id, index, total, data))
+}
+
+/// 拼装扫描到的分片（顺序任意，可重复）并解密，返回 (账户名, 备份内容)
+pub fn open(chunks: &[String], code: &str) -> Result<(String, Value), String> {
+    let mut transfer_id = None;
+    let mut expected_total = None;
+    let mut parts = BTreeMap::new();
+
+    for chunk in chunks {
+        let (id, index, total, data) = parse_chunk(chunk)?;
+        if *transfer_id.get_or_insert(id) != id || *expected_total.get_or_insert(total) != total {
+            return Err("分片来自不同的传输，请只扫描同一组二维码".to_string());
+        }
+        parts.insert(index, data);
+    }
+
+    let total = expected_total.ok_or_else(|| "没有扫描到分片".to_string())?;
+    let missing: Vec<String> = (1..=total)
+        .filter(|index| !parts.contains_key(index))
+        .map(|index| index.to_string())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("缺少分片: {}", missing.join(", ")));
+    }
+
+    let encoded: String = parts.into_values().collect();
+    let envelope: Envelope = serde_json::from_slice(
+        &URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|e| format!("分片数据解码失败: {}", e))?,
+    )
+    .map_err(|e| format!("加密信封格式无效: {}", e))?;
+
+    let salt = BASE64
+        .decode(&envelope.salt)
+        .map_err(|e| format!("salt 解码失败: {}", e))?;
+    let key = derive_key(
+        code,
+        &salt,
+        &crate::utils::kdf::KdfSettings {
+            m_cost: envelope.m_cost,
+            t_cost: envelope.t_cost,
+            p_cost: envelope.p_cost,
+        },
+    )?;
+    let plaintext = super::vault::unseal(&key, &envelope.sealed)
+        .map_err(|_| "解密失败：传输码错误或分片已损坏".to_string())?;
+
+    let mut payload: Value =
+        serde_json::from_slice(&plaintext).map_err(|e| format!("传输内容格式无效: {}", e))?;
+    let account = payload["account"]
+        .as_str()
+        .filter(|account| !account.is_empty())
+        .ok_or_else(|| "传输内容缺少账户名".to_string())?
+        .to_string();
+    Ok((account, payload["content"].take()))
+}
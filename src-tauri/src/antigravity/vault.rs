@@ -0,0 +1,398 @@
+// 备份目录加密模块
+// 开启后 `antigravity-accounts/` 下的每个备份文件都使用 AES-256-GCM 单独加密，
+// 数据密钥由主密码（Argon2id 派生）或系统钥匙串中的密钥包装后保存在 `backup_vault.json`；
+// 解锁后列表、恢复、导出等命令透明解密
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+use crate::utils::network_fs;
+
+/// 加密备份文件中的标记键
+const ENCRYPTED_KEY: &str = "$encrypted";
+
+/// 钥匙串模式下保存包装密钥的条目
+const KEYCHAIN_SERVICE: &str = "antigravity-agent";
+const KEYCHAIN_USER: &str = "backup-vault-key";
+
+type SecretKey = Zeroizing<[u8; 32]>;
+
+/// 已解锁的数据密钥（进程内存中）
+static DATA_KEY: Mutex<Option<SecretKey>> = Mutex::new(None);
+
+/// 包装密钥的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultMode {
+    /// 由主密码派生
+    Password,
+    /// 随机密钥保存在系统钥匙串中，启动时自动解锁
+    Keychain,
+}
+
+/// Argon2id 参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    salt: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+/// AES-GCM 密文
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+/// `backup_vault.json` 内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultMetadata {
+    version: u32,
+    mode: VaultMode,
+    /// 仅主密码模式
+    kdf: Option<KdfParams>,
+    /// 被包装的数据密钥
+    wrapped_key: Sealed,
+}
+
+/// 加密状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct VaultStatus {
+    pub enabled: bool,
+    pub mode: Option<VaultMode>,
+    pub unlocked: bool,
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+pub(crate) fn seal(key: &[u8; 32], plaintext: &[u8]) -> Result<Sealed, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| format!("加密失败: {}", e))?;
+    Ok(Sealed {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+pub(crate) fn unseal(key: &[u8; 32], sealed: &Sealed) -> Result<Zeroizing<Vec<u8>>, String> {
+    let nonce = BASE64
+        .decode(&sealed.nonce)
+        .map_err(|e| format!("nonce 解码失败: {}", e))?;
+    if nonce.len() != 12 {
+        return Err("nonce 长度无效".to_string());
+    }
+    let ciphertext = BASE64
+        .decode(&sealed.ciphertext)
+        .map_err(|e| format!("密文解码失败: {}", e))?;
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map(Zeroizing::new)
+        .map_err(|_| "解密失败：密钥错误或数据已损坏".to_string())
+}
+
+/// 由主密码派生包装密钥
+fn derive_key(password: &str, params: &KdfParams) -> Result<SecretKey, String> {
+    let salt = BASE64
+        .decode(&params.salt)
+        .map_err(|e| format!("salt 解码失败: {}", e))?;
+    let argon2 = crate::utils::kdf::argon2(
+        &crate::utils::kdf::KdfSettings {
+            m_cost: params.m_cost,
+            t_cost: params.t_cost,
+            p_cost: params.p_cost,
+        },
+        Some(32),
+    )?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    argon2
+        .hash_password_into(password.as_bytes(), &salt, key.as_mut())
+        .map_err(|e| format!("派生密钥失败: {}", e))?;
+    Ok(key)
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+        .map_err(|e| format!("打开钥匙串条目失败: {}", e))
+}
+
+/// 取得包装密钥（主密码模式需要密码）
+fn wrapping_key(metadata: &VaultMetadata, password: Option<&str>) -> Result<SecretKey, String> {
+    match metadata.mode {
+        VaultMode::Password => {
+            let password = password.ok_or_else(|| "请输入主密码".to_string())?;
+            let kdf = metadata
+                .kdf
+                .as_ref()
+                .ok_or_else(|| "加密元数据缺少密钥派生参数".to_string())?;
+            derive_key(password, kdf)
+        }
+        VaultMode::Keychain => {
+            let encoded = Zeroizing::new(
+                keychain_entry()?
+                    .get_password()
+                    .map_err(|e| format!("读取钥匙串中的备份密钥失败: {}", e))?,
+            );
+            let bytes = Zeroizing::new(
+                BASE64
+                    .decode(encoded.as_bytes())
+                    .map_err(|e| format!("钥匙串中的备份密钥无效: {}", e))?,
+            );
+            let key: [u8; 32] = bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| "钥匙串中的备份密钥长度无效".to_string())?;
+            Ok(Zeroizing::new(key))
+        }
+    }
+}
+
+fn load_metadata() -> Result<Option<VaultMetadata>, String> {
+    let path = crate::directories::get_backup_vault_file();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("读取备份加密元数据失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("解析备份加密元数据失败: {}", e))
+}
+
+fn save_metadata(metadata: &VaultMetadata) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(metadata)
+        .map_err(|e| format!("序列化备份加密元数据失败: {}", e))?;
+    network_fs::write(&crate::directories::get_backup_vault_file(), content)
+        .map_err(|e| format!("写入备份加密元数据失败: {}", e))
+}
+
+/// 备份目录是否已启用加密
+pub fn is_enabled() -> bool {
+    crate::directories::get_backup_vault_file().exists()
+}
+
+/// 数据密钥是否已在内存中（已解锁）
+pub fn is_unlocked() -> bool {
+    current_key().is_ok_and(|key| key.is_some())
+}
+
+fn current_key() -> Result<Option<SecretKey>, String> {
+    let guard = DATA_KEY
+        .lock()
+        .map_err(|e| format!("获取备份密钥锁失败: {}", e))?;
+    Ok(guard.clone())
+}
+
+fn set_key(key: Option<SecretKey>) -> Result<(), String> {
+    *DATA_KEY
+        .lock()
+        .map_err(|e| format!("获取备份密钥锁失败: {}", e))? = key;
+    Ok(())
+}
+
+/// 当前状态
+pub fn status() -> Result<VaultStatus, String> {
+    let metadata = load_metadata()?;
+    Ok(VaultStatus {
+        enabled: metadata.is_some(),
+        mode: metadata.map(|m| m.mode),
+        unlocked: current_key()?.is_some(),
+    })
+}
+
+/// 解锁（主密码模式需要密码；钥匙串模式从钥匙串读取）
+pub fn unlock(password: Option<&str>) -> Result<(), String> {
+    let metadata = load_metadata()?.ok_or_else(|| "备份目录未加密".to_string())?;
+    let wrapping_key = wrapping_key(&metadata, password)?;
+    let data_key = unseal(&wrapping_key, &metadata.wrapped_key)
+        .map_err(|_| "主密码错误或加密元数据已损坏".to_string())?;
+    let key: [u8; 32] = data_key
+        .as_slice()
+        .try_into()
+        .map_err(|_| "数据密钥长度无效".to_string())?;
+    set_key(Some(Zeroizing::new(key)))?;
+    tracing::info!(target: "backup::vault", mode = ?metadata.mode, "🔓 备份目录已解锁");
+    Ok(())
+}
+
+/// 锁定：清除内存中的数据密钥
+pub fn lock() -> Result<(), String> {
+    set_key(None)?;
+    tracing::info!(target: "backup::vault", "🔒 备份目录已锁定");
+    Ok(())
+}
+
+/// 启动时尝试自动解锁（仅钥匙串模式）
+pub fn auto_unlock() {
+    match load_metadata() {
+        Ok(Some(metadata)) if metadata.mode == VaultMode::Keychain => {
+            if let Err(e) = unlock(None) {
+                tracing::warn!(target: "backup::vault", error = %e, "自动解锁备份目录失败");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(target: "backup::vault", error = %e, "读取备份加密状态失败"),
+    }
+}
+
+/// 备份文件内容是否为加密形式
+pub fn is_sealed(value: &Value) -> bool {
+    value.get(ENCRYPTED_KEY).is_some()
+}
+
+/// 解析备份文件内容，已加密时使用当前数据密钥解密，已压缩时解压
+pub fn open(content: &str) -> Result<Value, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("解析备份文件失败: {}", e))?;
+    let Some(sealed) = value.get(ENCRYPTED_KEY) else {
+        return super::compression::unwrap(value);
+    };
+
+    let sealed: Sealed =
+        serde_json::from_value(sealed.clone()).map_err(|e| format!("加密备份格式无效: {}", e))?;
+    let key = current_key()?.ok_or_else(|| "备份目录已加密，请先解锁".to_string())?;
+    let plaintext = unseal(&key, &sealed)?;
+    let value =
+        serde_json::from_slice(&plaintext).map_err(|e| format!("解析解密后的备份失败: {}", e))?;
+    super::compression::unwrap(value)
+}
+
+/// 序列化备份内容，启用压缩时先压缩，启用加密时输出加密后的 JSON
+pub fn seal_backup(data: &Value) -> Result<String, String> {
+    let data = &super::compression::wrap(data)?;
+    if !is_enabled() {
+        return serde_json::to_string_pretty(data).map_err(|e| format!("序列化备份失败: {}", e));
+    }
+
+    let key = current_key()?.ok_or_else(|| "备份目录已加密，请先解锁".to_string())?;
+    let plaintext =
+        Zeroizing::new(serde_json::to_vec(data).map_err(|e| format!("序列化备份失败: {}", e))?);
+    let sealed = seal(&key, &plaintext)?;
+    serde_json::to_string_pretty(&json!({ ENCRYPTED_KEY: sealed }))
+        .map_err(|e| format!("序列化加密备份失败: {}", e))
+}
+
+/// 按当前加密状态重写给定的备份文件，返回处理的文件数
+fn rewrite_all(contents: Vec<(PathBuf, Value)>) -> Result<usize, String> {
+    let count = contents.len();
+    for (path, data) in contents {
+        network_fs::write(&path, seal_backup(&data)?)
+            .map_err(|e| format!("写入备份文件失败 {}: {}", path.display(), e))?;
+        super::backup_manifest::record(&path);
+        super::backup_index::refresh(&path);
+    }
+    Ok(count)
+}
+
+/// 读取所有备份文件的（解密后）内容
+fn read_all() -> Result<Vec<(PathBuf, Value)>, String> {
+    let mut paths: Vec<PathBuf> = crate::antigravity::backup::list_backup_files()?
+        .into_iter()
+        .map(|backup| backup.path)
+        .collect();
+    paths.extend(crate::antigravity::backup::list_safety_snapshots());
+    paths.extend(crate::antigravity::backup::list_all_version_files());
+    paths.extend(crate::antigravity::backup_chain::list_all_delta_files());
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = network_fs::read_to_string(&path)
+                .map_err(|e| format!("读取备份文件失败 {}: {}", path.display(), e))?;
+            let data = open(&content)?;
+            Ok((path, data))
+        })
+        .collect()
+}
+
+/// 启用加密并加密所有已有备份，返回加密的文件数
+pub fn enable(mode: VaultMode, password: Option<&str>) -> Result<usize, String> {
+    if is_enabled() {
+        return Err("备份目录已加密".to_string());
+    }
+
+    // 先读出明文，避免中途失败后无法恢复
+    let contents = read_all()?;
+
+    let (wrapping_key, kdf) = match mode {
+        VaultMode::Password => {
+            let password = password
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "主密码不能为空".to_string())?;
+            let settings = crate::utils::kdf::params();
+            let kdf = KdfParams {
+                salt: BASE64.encode(random_bytes::<16>()),
+                m_cost: settings.m_cost,
+                t_cost: settings.t_cost,
+                p_cost: settings.p_cost,
+            };
+            (derive_key(password, &kdf)?, Some(kdf))
+        }
+        VaultMode::Keychain => {
+            let key = Zeroizing::new(random_bytes::<32>());
+            keychain_entry()?
+                .set_password(&BASE64.encode(&key[..]))
+                .map_err(|e| format!("写入钥匙串失败: {}", e))?;
+            (key, None)
+        }
+    };
+
+    let data_key = Zeroizing::new(random_bytes::<32>());
+    let metadata = VaultMetadata {
+        version: 1,
+        mode,
+        kdf,
+        wrapped_key: seal(&wrapping_key, data_key.as_ref())?,
+    };
+    set_key(Some(data_key))?;
+    save_metadata(&metadata)?;
+
+    let count = rewrite_all(contents)?;
+    crate::state_journal::reseal()?;
+    tracing::info!(target: "backup::vault", mode = ?mode, files = count, "🔐 已启用备份目录加密");
+    Ok(count)
+}
+
+/// 关闭加密并将所有备份写回明文（需要已解锁或提供主密码），返回解密的文件数
+pub fn disable(password: Option<&str>) -> Result<usize, String> {
+    let metadata = load_metadata()?.ok_or_else(|| "备份目录未加密".to_string())?;
+    if current_key()?.is_none() {
+        unlock(password)?;
+    }
+
+    let contents = read_all()?;
+    fs::remove_file(crate::directories::get_backup_vault_file())
+        .map_err(|e| format!("删除备份加密元数据失败: {}", e))?;
+    let count = rewrite_all(contents)?;
+    // 数据密钥清除前重写变更日志，已加密的条目才能解密
+    crate::state_journal::reseal()?;
+    set_key(None)?;
+
+    if metadata.mode == VaultMode::Keychain {
+        if let Ok(entry) = keychain_entry() {
+            let _ = entry.delete_credential();
+        }
+    }
+
+    tracing::info!(target: "backup::vault", files = count, "🔓 已关闭备份目录加密");
+    Ok(count)
+}
@@ -0,0 +1,331 @@
+// Protobuf 线格式补丁
+// 直接在原始字节上修改指定路径的字段，其余字段（包括 proto 定义中没有的未知字段）按原样保留，
+// 避免经 prost 解码再编码时丢弃 Antigravity 新版本增加的字段
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_I64: u8 = 1;
+const WIRE_LEN: u8 = 2;
+const WIRE_START_GROUP: u8 = 3;
+const WIRE_END_GROUP: u8 = 4;
+const WIRE_I32: u8 = 5;
+
+/// 要写入的字段值
+pub enum WireValue<'a> {
+    /// varint（int32/int64/uint64/bool/enum）
+    Varint(u64),
+    /// 长度前缀（string/bytes）
+    Bytes(&'a [u8]),
+}
+
+impl WireValue<'_> {
+    fn wire_type(&self) -> u8 {
+        match self {
+            WireValue::Varint(_) => WIRE_VARINT,
+            WireValue::Bytes(_) => WIRE_LEN,
+        }
+    }
+}
+
+/// 消息中的一个字段（原始字节中的位置）
+struct RawField {
+    number: u32,
+    wire_type: u8,
+    /// 字段起始（含 key）
+    start: usize,
+    /// 长度前缀字段的内容起始
+    payload_start: usize,
+    /// 字段结束
+    end: usize,
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| format!("varint 在偏移 {} 处被截断", *pos))?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(format!("varint 在偏移 {} 处过长", *pos))
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn advance(buf: &[u8], pos: &mut usize, len: u64) -> Result<(), String> {
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| pos.checked_add(len))
+        .filter(|end| *end <= buf.len())
+        .ok_or_else(|| format!("字段在偏移 {} 处超出消息长度", *pos))?;
+    *pos = end;
+    Ok(())
+}
+
+/// 读取一个字段的 key，返回 (字段号, 线类型)
+fn read_key(buf: &[u8], pos: &mut usize) -> Result<(u32, u8), String> {
+    let key = read_varint(buf, pos)?;
+    let number = u32::try_from(key >> 3)
+        .ok()
+        .filter(|number| *number != 0)
+        .ok_or_else(|| format!("偏移 {} 处的字段号无效", *pos))?;
+    Ok((number, (key & 0x07) as u8))
+}
+
+/// 跳过一个字段的值
+fn skip_value(buf: &[u8], pos: &mut usize, number: u32, wire_type: u8) -> Result<(), String> {
+    match wire_type {
+        WIRE_VARINT => read_varint(buf, pos).map(|_| ()),
+        WIRE_I64 => advance(buf, pos, 8),
+        WIRE_LEN => {
+            let len = read_varint(buf, pos)?;
+            advance(buf, pos, len)
+        }
+        WIRE_I32 => advance(buf, pos, 4),
+        WIRE_START_GROUP => loop {
+            let (inner, inner_type) = read_key(buf, pos)?;
+            if inner_type == WIRE_END_GROUP {
+                if inner != number {
+                    return Err(format!("分组 {} 的结束标记不匹配", number));
+                }
+                return Ok(());
+            }
+            skip_value(buf, pos, inner, inner_type)?;
+        },
+        other => Err(format!("字段 {} 的线类型 {} 无效", number, other)),
+    }
+}
+
+/// 解析消息的顶层字段
+fn parse(buf: &[u8]) -> Result<Vec<RawField>, String> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let start = pos;
+        let (number, wire_type) = read_key(buf, &mut pos)?;
+        let payload_start = if wire_type == WIRE_LEN {
+            let mut payload_pos = pos;
+            read_varint(buf, &mut payload_pos)?;
+            payload_pos
+        } else {
+            pos
+        };
+        skip_value(buf, &mut pos, number, wire_type)?;
+        fields.push(RawField {
+            number,
+            wire_type,
+            start,
+            payload_start,
+            end: pos,
+        });
+    }
+    Ok(fields)
+}
+
+fn encode_field(number: u32, value: &WireValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(
+        &mut out,
+        (u64::from(number) << 3) | u64::from(value.wire_type()),
+    );
+    match value {
+        WireValue::Varint(v) => write_varint(&mut out, *v),
+        WireValue::Bytes(bytes) => {
+            write_varint(&mut out, bytes.len() as u64);
+            out.extend_from_slice(bytes);
+        }
+    }
+    out
+}
+
+/// 设置 `path`（逐级字段号，最后一级为要写入的字段）处的值，返回新的消息字节
+///
+/// 路径上的嵌套消息不存在时自动创建，已存在的字段在原位置替换；路径上的字段出现多次时
+/// 无法确定要修改哪一处，直接返回错误。不在路径上的字段（包括未知字段）保持原始字节与顺序不变
+pub fn set(buf: &[u8], path: &[u32], value: &WireValue) -> Result<Vec<u8>, String> {
+    let (&number, rest) = path
+        .split_first()
+        .ok_or_else(|| "字段路径为空".to_string())?;
+    let fields = parse(buf)?;
+    let mut matching = fields.iter().filter(|f| f.number == number);
+    let existing = matching.next();
+    if matching.next().is_some() {
+        return Err(format!("字段 {} 出现多次，无法确定要修改的位置", number));
+    }
+
+    let replacement = if rest.is_empty() {
+        // 线类型与现有数据不一致说明字段定义已变化，拒绝修改以免破坏数据
+        if let Some(field) = existing.filter(|f| f.wire_type != value.wire_type()) {
+            return Err(format!(
+                "字段 {} 的线类型为 {}，与要写入的值不一致",
+                number, field.wire_type
+            ));
+        }
+        encode_field(number, value)
+    } else {
+        let inner = match existing {
+            Some(field) if field.wire_type != WIRE_LEN => {
+                return Err(format!("字段 {} 不是嵌套消息", number));
+            }
+            Some(field) => set(&buf[field.payload_start..field.end], rest, value)?,
+            None => set(&[], rest, value)?,
+        };
+        encode_field(number, &WireValue::Bytes(&inner))
+    };
+
+    let mut out = Vec::with_capacity(buf.len() + replacement.len());
+    match existing {
+        Some(field) => {
+            out.extend_from_slice(&buf[..field.start]);
+            out.extend_from_slice(&replacement);
+            out.extend_from_slice(&buf[field.end..]);
+        }
+        None => {
+            out.extend_from_slice(buf);
+            out.extend_from_slice(&replacement);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{AuthInfo, AuthMetadata, SessionResponse, UserContext};
+    use prost::Message;
+
+    const ACCESS_TOKEN: &[u32] = &[6, 1];
+    const EXPIRY_TIMESTAMP: &[u32] = &[6, 4, 1];
+    const EMAIL: &[u32] = &[19, 7];
+
+    fn session() -> SessionResponse {
+        SessionResponse {
+            flags_f5: vec![1, 2, 3],
+            auth: Some(AuthInfo {
+                access_token: "ya29.old".to_string(),
+                r#type: "Bearer".to_string(),
+                id_token: "id.old".to_string(),
+                meta: None,
+            }),
+            f18: vec![9],
+            context: Some(UserContext {
+                status: 1,
+                plan_name: "Pro".to_string(),
+                email: "old@example.com".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// 按给定的 auth / context 内容拼出消息，并在两层都插入未知字段
+    fn with_unknown_fields(auth: &AuthInfo, context: &UserContext) -> Vec<u8> {
+        let mut auth_bytes = auth.encode_to_vec();
+        auth_bytes.extend(encode_field(99, &WireValue::Varint(42)));
+        let mut context_bytes = encode_field(120, &WireValue::Bytes(b"future"));
+        context_bytes.extend(context.encode_to_vec());
+
+        let mut out = encode_field(5, &WireValue::Bytes(&[1, 2, 3]));
+        out.extend(encode_field(500, &WireValue::Bytes(b"unknown top-level")));
+        out.extend(encode_field(6, &WireValue::Bytes(&auth_bytes)));
+        out.extend(encode_field(19, &WireValue::Bytes(&context_bytes)));
+        out.extend(encode_field(501, &WireValue::Varint(7)));
+        out
+    }
+
+    #[test]
+    fn decode_set_decode_changes_only_target_field() {
+        let original = session();
+        let bytes = set(
+            &original.encode_to_vec(),
+            ACCESS_TOKEN,
+            &WireValue::Bytes(b"ya29.new"),
+        )
+        .unwrap();
+
+        let mut expected = original;
+        expected.auth.as_mut().unwrap().access_token = "ya29.new".to_string();
+        assert_eq!(SessionResponse::decode(bytes.as_slice()).unwrap(), expected);
+    }
+
+    #[test]
+    fn set_creates_missing_nested_messages() {
+        let original = session();
+        let bytes = set(
+            &original.encode_to_vec(),
+            EXPIRY_TIMESTAMP,
+            &WireValue::Varint(1_700_000_000),
+        )
+        .unwrap();
+
+        let mut expected = original;
+        expected.auth.as_mut().unwrap().meta = Some(AuthMetadata {
+            expiry_timestamp: 1_700_000_000,
+        });
+        assert_eq!(SessionResponse::decode(bytes.as_slice()).unwrap(), expected);
+    }
+
+    #[test]
+    fn set_same_value_keeps_bytes_identical() {
+        let session = session();
+        let bytes = with_unknown_fields(
+            session.auth.as_ref().unwrap(),
+            session.context.as_ref().unwrap(),
+        );
+        let patched = set(&bytes, EMAIL, &WireValue::Bytes(b"old@example.com")).unwrap();
+        assert_eq!(patched, bytes);
+    }
+
+    #[test]
+    fn set_preserves_unknown_fields_and_order() {
+        let session = session();
+        let auth = session.auth.clone().unwrap();
+        let context = session.context.clone().unwrap();
+        let bytes = with_unknown_fields(&auth, &context);
+
+        let patched = set(&bytes, ACCESS_TOKEN, &WireValue::Bytes(b"ya29.new")).unwrap();
+        let patched = set(&patched, EMAIL, &WireValue::Bytes(b"new@example.com")).unwrap();
+
+        let expected = with_unknown_fields(
+            &AuthInfo {
+                access_token: "ya29.new".to_string(),
+                ..auth
+            },
+            &UserContext {
+                email: "new@example.com".to_string(),
+                ..context
+            },
+        );
+        assert_eq!(patched, expected);
+    }
+
+    #[test]
+    fn set_rejects_repeated_path_field() {
+        let auth = encode_field(
+            6,
+            &WireValue::Bytes(&encode_field(1, &WireValue::Bytes(b"a"))),
+        );
+        let mut bytes = auth.clone();
+        bytes.extend(auth);
+
+        assert!(set(&bytes, ACCESS_TOKEN, &WireValue::Bytes(b"b")).is_err());
+        assert!(set(&bytes, &[6], &WireValue::Bytes(b"b")).is_err());
+    }
+
+    #[test]
+    fn set_rejects_wire_type_mismatch() {
+        let bytes = session().encode_to_vec();
+        assert!(set(&bytes, ACCESS_TOKEN, &WireValue::Varint(1)).is_err());
+        // context.status 是 varint，不能作为嵌套消息
+        assert!(set(&bytes, &[19, 2, 1], &WireValue::Varint(1)).is_err());
+    }
+}
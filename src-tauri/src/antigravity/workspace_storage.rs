@@ -0,0 +1,161 @@
+// Antigravity 工作区存储模块
+// 列出 User/workspaceStorage 下每个工作区的状态目录（大小、最后使用时间、对应文件夹），
+// 并清理长期未使用的目录；Antigravity 从不自动删除这些目录，时间久了会累积数 GB
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::platform;
+use crate::utils::dir_size::dir_size;
+
+/// 工作区状态目录
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct WorkspaceStorageEntry {
+    /// 目录名（工作区哈希）
+    pub id: String,
+    pub path: String,
+    /// 工作区文件夹或 `.code-workspace` 文件的 URI（来自 workspace.json）
+    pub uri: Option<String>,
+    /// 本地工作区是否仍存在（远程工作区或无法判断时为 `None`）
+    pub exists: Option<bool>,
+    /// 最后使用时间（RFC 3339，取目录内最新的修改时间）
+    pub last_used: String,
+    /// 占用字节数
+    pub size: u64,
+}
+
+/// 清理结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct WorkspaceCleanupResult {
+    /// 已删除的目录数
+    pub deleted: u32,
+    /// 释放的字节数
+    pub freed_bytes: u64,
+    /// 删除失败的目录（目录名: 错误）
+    pub failed: Vec<String>,
+}
+
+/// workspace.json
+#[derive(Deserialize)]
+struct WorkspaceJson {
+    folder: Option<String>,
+    workspace: Option<String>,
+}
+
+/// `User/workspaceStorage`（与 `User/globalStorage` 同级）
+pub fn workspace_storage_dir() -> Option<PathBuf> {
+    platform::get_antigravity_data_dir()
+        .and_then(|dir| dir.parent().map(|user| user.join("workspaceStorage")))
+}
+
+/// 读取 workspace.json 中的工作区 URI
+fn read_uri(dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(dir.join("workspace.json")).ok()?;
+    let json: WorkspaceJson = serde_json::from_str(&content).ok()?;
+    json.folder.or(json.workspace)
+}
+
+/// `file://` URI 对应的本地路径是否存在
+fn local_exists(uri: &str) -> Option<bool> {
+    let url = reqwest::Url::parse(uri).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    url.to_file_path().ok().map(|path| path.exists())
+}
+
+/// 目录及其直接子项中最新的修改时间
+fn last_used(dir: &Path) -> SystemTime {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let children = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| modified(&entry.path()));
+    children
+        .chain(modified(dir))
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn scan() -> Result<Vec<(WorkspaceStorageEntry, SystemTime)>, String> {
+    let Some(root) = workspace_storage_dir().filter(|dir| dir.exists()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&root).map_err(|e| format!("读取工作区存储目录失败: {}", e))?
+    {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let uri = read_uri(&path);
+        let used = last_used(&path);
+        entries.push((
+            WorkspaceStorageEntry {
+                id: entry.file_name().to_string_lossy().to_string(),
+                path: path.display().to_string(),
+                exists: uri.as_deref().and_then(local_exists),
+                uri,
+                last_used: chrono::DateTime::<chrono::Local>::from(used).to_rfc3339(),
+                size: dir_size(&path),
+            },
+            used,
+        ));
+    }
+
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    Ok(entries)
+}
+
+/// 列出所有工作区状态目录（最近使用的在前）
+pub fn list() -> Result<Vec<WorkspaceStorageEntry>, String> {
+    Ok(scan()?.into_iter().map(|(entry, _)| entry).collect())
+}
+
+/// 删除超过 `older_than_days` 天未使用的工作区状态目录
+pub fn delete_stale(older_than_days: u32) -> Result<WorkspaceCleanupResult, String> {
+    if older_than_days == 0 {
+        return Err("天数至少为 1".to_string());
+    }
+    let cutoff = SystemTime::now()
+        .checked_sub(Duration::from_secs(u64::from(older_than_days) * 86400))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut result = WorkspaceCleanupResult {
+        deleted: 0,
+        freed_bytes: 0,
+        failed: Vec::new(),
+    };
+    for (entry, used) in scan()? {
+        if used >= cutoff {
+            continue;
+        }
+        match fs::remove_dir_all(&entry.path) {
+            Ok(()) => {
+                result.deleted += 1;
+                result.freed_bytes += entry.size;
+            }
+            Err(e) => {
+                tracing::warn!(target: "workspace_storage::cleanup", id = %entry.id, error = %e, "删除工作区存储目录失败");
+                result.failed.push(format!("{}: {}", entry.id, e));
+            }
+        }
+    }
+
+    tracing::info!(
+        target: "workspace_storage::cleanup",
+        deleted = result.deleted,
+        freed_bytes = result.freed_bytes,
+        older_than_days = older_than_days,
+        "🧹 已清理过期的工作区存储"
+    );
+    Ok(result)
+}
@@ -0,0 +1,198 @@
+//! 应用锁
+//! 设置密码后，启动时以及空闲超过设定分钟数时自动锁定；锁定期间账户相关命令返回
+//! `APP_LOCKED` 错误，直到 `unlock(password)` 验证通过。密码以 Argon2 哈希保存在设置中，
+//! 锁定/解锁时发出 `app-lock-changed` 事件
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 空闲检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 默认空闲锁定时间（分钟）
+pub const DEFAULT_IDLE_MINUTES: u64 = 15;
+
+/// 应用锁设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppLockSettings {
+    /// 密码的 Argon2 PHC 哈希，为空表示未启用应用锁
+    pub password_hash: Option<String>,
+    /// 空闲多少分钟后自动锁定（`None` 使用默认值，0 表示只在启动时锁定）
+    pub idle_minutes: Option<u64>,
+}
+
+/// 应用锁状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct AppLockStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    pub idle_minutes: u64,
+}
+
+/// `app-lock-changed` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct AppLockPayload {
+    pub locked: bool,
+}
+
+/// 是否已设置密码
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 是否处于锁定状态
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// 空闲锁定时间（分钟）
+static IDLE_MINUTES: AtomicU64 = AtomicU64::new(DEFAULT_IDLE_MINUTES);
+
+/// 最近一次活动时间
+static LAST_ACTIVITY: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// 用于发出事件的应用句柄
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 生成密码哈希（使用本机测量的 Argon2 参数）
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut aes_gcm::aead::OsRng);
+    crate::utils::kdf::current(None)?
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("生成密码哈希失败: {}", e))
+}
+
+/// 校验密码
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// 应用设置：设置了密码时启用应用锁
+pub fn apply_settings(settings: &AppLockSettings) {
+    ENABLED.store(settings.password_hash.is_some(), Ordering::Relaxed);
+    IDLE_MINUTES.store(
+        settings.idle_minutes.unwrap_or(DEFAULT_IDLE_MINUTES),
+        Ordering::Relaxed,
+    );
+    if settings.password_hash.is_none() {
+        LOCKED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// 启动时初始化：已设置密码时以锁定状态启动，并开始空闲检查
+pub fn init(app_handle: &AppHandle, settings: &AppLockSettings) {
+    let _ = APP_HANDLE.set(app_handle.clone());
+    apply_settings(settings);
+    LOCKED.store(is_enabled(), Ordering::Relaxed);
+    touch();
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            check_idle();
+        }
+    });
+}
+
+/// 是否已启用应用锁
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 是否处于锁定状态
+pub fn is_locked() -> bool {
+    LOCKED.load(Ordering::Relaxed)
+}
+
+/// 当前状态
+pub fn status() -> AppLockStatus {
+    AppLockStatus {
+        enabled: is_enabled(),
+        locked: is_locked(),
+        idle_minutes: IDLE_MINUTES.load(Ordering::Relaxed),
+    }
+}
+
+/// 记录一次用户活动
+pub fn touch() {
+    if let Ok(mut last) = LAST_ACTIVITY.lock() {
+        *last = Some(Instant::now());
+    }
+}
+
+/// 账户相关命令执行前调用：锁定时拒绝，否则记录活动
+pub fn ensure_unlocked() -> AgentResult<()> {
+    if is_locked() {
+        return Err(AgentError::Locked);
+    }
+    touch();
+    Ok(())
+}
+
+fn set_locked(locked: bool) {
+    if LOCKED.swap(locked, Ordering::Relaxed) == locked {
+        return;
+    }
+
+    tracing::info!(target: "app::lock", locked = locked, "{}", if locked { "🔒 应用已锁定" } else { "🔓 应用已解锁" });
+    if let Some(app_handle) = APP_HANDLE.get() {
+        if let Err(e) = crate::events::emit(app_handle, &AppLockPayload { locked }) {
+            tracing::error!(target: "app::lock", error = %e, "发射应用锁事件失败");
+        }
+    }
+}
+
+/// 立即锁定（未设置密码时忽略）
+pub fn lock() {
+    if is_enabled() {
+        set_locked(true);
+    }
+}
+
+/// 使用密码解锁
+pub fn unlock(password: &str, settings: &AppLockSettings) -> AgentResult<()> {
+    let Some(hash) = settings.password_hash.as_deref() else {
+        set_locked(false);
+        return Ok(());
+    };
+    if !verify_password(password, hash) {
+        tracing::warn!(target: "app::lock", "解锁失败：密码错误");
+        return Err(AgentError::AuthenticationFailed {
+            message: "密码错误".to_string(),
+        });
+    }
+
+    touch();
+    set_locked(false);
+    Ok(())
+}
+
+/// 空闲超时则锁定
+fn check_idle() {
+    let idle_minutes = IDLE_MINUTES.load(Ordering::Relaxed);
+    if !is_enabled() || is_locked() || idle_minutes == 0 {
+        return;
+    }
+
+    let idle = LAST_ACTIVITY
+        .lock()
+        .ok()
+        .and_then(|last| *last)
+        .map(|at| at.elapsed())
+        .unwrap_or_default();
+    if idle >= Duration::from_secs(idle_minutes * 60) {
+        set_locked(true);
+    }
+}
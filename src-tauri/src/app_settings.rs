@@ -1,9 +1,19 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use tauri::AppHandle;
 
+use crate::settings_migration;
+
+/// 加密设置文件中的标记键
+const ENCRYPTED_KEY: &str = "$encrypted";
+
+/// 由设备密钥派生设置加密密钥时使用的用途标识
+const KEY_PURPOSE: &str = "app-settings";
+
 /// 应用程序设置
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppSettings {
@@ -11,6 +21,87 @@ pub struct AppSettings {
     pub system_tray_enabled: bool,
     /// 是否启用静默启动（启动时最小化到托盘或后台）
     pub silent_start_enabled: bool,
+    /// 最小化窗口时是否隐藏到系统托盘
+    #[serde(default)]
+    pub minimize_to_tray_enabled: bool,
+    /// 账户操作进行中时退出前是否需要确认
+    #[serde(default)]
+    pub confirm_exit_during_operations: bool,
+    /// 托盘图标左键单击行为
+    #[serde(default)]
+    pub tray_left_click_action: crate::system_tray::TrayLeftClickAction,
+    /// 置顶账户（托盘快速切换菜单中排在最前）
+    #[serde(default)]
+    pub pinned_accounts: Vec<String>,
+    /// 主题偏好（跟随系统 / 浅色 / 深色）
+    #[serde(default)]
+    pub theme_preference: crate::window::theme::ThemePreference,
+    /// 各类型系统通知的开关
+    #[serde(default)]
+    pub notifications: crate::notifications::NotificationSettings,
+    /// 慢操作警告阈值（毫秒），未设置时使用默认值
+    #[serde(default)]
+    pub slow_operation_threshold_ms: Option<u64>,
+    /// 日志保留策略
+    #[serde(default)]
+    pub log_retention: crate::utils::log_retention::LogRetentionSettings,
+    /// 是否在本地保存离线错误报告（Sentry envelope 格式，不会自动上传）
+    #[serde(default)]
+    pub error_reports_enabled: bool,
+    /// 是否将备份中的登录状态保存到系统钥匙串
+    #[serde(default)]
+    pub keychain_enabled: bool,
+    /// 删除备份前是否先覆盖文件内容
+    #[serde(default)]
+    pub secure_delete_enabled: bool,
+    /// 是否记录登录状态变更日志
+    #[serde(default)]
+    pub state_journal_enabled: bool,
+    /// 新写入的备份使用的压缩格式
+    #[serde(default)]
+    pub backup_compression: crate::antigravity::compression::BackupCompression,
+    /// 敏感操作前是否要求系统身份验证（Windows Hello / Touch ID / polkit）
+    #[serde(default)]
+    pub require_os_auth: bool,
+    /// 应用锁（密码哈希与空闲锁定时间）
+    #[serde(default)]
+    pub app_lock: crate::app_lock::AppLockSettings,
+    /// 安全模式：拒绝执行清除数据、删除备份等破坏性命令
+    #[serde(default)]
+    pub safe_mode: bool,
+    /// 隐私模式：日志、事件与审计日志中的邮箱/账户名替换为加盐哈希
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// 本机测量的 Argon2id 参数，未设置时在启动时测量
+    #[serde(default)]
+    pub kdf: Option<crate::utils::kdf::KdfSettings>,
+    /// 远程功能使用的代理地址，为空时使用系统代理环境变量
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// 本地 HTTP 控制接口
+    #[serde(default)]
+    pub local_api: crate::local_api::LocalApiSettings,
+    /// Webhook 通知
+    #[serde(default)]
+    pub webhooks: Vec<crate::webhooks::WebhookConfig>,
+    /// 事件钩子脚本
+    #[serde(default)]
+    pub hooks: Vec<crate::hooks::HookConfig>,
+    /// 局域网对等发现（mDNS）
+    #[serde(default)]
+    pub peer_discovery: crate::peer_discovery::PeerDiscoverySettings,
+    /// 浏览器登录桥接（WebSocket）
+    #[serde(default)]
+    pub browser_bridge: crate::browser_bridge::BrowserBridgeSettings,
+    /// 界面语言（后端返回的错误与结果消息使用该语言）
+    #[serde(default)]
+    pub locale: crate::i18n::Locale,
+    /// 应用更新（通道与安装时机）
+    #[serde(default)]
+    pub update: crate::updater::UpdateSettings,
+    /// S3 兼容对象存储远程备份（Secret Access Key 保存在钥匙串中）
+    #[serde(default)]
+    pub s3_backup: Option<crate::remote_backup::S3BackupSettings>,
 }
 
 impl AppSettings {
@@ -37,22 +128,88 @@ impl AppSettings {
 pub struct AppSettingsManager {
     settings: Mutex<AppSettings>,
     config_path: PathBuf,
+    /// 设置文件是否使用设备密钥加密保存
+    encrypted: AtomicBool,
+    /// 用于发出 `settings-changed` 事件
+    app_handle: AppHandle,
+}
+
+/// 用设备密钥加密设置 JSON
+fn seal(json: &str) -> Result<String, String> {
+    let key = crate::device_identity::derive_key(KEY_PURPOSE)?;
+    let sealed = crate::antigravity::vault::seal(&key, json.as_bytes())?;
+    serde_json::to_string_pretty(&serde_json::json!({ ENCRYPTED_KEY: sealed }))
+        .map_err(|e| format!("序列化设置失败: {}", e))
+}
+
+/// 已加载的设置文件
+struct LoadedSettings {
+    settings: AppSettings,
+    /// 是否为加密文件
+    encrypted: bool,
+    /// 迁移前的版本（无需迁移时为 `None`）
+    migrated_from: Option<u32>,
+}
+
+/// 解析设置文件内容并迁移到当前版本；明文文件直接解析（兼容旧版本）
+fn open(content: &str) -> Result<LoadedSettings, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("设置文件格式无效: {}", e))?;
+    let (mut value, encrypted) = match value.get(ENCRYPTED_KEY) {
+        Some(sealed) => {
+            let sealed: crate::antigravity::vault::Sealed = serde_json::from_value(sealed.clone())
+                .map_err(|e| format!("加密设置格式无效: {}", e))?;
+            let key = crate::device_identity::derive_key(KEY_PURPOSE)?;
+            let plaintext = crate::antigravity::vault::unseal(&key, &sealed)?;
+            let value = serde_json::from_slice(&plaintext)
+                .map_err(|e| format!("解密后的设置格式无效: {}", e))?;
+            (value, true)
+        }
+        None => (value, false),
+    };
+
+    let migrated_from = settings_migration::migrate(&mut value);
+    Ok(LoadedSettings {
+        settings: settings_migration::deserialize(value),
+        encrypted,
+        migrated_from,
+    })
+}
+
+/// 只读加载设置文件（供不启动界面的辅助模式使用），文件不存在或无法读取时返回 `None`
+pub fn read_settings_file() -> Option<AppSettings> {
+    let content = fs::read_to_string(crate::directories::get_app_settings_file()).ok()?;
+    open(&content).ok().map(|loaded| loaded.settings)
 }
 
 impl AppSettingsManager {
     /// 创建新的设置管理器
-    pub fn new(_app_handle: &AppHandle) -> Self {
+    pub fn new(app_handle: &AppHandle) -> Self {
         // 使用统一的配置目录
         let config_path = crate::directories::get_app_settings_file();
 
-        // 尝试加载现有设置
-        let mut settings = if config_path.exists() {
+        // 尝试加载现有设置（加密文件使用设备密钥解密）
+        let (mut settings, encrypted, migrated_from) = if config_path.exists() {
             match fs::read_to_string(&config_path) {
-                Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-                Err(_) => AppSettings::default(),
+                Ok(content) => match open(&content) {
+                    Ok(loaded) => (loaded.settings, loaded.encrypted, loaded.migrated_from),
+                    Err(e) => {
+                        // 无法解密时保留原文件，避免之后保存设置时覆盖
+                        let preserved = config_path.with_extension("json.undecryptable");
+                        let _ = fs::copy(&config_path, &preserved);
+                        tracing::error!(
+                            target: "app_settings::init",
+                            error = %e,
+                            preserved = %preserved.display(),
+                            "无法读取设置文件，已保留原文件并使用默认设置"
+                        );
+                        (AppSettings::default(), false, None)
+                    }
+                },
+                Err(_) => (AppSettings::default(), false, None),
             }
         } else {
-            AppSettings::default()
+            (AppSettings::default(), false, None)
         };
 
         // 验证并修正已存在的设置
@@ -63,10 +220,71 @@ impl AppSettingsManager {
             );
         }
 
-        Self {
+        let manager = Self {
             settings: Mutex::new(settings),
             config_path,
+            encrypted: AtomicBool::new(encrypted),
+            app_handle: app_handle.clone(),
+        };
+
+        // 迁移后立即写回；无法备份原文件时保持原文件不变，等下次修改设置时再写入
+        if let Some(from) = migrated_from {
+            match settings_migration::backup(&manager.config_path, from) {
+                Ok(_) => {
+                    if let Err(e) = manager.persist(&manager.get_settings()) {
+                        tracing::error!(target: "app_settings::init", error = %e, "写入迁移后的设置失败");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(target: "app_settings::init", error = %e, "备份设置文件失败，暂不写回迁移结果")
+                }
+            }
         }
+
+        manager
+    }
+
+    /// 设置文件是否已加密
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted.load(Ordering::Relaxed)
+    }
+
+    /// 开启或关闭设置文件加密，并立即按新方式重写文件
+    pub fn set_encrypted(&self, encrypted: bool) -> Result<(), String> {
+        let settings = self.settings.lock().unwrap();
+        let previous = self.encrypted.swap(encrypted, Ordering::Relaxed);
+        if let Err(e) = self.persist(&settings) {
+            self.encrypted.store(previous, Ordering::Relaxed);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// 写入设置文件（带当前版本号；已开启加密时使用设备密钥加密）
+    fn persist(&self, settings: &AppSettings) -> Result<(), String> {
+        let mut value =
+            serde_json::to_value(settings).map_err(|e| format!("序列化设置失败: {}", e))?;
+        if let Value::Object(map) = &mut value {
+            map.insert(
+                settings_migration::VERSION_KEY.to_string(),
+                Value::from(settings_migration::CURRENT_VERSION),
+            );
+        }
+        let json =
+            serde_json::to_string_pretty(&value).map_err(|e| format!("序列化设置失败: {}", e))?;
+        let content = if self.is_encrypted() {
+            seal(&json)?
+        } else {
+            json
+        };
+
+        if let Some(parent) = self.config_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+
+        fs::write(&self.config_path, content).map_err(|e| format!("写入设置文件失败: {}", e))?;
+        crate::config_integrity::record(&self.config_path);
+        Ok(())
     }
 
     /// 获取当前设置的副本
@@ -85,6 +303,7 @@ impl AppSettingsManager {
         let old_silent_start = settings.silent_start_enabled;
         let old_system_tray = settings.system_tray_enabled;
 
+        let before = settings.clone();
         update_fn(&mut settings);
 
         // 验证设置的有效性，如果返回 true 表示有修改
@@ -100,15 +319,35 @@ impl AppSettingsManager {
         }
 
         // 保存到文件
-        let json = serde_json::to_string_pretty(&*settings)
-            .map_err(|e| format!("序列化设置失败: {}", e))?;
+        self.persist(&settings)?;
 
-        if let Some(parent) = self.config_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
-        }
+        // 释放锁后再通知，订阅方可能会读取设置
+        let changed_keys = crate::settings_watcher::changed_keys(&before, &settings);
+        drop(settings);
+        crate::settings_watcher::notify(
+            &self.app_handle,
+            crate::settings_watcher::SettingsFile::AppSettings,
+            crate::settings_watcher::SettingsChangeSource::App,
+            changed_keys,
+        );
+        Ok(())
+    }
 
-        fs::write(&self.config_path, json).map_err(|e| format!("写入设置文件失败: {}", e))?;
+    /// 从磁盘重新加载设置文件（外部编辑后调用），返回发生变化的字段
+    ///
+    /// 文件无法解析或解密时保留当前设置并返回错误，不会写回文件
+    pub fn reload(&self) -> Result<Vec<String>, String> {
+        let content = fs::read_to_string(&self.config_path)
+            .map_err(|e| format!("读取设置文件失败: {}", e))?;
+        let mut loaded = open(&content)?;
+        if loaded.settings.validate() {
+            tracing::warn!(target: "app_settings::reload", "重新加载的设置包含危险配置，已自动修正");
+        }
 
-        Ok(())
+        let mut settings = self.settings.lock().unwrap();
+        let changed_keys = crate::settings_watcher::changed_keys(&settings, &loaded.settings);
+        *settings = loaded.settings;
+        self.encrypted.store(loaded.encrypted, Ordering::Relaxed);
+        Ok(changed_keys)
     }
 }
@@ -0,0 +1,154 @@
+//! 操作审计日志
+//! 与诊断日志分开，只追加记录会改动用户数据的操作（切换、备份、恢复、清理、路径修改、数据库条目修改、设备标识重置、工作区存储清理）与钩子脚本的运行
+//! 及其结果与耗时，回答“Agent 到底对我的数据做了什么”
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 默认返回的审计条目数量
+const DEFAULT_LIMIT: usize = 500;
+
+/// 写入锁，避免并发追加时行内容交错
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 审计的操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    /// 切换账户
+    Switch,
+    /// 备份当前账户
+    Backup,
+    /// 将备份写回 Antigravity 数据库
+    Restore,
+    /// 登录新账户（备份后清除数据并重启）
+    SignIn,
+    /// 清除 Antigravity 数据
+    Cleanup,
+    /// 删除单个备份
+    DeleteBackup,
+    /// 清空所有备份
+    ClearBackups,
+    /// 导入备份文件
+    ImportBackups,
+    /// 修改 Antigravity 可执行文件路径
+    PathChange,
+    /// 运行事件钩子脚本
+    Hook,
+    /// 修改 Antigravity 数据库 ItemTable 条目
+    ItemTableEdit,
+    /// 重新生成 Antigravity 设备标识
+    RegenerateMachineIds,
+    /// 清理 Antigravity 工作区存储
+    WorkspaceCleanup,
+    /// 将有问题的备份移入回收目录
+    TrashBackups,
+    /// 将登录状态回滚到变更日志中的某一时刻
+    StateRollback,
+}
+
+/// 操作结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// 审计日志条目（每行一个 JSON 对象）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AuditEntry {
+    /// 操作时间（RFC 3339）
+    pub timestamp: String,
+    pub operation: AuditOperation,
+    /// 操作对象（账户名、路径等；隐私模式下账户名记录为哈希）
+    pub subject: Option<String>,
+    pub outcome: AuditOutcome,
+    pub duration_ms: u64,
+    /// 结果说明或错误信息（已移除凭据）
+    pub detail: Option<String>,
+}
+
+/// 追加一条审计记录
+pub fn record<T, E: std::fmt::Display>(
+    operation: AuditOperation,
+    subject: Option<&str>,
+    started: Instant,
+    result: &Result<T, E>,
+    detail: Option<&str>,
+) {
+    let (outcome, detail) = match result {
+        Ok(_) => (AuditOutcome::Success, detail.map(str::to_string)),
+        Err(e) => (AuditOutcome::Failure, Some(e.to_string())),
+    };
+
+    let entry = AuditEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        operation,
+        subject: subject.map(|subject| match operation {
+            AuditOperation::PathChange | AuditOperation::Hook | AuditOperation::ItemTableEdit => {
+                subject.to_string()
+            }
+            _ => crate::utils::privacy::account(subject),
+        }),
+        outcome,
+        duration_ms: started.elapsed().as_millis() as u64,
+        detail: detail.map(|d| {
+            crate::utils::privacy::scrub(&crate::utils::log_sanitizer::redact_secrets(&d))
+        }),
+    };
+
+    if let Err(e) = append(&entry) {
+        tracing::warn!(target: "audit::write", operation = ?operation, error = %e, "写入审计日志失败");
+    }
+}
+
+/// 执行一个操作并记录审计日志，成功时以返回消息作为说明
+pub async fn track<E, F>(
+    operation: AuditOperation,
+    subject: Option<&str>,
+    future: F,
+) -> Result<String, E>
+where
+    E: std::fmt::Display,
+    F: Future<Output = Result<String, E>>,
+{
+    let started = Instant::now();
+    let result = future.await;
+    record(operation, subject, started, &result, result.as_deref().ok());
+    result
+}
+
+fn append(entry: &AuditEntry) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|e| format!("序列化审计条目失败: {}", e))?;
+
+    let _guard = WRITE_LOCK
+        .lock()
+        .map_err(|e| format!("获取审计日志锁失败: {}", e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(crate::directories::get_audit_log_file())
+        .map_err(|e| format!("打开审计日志失败: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("追加审计日志失败: {}", e))
+}
+
+/// 读取审计日志（最新在前），`limit` 默认 500 条
+pub fn load(limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let path = crate::directories::get_audit_log_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取审计日志失败: {}", e))?;
+    Ok(content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .take(limit.unwrap_or(DEFAULT_LIMIT))
+        .collect())
+}
@@ -0,0 +1,448 @@
+//! 性能基准测试
+//! 在用户机器上测量备份写入、恢复写入、数据库全量读取与登录状态解码的耗时，分别使用合成数据
+//! 与当前登录的真实数据，并与参考基线对比。用于排查“切换账户要 30 秒”这类由杀毒软件实时扫描
+//! 或磁盘过慢导致的问题。
+//!
+//! 写入类测试只写临时文件（备份目录中的临时文件、Antigravity 数据目录中的临时数据库），
+//! 测完即删除，不会改动现有备份与 Antigravity 数据库
+
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::constants::database;
+use crate::utils::network_fs;
+
+/// 合成备份中填充数据的大小（字节）
+const SYNTHETIC_PAYLOAD_BYTES: usize = 1024 * 1024;
+
+/// 合成数据库的行数与每行大小（字节）
+const SYNTHETIC_DB_ROWS: usize = 500;
+const SYNTHETIC_DB_ROW_BYTES: usize = 2048;
+
+/// 中位数超过基线的倍数时判定为偏慢
+const SLOW_FACTOR: f64 = 3.0;
+
+/// 测试项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkId {
+    BackupWrite,
+    RestoreWrite,
+    DatabaseRead,
+    ProtoDecode,
+}
+
+impl BenchmarkId {
+    /// 测试项名称
+    fn label(self) -> &'static str {
+        match self {
+            BenchmarkId::BackupWrite => "备份写入",
+            BenchmarkId::RestoreWrite => "恢复写入",
+            BenchmarkId::DatabaseRead => "数据库全量读取",
+            BenchmarkId::ProtoDecode => "登录状态解码",
+        }
+    }
+
+    /// 重复次数
+    fn iterations(self) -> usize {
+        match self {
+            BenchmarkId::ProtoDecode => 20,
+            _ => 5,
+        }
+    }
+
+    /// 参考基线（SSD、未开启实时扫描时的中位数，毫秒）
+    fn baseline_ms(self) -> f64 {
+        match self {
+            BenchmarkId::BackupWrite => 20.0,
+            BenchmarkId::RestoreWrite => 30.0,
+            BenchmarkId::DatabaseRead => 20.0,
+            BenchmarkId::ProtoDecode => 1.0,
+        }
+    }
+}
+
+/// 测试数据来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkSource {
+    /// 固定大小的合成数据（不同机器之间可直接比较）
+    Synthetic,
+    /// 当前 Antigravity 数据库中的真实数据
+    Real,
+}
+
+/// 测试结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkStatus {
+    /// 在基线的 [`SLOW_FACTOR`] 倍以内
+    Normal,
+    /// 明显慢于基线
+    Slow,
+    /// 测试过程中出错
+    Failed,
+    /// 缺少测试数据（例如未登录时没有真实登录状态）
+    Skipped,
+}
+
+/// 单项测试结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BenchmarkResult {
+    pub id: BenchmarkId,
+    pub label: &'static str,
+    pub source: BenchmarkSource,
+    pub status: BenchmarkStatus,
+    /// 实际重复次数
+    pub iterations: usize,
+    /// 数据大小（字节）
+    pub bytes: usize,
+    /// 耗时中位数（毫秒）
+    pub median_ms: Option<f64>,
+    /// 最长耗时（毫秒）
+    pub max_ms: Option<f64>,
+    /// 参考基线（毫秒）
+    pub baseline_ms: f64,
+    /// 中位数与基线之比
+    pub ratio: Option<f64>,
+    /// 跳过或失败的原因
+    pub message: Option<String>,
+}
+
+/// 基准测试报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BenchmarkReport {
+    /// 是否有偏慢的测试项
+    pub slow: bool,
+    /// 全部测试的总耗时（毫秒）
+    pub total_ms: f64,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// 测试数据
+struct Dataset {
+    source: BenchmarkSource,
+    /// 登录状态（base64 编码的 protobuf）；没有时跳过相关测试
+    session_state: Result<String, String>,
+    /// 备份内容
+    backup: Value,
+    /// 全量读取与恢复写入所用的数据库（真实数据为 Antigravity 数据库本身）
+    database: Result<PathBuf, String>,
+}
+
+/// 重复执行 `op`，返回每次的耗时（毫秒）
+fn measure<F>(iterations: usize, mut op: F) -> Result<Vec<f64>, String>
+where
+    F: FnMut() -> Result<(), String>,
+{
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = Instant::now();
+        op()?;
+        samples.push(started.elapsed().as_secs_f64() * 1000.0);
+    }
+    Ok(samples)
+}
+
+fn median(samples: &mut [f64]) -> f64 {
+    samples.sort_by(|a, b| a.total_cmp(b));
+    samples[samples.len() / 2]
+}
+
+/// 汇总单项测试结果
+fn result(
+    id: BenchmarkId,
+    source: BenchmarkSource,
+    bytes: usize,
+    samples: Result<Result<Vec<f64>, String>, String>,
+) -> BenchmarkResult {
+    let baseline_ms = id.baseline_ms();
+    let mut result = BenchmarkResult {
+        id,
+        label: id.label(),
+        source,
+        status: BenchmarkStatus::Skipped,
+        iterations: 0,
+        bytes,
+        median_ms: None,
+        max_ms: None,
+        baseline_ms,
+        ratio: None,
+        message: None,
+    };
+
+    match samples {
+        Err(reason) => result.message = Some(reason),
+        Ok(Err(e)) => {
+            result.status = BenchmarkStatus::Failed;
+            result.message = Some(e);
+        }
+        Ok(Ok(mut samples)) => {
+            let median = median(&mut samples);
+            let ratio = median / baseline_ms;
+            result.status = if ratio > SLOW_FACTOR {
+                BenchmarkStatus::Slow
+            } else {
+                BenchmarkStatus::Normal
+            };
+            result.iterations = samples.len();
+            result.median_ms = Some(median);
+            result.max_ms = samples.last().copied();
+            result.ratio = Some(ratio);
+        }
+    }
+    result
+}
+
+/// 合成登录状态（令牌为随机内容，不可用于登录）
+fn synthetic_session_state() -> Result<String, String> {
+    let filler = "x".repeat(1200);
+    crate::antigravity::account::build_session_state(
+        "benchmark@example.com",
+        &format!("ya29.benchmark-{}", filler),
+        &format!("eyJhbGciOiJub25lIn0.{}.benchmark", filler),
+    )
+}
+
+/// 创建合成数据库（ItemTable 结构与 Antigravity 一致）
+fn create_synthetic_database(path: &Path, session_state: Option<&str>) -> Result<(), String> {
+    remove_database(path);
+    let mut conn = network_fs::open_connection(path)
+        .map_err(|e| format!("创建测试数据库失败 ({}): {}", path.display(), e))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB);",
+    )
+    .map_err(|e| format!("创建测试数据库失败: {}", e))?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("写入测试数据库失败: {}", e))?;
+    let value = "x".repeat(SYNTHETIC_DB_ROW_BYTES);
+    for index in 0..SYNTHETIC_DB_ROWS {
+        tx.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            params![format!("benchmark.key{}", index), value],
+        )
+        .map_err(|e| format!("写入测试数据库失败: {}", e))?;
+    }
+    if let Some(state) = session_state {
+        tx.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            params![database::AGENT_STATE, state],
+        )
+        .map_err(|e| format!("写入测试数据库失败: {}", e))?;
+    }
+    tx.commit()
+        .map_err(|e| format!("写入测试数据库失败: {}", e))
+}
+
+/// 删除临时数据库及其日志文件
+fn remove_database(path: &Path) {
+    for suffix in ["", "-journal", "-wal", "-shm"] {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        let _ = fs::remove_file(PathBuf::from(file));
+    }
+}
+
+/// 临时文件所在目录：Antigravity 数据目录（与恢复时写入的磁盘和扫描规则一致），找不到时使用配置目录
+fn scratch_directory() -> PathBuf {
+    crate::platform::get_antigravity_db_path()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(crate::directories::get_config_directory)
+}
+
+fn synthetic_dataset(scratch_db: &Path) -> Dataset {
+    let session_state = synthetic_session_state();
+    let backup = json!({
+        (database::AGENT_STATE): session_state.as_deref().unwrap_or_default(),
+        "benchmark.padding": "x".repeat(SYNTHETIC_PAYLOAD_BYTES),
+    });
+    let database = create_synthetic_database(scratch_db, session_state.as_deref().ok())
+        .map(|()| scratch_db.to_path_buf());
+    Dataset {
+        source: BenchmarkSource::Synthetic,
+        session_state,
+        backup,
+        database,
+    }
+}
+
+fn real_dataset() -> Dataset {
+    let database = crate::platform::get_antigravity_db_path()
+        .filter(|path| path.exists())
+        .ok_or_else(|| "未找到 Antigravity 状态数据库".to_string());
+    let session_state = database.clone().and_then(|path| {
+        network_fs::open_read_only_connection(&path)
+            .and_then(|conn| {
+                conn.query_row(
+                    "SELECT value FROM ItemTable WHERE key = ?",
+                    [database::AGENT_STATE],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()
+            })
+            .map_err(|e| format!("读取登录状态失败: {}", e))?
+            .ok_or_else(|| "当前未登录，没有登录状态".to_string())
+    });
+    let backup = json!({
+        (database::AGENT_STATE): session_state.as_deref().unwrap_or_default(),
+    });
+    Dataset {
+        source: BenchmarkSource::Real,
+        session_state,
+        backup,
+        database,
+    }
+}
+
+/// 备份写入：序列化（启用加密时加密）并写入备份目录中的临时文件
+fn bench_backup_write(dataset: &Dataset) -> BenchmarkResult {
+    let id = BenchmarkId::BackupWrite;
+    let path = crate::directories::get_accounts_directory().join(".benchmark.tmp");
+    let bytes = serde_json::to_string(&dataset.backup).map_or(0, |s| s.len());
+    let samples = dataset
+        .session_state
+        .as_ref()
+        .map_err(String::clone)
+        .map(|_| {
+            let samples = measure(id.iterations(), || {
+                let content = crate::antigravity::vault::seal_backup(&dataset.backup)?;
+                network_fs::write(&path, content)
+                    .map_err(|e| format!("写入测试文件失败 {}: {}", path.display(), e))
+            });
+            let _ = fs::remove_file(&path);
+            samples
+        });
+    result(id, dataset.source, bytes, samples)
+}
+
+/// 恢复写入：在临时数据库上按恢复流程写入登录状态（真实数据先复制一份 Antigravity 数据库）
+fn bench_restore_write(dataset: &Dataset, scratch_db: &Path) -> BenchmarkResult {
+    let id = BenchmarkId::RestoreWrite;
+    let state = match dataset.session_state.as_ref() {
+        Ok(state) => state,
+        Err(e) => return result(id, dataset.source, 0, Err(e.clone())),
+    };
+    let samples = dataset
+        .database
+        .as_ref()
+        .map_err(String::clone)
+        .map(|database| {
+            let target = if dataset.source == BenchmarkSource::Real {
+                let copy = scratch_db.with_extension("real");
+                if let Err(e) = fs::copy(database, &copy) {
+                    return Err(format!("复制数据库失败: {}", e));
+                }
+                copy
+            } else {
+                database.clone()
+            };
+
+            let samples = measure(id.iterations(), || {
+                let mut conn = network_fs::open_connection(&target)
+                    .map_err(|e| format!("打开测试数据库失败: {}", e))?;
+                let tx = conn.transaction().map_err(|e| e.to_string())?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                    params![database::AGENT_STATE, state],
+                )
+                .map_err(|e| e.to_string())?;
+                tx.execute(
+                    "DELETE FROM ItemTable WHERE key = ?",
+                    [database::AUTH_STATUS],
+                )
+                .map_err(|e| e.to_string())?;
+                tx.commit().map_err(|e| e.to_string())
+            });
+            if target != *database {
+                remove_database(&target);
+            }
+            samples
+        });
+    result(id, dataset.source, state.len(), samples)
+}
+
+/// 数据库全量读取：每次新建只读连接并读取整个 ItemTable（与数据库监控的读取方式一致）
+fn bench_database_read(dataset: &Dataset) -> BenchmarkResult {
+    let id = BenchmarkId::DatabaseRead;
+    let path = match dataset.database.as_ref() {
+        Ok(path) => path,
+        Err(e) => return result(id, dataset.source, 0, Err(e.clone())),
+    };
+    let bytes = fs::metadata(path).map_or(0, |meta| meta.len() as usize);
+    let samples = measure(id.iterations(), || {
+        let conn = network_fs::open_read_only_connection(path)
+            .map_err(|e| format!("打开数据库失败 ({}): {}", path.display(), e))?;
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM ItemTable ORDER BY key")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| e.to_string())?;
+        std::hint::black_box(rows);
+        Ok(())
+    });
+    result(id, dataset.source, bytes, Ok(samples))
+}
+
+/// 登录状态解码：base64 + protobuf 解码为 JSON
+fn bench_proto_decode(dataset: &Dataset) -> BenchmarkResult {
+    let id = BenchmarkId::ProtoDecode;
+    let state = match dataset.session_state.as_ref() {
+        Ok(state) => state,
+        Err(e) => return result(id, dataset.source, 0, Err(e.clone())),
+    };
+    let samples = measure(id.iterations(), || {
+        crate::antigravity::account::decode_jetski_state_proto(state).map(|decoded| {
+            std::hint::black_box(decoded);
+        })
+    });
+    result(id, dataset.source, state.len(), Ok(samples))
+}
+
+/// 执行全部测试（合成数据在前，真实数据在后）
+pub fn run() -> BenchmarkReport {
+    let started = Instant::now();
+    let scratch_db = scratch_directory().join("state.vscdb.benchmark");
+
+    let mut results = Vec::new();
+    for dataset in [synthetic_dataset(&scratch_db), real_dataset()] {
+        results.push(bench_backup_write(&dataset));
+        results.push(bench_restore_write(&dataset, &scratch_db));
+        results.push(bench_database_read(&dataset));
+        results.push(bench_proto_decode(&dataset));
+    }
+    remove_database(&scratch_db);
+
+    let slow = results
+        .iter()
+        .any(|result| result.status == BenchmarkStatus::Slow);
+    let total_ms = started.elapsed().as_secs_f64() * 1000.0;
+    tracing::info!(target: "app::benchmark", slow, total_ms, "⏱️ 性能基准测试完成");
+    for result in results.iter().filter(|r| r.status == BenchmarkStatus::Slow) {
+        tracing::warn!(
+            target: "app::benchmark",
+            id = ?result.id,
+            source = ?result.source,
+            median_ms = result.median_ms,
+            baseline_ms = result.baseline_ms,
+            "🐢 测试项明显慢于基线"
+        );
+    }
+
+    BenchmarkReport {
+        slow,
+        total_ms,
+        results,
+    }
+}
@@ -0,0 +1,222 @@
+//! 前端类型绑定
+//! 通过 specta / tauri-specta 从命令签名与参数、返回值类型生成带类型的 TypeScript 客户端
+//! （`src/bindings.ts`），避免前端手写的接口与 Rust 结构体悄悄不一致。
+//! 调试构建启动时自动重新生成；也可以 `antigravity-agent --export-bindings` 单独生成
+
+use crate::commands::*;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+
+/// 只导出绑定后退出的命令行参数
+pub const EXPORT_ARG: &str = "--export-bindings";
+
+/// 生成的 TypeScript 客户端路径
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/bindings.ts");
+
+/// 注册所有命令
+pub fn builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new().commands(tauri_specta::collect_commands![
+        collect_account_contents,
+        get_backup_content,
+        list_backups_indexed,
+        restore_backup_files,
+        import_config_archive,
+        export_backup_archive,
+        import_backup_archive,
+        delete_backup,
+        clear_all_backups,
+        export_account_report,
+        create_transfer_payload,
+        import_transfer_payload,
+        detect_import_format,
+        import_from_other_tool,
+        get_backup_vault_status,
+        enable_backup_vault,
+        unlock_backup_vault,
+        lock_backup_vault,
+        disable_backup_vault,
+        // 账户基础命令
+        get_antigravity_accounts,
+        get_current_antigravity_account_info,
+        get_app_state,
+        save_antigravity_current_account,
+        restore_antigravity_account,
+        preview_restore,
+        list_backup_versions,
+        restore_backup_version,
+        switch_to_antigravity_account,
+        restore_backup_with_safety_snapshot,
+        patch_session_state,
+        create_account_from_token,
+        get_decoded_session_state,
+        get_available_models,
+        clear_all_antigravity_data,
+        is_antigravity_running,
+        sign_in_new_antigravity_account,
+        // 平台支持命令
+        get_platform_info,
+        find_antigravity_installations,
+        get_current_paths,
+        run_self_test,
+        run_benchmark,
+        run_onboarding_detection,
+        export_shell_env,
+        get_disk_usage_report,
+        harden_config_dir,
+        copy_secret_to_clipboard,
+        get_config_integrity_warnings,
+        // 数据库路径相关
+        detect_antigravity_installation,
+        // 可执行文件路径相关
+        validate_antigravity_executable,
+        detect_antigravity_executable,
+        save_antigravity_executable,
+        enable_system_tray,
+        disable_system_tray,
+        minimize_to_tray,
+        restore_from_tray,
+        get_system_tray_state,
+        toggle_system_tray,
+        set_window_zoom,
+        get_window_zoom,
+        confirm_exit,
+        update_tray_menu_command,
+        set_tray_account_pinned,
+        get_tray_left_click_action,
+        set_tray_left_click_action,
+        // 快速切换窗口命令
+        list_quick_switch_accounts,
+        quick_switch_account,
+        close_quick_switcher,
+        is_silent_start_enabled,
+        save_silent_start_state,
+        is_minimize_to_tray_enabled,
+        save_minimize_to_tray_state,
+        is_confirm_exit_enabled,
+        save_confirm_exit_state,
+        get_all_settings,
+        get_startup_progress,
+        get_notification_settings,
+        save_notification_settings,
+        get_system_theme,
+        get_theme_preference,
+        get_locale,
+        get_message_catalog,
+        get_slow_operation_threshold,
+        save_slow_operation_threshold,
+        get_log_retention_settings,
+        save_log_retention_settings,
+        get_scheduled_jobs,
+        export_schedule_ics,
+        is_error_reports_enabled,
+        save_error_reports_enabled,
+        is_keychain_enabled,
+        save_keychain_enabled,
+        get_backup_compression,
+        save_backup_compression,
+        is_secure_delete_enabled,
+        save_secure_delete_enabled,
+        is_state_journal_enabled,
+        save_state_journal_enabled,
+        is_os_auth_required,
+        save_os_auth_required,
+        is_safe_mode_enabled,
+        save_safe_mode_enabled,
+        is_privacy_mode_enabled,
+        save_privacy_mode_enabled,
+        rebenchmark_kdf,
+        get_http_proxy,
+        save_http_proxy,
+        get_webhooks,
+        save_webhooks,
+        test_webhook,
+        get_hooks,
+        save_hooks,
+        is_settings_encryption_enabled,
+        save_settings_encryption_enabled,
+        get_peer_discovery_status,
+        save_peer_discovery_settings,
+        // 插件命令
+        list_plugins,
+        reload_plugins,
+        run_plugin_command,
+        get_device_identity,
+        rotate_device_identity,
+        list_trusted_signers,
+        remove_trusted_signer,
+        get_local_api_status,
+        save_local_api_settings,
+        regenerate_local_api_token,
+        get_browser_bridge_status,
+        save_browser_bridge_settings,
+        regenerate_browser_bridge_token,
+        get_app_lock_status,
+        unlock,
+        lock_app,
+        report_activity,
+        set_app_lock_password,
+        save_app_lock_idle_minutes,
+        save_theme_preference,
+        save_locale,
+        // 数据库监控命令
+        is_database_monitoring_running,
+        start_database_monitoring,
+        stop_database_monitoring,
+        reconstruct_state_at,
+        // ItemTable 浏览命令
+        db_list_keys,
+        db_get_value,
+        db_set_value,
+        // Antigravity 设备标识命令
+        get_machine_ids,
+        regenerate_machine_ids,
+        // 工作区存储命令
+        list_workspace_storage,
+        clean_workspace_storage,
+        // 备份检查命令
+        scan_backup_problems,
+        verify_backups,
+        trash_backups,
+        // 远程备份命令
+        get_s3_backup_status,
+        save_s3_backup_settings,
+        list_remote_backups,
+        push_remote_backups,
+        pull_remote_backups,
+        // 应用更新命令
+        check_for_updates,
+        download_update,
+        install_update,
+        get_update_status,
+        defer_update_install,
+        cancel_deferred_update_install,
+        get_update_settings,
+        save_update_settings,
+        decrypt_config_data,
+        encrypt_config_data,
+        write_text_file,
+        write_frontend_log,
+        query_logs,
+        export_logs,
+        get_run_id,
+        list_event_types,
+        cancel_operation,
+        clear_logs,
+        export_error_reports,
+        get_crash_reports,
+        get_command_metrics,
+        reset_command_metrics,
+        get_audit_log,
+    ])
+}
+
+/// 导出 TypeScript 客户端（64 位整数导出为 `number`，数值均在安全整数范围内）
+pub fn export(builder: &tauri_specta::Builder<tauri::Wry>) -> Result<(), String> {
+    builder
+        .export(
+            Typescript::default()
+                .bigint(BigIntExportBehavior::Number)
+                .header("// @ts-nocheck\n// 由 src-tauri/src/bindings.rs 生成，请勿手动修改"),
+            BINDINGS_PATH,
+        )
+        .map_err(|e| format!("导出前端类型绑定失败 ({}): {}", BINDINGS_PATH, e))
+}
@@ -0,0 +1,331 @@
+//! 浏览器登录桥接
+//! 开启后在 `127.0.0.1` 上提供 WebSocket 端点（端口可配置），供配套浏览器扩展在网页登录 Antigravity
+//! 完成后把令牌交给 Agent，直接按令牌创建账户备份，批量添加账户时无需逐个在 Antigravity 中切换登录。
+//! - 连接地址：`ws://127.0.0.1:<port>/v1/bridge?token=<token>`，只接受浏览器扩展或非浏览器来源
+//! - 客户端消息：`{"type": "login", "email": ..., "access_token": ..., "id_token": ...}` / `{"type": "ping"}`
+//! - 服务端消息：`hello` / `pong` / `imported` / `error`（`error` 带与命令错误相同的 `code`）
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+
+/// 默认监听端口
+pub const DEFAULT_PORT: u16 = 47825;
+
+/// 单条消息的最大字节数（令牌通常只有几 KB）
+const MAX_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// 允许连接的浏览器扩展来源
+const EXTENSION_ORIGINS: &[&str] = &[
+    "chrome-extension://",
+    "moz-extension://",
+    "safari-web-extension://",
+];
+
+/// 浏览器桥接设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserBridgeSettings {
+    /// 是否启用
+    pub enabled: bool,
+    /// 监听端口
+    pub port: u16,
+    /// 配对令牌（首次启用时生成，填入浏览器扩展）
+    pub token: Option<String>,
+}
+
+impl Default for BrowserBridgeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: None,
+        }
+    }
+}
+
+/// 浏览器桥接状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct BrowserBridgeStatus {
+    pub enabled: bool,
+    /// 服务是否正在监听
+    pub running: bool,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+/// `browser-login-imported` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct BrowserLoginPayload {
+    pub account: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// 正在运行的服务
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+/// 连接处理共享的状态
+#[derive(Clone)]
+struct BridgeState {
+    app: AppHandle,
+    token: Arc<str>,
+}
+
+/// 连接参数
+#[derive(Deserialize)]
+struct ConnectQuery {
+    #[serde(default)]
+    token: String,
+}
+
+/// 扩展发来的消息
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Ping,
+    Login {
+        email: String,
+        access_token: String,
+        id_token: String,
+    },
+}
+
+/// 回复扩展的消息
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Hello { version: &'static str },
+    Pong,
+    Imported { account: String, message: String },
+    Error { code: String, message: String },
+}
+
+/// 当前状态
+pub fn status(settings: &BrowserBridgeSettings) -> BrowserBridgeStatus {
+    let running = SERVER
+        .lock()
+        .map(|server| server.as_ref().map(|server| server.port))
+        .ok()
+        .flatten();
+    BrowserBridgeStatus {
+        enabled: settings.enabled,
+        running: running.is_some(),
+        port: running.unwrap_or(settings.port),
+        token: settings.token.clone(),
+    }
+}
+
+/// 停止服务
+pub fn stop() {
+    let server = SERVER.lock().ok().and_then(|mut server| server.take());
+    if let Some(server) = server {
+        let _ = server.shutdown.send(());
+        tracing::info!(target: "browser_bridge::server", port = server.port, "🛑 浏览器桥接已停止");
+    }
+}
+
+/// 按设置启动或停止服务（已在运行时先停止，再以新设置启动）
+pub async fn apply(app_handle: &AppHandle, settings: &BrowserBridgeSettings) -> Result<(), String> {
+    stop();
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    crate::local_api::validate_port(settings.port)?;
+    let token = settings
+        .token
+        .clone()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| "浏览器桥接缺少配对令牌".to_string())?;
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|e| format!("监听 {} 失败: {}", address, e))?;
+
+    let state = BridgeState {
+        app: app_handle.clone(),
+        token: token.into(),
+    };
+    let router = Router::new()
+        .route("/v1/bridge", get(connect))
+        .with_state(state);
+
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+    if let Ok(mut server) = SERVER.lock() {
+        *server = Some(RunningServer {
+            port: settings.port,
+            shutdown,
+        });
+    }
+
+    let port = settings.port;
+    tauri::async_runtime::spawn(async move {
+        let result = axum::serve(listener, router)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+        if let Err(e) = result {
+            tracing::error!(target: "browser_bridge::server", port = port, error = %e, "浏览器桥接异常退出");
+        }
+    });
+
+    tracing::info!(target: "browser_bridge::server", port = settings.port, "🧩 浏览器桥接已启动");
+    Ok(())
+}
+
+fn error_response(status: StatusCode, code: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({ "code": code, "message": message, "context": {} })),
+    )
+        .into_response()
+}
+
+/// 校验来源、Host 与配对令牌后升级为 WebSocket
+async fn connect(
+    State(state): State<BridgeState>,
+    Query(query): Query<ConnectQuery>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    // 普通网页不能连接：浏览器总会带 Origin，只放行扩展来源
+    let origin_allowed = headers
+        .get(header::ORIGIN)
+        .map(|origin| {
+            origin.to_str().is_ok_and(|origin| {
+                EXTENSION_ORIGINS
+                    .iter()
+                    .any(|prefix| origin.starts_with(prefix))
+            })
+        })
+        .unwrap_or(true);
+    if !origin_allowed {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+            "只接受浏览器扩展发起的连接",
+        );
+    }
+
+    // Host 必须是本机地址，防止 DNS 重绑定
+    let host_allowed = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| host.rsplit_once(':').map_or(host, |(host, _)| host))
+        .is_some_and(|host| host == "127.0.0.1" || host.eq_ignore_ascii_case("localhost"));
+    if !host_allowed {
+        return error_response(StatusCode::FORBIDDEN, "FORBIDDEN", "Host 不是本机地址");
+    }
+
+    if !crate::local_api::constant_time_eq(query.token.as_bytes(), state.token.as_bytes()) {
+        tracing::warn!(target: "browser_bridge::connect", "🚫 浏览器桥接连接未通过令牌校验");
+        return error_response(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "配对令牌无效");
+    }
+
+    tracing::info!(target: "browser_bridge::connect", "🧩 浏览器扩展已连接");
+    ws.max_message_size(MAX_MESSAGE_SIZE)
+        .on_upgrade(move |socket| handle_socket(socket, state.app))
+}
+
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+async fn handle_socket(mut socket: WebSocket, app: AppHandle) {
+    let hello = ServerMessage::Hello {
+        version: env!("CARGO_PKG_VERSION"),
+    };
+    if !send(&mut socket, &hello).await {
+        return;
+    }
+
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let reply = match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(ClientMessage::Ping) => ServerMessage::Pong,
+            Ok(ClientMessage::Login {
+                email,
+                access_token,
+                id_token,
+            }) => import_login(&app, email, access_token, id_token).await,
+            Err(e) => ServerMessage::Error {
+                code: "BAD_REQUEST".to_string(),
+                message: format!("无法解析消息: {}", e),
+            },
+        };
+        if !send(&mut socket, &reply).await {
+            break;
+        }
+    }
+    tracing::info!(target: "browser_bridge::connect", "浏览器扩展已断开");
+}
+
+/// 按令牌创建账户备份（与 `create_account_from_token` 命令相同的路径），并通知前端
+async fn import_login(
+    app: &AppHandle,
+    email: String,
+    access_token: String,
+    id_token: String,
+) -> ServerMessage {
+    let account = email.trim().to_string();
+    tracing::info!(target: "browser_bridge::login", account = %crate::system_tray::mask_email(&account), "收到浏览器登录");
+
+    let result =
+        crate::commands::create_account_from_token(app.clone(), email, access_token, id_token)
+            .await;
+
+    let payload = BrowserLoginPayload {
+        account: account.clone(),
+        success: result.is_ok(),
+        message: match &result {
+            Ok(message) => message.clone(),
+            Err(e) => e.to_string(),
+        },
+    };
+    if let Err(e) = crate::events::emit(app, &payload) {
+        tracing::error!(target: "browser_bridge::login", error = %e, "发射浏览器登录事件失败");
+    }
+
+    match result {
+        Ok(message) => {
+            crate::notifications::notify(
+                app,
+                crate::notifications::NotificationKind::BackupCreated,
+                format!(
+                    "已从浏览器添加账户 {}",
+                    crate::system_tray::mask_email(&account)
+                ),
+            );
+            ServerMessage::Imported { account, message }
+        }
+        Err(e) => ServerMessage::Error {
+            code: e.code().to_string(),
+            message: e.to_string(),
+        },
+    }
+}
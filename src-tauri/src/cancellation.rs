@@ -0,0 +1,205 @@
+//! 长耗时操作取消
+//! 切换流水线、备份检查、配置文件导入导出等操作执行时登记一个取消令牌，前端通过
+//! `cancel_operation(operation_id)` 取消：异步步骤在下一个等待点直接中止，阻塞线程中的
+//! 循环在每个条目之间检查令牌。操作因取消而结束后发出 `operation-cancelled` 事件。
+//! 在可取消操作内部再启动的操作（例如切换流程调用恢复）共用外层的令牌
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::sync::Notify;
+
+use crate::error::{CoreError, CoreResult};
+
+/// 取消令牌（克隆后共享同一状态）
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+struct TokenInner {
+    /// 操作名（用于错误信息）
+    operation: String,
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    fn new(operation: &str) -> Self {
+        Self {
+            inner: Arc::new(TokenInner {
+                operation: operation.to_string(),
+                cancelled: AtomicBool::new(false),
+                notify: Notify::new(),
+            }),
+        }
+    }
+
+    /// 请求取消
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 等待取消请求
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// 已请求取消时返回 [`CoreError::Cancelled`]（供阻塞循环逐项检查）
+    pub fn check(&self) -> CoreResult<()> {
+        if self.is_cancelled() {
+            return Err(CoreError::Cancelled {
+                operation: self.inner.operation.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// `operation-cancelled` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationCancelledPayload {
+    pub operation_id: String,
+    /// 操作名（switch / restore / import / export / backup_check 等）
+    pub operation: String,
+}
+
+/// 登记中的操作
+struct Registered {
+    operation: String,
+    token: CancellationToken,
+}
+
+/// 正在执行的可取消操作（按操作 ID 索引）
+static OPERATIONS: Mutex<Option<HashMap<String, Registered>>> = Mutex::new(None);
+
+tokio::task_local! {
+    /// 当前任务所在的可取消操作（ID 与令牌）
+    static CURRENT: (String, CancellationToken);
+}
+
+/// 登记守卫，离开作用域（包括 future 被取消）时移除登记
+struct Registration {
+    operation_id: String,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Ok(mut operations) = OPERATIONS.lock() {
+            if let Some(operations) = operations.as_mut() {
+                operations.remove(&self.operation_id);
+            }
+        }
+    }
+}
+
+fn register(operation_id: &str, operation: &str, token: &CancellationToken) -> Registration {
+    if let Ok(mut operations) = OPERATIONS.lock() {
+        operations.get_or_insert_with(HashMap::new).insert(
+            operation_id.to_string(),
+            Registered {
+                operation: operation.to_string(),
+                token: token.clone(),
+            },
+        );
+    }
+    Registration {
+        operation_id: operation_id.to_string(),
+    }
+}
+
+/// 生成新的操作 ID
+pub fn new_operation_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 当前任务所在操作的取消令牌；不在可取消操作中时返回一个不会被取消的令牌
+///
+/// 需要在阻塞线程中检查取消时，先在异步上下文中取得令牌再移入闭包
+pub fn current() -> CancellationToken {
+    CURRENT
+        .try_with(|(_, token)| token.clone())
+        .unwrap_or_else(|_| CancellationToken::new(""))
+}
+
+/// 当前任务所在操作的 ID
+pub fn current_id() -> Option<String> {
+    CURRENT.try_with(|(id, _)| id.clone()).ok()
+}
+
+/// 作为可取消操作执行 `future`
+///
+/// `operation_id` 由前端传入以便随后取消，未传入时自动生成。取消后 `future` 在下一个等待点
+/// 被丢弃，返回 [`CoreError::Cancelled`] 并发出 `operation-cancelled` 事件
+pub async fn run<T, E, F>(
+    app_handle: &AppHandle,
+    operation: &str,
+    operation_id: Option<String>,
+    future: F,
+) -> Result<T, E>
+where
+    E: From<CoreError>,
+    F: Future<Output = Result<T, E>>,
+{
+    let operation_id = operation_id.unwrap_or_else(new_operation_id);
+    // 嵌套操作共用外层令牌，取消任一 ID 都会中止整个流程
+    let token = CURRENT
+        .try_with(|(_, token)| token.clone())
+        .unwrap_or_else(|_| CancellationToken::new(operation));
+    let _registration = register(&operation_id, operation, &token);
+
+    let result = tokio::select! {
+        result = CURRENT.scope((operation_id.clone(), token.clone()), future) => result,
+        _ = token.cancelled() => Err(CoreError::Cancelled {
+            operation: operation.to_string(),
+        }
+        .into()),
+    };
+
+    if token.is_cancelled() {
+        tracing::info!(target: "app::cancellation", operation = %operation, operation_id = %operation_id, "⏹️ 操作已取消");
+        let payload = OperationCancelledPayload {
+            operation_id,
+            operation: operation.to_string(),
+        };
+        if let Err(e) = crate::events::emit(app_handle, &payload) {
+            tracing::error!(target: "app::cancellation", error = %e, "发射操作取消事件失败");
+        }
+    }
+
+    result
+}
+
+/// 取消指定操作；操作不存在（已结束）时返回 `false`
+pub fn cancel(operation_id: &str) -> bool {
+    let registered = OPERATIONS.lock().ok().and_then(|operations| {
+        operations
+            .as_ref()
+            .and_then(|operations| operations.get(operation_id))
+            .map(|registered| (registered.operation.clone(), registered.token.clone()))
+    });
+
+    match registered {
+        Some((operation, token)) => {
+            tracing::info!(target: "app::cancellation", operation = %operation, operation_id = %operation_id, "⏹️ 请求取消操作");
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
@@ -1,29 +1,38 @@
 //! 账户基础命令：查询、备份、恢复、切换、清理
 
 use crate::antigravity::account::decode_jetski_state_proto;
+use crate::error::{AgentError, AgentResult};
+use crate::utils::{blocking, network_fs};
 use base64::Engine;
 use prost::Message;
-use rusqlite::{Connection, OptionalExtension};
-use serde_json::{from_str, Value};
+use rusqlite::OptionalExtension;
+use serde_json::Value;
 use std::fs;
-use tauri::State;
+use std::path::PathBuf;
+use tauri::{Manager, State};
 use tracing::instrument;
 
 /// 获取所有 Antigravity 账户（解码 jetskiStateSync.agentManagerInitState，返回完整 SessionResponse JSON）
+///
+/// `sanitize` 为 true 时移除令牌与原始 protobuf 片段，返回可公开分享的账户列表
 #[tauri::command]
+#[specta::specta]
 #[instrument]
 pub async fn get_antigravity_accounts(
     state: State<'_, crate::AppState>,
-) -> Result<Vec<Value>, String> {
+    sanitize: Option<bool>,
+) -> AgentResult<Vec<Value>> {
+    crate::app_lock::ensure_unlocked()?;
     tracing::debug!("📋 开始获取所有 Antigravity 账户");
 
     let start_time = std::time::Instant::now();
 
-    let result = async {
-        let mut accounts: Vec<(std::time::SystemTime, Value)> = Vec::new();
+    // 获取备份目录路径
+    let antigravity_dir = state.config_dir.join("antigravity-accounts");
+    let sanitize = sanitize.unwrap_or(false);
 
-        // 获取备份目录路径
-        let antigravity_dir = state.config_dir.join("antigravity-accounts");
+    let result = blocking::run("读取账户列表", move || -> AgentResult<Vec<Value>> {
+        let mut accounts: Vec<(std::time::SystemTime, Value)> = Vec::new();
 
         if !antigravity_dir.exists() {
             tracing::info!("📂 备份目录不存在，返回空列表");
@@ -32,10 +41,10 @@ pub async fn get_antigravity_accounts(
 
         // 读取目录中的所有 JSON 文件
         let entries =
-            fs::read_dir(&antigravity_dir).map_err(|e| format!("读取备份目录失败: {}", e))?;
+            fs::read_dir(&antigravity_dir).map_err(|e| AgentError::io(&antigravity_dir, e))?;
 
         for entry in entries {
-            let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+            let entry = entry.map_err(|e| AgentError::io(&antigravity_dir, e))?;
             let path = entry.path();
 
             // 只处理 JSON 文件
@@ -48,23 +57,29 @@ pub async fn get_antigravity_accounts(
                 tracing::debug!("📄 正在解析备份文件: {}", file_name);
 
                 // 读取并解析 JSON 文件
-                let content = fs::read_to_string(&path)
-                    .map_err(|e| format!("读取文件失败 {}: {}", file_name, e))?;
+                let content =
+                    network_fs::read_to_string(&path).map_err(|e| AgentError::io(&path, e))?;
 
-                let backup_data: Value = from_str(&content)
-                    .map_err(|e| format!("解析 JSON 失败 {}: {}", file_name, e))?;
+                let mut backup_data: Value = crate::antigravity::vault::open(&content)
+                    .map_err(|e| AgentError::decode(format!("{}: {}", file_name, e)))?;
+                crate::antigravity::keychain::reassemble(&mut backup_data)?;
+                crate::antigravity::backup_chain::apply(&path, &mut backup_data)?;
 
                 let jetski_state = backup_data
                     .get("jetskiStateSync.agentManagerInitState")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| {
-                        format!(
+                        AgentError::decode(format!(
                             "备份文件 {} 缺少 jetskiStateSync.agentManagerInitState",
                             file_name
-                        )
+                        ))
                     })?;
 
-                let decoded = decode_jetski_state_proto(jetski_state)?;
+                let mut decoded =
+                    decode_jetski_state_proto(jetski_state).map_err(AgentError::decode)?;
+                if sanitize {
+                    crate::antigravity::account::sanitize_session(&mut decoded);
+                }
 
                 let modified_time = fs::metadata(&path)
                     .and_then(|m| m.modified())
@@ -83,7 +98,7 @@ pub async fn get_antigravity_accounts(
         tracing::debug!("🎉 成功加载 {} 个账户", decoded_only.len());
 
         Ok(decoded_only)
-    }
+    })
     .await;
 
     let duration = start_time.elapsed();
@@ -108,54 +123,151 @@ pub async fn get_antigravity_accounts(
     }
 }
 
-/// 获取当前 Antigravity 账户信息
-#[tauri::command]
-#[instrument]
-pub async fn get_current_antigravity_account_info() -> Result<Value, String> {
-    tracing::info!("开始获取当前 Antigravity 信息");
-
-    let start_time = std::time::Instant::now();
-
-    let result = async {
-        // 尝试获取 Antigravity 状态数据库路径
-        let app_data = match crate::platform::get_antigravity_db_path() {
-            Some(path) => path,
-            None => {
-                // 如果主路径不存在，尝试其他可能的位置
-                let possible_paths = crate::platform::get_all_antigravity_db_paths();
-                if possible_paths.is_empty() {
-                    return Err("未找到Antigravity安装位置".to_string());
-                }
-                possible_paths[0].clone()
+/// 读取当前 Antigravity 数据库中的 jetskiStateSync.agentManagerInitState
+fn read_live_agent_state() -> AgentResult<String> {
+    // 尝试获取 Antigravity 状态数据库路径
+    let app_data = match crate::platform::get_antigravity_db_path() {
+        Some(path) => path,
+        None => {
+            // 如果主路径不存在，尝试其他可能的位置
+            let possible_paths = crate::platform::get_all_antigravity_db_paths();
+            if possible_paths.is_empty() {
+                return Err(AgentError::AntigravityNotFound);
             }
-        };
-
-        if !app_data.exists() {
-            return Err(format!(
-                "Antigravity 状态数据库文件不存在: {}",
-                app_data.display()
-            ));
+            possible_paths[0].clone()
         }
+    };
 
-        // 连接到 SQLite 数据库并获取认证信息
-        let conn = Connection::open(&app_data)
-            .map_err(|e| format!("连接数据库失败 ({}): {}", app_data.display(), e))?;
+    if !app_data.exists() {
+        return Err(AgentError::DatabaseNotFound {
+            path: app_data.display().to_string(),
+        });
+    }
 
-        // jetski 状态（可选）
-        let jetski_state: Option<String> = conn
-            .query_row(
+    // jetski 状态（可选），复用只读连接
+    let read_state = || {
+        crate::utils::db_pool::with_read_connection(&app_data, |conn| {
+            conn.query_row(
                 "SELECT value FROM ItemTable WHERE key = 'jetskiStateSync.agentManagerInitState'",
                 [],
-                |row| row.get(0),
+                |row| row.get::<_, String>(0),
             )
             .optional()
-            .map_err(|e| format!("查询 jetskiStateSync.agentManagerInitState 失败: {}", e))?;
+        })
+    };
+    let jetski_state = crate::slow_operation::measure("db::read_agent_state", read_state)
+        .map_err(|e| AgentError::database(&app_data, e))?;
+
+    jetski_state.ok_or_else(|| AgentError::StateMissing {
+        key: "jetskiStateSync.agentManagerInitState".to_string(),
+    })
+}
+
+/// 获取当前登录状态的结构化内容（套餐、模型、会话历史），不包含原始 protobuf 片段
+///
+/// `redact_secrets` 默认为 true，令牌等凭据替换为占位文本
+#[tauri::command]
+#[specta::specta]
+pub async fn get_decoded_session_state(redact_secrets: Option<bool>) -> AgentResult<Value> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "get_decoded_session_state",
+        args = (redact_secrets),
+        async {
+            let state = blocking::run("读取登录状态", read_live_agent_state).await?;
+
+            let mut session = decode_jetski_state_proto(&state).map_err(AgentError::decode)?;
+            crate::antigravity::account::remove_raw_fields(&mut session);
+            if redact_secrets.unwrap_or(true) {
+                crate::utils::redacted_json::redact(&mut session);
+            }
+            Ok(session)
+        }
+    )
+}
+
+/// 获取当前账户与每个备份的可用模型，便于切换前比较各账户的模型权限
+#[tauri::command]
+#[specta::specta]
+pub async fn get_available_models() -> AgentResult<Vec<crate::antigravity::account::AvailableModels>>
+{
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("get_available_models", async {
+        blocking::run("读取模型信息", || {
+            let mut reports = Vec::new();
+
+            // 当前登录账户（未登录时跳过）
+            match read_live_agent_state() {
+                Ok(state) => {
+                    let email = decode_jetski_state_proto(&state)
+                        .ok()
+                        .and_then(|session| {
+                            session["context"]["email"].as_str().map(str::to_string)
+                        })
+                        .unwrap_or_default();
+                    reports.push(crate::antigravity::account::available_models(
+                        &email, true, &state,
+                    ));
+                }
+                Err(e) => {
+                    tracing::debug!(target: "account::models", error = %e, "未读取到当前登录状态，跳过")
+                }
+            }
+
+            // 每个备份
+            for entry in crate::antigravity::backup::list_backup_files()? {
+                let state = crate::antigravity::keychain::read_backup(&entry.path).and_then(|backup| {
+                    backup
+                        .get(crate::constants::database::AGENT_STATE)
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .ok_or_else(|| "备份缺少登录状态".to_string())
+                });
+                reports.push(match state {
+                    Ok(state) => {
+                        crate::antigravity::account::available_models(&entry.name, false, &state)
+                    }
+                    Err(e) => crate::antigravity::account::AvailableModels {
+                        account: entry.name,
+                        current: false,
+                        plan_name: String::new(),
+                        models: Vec::new(),
+                        recommended: Vec::new(),
+                        error: Some(e),
+                    },
+                });
+            }
+            Ok::<_, AgentError>(reports)
+        })
+        .await
+    })
+}
 
-        let state_str = jetski_state
-            .ok_or_else(|| "未找到 jetskiStateSync.agentManagerInitState".to_string())?;
+/// 获取已保存的应用状态（配置档案与最近一次切换到的账户，重启后保留）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_state(
+    state: State<'_, crate::AppState>,
+) -> AgentResult<crate::state::AppStateSummary> {
+    crate::app_lock::ensure_unlocked()?;
+    Ok(state.summary())
+}
+
+/// 获取当前 Antigravity 账户信息
+#[tauri::command]
+#[specta::specta]
+#[instrument]
+pub async fn get_current_antigravity_account_info() -> AgentResult<Value> {
+    crate::app_lock::ensure_unlocked()?;
+    tracing::info!("开始获取当前 Antigravity 信息");
+
+    let start_time = std::time::Instant::now();
+
+    let result = async {
+        let state_str = blocking::run("读取登录状态", read_live_agent_state).await?;
 
         // 解码 jetski 状态（base64 + proto）；失败直接报错
-        let decoded = decode_jetski_state_proto(&state_str)?;
+        let decoded = decode_jetski_state_proto(&state_str).map_err(AgentError::decode)?;
 
         Ok(serde_json::json!(decoded))
     }
@@ -182,95 +294,134 @@ pub async fn get_current_antigravity_account_info() -> Result<Value, String> {
     }
 }
 
-/// 备份当前 Antigravity 账户
-#[tauri::command]
-#[instrument]
-pub async fn save_antigravity_current_account() -> Result<String, String> {
-    tracing::info!("📥 开始保存 jetskiStateSync.agentManagerInitState");
-
-    let start_time = std::time::Instant::now();
-
-    let result = async {
-        // 尝试获取 Antigravity 状态数据库路径
-        let app_data = match crate::platform::get_antigravity_db_path() {
-            Some(path) => path,
-            None => {
-                // 如果主路径不存在，尝试其他可能的位置
-                let possible_paths = crate::platform::get_all_antigravity_db_paths();
-                if possible_paths.is_empty() {
-                    return Err("未找到Antigravity安装位置".to_string());
-                }
-                possible_paths[0].clone()
+/// 读取当前登录状态并写入以邮箱命名的备份文件，返回邮箱、数据库路径与备份文件路径
+fn backup_current_account() -> AgentResult<(String, PathBuf, PathBuf)> {
+    // 尝试获取 Antigravity 状态数据库路径
+    let app_data = match crate::platform::get_antigravity_db_path() {
+        Some(path) => path,
+        None => {
+            // 如果主路径不存在，尝试其他可能的位置
+            let possible_paths = crate::platform::get_all_antigravity_db_paths();
+            if possible_paths.is_empty() {
+                return Err(AgentError::AntigravityNotFound);
             }
-        };
-
-        if !app_data.exists() {
-            return Err(format!(
-                "Antigravity 状态数据库文件不存在: {}",
-                app_data.display()
-            ));
+            possible_paths[0].clone()
         }
+    };
 
-        // 连接到 SQLite 数据库并获取认证信息
-        let conn = Connection::open(&app_data)
-            .map_err(|e| format!("连接数据库失败 ({}): {}", app_data.display(), e))?;
+    if !app_data.exists() {
+        return Err(AgentError::DatabaseNotFound {
+            path: app_data.display().to_string(),
+        });
+    }
 
-        // jetski 状态（必需）
-        let jetski_state: String = conn
-            .query_row(
+    // jetski 状态（必需），复用只读连接
+    let jetski_state: String = crate::slow_operation::measure("db::read_agent_state", || {
+        crate::utils::db_pool::with_read_connection(&app_data, |conn| {
+            conn.query_row(
                 "SELECT value FROM ItemTable WHERE key = 'jetskiStateSync.agentManagerInitState'",
                 [],
                 |row| row.get(0),
             )
             .optional()
-            .map_err(|e| format!("查询 jetskiStateSync.agentManagerInitState 失败: {}", e))?
-            .ok_or_else(|| "未找到 jetskiStateSync.agentManagerInitState".to_string())?;
-
-        // 从 jetski proto 解码邮箱（仅用于文件名）
-        let bytes = base64::engine::general_purpose::STANDARD
-            .decode(jetski_state.trim())
-            .map_err(|e| format!("jetskiStateSync Base64 解码失败: {}", e))?;
-        let msg = crate::proto::SessionResponse::decode(bytes.as_slice())
-            .map_err(|e| format!("jetskiStateSync Protobuf 解码失败: {}", e))?;
-
-        let email = msg
-            .context
-            .as_ref()
-            .and_then(|c| {
-                if c.email.is_empty() {
-                    None
-                } else {
-                    Some(c.email.as_str())
-                }
-            })
-            .ok_or_else(|| "jetskiStateSync 中未找到邮箱字段，无法确定备份文件名".to_string())?;
+        })
+    })
+    .map_err(|e| AgentError::database(&app_data, e))?
+    .ok_or_else(|| AgentError::StateMissing {
+        key: "jetskiStateSync.agentManagerInitState".to_string(),
+    })?;
+
+    // 从 jetski proto 解码邮箱（仅用于文件名）
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(jetski_state.trim())
+        .map_err(|e| AgentError::decode(format!("jetskiStateSync Base64 解码失败: {}", e)))?;
+    let msg = crate::proto::SessionResponse::decode(bytes.as_slice())
+        .map_err(|e| AgentError::decode(format!("jetskiStateSync Protobuf 解码失败: {}", e)))?;
+
+    let email = msg
+        .context
+        .as_ref()
+        .and_then(|c| {
+            if c.email.is_empty() {
+                None
+            } else {
+                Some(c.email.as_str())
+            }
+        })
+        .ok_or_else(|| {
+            AgentError::decode("jetskiStateSync 中未找到邮箱字段，无法确定备份文件名")
+        })?;
 
-        // 直接保存原始字符串，不解码，文件名与原逻辑保持：{email}.json
-        let accounts_dir = crate::directories::get_accounts_directory();
-        if let Err(e) = std::fs::create_dir_all(&accounts_dir) {
-            return Err(format!("创建账户目录失败: {}", e));
-        }
+    // 直接保存原始字符串，不解码，文件名与原逻辑保持：{email}.json
+    let accounts_dir = crate::directories::get_accounts_directory();
+    if let Err(e) = std::fs::create_dir_all(&accounts_dir) {
+        return Err(AgentError::io(&accounts_dir, e));
+    }
 
-        let account_file = accounts_dir.join(format!("{email}.json"));
-        let content = serde_json::json!({
-            "jetskiStateSync.agentManagerInitState": jetski_state
-        });
-        std::fs::write(
-            &account_file,
-            serde_json::to_string_pretty(&content).unwrap(),
-        )
-        .map_err(|e| format!("写入 jetski 状态失败: {}", e))?;
+    // 覆盖前把现有备份保存为历史版本（失败不影响本次备份）
+    if let Err(e) = crate::antigravity::backup::archive_backup_version(email, &jetski_state) {
+        tracing::warn!(target: "backup::version", error = %e, "保存备份历史版本失败");
+    }
+
+    let account_file = accounts_dir.join(format!("{email}.json"));
+    let content = serde_json::json!({
+        "jetskiStateSync.agentManagerInitState": jetski_state
+    });
+    // 与上一次备份相比只写入变化的键，恢复时重放增量链
+    crate::antigravity::backup_chain::save(email, &account_file, &content)
+        .map_err(|e| AgentError::io(&account_file, e))?;
+
+    Ok((email.to_string(), app_data, account_file))
+}
+
+/// 备份当前 Antigravity 账户
+#[tauri::command]
+#[specta::specta]
+#[instrument(skip(app))]
+pub async fn save_antigravity_current_account(app: tauri::AppHandle) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    tracing::info!("📥 开始保存 jetskiStateSync.agentManagerInitState");
+
+    let start_time = std::time::Instant::now();
+
+    let result = crate::pipeline::run(&app, "backup", async {
+        let (email, app_data, account_file) =
+            blocking::run("备份当前账户", backup_current_account).await?;
+
+        app.state::<crate::AppState>()
+            .record_profile(&email, &app_data, &account_file);
 
         let message = format!(
             "已保存 jetskiStateSync.agentManagerInitState 到 {}",
             account_file.display()
         );
         tracing::info!(file = %account_file.display(), "✅ 保存 jetski 状态完成");
+        crate::notifications::notify(
+            &app,
+            crate::notifications::NotificationKind::BackupCreated,
+            format!("已备份账户 {}", crate::system_tray::mask_email(&email)),
+        );
+        crate::webhooks::dispatch(
+            &app,
+            crate::webhooks::WebhookEvent::BackupCreated,
+            serde_json::json!({ "account": email }),
+        );
+        let data =
+            serde_json::json!({ "account": email, "file": account_file.display().to_string() });
+        crate::plugins::dispatch(&app, crate::hooks::HookEvent::BackupCreated, &data);
+        crate::hooks::dispatch(&app, crate::hooks::HookEvent::BackupCreated, data);
         Ok(message)
-    }
+    })
     .await;
 
     let duration = start_time.elapsed();
+    crate::audit::record(
+        crate::audit::AuditOperation::Backup,
+        None,
+        start_time,
+        &result,
+        result.as_deref().ok(),
+    );
 
     match result {
         Ok(message) => {
@@ -279,6 +430,7 @@ pub async fn save_antigravity_current_account() -> Result<String, String> {
                 result_message = %message,
                 "账户保存操作完成"
             );
+            crate::system_tray::refresh_tray_menu(&app);
             Ok(message)
         }
         Err(e) => {
@@ -294,74 +446,363 @@ pub async fn save_antigravity_current_account() -> Result<String, String> {
 
 /// 清除所有 Antigravity 数据
 #[tauri::command]
-pub async fn clear_all_antigravity_data() -> Result<String, String> {
-    crate::antigravity::cleanup::clear_all_antigravity_data().await
+#[specta::specta]
+pub async fn clear_all_antigravity_data(app: tauri::AppHandle) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("clear_all_antigravity_data")?;
+    crate::os_auth::require(
+        "clear_all_antigravity_data",
+        "清除 Antigravity 的所有登录数据",
+    )
+    .await?;
+
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::audit::track(
+        crate::audit::AuditOperation::Cleanup,
+        None,
+        guard.run(
+            "cleanup",
+            crate::antigravity::cleanup::clear_all_antigravity_data(),
+        ),
+    )
+    .await
+    .map_err(AgentError::from)
 }
 
 /// 恢复 Antigravity 账户
 #[tauri::command]
-pub async fn restore_antigravity_account(account_name: String) -> Result<String, String> {
+#[specta::specta]
+pub async fn restore_antigravity_account(
+    app: tauri::AppHandle,
+    account_name: String,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
     tracing::debug!(target: "account::restore", account_name = %account_name, "调用 restore_antigravity_account");
+    crate::os_auth::require("restore_antigravity_account", "恢复 Antigravity 账户").await?;
 
-    // 1. 构建备份文件路径
+    // 1. 构建备份文件路径（账户名来自前端，拼接前先校验）
+    crate::antigravity::archive::validate_backup_name(&account_name)?;
     let accounts_dir = crate::directories::get_accounts_directory();
     let account_file = accounts_dir.join(format!("{account_name}.json"));
+    if !account_file.exists() {
+        return Err(AgentError::BackupNotFound {
+            account: account_name,
+        });
+    }
 
     // 2. 调用统一的恢复函数
-    crate::antigravity::restore::save_antigravity_account_to_file(account_file).await
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::audit::track(
+        crate::audit::AuditOperation::Restore,
+        Some(&account_name),
+        guard.run(
+            "restore",
+            crate::pipeline::run(&app, "restore", async {
+                crate::antigravity::restore::save_antigravity_account_to_file(account_file)
+                    .await
+                    .map_err(AgentError::from)
+            }),
+        ),
+    )
+    .await
+}
+
+/// 预览恢复 Antigravity 账户：列出恢复时将写入、替换或删除的键，不修改数据库
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_restore(
+    account_name: String,
+) -> AgentResult<crate::antigravity::restore::RestorePreview> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::antigravity::archive::validate_backup_name(&account_name)?;
+    let account_file =
+        crate::directories::get_accounts_directory().join(format!("{account_name}.json"));
+    if !account_file.exists() {
+        return Err(AgentError::BackupNotFound {
+            account: account_name,
+        });
+    }
+
+    crate::log_async_command!("preview_restore", args = (account_name), async {
+        crate::antigravity::restore::preview_restore(account_file)
+            .await
+            .map_err(AgentError::from)
+    })
+}
+
+/// 列出账户备份的历史版本（最新的在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backup_versions(
+    account_name: String,
+) -> AgentResult<Vec<crate::antigravity::backup::BackupVersion>> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("list_backup_versions", args = (account_name), async {
+        blocking::run("列出备份历史版本", move || {
+            crate::antigravity::backup::list_backup_versions(&account_name)
+        })
+        .await
+        .map_err(AgentError::from)
+    })
+}
+
+/// 将账户备份的某个历史版本写回 Antigravity 数据库
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_backup_version(
+    app: tauri::AppHandle,
+    account_name: String,
+    version: String,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::os_auth::require("restore_backup_version", "恢复账户备份的历史版本").await?;
+
+    let version_file = crate::antigravity::backup::backup_version_path(&account_name, &version)?;
+    let subject = format!("{} @ {}", account_name, version);
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::log_async_command!(
+        "restore_backup_version",
+        args = (account_name, version),
+        crate::audit::track(
+            crate::audit::AuditOperation::Restore,
+            Some(&subject),
+            guard.run(
+                "restore",
+                crate::pipeline::run(&app, "restore", async {
+                    crate::antigravity::restore::save_antigravity_account_to_file(version_file)
+                        .await
+                        .map_err(AgentError::from)
+                }),
+            ),
+        )
+    )
 }
 
 /// 切换到 Antigravity 账户（调用 restore_antigravity_account）
 #[tauri::command]
-pub async fn switch_to_antigravity_account(account_name: String) -> Result<String, String> {
-    crate::log_async_command!("switch_to_antigravity_account", async {
-        // 1. 关闭 Antigravity 进程 (如果存在)
-        let kill_result = match crate::platform::kill_antigravity_processes() {
-            Ok(result) => {
-                if result.contains("not found") || result.contains("未找到") {
-                    tracing::debug!(target: "account::switch::step1", "Antigravity 进程未运行，跳过关闭步骤");
-                    "Antigravity 进程未运行".to_string()
-                } else {
+#[specta::specta]
+pub async fn switch_to_antigravity_account(
+    app: tauri::AppHandle,
+    account_name: String,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::os_auth::require("switch_to_antigravity_account", "切换 Antigravity 账户").await?;
+
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    let started = std::time::Instant::now();
+    let result = crate::log_async_command!(
+        "switch_to_antigravity_account",
+        args = (account_name),
+        guard.run("switch", crate::pipeline::run(&app, "switch", async {
+            // 1. 关闭 Antigravity 进程 (如果存在)
+            let kill_result = match crate::slow_operation::measure(
+                "switch::close_antigravity",
+                crate::platform::kill_antigravity_processes,
+            ) {
+                Ok(result) => {
                     tracing::debug!(target: "account::switch::step1", result = %result, "进程关闭完成");
                     result
                 }
-            }
-            Err(e) => {
-                if e.contains("not found") || e.contains("未找到") {
+                Err(e) if e.is_not_found() => {
                     tracing::debug!(target: "account::switch::step1", "Antigravity 进程未运行，跳过关闭步骤");
                     "Antigravity 进程未运行".to_string()
-                } else {
+                }
+                Err(e) => {
                     tracing::error!(target: "account::switch::step1", error = %e, "关闭进程时发生错误");
-                    return Err(format!("关闭进程时发生错误: {}", e));
+                    return Err(AgentError::Process {
+                        message: format!("关闭进程时发生错误: {}", e),
+                    });
                 }
-            }
-        };
+            };
 
-        // 等待一秒确保进程完全关闭
-        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+            crate::pipeline::emit_progress(&app, "switch", 25, "Antigravity 已关闭");
 
-        // 2. 恢复指定账户到 Antigravity 数据库
-        let restore_result = restore_antigravity_account(account_name.clone()).await?;
-        tracing::debug!(target: "account::switch::step2", result = %restore_result, "账户数据恢复完成");
+            // 等待一秒确保进程完全关闭
+            tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
 
-        // 等待一秒确保数据库操作完成
-        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+            // 2. 恢复指定账户到 Antigravity 数据库
+            let restore_result =
+                restore_antigravity_account(app.clone(), account_name.clone()).await?;
+            tracing::debug!(target: "account::switch::step2", result = %restore_result, "账户数据恢复完成");
 
-        // 3. 重新启动 Antigravity 进程
-        let start_result = crate::antigravity::starter::start_antigravity();
-        let start_message = match start_result {
-            Ok(result) => {
-                tracing::debug!(target: "account::switch::step3", result = %result, "Antigravity 启动成功");
-                result
-            }
-            Err(e) => {
-                tracing::warn!(target: "account::switch::step3", error = %e, "Antigravity 启动失败");
-                format!("启动失败: {}", e)
+            crate::pipeline::emit_progress(&app, "switch", 60, "账户数据已恢复");
+
+            // 等待一秒确保数据库操作完成
+            tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+
+            // 3. 重新启动 Antigravity 进程
+            let start_result = crate::slow_operation::measure(
+                "switch::start_antigravity",
+                crate::antigravity::starter::start_antigravity,
+            );
+            let start_message = match start_result {
+                Ok(result) => {
+                    tracing::debug!(target: "account::switch::step3", result = %result, "Antigravity 启动成功");
+                    result
+                }
+                Err(e) => {
+                    tracing::warn!(target: "account::switch::step3", error = %e, "Antigravity 启动失败");
+                    format!("启动失败: {}", e)
+                }
+            };
+
+            crate::pipeline::emit_progress(&app, "switch", 90, "Antigravity 已启动");
+
+            let final_message =
+                format!("{} -> {} -> {}", kill_result, restore_result, start_message);
+            app.state::<crate::AppState>().set_current_account(&account_name);
+
+            crate::notifications::notify(
+                &app,
+                crate::notifications::NotificationKind::SwitchComplete,
+                format!(
+                    "已切换到账户 {}",
+                    crate::system_tray::mask_email(&account_name)
+                ),
+            );
+            crate::webhooks::dispatch(
+                &app,
+                crate::webhooks::WebhookEvent::SwitchComplete,
+                serde_json::json!({ "account": account_name }),
+            );
+
+            Ok(final_message)
+        }))
+    );
+
+    crate::audit::record(
+        crate::audit::AuditOperation::Switch,
+        Some(&account_name),
+        started,
+        &result,
+        result.as_deref().ok(),
+    );
+    result
+}
+
+/// 恢复备份前先为当前登录状态创建安全快照，再执行完整切换流程
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_backup_with_safety_snapshot(
+    app: tauri::AppHandle,
+    account_name: String,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::log_async_command!(
+        "restore_backup_with_safety_snapshot",
+        args = (account_name),
+        guard.run("restore", async {
+            crate::os_auth::require("restore_backup_with_safety_snapshot", "恢复账户备份").await?;
+
+            match blocking::run(
+                "创建安全快照",
+                crate::antigravity::backup::create_safety_snapshot,
+            )
+            .await?
+            {
+                Some(snapshot) => {
+                    tracing::info!(target: "account::restore::safety", snapshot = %snapshot.display(), "安全快照已创建");
+                }
+                None => {
+                    tracing::debug!(target: "account::restore::safety", "当前无登录状态，跳过安全快照");
+                }
             }
-        };
 
-        let final_message = format!("{} -> {} -> {}", kill_result, restore_result, start_message);
+            switch_to_antigravity_account(app.clone(), account_name.clone()).await
+        })
+    )
+}
 
-        Ok(final_message)
-    })
+/// 用粘贴的令牌创建账户备份，无需在本机的 Antigravity 中登录该账户
+///
+/// 同名备份已存在时拒绝覆盖
+#[tauri::command]
+#[specta::specta]
+pub async fn create_account_from_token(
+    app: tauri::AppHandle,
+    email: String,
+    access_token: String,
+    id_token: String,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let email = email.trim().to_string();
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    // 不记录参数：包含令牌
+    crate::log_async_command!(
+        "create_account_from_token",
+        guard.run(
+            "import",
+            crate::audit::track(
+                crate::audit::AuditOperation::ImportBackups,
+                Some(&email),
+                async {
+                    let state = crate::antigravity::account::build_session_state(
+                        &email,
+                        access_token.trim(),
+                        id_token.trim(),
+                    )
+                    .map_err(AgentError::decode)?;
+
+                    let content = serde_json::json!({
+                        crate::constants::database::AGENT_STATE: state
+                    });
+                    let filename = format!("{email}.json");
+                    crate::antigravity::archive::validate_backup(&filename, &content)?;
+
+                    let account = email.clone();
+                    let account_file = blocking::run("写入账户备份", move || {
+                        let accounts_dir = crate::directories::get_accounts_directory();
+                        fs::create_dir_all(&accounts_dir)
+                            .map_err(|e| AgentError::io(&accounts_dir, e))?;
+                        let account_file = accounts_dir.join(&filename);
+                        if account_file.exists() {
+                            return Err(AgentError::Internal(format!(
+                                "账户 {} 的备份已存在",
+                                account
+                            )));
+                        }
+                        crate::antigravity::keychain::write_backup(
+                            &account_file,
+                            &account,
+                            &content,
+                        )
+                        .map_err(|e| AgentError::io(&account_file, e))?;
+                        Ok(account_file)
+                    })
+                    .await?;
+
+                    crate::system_tray::refresh_tray_menu(&app);
+                    tracing::info!(file = %account_file.display(), "✅ 已用令牌创建账户备份");
+                    Ok::<_, AgentError>(format!("已用令牌创建账户 {}", email))
+                },
+            ),
+        )
+    )
+}
+
+/// 就地修改当前登录状态中的令牌、邮箱或套餐字段（修改前自动创建安全快照）
+#[tauri::command]
+#[specta::specta]
+pub async fn patch_session_state(
+    app: tauri::AppHandle,
+    patch: crate::antigravity::account::SessionPatch,
+) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("patch_session_state")?;
+    crate::os_auth::require("patch_session_state", "修改 Antigravity 登录状态").await?;
+
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::log_async_command!(
+        "patch_session_state",
+        args = (patch),
+        guard.run("restore", async {
+            blocking::run("修改登录状态", move || {
+                crate::antigravity::restore::patch_session_state(&patch)
+            })
+            .await
+            .map_err(AgentError::from)
+        })
+    )
 }
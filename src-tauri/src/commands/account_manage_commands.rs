@@ -1,14 +1,15 @@
 //! 账户备份/导入导出与加解密命令
 
 use crate::log_async_command;
+use crate::utils::{blocking, network_fs};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
 use std::time::SystemTime;
-use tauri::State;
+use tauri::{Manager, State};
 
 /// 备份数据收集结构
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, specta::Type)]
 pub struct AccountExportedData {
     filename: String,
     #[serde(rename = "content")]
@@ -18,33 +19,160 @@ pub struct AccountExportedData {
 }
 
 /// 恢复结果
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, specta::Type)]
 pub struct RestoreResult {
     #[serde(rename = "restoredCount")]
     restored_count: u32,
     failed: Vec<FailedAccountExportedData>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, specta::Type)]
 pub struct FailedAccountExportedData {
     filename: String,
     error: String,
 }
 
-/// 收集所有账户文件的完整内容, 用于导出
+/// 分页的备份内容
+#[derive(Serialize, Deserialize, Debug, specta::Type)]
+pub struct BackupContentsPage {
+    /// 当前页的备份（按文件名排序）；`metadata_only` 为 true 时 `content` 为 `null`
+    items: Vec<AccountExportedData>,
+    /// 备份总数
+    total: usize,
+    /// 下一页的起始位置，没有更多时为 `None`
+    #[serde(rename = "nextOffset")]
+    next_offset: Option<usize>,
+}
+
+/// 收集账户文件的内容, 用于导出
+///
+/// `share` 为 true 时遮盖登录状态与令牌等凭据，仅保留结构，便于分享给他人排查问题；
+/// `sanitize` 为 true 时直接移除令牌与原始登录状态，只保留解码后的非敏感元数据，可公开分享。
+/// `offset` / `limit` 按文件名分页（不传 `limit` 时返回剩余全部）；`metadata_only` 为 true 时
+/// 只返回文件名，不读取备份内容，完整内容通过 `get_backup_content` 按需获取。
+/// 可传入 `operation_id`，读取过程中通过 `cancel_operation` 取消
 #[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub async fn collect_account_contents(
+    app: tauri::AppHandle,
+    state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    share: Option<bool>,
+    sanitize: Option<bool>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    metadata_only: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<BackupContentsPage, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let share = share.unwrap_or(false);
+    let sanitize = sanitize.unwrap_or(false);
+    let metadata_only = metadata_only.unwrap_or(false);
+    if !share && !sanitize && !metadata_only {
+        crate::os_auth::require("collect_account_contents", "导出包含登录凭据的账户备份").await?;
+    }
+
+    let config_dir = state.config_dir.clone();
+    let export = crate::cancellation::run(&app, "export", operation_id, async {
+        let token = crate::cancellation::current();
+        blocking::run("读取账户备份", move || {
+            let files = list_account_files(&config_dir)?;
+            let total = files.len();
+            let offset = offset.unwrap_or(0).min(total);
+            let end = limit.map_or(total, |limit| offset.saturating_add(limit).min(total));
+
+            let mut items = Vec::with_capacity(end - offset);
+            for (filename, path) in files.into_iter().skip(offset).take(end - offset) {
+                token.check()?;
+                if metadata_only {
+                    items.push(AccountExportedData {
+                        filename,
+                        content: Value::Null,
+                        timestamp: unix_now(),
+                    });
+                } else if let Some(item) = read_account_content(&path, filename, share, sanitize)? {
+                    items.push(item);
+                }
+            }
+
+            Ok(BackupContentsPage {
+                items,
+                total,
+                next_offset: (end < total).then_some(end),
+            })
+        })
+        .await
+    });
+    guard.run("export", export).await
+}
+
+/// 获取单个备份的完整内容（`share` / `sanitize` 含义同 `collect_account_contents`）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_backup_content(
     state: State<'_, crate::AppState>,
-) -> Result<Vec<AccountExportedData>, String> {
-    let mut backups_with_content = Vec::new();
+    name: String,
+    share: Option<bool>,
+    sanitize: Option<bool>,
+) -> Result<AccountExportedData, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let share = share.unwrap_or(false);
+    let sanitize = sanitize.unwrap_or(false);
+    if !share && !sanitize {
+        crate::os_auth::require("get_backup_content", "读取包含登录凭据的账户备份").await?;
+    }
 
+    let config_dir = state.config_dir.clone();
+    log_async_command!(
+        "get_backup_content",
+        args = (name, share, sanitize),
+        blocking::run("读取账户备份", move || {
+            // 只在已有备份中查找，避免名称中的路径分隔符指向备份目录之外
+            let (filename, path) = list_account_files(&config_dir)?
+                .into_iter()
+                .find(|(filename, _)| filename.strip_suffix(".json") == Some(name.as_str()))
+                .ok_or_else(|| crate::i18n::t("backup.file_missing", &Value::Null))?;
+            read_account_content(&path, filename.clone(), share, sanitize)?
+                .ok_or_else(|| format!("备份 {} 无法读取或已损坏", filename))
+        })
+    )
+}
+
+/// 按元数据索引列出备份（文件名、邮箱、备份时间、大小与键摘要）
+///
+/// 不返回登录状态，也不读取备份内容；索引中没有或已过期的备份标记为 `stale`，在后台重建
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backups_indexed(
+) -> Result<Vec<crate::antigravity::backup_index::BackupIndexEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "list_backups_indexed",
+        blocking::run("列出备份索引", crate::antigravity::backup_index::list)
+    )
+}
+
+/// 当前 Unix 时间（秒）
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 列出备份目录中的账户文件（文件名与路径），按文件名排序
+fn list_account_files(
+    config_dir: &std::path::Path,
+) -> Result<Vec<(String, std::path::PathBuf)>, String> {
     // 读取Antigravity账户目录中的JSON文件
-    let antigravity_dir = state.config_dir.join("antigravity-accounts");
+    let antigravity_dir = config_dir.join("antigravity-accounts");
 
     if !antigravity_dir.exists() {
-        return Ok(backups_with_content);
+        return Ok(Vec::new());
     }
 
+    let mut files = Vec::new();
     for entry in fs::read_dir(&antigravity_dir).map_err(|e| format!("读取用户目录失败: {}", e))?
     {
         let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
@@ -60,63 +188,328 @@ pub async fn collect_account_contents(
             if filename.is_empty() {
                 continue;
             }
+            files.push((filename, path));
+        }
+    }
 
-            match fs::read_to_string(&path).map_err(|e| format!("读取文件失败 {}: {}", filename, e))
-            {
-                Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
-                    Ok(json_value) => {
-                        backups_with_content.push(AccountExportedData {
-                            filename,
-                            content: json_value,
-                            timestamp: SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs(),
-                        });
-                    }
-                    Err(e) => {
-                        tracing::warn!(target: "backup::scan", filename = %filename, error = %e, "跳过损坏的备份文件");
-                    }
-                },
-                Err(_) => {
-                    tracing::warn!(target: "backup::scan", filename = %filename, "跳过无法读取的文件");
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// 读取单个账户文件的内容；无法读取或已损坏的文件记录警告后返回 `None`
+fn read_account_content(
+    path: &std::path::Path,
+    filename: String,
+    share: bool,
+    sanitize: bool,
+) -> Result<Option<AccountExportedData>, String> {
+    match network_fs::read_to_string(path).map_err(|e| format!("读取文件失败 {}: {}", filename, e))
+    {
+        Ok(content) => match crate::antigravity::vault::open(&content) {
+            Ok(mut json_value) => {
+                // 登录状态保存在钥匙串中时，导出前重新组装
+                if let Err(e) = crate::antigravity::keychain::reassemble(&mut json_value) {
+                    tracing::warn!(target: "backup::scan", filename = %filename, error = %e, "跳过无法从钥匙串读取的备份");
+                    return Ok(None);
+                }
+                if let Err(e) = crate::antigravity::backup_chain::apply(path, &mut json_value) {
+                    tracing::warn!(target: "backup::scan", filename = %filename, error = %e, "跳过无法重放增量记录的备份");
+                    return Ok(None);
                 }
+                let content = if sanitize {
+                    crate::antigravity::account::sanitize_backup(&json_value)
+                } else if share {
+                    crate::utils::redacted_json::to_value(&json_value)
+                        .map_err(|e| format!("脱敏备份内容失败 {}: {}", filename, e))?
+                } else {
+                    json_value
+                };
+                Ok(Some(AccountExportedData {
+                    filename,
+                    content,
+                    timestamp: unix_now(),
+                }))
+            }
+            Err(e) => {
+                tracing::warn!(target: "backup::scan", filename = %filename, error = %e, "跳过损坏的备份文件");
+                Ok(None)
             }
+        },
+        Err(_) => {
+            tracing::warn!(target: "backup::scan", filename = %filename, "跳过无法读取的文件");
+            Ok(None)
         }
     }
-
-    Ok(backups_with_content)
 }
 
 /// 恢复备份文件到本地
+///
+/// 可传入 `operation_id`，写入过程中通过 `cancel_operation` 取消（已写入的备份保留）
 #[tauri::command]
+#[specta::specta]
 pub async fn restore_backup_files(
+    app: tauri::AppHandle,
     account_file_data: Vec<AccountExportedData>,
     state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    operation_id: Option<String>,
 ) -> Result<RestoreResult, String> {
-    let mut results = RestoreResult {
-        restored_count: 0,
-        failed: Vec::new(),
+    crate::app_lock::ensure_unlocked()?;
+    guard
+        .run(
+            "import",
+            crate::cancellation::run(
+                &app,
+                "import",
+                operation_id,
+                write_backup_files(&app, &state, account_file_data),
+            ),
+        )
+        .await
+}
+
+/// 导入账户配置文件：先解密并校验格式版本、签名与备份内容，全部通过后才写入备份目录
+///
+/// `allow_unsigned` 为 true 时允许导入旧版本导出的未签名文件；`trust_signer` 为 true 时
+/// 信任文件的签名设备（不是本机也不在信任列表中时需要用户确认）；
+/// 可传入 `operation_id`，导入过程中通过 `cancel_operation` 取消
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_config_archive(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    path: String,
+    password: String,
+    allow_unsigned: Option<bool>,
+    trust_signer: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<RestoreResult, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "import_config_archive",
+        window = window.label(),
+        args = (path, password, allow_unsigned, trust_signer),
+        guard.run(
+            "import",
+            crate::cancellation::run(&app, "import", operation_id, async {
+                let config = blocking::run("读取配置文件", move || {
+                    crate::antigravity::archive::open(
+                        std::path::Path::new(&path),
+                        &password,
+                        allow_unsigned.unwrap_or(false),
+                        trust_signer.unwrap_or(false),
+                    )
+                })
+                .await?;
+                let backups: Vec<AccountExportedData> =
+                    serde_json::from_value(config["backups"].clone())
+                        .map_err(|e| format!("配置文件备份格式无效: {}", e))?;
+                write_backup_files(&app, &state, backups).await
+            })
+        )
+    )
+}
+
+/// 将所有账户备份（含钥匙串中的登录状态）与 Antigravity 路径配置导出为一个加密的 `.agbak` 归档，
+/// 用于整体迁移到新机器
+///
+/// 可传入 `operation_id`，打包过程中通过 `cancel_operation` 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn export_backup_archive(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    path: String,
+    password: String,
+    operation_id: Option<String>,
+) -> Result<crate::antigravity::backup_archive::BackupArchiveSummary, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::os_auth::require("export_backup_archive", "导出包含登录凭据的完整备份归档").await?;
+    // 不记录密码
+    log_async_command!(
+        "export_backup_archive",
+        window = window.label(),
+        args = (path),
+        guard.run(
+            "export",
+            crate::cancellation::run(&app, "export", operation_id, async {
+                let token = crate::cancellation::current();
+                blocking::run("导出备份归档", move || {
+                    crate::antigravity::backup_archive::export(
+                        std::path::Path::new(&path),
+                        &password,
+                        &token,
+                    )
+                })
+                .await
+            })
+        )
+    )
+}
+
+/// 解密 `.agbak` 归档并合并到备份目录，与已有备份同名时按 `strategy` 跳过、覆盖或另存为新名称，
+/// 返回每个文件的导入结果
+///
+/// 旧版未签名归档需要 `allow_unsigned`，未受信任设备签名的归档需要 `trust_signer`（加入信任列表）；
+/// 归档中的自定义可执行文件路径只在 `import_executable_path` 为 true 时导入（均需用户确认）
+///
+/// 可传入 `operation_id`，导入过程中通过 `cancel_operation` 取消（已写入的备份保留）
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_backup_archive(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    path: String,
+    password: String,
+    strategy: crate::antigravity::backup_archive::ConflictStrategy,
+    allow_unsigned: Option<bool>,
+    trust_signer: Option<bool>,
+    import_executable_path: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<crate::antigravity::backup_archive::ArchiveImportReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let options = crate::antigravity::backup_archive::ArchiveImportOptions {
+        allow_unsigned: allow_unsigned.unwrap_or(false),
+        trust_signer: trust_signer.unwrap_or(false),
+        import_executable_path: import_executable_path.unwrap_or(false),
     };
+    // 不记录密码
+    log_async_command!(
+        "import_backup_archive",
+        window = window.label(),
+        args = (
+            path,
+            strategy,
+            allow_unsigned,
+            trust_signer,
+            import_executable_path
+        ),
+        guard.run(
+            "import",
+            crate::cancellation::run(&app, "import", operation_id, async {
+                let started = std::time::Instant::now();
+                let token = crate::cancellation::current();
+                let result = blocking::run("导入备份归档", move || {
+                    crate::antigravity::backup_archive::import(
+                        std::path::Path::new(&path),
+                        &password,
+                        strategy,
+                        &options,
+                        &token,
+                    )
+                })
+                .await;
+                crate::system_tray::refresh_tray_menu(&app);
+
+                let detail = result.as_ref().ok().map(|report| {
+                    format!(
+                        "导入 {} 个，跳过 {} 个，失败 {} 个",
+                        report.restored_count, report.skipped_count, report.failed_count
+                    )
+                });
+                crate::audit::record(
+                    crate::audit::AuditOperation::ImportBackups,
+                    None,
+                    started,
+                    &result,
+                    detail.as_deref(),
+                );
+                result
+            })
+        )
+    )
+}
+
+/// 将备份写入备份目录并记录审计日志（在可取消操作中执行时逐个检查取消）
+async fn write_backup_files(
+    app: &tauri::AppHandle,
+    state: &crate::AppState,
+    account_file_data: Vec<AccountExportedData>,
+) -> Result<RestoreResult, String> {
+    let started = std::time::Instant::now();
+
+    if account_file_data.len() > crate::antigravity::archive::MAX_BACKUPS {
+        return Err(format!(
+            "备份数量超过 {} 个",
+            crate::antigravity::archive::MAX_BACKUPS
+        ));
+    }
 
     // 获取目标目录
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
+    let token = crate::cancellation::current();
+    let results = blocking::run("写入备份", move || {
+        write_backup_entries(&antigravity_dir, account_file_data, &token)
+    })
+    .await?;
+
+    crate::system_tray::refresh_tray_menu(app);
+
+    let detail = format!(
+        "导入 {} 个，失败 {} 个",
+        results.restored_count,
+        results.failed.len()
+    );
+    let result = Ok(results);
+    crate::audit::record(
+        crate::audit::AuditOperation::ImportBackups,
+        None,
+        started,
+        &result,
+        Some(&detail),
+    );
+    result
+}
+
+/// 逐个校验并写入备份文件；`token` 被取消时停止写入剩余备份
+fn write_backup_entries(
+    antigravity_dir: &std::path::Path,
+    account_file_data: Vec<AccountExportedData>,
+    token: &crate::cancellation::CancellationToken,
+) -> Result<RestoreResult, String> {
+    let mut results = RestoreResult {
+        restored_count: 0,
+        failed: Vec::new(),
+    };
 
     // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&antigravity_dir) {
+    if let Err(e) = fs::create_dir_all(antigravity_dir) {
         return Err(format!("创建目录失败: {}", e));
     }
 
     // 遍历每个备份
     for account_file in account_file_data {
+        token.check()?;
+
+        // 文件名不能包含路径，避免写到备份目录之外；内容大小与结构不能超限
+        if let Err(e) = crate::antigravity::archive::validate_backup(
+            &account_file.filename,
+            &account_file.content,
+        ) {
+            results.failed.push(FailedAccountExportedData {
+                filename: account_file.filename,
+                error: e,
+            });
+            continue;
+        }
+
         let file_path = antigravity_dir.join(&account_file.filename);
+        let account_name = file_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
 
-        match fs::write(
+        match crate::antigravity::keychain::write_backup(
             &file_path,
-            serde_json::to_string_pretty(&account_file.content).unwrap_or_default(),
-        )
-        .map_err(|e| format!("写入文件失败: {}", e))
-        {
+            &account_name,
+            &account_file.content,
+        ) {
             Ok(_) => {
                 results.restored_count += 1;
             }
@@ -134,195 +527,498 @@ pub async fn restore_backup_files(
 
 /// 删除指定备份
 #[tauri::command]
+#[specta::specta]
 pub async fn delete_backup(
+    app: tauri::AppHandle,
     name: String,
     state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
 ) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("delete_backup")?;
     // 只删除Antigravity账户JSON文件
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
     let antigravity_file = antigravity_dir.join(format!("{}.json", name));
 
-    if antigravity_file.exists() {
-        fs::remove_file(&antigravity_file).map_err(|e| format!("删除用户文件失败: {}", e))?;
-        Ok(format!("删除用户成功: {}", name))
-    } else {
-        Err("用户文件不存在".to_string())
-    }
+    crate::audit::track(
+        crate::audit::AuditOperation::DeleteBackup,
+        Some(&name),
+        guard.run("delete_backup", async {
+            if antigravity_file.exists() {
+                let account = name.clone();
+                blocking::run("删除备份", move || {
+                    crate::utils::secure_delete::remove_file(&antigravity_file)
+                        .map_err(|e| format!("删除用户文件失败: {}", e))?;
+                    crate::antigravity::backup_manifest::forget(&antigravity_file);
+                    crate::antigravity::backup_index::forget(&antigravity_file);
+                    crate::antigravity::backup_chain::reset(&antigravity_file);
+                    crate::antigravity::keychain::delete(&account);
+                    Ok::<_, String>(())
+                })
+                .await?;
+                state.remove_profiles(Some(&name));
+                crate::system_tray::refresh_tray_menu(&app);
+                Ok(crate::i18n::t(
+                    "backup.deleted",
+                    &serde_json::json!({ "name": name }),
+                ))
+            } else {
+                Err(crate::i18n::t(
+                    "backup.file_missing",
+                    &serde_json::Value::Null,
+                ))
+            }
+        }),
+    )
+    .await
 }
 
 /// 清空所有备份
 #[tauri::command]
-pub async fn clear_all_backups(state: State<'_, crate::AppState>) -> Result<String, String> {
+#[specta::specta]
+pub async fn clear_all_backups(
+    app: tauri::AppHandle,
+    state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("clear_all_backups")?;
+    crate::os_auth::require("clear_all_backups", "清空所有账户备份").await?;
+
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
 
-    if antigravity_dir.exists() {
-        // 读取目录中的所有文件
-        let mut deleted_count = 0;
-        for entry in
-            fs::read_dir(&antigravity_dir).map_err(|e| format!("读取用户目录失败: {}", e))?
-        {
-            let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
-            let path = entry.path();
-
-            // 只删除 JSON 文件
-            if path.extension().is_some_and(|ext| ext == "json") {
-                fs::remove_file(&path)
-                    .map_err(|e| format!("删除文件 {} 失败: {}", path.display(), e))?;
-                deleted_count += 1;
-            }
+    let clear = guard.run("clear_backups", async {
+        if antigravity_dir.exists() {
+            let deleted_count = blocking::run("清空备份", move || {
+                // 读取目录中的所有文件
+                let mut deleted_count = 0;
+                for entry in fs::read_dir(&antigravity_dir)
+                    .map_err(|e| format!("读取用户目录失败: {}", e))?
+                {
+                    let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+                    let path = entry.path();
+
+                    // 只删除 JSON 文件
+                    if path.extension().is_some_and(|ext| ext == "json") {
+                        crate::utils::secure_delete::remove_file(&path)
+                            .map_err(|e| format!("删除文件 {} 失败: {}", path.display(), e))?;
+                        crate::antigravity::backup_chain::reset(&path);
+                        if let Some(stem) = path.file_stem() {
+                            crate::antigravity::keychain::delete(&stem.to_string_lossy());
+                        }
+                        deleted_count += 1;
+                    }
+                }
+                crate::antigravity::backup_manifest::clear();
+                crate::antigravity::backup_index::clear();
+                if let Err(e) = crate::state_journal::clear() {
+                    tracing::warn!(target: "backup::clear", error = %e, "删除登录状态变更日志失败");
+                }
+                Ok::<_, String>(deleted_count)
+            })
+            .await?;
+
+            state.remove_profiles(None);
+            crate::system_tray::refresh_tray_menu(&app);
+
+            Ok(crate::i18n::t(
+                "backup.cleared",
+                &serde_json::json!({ "count": deleted_count }),
+            ))
+        } else {
+            Ok(crate::i18n::t(
+                "backup.nothing_to_clear",
+                &serde_json::Value::Null,
+            ))
         }
+    });
 
-        Ok(format!(
-            "已清空所有用户备份，共删除 {} 个文件",
-            deleted_count
-        ))
-    } else {
-        Ok("用户目录不存在，无需清空".to_string())
-    }
+    crate::audit::track(crate::audit::AuditOperation::ClearBackups, None, clear).await
 }
 
-/// 加密配置数据（用于账户导出）
+/// 导出账户报表（邮箱、套餐、令牌到期、最近备份/切换时间、备份大小），返回账户数
 #[tauri::command]
-pub async fn encrypt_config_data(json_data: String, password: String) -> Result<String, String> {
-    log_async_command!("encrypt_config_data", async {
-        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+#[specta::specta]
+pub async fn export_account_report(
+    window: tauri::Window,
+    format: crate::antigravity::report::ReportFormat,
+    path: String,
+) -> Result<usize, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "export_account_report",
+        window = window.label(),
+        args = (format, path),
+        async {
+            blocking::run("导出账户报表", move || {
+                crate::antigravity::report::export(format, std::path::Path::new(&path))
+            })
+            .await
+        }
+    )
+}
 
-        if password.is_empty() {
-            return Err("密码不能为空".to_string());
+/// 为指定账户生成加密分片传输载荷，前端将每个分片渲染为二维码，传输码需单独告知接收方
+#[tauri::command]
+#[specta::specta]
+pub async fn create_transfer_payload(
+    window: tauri::Window,
+    account_name: String,
+) -> Result<crate::antigravity::transfer::TransferPayload, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::os_auth::require("create_transfer_payload", "生成账户传输二维码").await?;
+    log_async_command!(
+        "create_transfer_payload",
+        window = window.label(),
+        args = (account_name),
+        async {
+            blocking::run("生成传输载荷", move || {
+                crate::antigravity::transfer::create(&account_name)
+            })
+            .await
         }
+    )
+}
 
-        let password_bytes = password.as_bytes();
-        let mut result = Vec::new();
+/// 拼装扫描到的分片，用传输码解密后导入为账户备份
+#[tauri::command]
+#[specta::specta]
+pub async fn import_transfer_payload(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    state: State<'_, crate::AppState>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    chunks: Vec<String>,
+    code: String,
+) -> Result<RestoreResult, String> {
+    crate::app_lock::ensure_unlocked()?;
+    // 不记录参数：传输码可解密分片
+    log_async_command!(
+        "import_transfer_payload",
+        window = window.label(),
+        guard.run("import", async {
+            let (account, content) = blocking::run("解析传输载荷", move || {
+                crate::antigravity::transfer::open(&chunks, &code)
+            })
+            .await?;
+            let backup = AccountExportedData {
+                filename: format!("{}.json", account),
+                content,
+                timestamp: unix_now(),
+            };
+            write_backup_files(&app, &state, vec![backup]).await
+        })
+    )
+}
 
-        // XOR 加密
-        for (i, byte) in json_data.as_bytes().iter().enumerate() {
-            let key_byte = password_bytes[i % password_bytes.len()];
-            result.push(byte ^ key_byte);
+/// 识别其他账户切换工具导出文件的格式与记录数
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_import_format(
+    window: tauri::Window,
+    path: String,
+) -> Result<crate::antigravity::importers::ImportDetection, String> {
+    log_async_command!(
+        "detect_import_format",
+        window = window.label(),
+        args = (path),
+        async {
+            blocking::run("识别导入格式", move || {
+                crate::antigravity::importers::detect(std::path::Path::new(&path))
+            })
+            .await
         }
+    )
+}
+
+/// 从其他账户切换工具的导出文件导入账户，返回每条记录的导入结果
+///
+/// `format` 指定时与识别结果不一致则拒绝导入
+#[tauri::command]
+#[specta::specta]
+pub async fn import_from_other_tool(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    path: String,
+    format: Option<crate::antigravity::importers::ImportFormat>,
+) -> Result<crate::antigravity::importers::ImportReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "import_from_other_tool",
+        window = window.label(),
+        args = (path, format),
+        guard.run("import", async {
+            let started = std::time::Instant::now();
+            let result = blocking::run("导入", move || {
+                crate::antigravity::importers::import(std::path::Path::new(&path), format)
+            })
+            .await;
+
+            let detail = result.as_ref().ok().map(|report| {
+                format!(
+                    "从 {:?} 导入 {} 个，失败 {} 个",
+                    report.format, report.imported, report.failed
+                )
+            });
+            crate::audit::record(
+                crate::audit::AuditOperation::ImportBackups,
+                None,
+                started,
+                &result,
+                detail.as_deref(),
+            );
+            crate::system_tray::refresh_tray_menu(&app);
+            result
+        })
+    )
+}
+
+/// 获取备份目录加密状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_backup_vault_status() -> Result<crate::antigravity::vault::VaultStatus, String> {
+    log_async_command!("get_backup_vault_status", async {
+        crate::antigravity::vault::status()
+    })
+}
 
-        // Base64 编码
-        let encoded = BASE64.encode(&result);
+/// 启用备份目录加密并加密已有备份，返回加密的文件数
+#[tauri::command]
+#[specta::specta]
+pub async fn enable_backup_vault(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    mode: crate::antigravity::vault::VaultMode,
+    password: Option<String>,
+) -> Result<usize, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "enable_backup_vault",
+        window = window.label(),
+        args = (mode, password),
+        guard.run("backup_encryption", async {
+            let count = blocking::run("加密备份", move || {
+                crate::antigravity::vault::enable(mode, password.as_deref())
+            })
+            .await?;
+            crate::system_tray::refresh_tray_menu(&app);
+            Ok(count)
+        })
+    )
+}
+
+/// 使用主密码解锁备份目录
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_backup_vault(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    password: String,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "unlock_backup_vault",
+        window = window.label(),
+        args = (password),
+        async {
+            blocking::run("解锁", move || {
+                crate::antigravity::vault::unlock(Some(&password))
+            })
+            .await?;
+            crate::system_tray::refresh_tray_menu(&app);
+            Ok(())
+        }
+    )
+}
 
-        Ok(encoded)
+/// 锁定备份目录（清除内存中的密钥）
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_backup_vault(window: tauri::Window) -> Result<(), String> {
+    log_async_command!("lock_backup_vault", window = window.label(), async {
+        crate::antigravity::vault::lock()
     })
 }
 
+/// 关闭备份目录加密并解密所有备份，返回解密的文件数
+#[tauri::command]
+#[specta::specta]
+pub async fn disable_backup_vault(
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    password: Option<String>,
+) -> Result<usize, String> {
+    crate::app_lock::ensure_unlocked()?;
+    log_async_command!(
+        "disable_backup_vault",
+        window = window.label(),
+        args = (password),
+        guard.run("backup_encryption", async {
+            blocking::run("解密备份", move || {
+                crate::antigravity::vault::disable(password.as_deref())
+            })
+            .await
+        })
+    )
+}
+
+/// 加密配置数据（用于账户导出）
+#[tauri::command]
+#[specta::specta]
+pub async fn encrypt_config_data(
+    window: tauri::Window,
+    json_data: String,
+    password: String,
+) -> Result<String, String> {
+    log_async_command!(
+        "encrypt_config_data",
+        window = window.label(),
+        args = (json_data, password),
+        async {
+            use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+            if password.is_empty() {
+                return Err("密码不能为空".to_string());
+            }
+
+            // 写入格式版本与签名，导入时据此校验来源
+            let signed = crate::antigravity::archive::sign(&json_data, &password)?;
+
+            // XOR 加密
+            let result = crate::antigravity::archive::xor(signed.as_bytes(), password.as_bytes());
+
+            // Base64 编码
+            let encoded = BASE64.encode(&result);
+
+            Ok(encoded)
+        }
+    )
+}
+
 /// 解密配置数据（用于账户导入）
 #[tauri::command]
+#[specta::specta]
 pub async fn decrypt_config_data(
+    window: tauri::Window,
     encrypted_data: String,
     password: String,
 ) -> Result<String, String> {
-    log_async_command!("decrypt_config_data", async {
-        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
-
-        if password.is_empty() {
-            return Err("密码不能为空".to_string());
-        }
+    log_async_command!(
+        "decrypt_config_data",
+        window = window.label(),
+        args = (encrypted_data, password),
+        async {
+            use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 
-        let decoded = BASE64
-            .decode(encrypted_data)
-            .map_err(|_| "Base64 解码失败".to_string())?;
+            if password.is_empty() {
+                return Err("密码不能为空".to_string());
+            }
 
-        let password_bytes = password.as_bytes();
-        let mut result = Vec::new();
+            let decoded = BASE64
+                .decode(encrypted_data)
+                .map_err(|_| "Base64 解码失败".to_string())?;
 
-        for (i, byte) in decoded.iter().enumerate() {
-            let key_byte = password_bytes[i % password_bytes.len()];
-            result.push(byte ^ key_byte);
-        }
+            let result = crate::antigravity::archive::xor(&decoded, password.as_bytes());
 
-        let decrypted =
-            String::from_utf8(result).map_err(|_| "解密失败，数据可能已损坏".to_string())?;
+            let decrypted =
+                String::from_utf8(result).map_err(|_| "解密失败，数据可能已损坏".to_string())?;
 
-        Ok(decrypted)
-    })
+            Ok(decrypted)
+        }
+    )
 }
 
 /// 备份并重启 Antigravity（迁移自 process_commands）
 #[tauri::command]
-pub async fn sign_in_new_antigravity_account() -> Result<String, String> {
-    println!("🔄 开始执行 sign_in_new_antigravity_account 命令");
-
-    // 1. 关闭进程 (如果存在)
-    println!("🛑 步骤1: 检查并关闭 Antigravity 进程");
-    let kill_result = match crate::platform::kill_antigravity_processes() {
-        Ok(result) => {
-            if result.contains("not found") || result.contains("未找到") {
-                println!("ℹ️ Antigravity 进程未运行，跳过关闭步骤");
-                "Antigravity 进程未运行".to_string()
-            } else {
-                println!("✅ 进程关闭结果: {}", result);
+#[specta::specta]
+pub async fn sign_in_new_antigravity_account(app: tauri::AppHandle) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    tracing::info!(target: "account::sign_in", "🔄 开始执行 sign_in_new_antigravity_account 命令");
+
+    let pipeline = crate::pipeline::run(&app, "sign_in", async {
+        // 1. 关闭进程 (如果存在)
+        tracing::info!(target: "account::sign_in", "🛑 步骤1: 检查并关闭 Antigravity 进程");
+        let kill_result = match crate::platform::kill_antigravity_processes() {
+            Ok(result) => {
+                tracing::info!(target: "account::sign_in", "✅ 进程关闭结果: {}", result);
                 result
             }
-        }
-        Err(e) => {
-            if e.contains("not found") || e.contains("未找到") {
-                println!("ℹ️ Antigravity 进程未运行，跳过关闭步骤");
+            Err(e) if e.is_not_found() => {
+                tracing::info!(target: "account::sign_in", "ℹ️ Antigravity 进程未运行，跳过关闭步骤");
                 "Antigravity 进程未运行".to_string()
-            } else {
-                return Err(format!("关闭进程时发生错误: {}", e));
             }
-        }
-    };
+            Err(e) => return Err(format!("关闭进程时发生错误: {}", e)),
+        };
 
-    // 等待500ms确保进程完全关闭（缩短等待时间避免前端超时）
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        // 等待500ms确保进程完全关闭（缩短等待时间避免前端超时）
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
-    // 2. 备份当前账户信息（直接调用 save_antigravity_current_account）
-    println!("💾 步骤2: 调用 save_antigravity_current_account 备份当前账户信息");
-    let backup_info = match crate::commands::save_antigravity_current_account().await {
-        Ok(msg) => {
-            println!("✅ 备份完成: {}", msg);
-            Some(msg)
-        }
-        Err(e) => {
-            println!("⚠️ 备份失败: {}", e);
-            None
-        }
-    };
+        // 2. 备份当前账户信息（直接调用 save_antigravity_current_account）
+        tracing::info!(target: "account::sign_in", "💾 步骤2: 调用 save_antigravity_current_account 备份当前账户信息");
+        let backup_info = match crate::commands::save_antigravity_current_account(app.clone()).await
+        {
+            Ok(msg) => {
+                tracing::info!(target: "account::sign_in", "✅ 备份完成: {}", msg);
+                Some(msg)
+            }
+            Err(e) => {
+                tracing::warn!(target: "account::sign_in", "⚠️ 备份失败: {}", e);
+                None
+            }
+        };
 
-    // 3. 清除 Antigravity 所有数据 (彻底注销)
-    println!("🗑️ 步骤3: 清除所有 Antigravity 数据 (彻底注销)");
-    match crate::antigravity::cleanup::clear_all_antigravity_data().await {
-        Ok(result) => {
-            println!("✅ 清除完成: {}", result);
-        }
-        Err(e) => {
-            // 清除失败可能是因为数据库本来就是空的，这是正常情况
-            println!("ℹ️ 清除数据时出现: {}（可能数据库本来就是空的）", e);
+        // 3. 清除 Antigravity 所有数据 (彻底注销)
+        tracing::info!(target: "account::sign_in", "🗑️ 步骤3: 清除所有 Antigravity 数据 (彻底注销)");
+        match crate::antigravity::cleanup::clear_all_antigravity_data().await {
+            Ok(result) => {
+                tracing::info!(target: "account::sign_in", "✅ 清除完成: {}", result);
+            }
+            Err(e) => {
+                // 清除失败可能是因为数据库本来就是空的，这是正常情况
+                tracing::info!(target: "account::sign_in", "ℹ️ 清除数据时出现: {}（可能数据库本来就是空的）", e);
+            }
         }
-    }
 
-    // 等待300ms确保操作完成（缩短等待时间避免前端超时）
-    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        // 等待300ms确保操作完成（缩短等待时间避免前端超时）
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
 
-    // 4. 重新启动进程
-    println!("🚀 步骤4: 重新启动 Antigravity");
-    let start_result = crate::antigravity::starter::start_antigravity();
-    let start_message = match start_result {
-        Ok(result) => {
-            println!("✅ 启动结果: {}", result);
-            result
-        }
-        Err(e) => {
-            println!("⚠️ 启动失败: {}", e);
-            format!("启动失败: {}", e)
-        }
-    };
+        // 4. 重新启动进程
+        tracing::info!(target: "account::sign_in", "🚀 步骤4: 重新启动 Antigravity");
+        let start_result = crate::antigravity::starter::start_antigravity();
+        let start_message = match start_result {
+            Ok(result) => {
+                tracing::info!(target: "account::sign_in", "✅ 启动结果: {}", result);
+                result
+            }
+            Err(e) => {
+                tracing::warn!(target: "account::sign_in", "⚠️ 启动失败: {}", e);
+                format!("启动失败: {}", e)
+            }
+        };
 
-    let final_message = if let Some(backup_message) = backup_info {
-        format!(
-            "{} -> 已备份: {} -> 已清除账户数据 -> {}",
-            kill_result, backup_message, start_message
-        )
-    } else {
-        format!(
-            "{} -> 未检测到登录用户（跳过备份） -> 已清除账户数据 -> {}",
-            kill_result, start_message
-        )
-    };
-    println!("🎉 所有操作完成: {}", final_message);
+        let final_message = if let Some(backup_message) = backup_info {
+            format!(
+                "{} -> 已备份: {} -> 已清除账户数据 -> {}",
+                kill_result, backup_message, start_message
+            )
+        } else {
+            format!(
+                "{} -> 未检测到登录用户（跳过备份） -> 已清除账户数据 -> {}",
+                kill_result, start_message
+            )
+        };
+        tracing::info!(target: "account::sign_in", "🎉 所有操作完成: {}", final_message);
+
+        Ok(final_message)
+    });
 
-    Ok(final_message)
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    crate::audit::track(
+        crate::audit::AuditOperation::SignIn,
+        None,
+        guard.run("sign_in", pipeline),
+    )
+    .await
 }
@@ -0,0 +1,126 @@
+//! 应用锁命令
+//! 设置/清除锁定密码、调整空闲锁定时间、手动锁定与解锁
+
+use crate::app_lock::{self, AppLockStatus};
+use crate::error::{AgentError, AgentResult};
+use tauri::{AppHandle, Manager};
+
+/// 获取应用锁状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_lock_status() -> Result<AppLockStatus, String> {
+    Ok(app_lock::status())
+}
+
+/// 使用密码解锁应用
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock(app: AppHandle, window: tauri::Window, password: String) -> AgentResult<()> {
+    crate::log_async_command!(
+        "unlock",
+        window = window.label(),
+        args = (password),
+        async {
+            let settings = app
+                .state::<crate::app_settings::AppSettingsManager>()
+                .get_settings()
+                .app_lock;
+            tokio::task::spawn_blocking(move || app_lock::unlock(&password, &settings))
+                .await
+                .map_err(|e| AgentError::Internal(format!("解锁任务失败: {}", e)))?
+        }
+    )
+}
+
+/// 立即锁定应用
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_app(window: tauri::Window) -> Result<(), String> {
+    crate::log_async_command!("lock_app", window = window.label(), async {
+        app_lock::lock();
+        Ok::<(), String>(())
+    })
+}
+
+/// 前端上报用户活动，用于空闲计时（锁定状态下忽略）
+#[tauri::command]
+#[specta::specta]
+pub async fn report_activity() -> Result<(), String> {
+    if !app_lock::is_locked() {
+        app_lock::touch();
+    }
+    Ok(())
+}
+
+/// 设置、修改或清除（`new_password` 为空）应用锁密码；已设置密码时需要提供当前密码
+#[tauri::command]
+#[specta::specta]
+pub async fn set_app_lock_password(
+    app: AppHandle,
+    window: tauri::Window,
+    current_password: Option<String>,
+    new_password: Option<String>,
+) -> AgentResult<AppLockStatus> {
+    crate::log_async_command!(
+        "set_app_lock_password",
+        window = window.label(),
+        args = (current_password, new_password),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let current = settings_manager.get_settings().app_lock;
+
+            let password_hash = tokio::task::spawn_blocking(move || {
+                if let Some(hash) = current.password_hash.as_deref() {
+                    let verified = current_password
+                        .as_deref()
+                        .is_some_and(|password| app_lock::verify_password(password, hash));
+                    if !verified {
+                        return Err(AgentError::AuthenticationFailed {
+                            message: "当前密码错误".to_string(),
+                        });
+                    }
+                }
+
+                new_password
+                    .filter(|password| !password.is_empty())
+                    .map(|password| app_lock::hash_password(&password))
+                    .transpose()
+                    .map_err(AgentError::Internal)
+            })
+            .await
+            .map_err(|e| AgentError::Internal(format!("设置密码任务失败: {}", e)))??;
+
+            settings_manager.update_settings(|settings| {
+                settings.app_lock.password_hash = password_hash;
+            })?;
+            app_lock::apply_settings(&settings_manager.get_settings().app_lock);
+            app_lock::touch();
+
+            Ok(app_lock::status())
+        }
+    )
+}
+
+/// 保存空闲锁定时间（分钟，0 表示只在启动时锁定）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_app_lock_idle_minutes(
+    app: AppHandle,
+    window: tauri::Window,
+    minutes: u64,
+) -> Result<AppLockStatus, String> {
+    crate::log_async_command!(
+        "save_app_lock_idle_minutes",
+        window = window.label(),
+        args = (minutes),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            settings_manager.update_settings(|settings| {
+                settings.app_lock.idle_minutes = Some(minutes);
+            })?;
+            app_lock::apply_settings(&settings_manager.get_settings().app_lock);
+
+            Ok::<_, String>(app_lock::status())
+        }
+    )
+}
@@ -0,0 +1,85 @@
+//! 备份检查命令
+//! 找出无法使用的账户备份（损坏、登录状态丢失、没有邮箱），按完整性清单校验备份，并移入回收目录
+
+use crate::antigravity::backup_check::{self, BackupProblem};
+use crate::antigravity::backup_manifest::{self, BackupVerifyReport};
+use crate::utils::blocking;
+use tauri::{AppHandle, State};
+
+/// 扫描有问题的备份
+///
+/// 可传入 `operation_id`，扫描过程中通过 `cancel_operation` 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_backup_problems(
+    app: AppHandle,
+    operation_id: Option<String>,
+) -> Result<Vec<BackupProblem>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "scan_backup_problems",
+        crate::cancellation::run(&app, "backup_check", operation_id, async {
+            let token = crate::cancellation::current();
+            blocking::run("备份检查", move || backup_check::scan(&token)).await
+        })
+    )
+}
+
+/// 按完整性清单校验所有备份，找出被截断、损坏、被修改或丢失的备份
+///
+/// 可传入 `operation_id`，校验过程中通过 `cancel_operation` 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_backups(
+    app: AppHandle,
+    operation_id: Option<String>,
+) -> Result<BackupVerifyReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "verify_backups",
+        crate::cancellation::run(&app, "verify_backups", operation_id, async {
+            let token = crate::cancellation::current();
+            blocking::run("备份完整性校验", move || {
+                backup_manifest::verify(&token)
+            })
+            .await
+        })
+    )
+}
+
+/// 将指定备份移入回收目录（安全模式下拒绝；执行前弹出确认对话框）
+#[tauri::command]
+#[specta::specta]
+pub async fn trash_backups(
+    app: AppHandle,
+    names: Vec<String>,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("trash_backups")?;
+    if names.is_empty() {
+        return Ok("没有需要移动的备份".to_string());
+    }
+
+    let message = format!(
+        "即将把 {} 个备份移入回收目录：\n{}\n\n移出后不再出现在账户列表中，可从配置目录的 trash 中手动找回。是否继续？",
+        names.len(),
+        names.join("\n")
+    );
+    if !crate::local_api::confirm(&app, message).await {
+        tracing::info!(target: "backup::check", "用户取消了移动问题备份");
+        return Err(crate::i18n::t("common.cancelled", &serde_json::Value::Null));
+    }
+
+    crate::audit::track(
+        crate::audit::AuditOperation::TrashBackups,
+        None,
+        guard.run("trash_backups", async {
+            let moved =
+                blocking::run("移动备份", move || backup_check::move_to_trash(&names)).await?;
+            crate::system_tray::refresh_tray_menu(&app);
+            Ok(format!("已将 {} 个备份移入回收目录", moved))
+        }),
+    )
+    .await
+}
@@ -0,0 +1,76 @@
+//! 浏览器登录桥接命令
+//! 查询状态、开关 WebSocket 端点、修改端口与重新生成配对令牌
+
+use crate::browser_bridge::{self, BrowserBridgeSettings, BrowserBridgeStatus};
+use tauri::{AppHandle, Manager};
+
+/// 获取浏览器桥接状态（含配对令牌）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_browser_bridge_status(app: AppHandle) -> Result<BrowserBridgeStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(browser_bridge::status(
+        &settings_manager.get_settings().browser_bridge,
+    ))
+}
+
+/// 启用/停用浏览器桥接或修改端口；首次启用时生成配对令牌
+#[tauri::command]
+#[specta::specta]
+pub async fn save_browser_bridge_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<BrowserBridgeStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "save_browser_bridge_settings",
+        window = window.label(),
+        args = (enabled, port),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let current = settings_manager.get_settings().browser_bridge;
+            let port = port.unwrap_or(current.port);
+            crate::local_api::validate_port(port)?;
+
+            let settings = BrowserBridgeSettings {
+                enabled,
+                port,
+                token: current
+                    .token
+                    .or_else(|| enabled.then(crate::local_api::generate_token)),
+            };
+            // 先按新设置启动，端口被占用等错误时不保存
+            browser_bridge::apply(&app, &settings).await?;
+            settings_manager.update_settings(|s| s.browser_bridge = settings.clone())?;
+            Ok::<_, String>(browser_bridge::status(&settings))
+        }
+    )
+}
+
+/// 重新生成配对令牌（已配对的扩展需要重新填写）
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_browser_bridge_token(
+    app: AppHandle,
+    window: tauri::Window,
+) -> Result<BrowserBridgeStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "regenerate_browser_bridge_token",
+        window = window.label(),
+        args = (),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let settings = BrowserBridgeSettings {
+                token: Some(crate::local_api::generate_token()),
+                ..settings_manager.get_settings().browser_bridge
+            };
+            settings_manager.update_settings(|s| s.browser_bridge = settings.clone())?;
+            browser_bridge::apply(&app, &settings).await?;
+            Ok::<_, String>(browser_bridge::status(&settings))
+        }
+    )
+}
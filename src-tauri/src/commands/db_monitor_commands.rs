@@ -2,11 +2,15 @@
 //! 提供数据库监控状态的查询和控制功能
 
 use crate::db_monitor::DatabaseMonitor;
+use crate::error::{AgentError, AgentResult};
+use crate::state_journal::{self, ReconstructedState};
+use crate::utils::blocking;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
 /// 获取数据库监控运行状态
 #[tauri::command]
+#[specta::specta]
 pub async fn is_database_monitoring_running(_app: AppHandle) -> Result<bool, String> {
     crate::log_async_command!("is_database_monitoring_running", async {
         // 智能监控现在是默认功能，总是返回 true
@@ -16,19 +20,34 @@ pub async fn is_database_monitoring_running(_app: AppHandle) -> Result<bool, Str
 
 /// 手动启动数据库监控
 #[tauri::command]
+#[specta::specta]
 pub async fn start_database_monitoring(app: AppHandle) -> Result<String, String> {
     crate::log_async_command!("start_database_monitoring", async {
         let monitor = app.state::<Arc<DatabaseMonitor>>();
-        monitor
+        let result = monitor
             .start_monitoring()
             .await
-            .map_err(|e| format!("启动监控失败: {}", e))?;
+            .map_err(|e| format!("启动监控失败: {}", e));
+
+        let (status, message) = match &result {
+            Ok(()) => (crate::startup::StartupStepStatus::Completed, None),
+            Err(e) => (crate::startup::StartupStepStatus::Failed, Some(e.clone())),
+        };
+        crate::startup::report(
+            &app,
+            crate::startup::StartupStep::MonitorStarted,
+            status,
+            message,
+        );
+
+        result?;
         Ok("数据库监控已启动".to_string())
     })
 }
 
 /// 手动停止数据库监控
 #[tauri::command]
+#[specta::specta]
 pub async fn stop_database_monitoring(app: AppHandle) -> Result<String, String> {
     crate::log_async_command!("stop_database_monitoring", async {
         let monitor = app.state::<Arc<DatabaseMonitor>>();
@@ -36,3 +55,55 @@ pub async fn stop_database_monitoring(app: AppHandle) -> Result<String, String>
         Ok("数据库监控已停止".to_string())
     })
 }
+
+/// 根据登录状态变更日志重建指定时刻（RFC 3339）的关键键值
+///
+/// `restore` 为 true 时把重建的值写回 Antigravity 数据库（写入前自动创建安全快照）
+#[tauri::command]
+#[specta::specta]
+pub async fn reconstruct_state_at(
+    app: AppHandle,
+    timestamp: String,
+    restore: Option<bool>,
+) -> AgentResult<ReconstructedState> {
+    crate::app_lock::ensure_unlocked()?;
+    let restore = restore.unwrap_or(false);
+    if !restore {
+        return crate::log_async_command!("reconstruct_state_at", args = (timestamp), async {
+            blocking::run("重建登录状态", move || {
+                state_journal::reconstruct_at(&timestamp, false)
+            })
+            .await
+            .map_err(AgentError::from)
+        });
+    }
+
+    crate::safe_mode::ensure_allowed("reconstruct_state_at")?;
+    crate::os_auth::require("reconstruct_state_at", "回滚 Antigravity 登录状态").await?;
+
+    let guard = app.state::<crate::operation_guard::OperationGuard>();
+    let started = std::time::Instant::now();
+    let result = crate::log_async_command!(
+        "reconstruct_state_at",
+        args = (timestamp, restore),
+        guard.run("restore", async {
+            let timestamp = timestamp.clone();
+            blocking::run("回滚登录状态", move || {
+                state_journal::reconstruct_at(&timestamp, true)
+            })
+            .await
+            .map_err(AgentError::from)
+        })
+    );
+    crate::audit::record(
+        crate::audit::AuditOperation::StateRollback,
+        Some(&timestamp),
+        started,
+        &result,
+        result
+            .as_ref()
+            .ok()
+            .and_then(|state| state.restore_message.as_deref()),
+    );
+    result
+}
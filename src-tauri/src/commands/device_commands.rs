@@ -0,0 +1,65 @@
+//! 设备身份命令
+//! 查看本机设备 ID 与公钥，轮换设备密钥，管理受信任的签名设备
+
+use crate::device_identity::{self, DeviceIdentity, TrustedSigner};
+use tauri::{AppHandle, Manager};
+
+/// 获取本机设备身份（首次调用时生成密钥）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_device_identity() -> Result<DeviceIdentity, String> {
+    tokio::task::spawn_blocking(device_identity::get)
+        .await
+        .map_err(|e| format!("读取设备身份任务失败: {}", e))?
+}
+
+/// 轮换设备密钥（设置文件已加密时用新密钥重新加密）
+#[tauri::command]
+#[specta::specta]
+pub async fn rotate_device_identity(
+    app: AppHandle,
+    window: tauri::Window,
+) -> Result<DeviceIdentity, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("rotate_device_identity", window = window.label(), async {
+        tokio::task::spawn_blocking(move || {
+            let identity = device_identity::rotate()?;
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            if settings_manager.is_encrypted() {
+                settings_manager.set_encrypted(true)?;
+            }
+            Ok(identity)
+        })
+        .await
+        .map_err(|e| format!("轮换设备密钥任务失败: {}", e))?
+    })
+}
+
+/// 列出受信任的签名设备（导入配置文件时接受其签名）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_trusted_signers() -> Result<Vec<TrustedSigner>, String> {
+    tokio::task::spawn_blocking(device_identity::trusted_signers)
+        .await
+        .map_err(|e| format!("读取信任列表任务失败: {}", e))
+}
+
+/// 从信任列表移除签名设备，返回是否存在
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_trusted_signer(
+    window: tauri::Window,
+    device_id: String,
+) -> Result<bool, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "remove_trusted_signer",
+        window = window.label(),
+        args = (device_id),
+        async {
+            tokio::task::spawn_blocking(move || device_identity::untrust(&device_id))
+                .await
+                .map_err(|e| format!("移除签名设备任务失败: {}", e))?
+        }
+    )
+}
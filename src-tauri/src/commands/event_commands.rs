@@ -0,0 +1,11 @@
+//! 事件命令
+//! 查询后端会发出的所有事件及其负载类型
+
+use crate::events::{self, EventType};
+
+/// 列出所有事件类型
+#[tauri::command]
+#[specta::specta]
+pub async fn list_event_types() -> Result<Vec<EventType>, String> {
+    Ok(events::event_types())
+}
@@ -0,0 +1,68 @@
+//! ItemTable 浏览命令
+//! 查看和修改 Antigravity 状态数据库中的任意条目，无需另装 SQLite 浏览器
+
+use crate::antigravity::item_table::{self, ItemTableKey, ItemTableValue};
+use crate::error::{AgentError, AgentResult};
+use tauri::AppHandle;
+
+/// 列出匹配模式的键（`*` / `?` 通配，不含通配符时按包含匹配）
+#[tauri::command]
+#[specta::specta]
+pub async fn db_list_keys(pattern: Option<String>) -> Result<Vec<ItemTableKey>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("db_list_keys", args = (pattern), async {
+        tokio::task::spawn_blocking(move || item_table::list_keys(pattern.as_deref()))
+            .await
+            .map_err(|e| format!("查询 ItemTable 任务失败: {}", e))?
+    })
+}
+
+/// 读取指定键的值，键不存在时返回 null
+#[tauri::command]
+#[specta::specta]
+pub async fn db_get_value(key: String) -> Result<Option<ItemTableValue>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("db_get_value", args = (key), async {
+        tokio::task::spawn_blocking(move || item_table::get_value(&key))
+            .await
+            .map_err(|e| format!("读取 ItemTable 任务失败: {}", e))?
+    })
+}
+
+/// 写入指定键的值（安全模式下拒绝；写入前弹出确认对话框并创建安全快照）
+#[tauri::command]
+#[specta::specta]
+pub async fn db_set_value(app: AppHandle, key: String, value: String) -> AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("db_set_value")?;
+
+    let message = format!(
+        "即将修改 Antigravity 数据库条目：\n{}\n\n错误的值可能导致 Antigravity 无法正常启动（修改前会自动创建安全快照），是否继续？",
+        key
+    );
+    if !crate::local_api::confirm(&app, message).await {
+        tracing::info!(target: "item_table::set", key = %key, "用户取消了数据库条目修改");
+        return Err(AgentError::Internal(crate::i18n::t(
+            "common.cancelled",
+            &serde_json::Value::Null,
+        )));
+    }
+
+    // 值可能包含凭据，不记录参数
+    let started = std::time::Instant::now();
+    let result = crate::log_async_command!("db_set_value", async {
+        let key = key.clone();
+        tokio::task::spawn_blocking(move || item_table::set_value(&key, &value))
+            .await
+            .map_err(|e| AgentError::Internal(format!("写入 ItemTable 任务失败: {}", e)))?
+            .map_err(AgentError::from)
+    });
+    crate::audit::record(
+        crate::audit::AuditOperation::ItemTableEdit,
+        Some(&key),
+        started,
+        &result,
+        result.as_deref().ok(),
+    );
+    result
+}
@@ -0,0 +1,77 @@
+//! 本地 HTTP 控制接口命令
+//! 查询状态、开关接口、修改端口与重新生成访问令牌
+
+use crate::local_api::{self, LocalApiSettings, LocalApiStatus};
+use tauri::{AppHandle, Manager};
+
+/// 获取本地接口状态（含访问令牌）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_local_api_status(app: AppHandle) -> Result<LocalApiStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(local_api::status(
+        &settings_manager.get_settings().local_api,
+    ))
+}
+
+/// 启用/停用本地接口、修改端口或切换账户前的确认要求；首次启用时生成访问令牌
+#[tauri::command]
+#[specta::specta]
+pub async fn save_local_api_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+    port: Option<u16>,
+    require_confirmation: Option<bool>,
+) -> Result<LocalApiStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "save_local_api_settings",
+        window = window.label(),
+        args = (enabled, port, require_confirmation),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let current = settings_manager.get_settings().local_api;
+            let port = port.unwrap_or(current.port);
+            local_api::validate_port(port)?;
+
+            let settings = LocalApiSettings {
+                enabled,
+                port,
+                token: current
+                    .token
+                    .or_else(|| enabled.then(local_api::generate_token)),
+                require_confirmation: require_confirmation.unwrap_or(current.require_confirmation),
+            };
+            // 先按新设置启动，端口被占用等错误时不保存
+            local_api::apply(&app, &settings).await?;
+            settings_manager.update_settings(|s| s.local_api = settings.clone())?;
+            Ok::<_, String>(local_api::status(&settings))
+        }
+    )
+}
+
+/// 重新生成访问令牌（旧令牌立即失效）
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_local_api_token(
+    app: AppHandle,
+    window: tauri::Window,
+) -> Result<LocalApiStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "regenerate_local_api_token",
+        window = window.label(),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let settings = LocalApiSettings {
+                token: Some(local_api::generate_token()),
+                ..settings_manager.get_settings().local_api
+            };
+            settings_manager.update_settings(|s| s.local_api = settings.clone())?;
+            local_api::apply(&app, &settings).await?;
+            Ok::<_, String>(local_api::status(&settings))
+        }
+    )
+}
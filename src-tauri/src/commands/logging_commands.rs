@@ -6,25 +6,36 @@ use std::path::Path;
 /// 写入文本文件
 /// 将文本内容写入指定路径的文件
 #[tauri::command]
-pub async fn write_text_file(path: String, content: String) -> Result<String, String> {
-    crate::log_async_command!("write_text_file", async {
-        let file_path = Path::new(&path);
+#[specta::specta]
+pub async fn write_text_file(
+    window: tauri::Window,
+    path: String,
+    content: String,
+) -> Result<String, String> {
+    crate::log_async_command!(
+        "write_text_file",
+        window = window.label(),
+        args = (path, content),
+        async {
+            let file_path = Path::new(&path);
 
-        // 确保父目录存在
-        if let Some(parent) = file_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
-        }
+            // 确保父目录存在
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+            }
 
-        // 写入文件
-        fs::write(file_path, content).map_err(|e| format!("写入文件失败: {}", e))?;
+            // 写入文件
+            fs::write(file_path, content).map_err(|e| format!("写入文件失败: {}", e))?;
 
-        Ok(format!("文件写入成功: {}", path))
-    })
+            Ok(format!("文件写入成功: {}", path))
+        }
+    )
 }
 
 /// 写入前端日志
 /// 将前端日志条目写入到后端日志系统
 #[tauri::command]
+#[specta::specta]
 pub async fn write_frontend_log(log_entry: serde_json::Value) -> Result<(), String> {
     use tracing::{debug, error, info, warn};
 
@@ -66,3 +77,111 @@ pub async fn write_frontend_log(log_entry: serde_json::Value) -> Result<(), Stri
 
     Ok(())
 }
+
+/// 查询日志
+/// 按级别、目标模块、时间范围和关键字过滤日志文件，返回分页后的结构化条目
+#[tauri::command]
+#[specta::specta]
+pub async fn query_logs(
+    filter: crate::utils::log_query::LogQueryFilter,
+) -> Result<crate::utils::log_query::LogQueryResult, String> {
+    let log_dir = crate::directories::get_log_directory();
+    tokio::task::spawn_blocking(move || crate::utils::log_query::query(&log_dir, &filter))
+        .await
+        .map_err(|e| format!("查询日志任务失败: {}", e))?
+}
+
+/// 导出日志
+/// 将符合过滤条件（可按运行 ID 过滤）的全部日志条目写入指定文件，返回导出条数
+#[tauri::command]
+#[specta::specta]
+pub async fn export_logs(
+    window: tauri::Window,
+    path: String,
+    filter: crate::utils::log_query::LogQueryFilter,
+) -> Result<usize, String> {
+    crate::log_async_command!(
+        "export_logs",
+        window = window.label(),
+        args = (path, filter),
+        async {
+            let log_dir = crate::directories::get_log_directory();
+            tokio::task::spawn_blocking(move || {
+                crate::utils::log_query::export(&log_dir, &filter, Path::new(&path))
+            })
+            .await
+            .map_err(|e| format!("导出日志任务失败: {}", e))?
+        }
+    )
+}
+
+/// 清空日志
+/// 删除除当天正在写入的分段以外的所有日志文件
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_logs(
+    window: tauri::Window,
+) -> Result<crate::utils::log_retention::LogCleanupResult, String> {
+    crate::log_async_command!("clear_logs", window = window.label(), args = (), async {
+        let log_dir = crate::directories::get_log_directory();
+        tokio::task::spawn_blocking(move || crate::utils::log_retention::clear(&log_dir))
+            .await
+            .map_err(|e| format!("清空日志任务失败: {}", e))
+    })
+}
+
+/// 导出离线错误报告
+/// 将本地保存的 Sentry envelope 文件复制到指定目录，返回导出的文件数
+#[tauri::command]
+#[specta::specta]
+pub async fn export_error_reports(target_dir: String) -> Result<usize, String> {
+    crate::log_async_command!("export_error_reports", args = (target_dir), async {
+        tokio::task::spawn_blocking(move || crate::error_report::export(Path::new(&target_dir)))
+            .await
+            .map_err(|e| format!("导出错误报告任务失败: {}", e))?
+    })
+}
+
+/// 获取本次运行 ID
+#[tauri::command]
+#[specta::specta]
+pub async fn get_run_id() -> Result<String, String> {
+    Ok(crate::run_id::run_id().to_string())
+}
+
+/// 获取崩溃报告
+/// 返回日志目录中保存的 panic 崩溃报告（最新在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_crash_reports() -> Result<Vec<crate::crash_report::CrashReport>, String> {
+    tokio::task::spawn_blocking(crate::crash_report::load_reports)
+        .await
+        .map_err(|e| format!("读取崩溃报告任务失败: {}", e))
+}
+
+/// 获取命令性能指标
+/// 返回每个命令的调用次数、失败次数、平均耗时与 p95 耗时
+#[tauri::command]
+#[specta::specta]
+pub async fn get_command_metrics(
+) -> Result<Vec<crate::utils::command_metrics::CommandMetrics>, String> {
+    Ok(crate::utils::command_metrics::snapshot())
+}
+
+/// 清空命令性能指标
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_command_metrics() -> Result<(), String> {
+    crate::utils::command_metrics::reset();
+    Ok(())
+}
+
+/// 获取操作审计日志
+/// 返回切换、备份、恢复、清理、路径修改等操作的记录（最新在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_audit_log(limit: Option<usize>) -> Result<Vec<crate::audit::AuditEntry>, String> {
+    tokio::task::spawn_blocking(move || crate::audit::load(limit))
+        .await
+        .map_err(|e| format!("读取审计日志任务失败: {}", e))?
+}
@@ -0,0 +1,51 @@
+//! Antigravity 设备标识命令
+//! 查看并重新生成 Antigravity 的遥测设备标识（machineId / devDeviceId 等），供需要全新设备身份的用户使用
+
+use crate::antigravity::machine_id::{self, MachineIds};
+use crate::error::{AgentError, AgentResult};
+use tauri::AppHandle;
+
+/// 获取当前的设备标识
+#[tauri::command]
+#[specta::specta]
+pub async fn get_machine_ids() -> Result<MachineIds, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("get_machine_ids", async {
+        tokio::task::spawn_blocking(machine_id::read)
+            .await
+            .map_err(|e| format!("读取设备标识任务失败: {}", e))?
+    })
+}
+
+/// 重新生成设备标识（安全模式下拒绝；执行前弹出确认对话框，旧标识与登录状态会先备份）
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_machine_ids(app: AppHandle) -> AgentResult<MachineIds> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("regenerate_machine_ids")?;
+
+    let message = "即将为 Antigravity 重新生成设备标识（machineId、devDeviceId 等），Antigravity 将被视为一台新设备。\n\n旧标识会备份到配置目录，是否继续？".to_string();
+    if !crate::local_api::confirm(&app, message).await {
+        tracing::info!(target: "machine_id::regenerate", "用户取消了重新生成设备标识");
+        return Err(AgentError::Internal(crate::i18n::t(
+            "common.cancelled",
+            &serde_json::Value::Null,
+        )));
+    }
+
+    let started = std::time::Instant::now();
+    let result = crate::log_async_command!("regenerate_machine_ids", async {
+        tokio::task::spawn_blocking(machine_id::regenerate)
+            .await
+            .map_err(|e| AgentError::Internal(format!("重新生成设备标识任务失败: {}", e)))?
+            .map_err(AgentError::from)
+    });
+    crate::audit::record(
+        crate::audit::AuditOperation::RegenerateMachineIds,
+        None,
+        started,
+        &result,
+        None,
+    );
+    result
+}
@@ -16,22 +16,85 @@ pub mod window_commands;
 // 系统托盘命令
 pub mod tray_commands;
 
+// 快速切换窗口命令
+pub mod quick_switcher_commands;
+
 // 日志相关命令
 pub mod logging_commands;
 
+// 事件命令
+pub mod event_commands;
+pub mod peer_discovery_commands;
+
+// 计划任务命令
+pub mod schedule_commands;
+
 // 应用设置命令
 pub mod settings_commands;
 
+// 应用锁命令
+pub mod app_lock_commands;
+
 // 数据库监控命令
 pub mod db_monitor_commands;
+
+// ItemTable 浏览命令
+pub mod item_table_commands;
+
+// 设备身份命令
+pub mod device_commands;
+
+// Antigravity 设备标识命令
+pub mod machine_id_commands;
+
+// 工作区存储命令
+pub mod workspace_storage_commands;
+
+// 备份检查命令
+pub mod backup_check_commands;
+
+// 远程备份命令
+pub mod remote_backup_commands;
+
+// 应用更新命令
+pub mod update_commands;
+
+// 本地 HTTP 控制接口命令
+pub mod local_api_commands;
+
+// 浏览器登录桥接命令
+pub mod browser_bridge_commands;
+
+// 插件命令
+pub mod plugin_commands;
+
+// 长耗时操作取消命令
+pub mod operation_commands;
 // 语言服务器相关命令（在 src/language_server 下）
 
 // 重新导出所有命令，保持与 main.rs 的兼容性
 pub use account_commands::*;
 pub use account_manage_commands::*;
+pub use app_lock_commands::*;
+pub use backup_check_commands::*;
+pub use browser_bridge_commands::*;
 pub use db_monitor_commands::*;
+pub use device_commands::*;
+pub use event_commands::*;
+pub use item_table_commands::*;
+pub use local_api_commands::*;
 pub use logging_commands::*;
+pub use machine_id_commands::*;
+pub use operation_commands::*;
+pub use peer_discovery_commands::*;
 pub use platform_commands::*;
+pub use plugin_commands::*;
 pub use process_commands::*;
+pub use quick_switcher_commands::*;
+pub use remote_backup_commands::*;
+pub use schedule_commands::*;
 pub use settings_commands::*;
 pub use tray_commands::*;
+pub use update_commands::*;
+pub use window_commands::*;
+pub use workspace_storage_commands::*;
@@ -0,0 +1,11 @@
+//! 长耗时操作命令
+//! 取消正在执行的切换、恢复、备份检查、导入导出等操作
+
+/// 取消指定操作；返回 `false` 表示操作不存在或已经结束
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_operation(operation_id: String) -> Result<bool, String> {
+    crate::log_async_command!("cancel_operation", args = (operation_id), async {
+        Ok::<_, String>(crate::cancellation::cancel(&operation_id))
+    })
+}
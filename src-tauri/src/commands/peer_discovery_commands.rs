@@ -0,0 +1,70 @@
+//! 局域网对等发现命令
+//! 查询发现状态与对端列表，开关 mDNS 广播/浏览
+
+use crate::peer_discovery::{self, Peer};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// 对等发现状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct PeerDiscoveryStatus {
+    pub enabled: bool,
+    /// mDNS 服务是否正在运行
+    pub running: bool,
+    /// 广播的局域网传输端口
+    pub port: u16,
+    /// 已发现的对端
+    pub peers: Vec<Peer>,
+}
+
+fn status(settings: &peer_discovery::PeerDiscoverySettings) -> PeerDiscoveryStatus {
+    PeerDiscoveryStatus {
+        enabled: settings.enabled,
+        running: peer_discovery::is_running(),
+        port: settings.port,
+        peers: peer_discovery::peers(),
+    }
+}
+
+/// 获取对等发现状态与已发现的对端
+#[tauri::command]
+#[specta::specta]
+pub async fn get_peer_discovery_status(app: AppHandle) -> Result<PeerDiscoveryStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(status(&settings_manager.get_settings().peer_discovery))
+}
+
+/// 启用/停用对等发现或修改广播端口
+#[tauri::command]
+#[specta::specta]
+pub async fn save_peer_discovery_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<PeerDiscoveryStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "save_peer_discovery_settings",
+        window = window.label(),
+        args = (enabled, port),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let settings = peer_discovery::PeerDiscoverySettings {
+                enabled,
+                port: port.unwrap_or(settings_manager.get_settings().peer_discovery.port),
+            };
+            // 先按新设置启动，失败时不保存
+            let apply_handle = app.clone();
+            let apply_settings = settings.clone();
+            tokio::task::spawn_blocking(move || {
+                peer_discovery::apply(&apply_handle, &apply_settings)
+            })
+            .await
+            .map_err(|e| format!("启动对等发现任务失败: {}", e))??;
+            settings_manager.update_settings(|s| s.peer_discovery = settings.clone())?;
+            Ok::<_, String>(status(&settings))
+        }
+    )
+}
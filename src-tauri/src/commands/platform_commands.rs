@@ -5,6 +5,7 @@ use serde_json::Value;
 
 /// 获取平台信息
 #[tauri::command]
+#[specta::specta]
 pub async fn get_platform_info() -> Result<Value, String> {
     let os_type = std::env::consts::OS;
     let arch = std::env::consts::ARCH;
@@ -27,6 +28,7 @@ pub async fn get_platform_info() -> Result<Value, String> {
 
 /// 查找 Antigravity 安装位置
 #[tauri::command]
+#[specta::specta]
 pub async fn find_antigravity_installations() -> Result<Vec<String>, String> {
     let paths = crate::platform::find_antigravity_installations();
     Ok(paths
@@ -37,6 +39,7 @@ pub async fn find_antigravity_installations() -> Result<Vec<String>, String> {
 
 /// 验证 Antigravity 可执行文件路径
 #[tauri::command]
+#[specta::specta]
 pub async fn validate_antigravity_executable(path: String) -> Result<bool, String> {
     Ok(crate::antigravity::path_config::validate_executable_path(
         &path,
@@ -45,6 +48,7 @@ pub async fn validate_antigravity_executable(path: String) -> Result<bool, Strin
 
 /// 检测 Antigravity 安装状态（数据库路径）
 #[tauri::command]
+#[specta::specta]
 pub async fn detect_antigravity_installation() -> Result<serde_json::Value, String> {
     // 自动检测 Antigravity 数据库路径
     if let Some(db_path) = crate::platform::get_antigravity_db_path() {
@@ -54,28 +58,36 @@ pub async fn detect_antigravity_installation() -> Result<serde_json::Value, Stri
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            println!("📁 检测到 Antigravity 数据库: {}", db_path.display());
-            println!("📂 Antigravity 数据目录: {}", data_dir);
+            let is_network_path = crate::utils::network_fs::is_network_path(&db_path);
+
+            tracing::info!(target: "platform::detect", "📁 检测到 Antigravity 数据库: {}", db_path.display());
+            tracing::info!(target: "platform::detect", "📂 Antigravity 数据目录: {}", data_dir);
+            if is_network_path {
+                tracing::info!(target: "platform::detect", "🌐 数据目录位于网络路径，读写将启用重试与延长超时");
+            }
 
             return Ok(serde_json::json!({
                 "found": true,
                 "path": data_dir,
-                "isCustomPath": false
+                "isCustomPath": false,
+                "isNetworkPath": is_network_path
             }));
         }
     }
 
     // 未找到
-    println!("⚠️ 未找到 Antigravity 数据库");
+    tracing::warn!(target: "platform::detect", "⚠️ 未找到 Antigravity 数据库");
     Ok(serde_json::json!({
         "found": false,
         "path": null,
-        "isCustomPath": false
+        "isCustomPath": false,
+        "isNetworkPath": false
     }))
 }
 
 /// 检测 Antigravity 可执行文件
 #[tauri::command]
+#[specta::specta]
 pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String> {
     // 1. 尝试从配置读取自定义可执行文件路径
     let custom_exec = crate::antigravity::path_config::get_custom_executable_path().unwrap_or(None);
@@ -86,7 +98,8 @@ pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String
             return Ok(serde_json::json!({
                 "found": true,
                 "path": path,
-                "isCustomPath": true
+                "isCustomPath": true,
+                "isNetworkPath": crate::utils::network_fs::is_network_path(std::path::Path::new(path))
             }));
         }
     }
@@ -94,40 +107,55 @@ pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String
     // 3. 尝试自动检测
     let detected_path = crate::antigravity::starter::detect_antigravity_executable();
     if let Some(exec_path) = detected_path {
-        println!("✅ 检测到 Antigravity 可执行文件: {}", exec_path.display());
+        tracing::info!(target: "platform::detect", "✅ 检测到 Antigravity 可执行文件: {}", exec_path.display());
 
         return Ok(serde_json::json!({
             "found": true,
             "path": exec_path.to_string_lossy().to_string(),
-            "isCustomPath": false
+            "isCustomPath": false,
+            "isNetworkPath": crate::utils::network_fs::is_network_path(&exec_path)
         }));
     }
 
     // 4. 未找到
-    println!("⚠️ 未找到 Antigravity 可执行文件，启动功能可能不可用");
+    tracing::warn!(target: "platform::detect", "⚠️ 未找到 Antigravity 可执行文件，启动功能可能不可用");
     Ok(serde_json::json!({
         "found": false,
         "path": null,
-        "isCustomPath": false
+        "isCustomPath": false,
+        "isNetworkPath": false
     }))
 }
 
 /// 保存用户自定义的 Antigravity 可执行文件路径
 #[tauri::command]
+#[specta::specta]
 pub async fn save_antigravity_executable(path: String) -> Result<String, String> {
-    // 1. 验证路径有效性
-    if !crate::antigravity::path_config::validate_executable_path(&path) {
-        return Err(format!("路径无效：文件 '{}' 不存在或不是可执行文件", path));
-    }
+    crate::audit::track(
+        crate::audit::AuditOperation::PathChange,
+        Some(&path),
+        async {
+            // 1. 验证路径有效性
+            if !crate::antigravity::path_config::validate_executable_path(&path) {
+                return Err(format!("路径无效：文件 '{}' 不存在或不是可执行文件", path));
+            }
 
-    // 2. 保存路径到配置
-    crate::antigravity::path_config::save_custom_executable_path(path.clone())?;
+            // 2. 保存路径到配置
+            let executable = path.clone();
+            crate::utils::blocking::run("保存路径配置", move || {
+                crate::antigravity::path_config::save_custom_executable_path(executable)
+            })
+            .await?;
 
-    Ok(format!("已保存 Antigravity 可执行文件路径: {}", path))
+            Ok(format!("已保存 Antigravity 可执行文件路径: {}", path))
+        },
+    )
+    .await
 }
 
 /// 获取当前配置的路径
 #[tauri::command]
+#[specta::specta]
 pub async fn get_current_paths() -> Result<serde_json::Value, String> {
     let exec_path = crate::antigravity::path_config::get_custom_executable_path().unwrap_or(None);
 
@@ -135,3 +163,112 @@ pub async fn get_current_paths() -> Result<serde_json::Value, String> {
         "executablePath": exec_path
     }))
 }
+
+/// 检查配置目录权限，`repair` 为 true 时收紧为仅当前用户可访问
+#[tauri::command]
+#[specta::specta]
+pub async fn harden_config_dir(
+    window: tauri::Window,
+    repair: Option<bool>,
+) -> Result<crate::utils::dir_permissions::HardenReport, String> {
+    crate::log_async_command!(
+        "harden_config_dir",
+        window = window.label(),
+        args = (repair),
+        async {
+            let repair = repair.unwrap_or(true);
+            tokio::task::spawn_blocking(move || {
+                crate::utils::dir_permissions::harden(
+                    &crate::directories::get_config_directory(),
+                    repair,
+                )
+            })
+            .await
+            .map_err(|e| format!("权限检查任务失败: {}", e))
+        }
+    )
+}
+
+/// 获取启动时配置文件完整性检查发现的问题
+#[tauri::command]
+#[specta::specta]
+pub async fn get_config_integrity_warnings(
+) -> Result<Vec<crate::config_integrity::IntegrityWarning>, String> {
+    Ok(crate::config_integrity::warnings())
+}
+
+/// 复制令牌等敏感内容到剪贴板，`ttl` 秒后（默认 30 秒）若剪贴板内容未变则自动清空
+#[tauri::command]
+#[specta::specta]
+pub async fn copy_secret_to_clipboard(value: String, ttl: Option<u64>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    tokio::task::spawn_blocking(move || crate::utils::clipboard::copy_secret(value, ttl))
+        .await
+        .map_err(|e| format!("复制任务失败: {}", e))?
+}
+
+/// 运行自检
+/// 检查配置目录、Antigravity 数据库、可执行文件、备份目录与登录状态解码，返回逐项结果
+#[tauri::command]
+#[specta::specta]
+pub async fn run_self_test() -> Result<crate::self_test::SelfTestReport, String> {
+    crate::log_async_command!("run_self_test", async {
+        tokio::task::spawn_blocking(crate::self_test::run)
+            .await
+            .map_err(|e| format!("自检任务失败: {}", e))
+    })
+}
+
+/// 运行性能基准测试
+/// 用合成数据与真实数据测量备份写入、恢复写入、数据库读取与登录状态解码耗时，并与参考基线对比
+#[tauri::command]
+#[specta::specta]
+pub async fn run_benchmark() -> Result<crate::benchmark::BenchmarkReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("run_benchmark", async {
+        crate::utils::blocking::run("性能基准测试", || {
+            Ok::<_, String>(crate::benchmark::run())
+        })
+        .await
+    })
+}
+
+/// 首次运行引导检测
+/// 检测可执行文件与数据目录、检查目录权限并探测当前登录，检测成功的路径写入路径配置
+#[tauri::command]
+#[specta::specta]
+pub async fn run_onboarding_detection() -> Result<crate::onboarding::OnboardingReport, String> {
+    crate::log_async_command!("run_onboarding_detection", async {
+        crate::utils::blocking::run("首次运行检测", || {
+            Ok::<_, String>(crate::onboarding::run())
+        })
+        .await
+    })
+}
+
+/// 导出 Antigravity 路径与当前账户为 shell 环境变量语句（`ANTIGRAVITY_*`）
+#[tauri::command]
+#[specta::specta]
+pub async fn export_shell_env(format: crate::shell_env::ShellFormat) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("export_shell_env", args = (format), async {
+        // 未登录或读取失败时只输出路径
+        let account = crate::commands::get_current_antigravity_account_info()
+            .await
+            .ok();
+        tokio::task::spawn_blocking(move || crate::shell_env::render(format, account.as_ref()))
+            .await
+            .map_err(|e| format!("生成环境变量任务失败: {}", e))
+    })
+}
+
+/// 获取磁盘占用报告（Antigravity 数据目录与 Agent 的备份、日志、配置，按子目录组织为树）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_disk_usage_report() -> Result<crate::disk_usage::DiskUsageReport, String> {
+    crate::log_async_command!("get_disk_usage_report", async {
+        tokio::task::spawn_blocking(crate::disk_usage::report)
+            .await
+            .map_err(|e| format!("统计磁盘占用任务失败: {}", e))
+    })
+}
@@ -0,0 +1,57 @@
+//! 插件命令
+//! 查看、重新加载插件脚本，调用插件注册的自定义命令
+
+use crate::plugins::{self, PluginInfo};
+use serde_json::Value;
+use tauri::AppHandle;
+
+/// 获取已加载的插件
+#[tauri::command]
+#[specta::specta]
+pub async fn list_plugins() -> Result<Vec<PluginInfo>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    Ok(plugins::list())
+}
+
+/// 重新加载插件目录中的脚本
+#[tauri::command]
+#[specta::specta]
+pub async fn reload_plugins(window: tauri::Window) -> Result<Vec<PluginInfo>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "reload_plugins",
+        window = window.label(),
+        args = (),
+        async {
+            tokio::task::spawn_blocking(plugins::load)
+                .await
+                .map_err(|e| format!("加载插件任务失败: {}", e))
+        }
+    )
+}
+
+/// 运行插件的自定义命令，返回脚本的返回值
+#[tauri::command]
+#[specta::specta]
+pub async fn run_plugin_command(
+    app: AppHandle,
+    window: tauri::Window,
+    plugin: String,
+    command: String,
+    args: Option<Value>,
+) -> Result<Value, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "run_plugin_command",
+        window = window.label(),
+        args = (plugin, command),
+        async {
+            let args = args.unwrap_or(Value::Null);
+            tokio::task::spawn_blocking(move || {
+                plugins::run_command(&app, &plugin, &command, &args)
+            })
+            .await
+            .map_err(|e| format!("插件命令任务失败: {}", e))?
+        }
+    )
+}
@@ -0,0 +1,60 @@
+//! 快速切换窗口命令
+//! 为快速切换窗口提供轻量的账户列表与切换命令，不加载完整的账户数据
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// 快速切换列表中的账户
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct QuickSwitchAccount {
+    pub email: String,
+    pub pinned: bool,
+    /// 最近备份时间（毫秒时间戳）
+    pub last_backup_ms: i64,
+}
+
+/// 列出可切换的账户（置顶账户在前，其余按最近备份时间排序）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_quick_switch_accounts(app: AppHandle) -> Result<Vec<QuickSwitchAccount>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let pinned_accounts = app
+        .state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .pinned_accounts;
+
+    let mut accounts: Vec<QuickSwitchAccount> = crate::antigravity::backup::list_backup_files()?
+        .into_iter()
+        .map(|entry| {
+            let modified: chrono::DateTime<chrono::Utc> = entry.modified.into();
+            QuickSwitchAccount {
+                pinned: pinned_accounts.contains(&entry.name),
+                email: entry.name,
+                last_backup_ms: modified.timestamp_millis(),
+            }
+        })
+        .collect();
+
+    // 稳定排序：置顶账户在前，保持备份时间倒序
+    accounts.sort_by_key(|account| !account.pinned);
+    Ok(accounts)
+}
+
+/// 从快速切换窗口切换账户（先关闭窗口，再执行完整切换流程）
+#[tauri::command]
+#[specta::specta]
+pub async fn quick_switch_account(
+    app: AppHandle,
+    email: String,
+) -> crate::error::AgentResult<String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::window::quick_switcher::close(&app)?;
+    crate::commands::switch_to_antigravity_account(app.clone(), email).await
+}
+
+/// 关闭快速切换窗口
+#[tauri::command]
+#[specta::specta]
+pub async fn close_quick_switcher(app: AppHandle) -> Result<(), String> {
+    crate::window::quick_switcher::close(&app)
+}
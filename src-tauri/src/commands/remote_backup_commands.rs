@@ -0,0 +1,150 @@
+//! 远程备份命令
+//! 配置 S3 兼容对象存储，对比本地与远程备份，推送与拉取备份
+
+use crate::antigravity::backup_archive::{ArchiveImportReport, ConflictStrategy};
+use crate::remote_backup::{
+    self, RemoteBackupEntry, RemotePushReport, S3BackupSettings, S3BackupStatus,
+};
+use tauri::{AppHandle, Manager, State};
+
+/// 读取已保存的配置，未配置时返回错误
+fn configured(app: &AppHandle) -> Result<S3BackupSettings, String> {
+    app.state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .s3_backup
+        .ok_or_else(|| "尚未配置远程备份".to_string())
+}
+
+/// 获取远程备份配置与密钥保存状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_s3_backup_status(app: AppHandle) -> Result<S3BackupStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(S3BackupStatus {
+        settings: settings_manager.get_settings().s3_backup,
+        secret_stored: remote_backup::has_secret(),
+    })
+}
+
+/// 保存远程备份配置；传入 `secret_access_key` 时保存到钥匙串（参数含密钥，不写入日志），
+/// `settings` 为空时清除配置与钥匙串中的密钥
+#[tauri::command]
+#[specta::specta]
+pub async fn save_s3_backup_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    settings: Option<S3BackupSettings>,
+    secret_access_key: Option<String>,
+) -> Result<S3BackupStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("save_s3_backup_settings", window = window.label(), async {
+        match &settings {
+            Some(settings) => {
+                remote_backup::validate(settings)?;
+                if let Some(secret) = secret_access_key.filter(|secret| !secret.is_empty()) {
+                    remote_backup::store_secret(&secret)?;
+                }
+            }
+            None => remote_backup::delete_secret(),
+        }
+
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        settings_manager.update_settings(|s| s.s3_backup = settings.clone())?;
+        Ok::<_, String>(S3BackupStatus {
+            settings,
+            secret_stored: remote_backup::has_secret(),
+        })
+    })
+}
+
+/// 对比列出本地与远程的备份
+///
+/// 可传入 `operation_id`，列举过程中通过 `cancel_operation` 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn list_remote_backups(
+    app: AppHandle,
+    operation_id: Option<String>,
+) -> Result<Vec<RemoteBackupEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings = configured(&app)?;
+    crate::log_async_command!(
+        "list_remote_backups",
+        crate::cancellation::run(&app, "remote_backup", operation_id, async {
+            let token = crate::cancellation::current();
+            remote_backup::list(&settings, &token).await
+        })
+    )
+}
+
+/// 推送本地备份到远程（`names` 为空时推送全部）
+///
+/// 可传入 `operation_id`，上传过程中通过 `cancel_operation` 取消（已上传的保留）
+#[tauri::command]
+#[specta::specta]
+pub async fn push_remote_backups(
+    app: AppHandle,
+    window: tauri::Window,
+    names: Option<Vec<String>>,
+    operation_id: Option<String>,
+) -> Result<RemotePushReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::os_auth::require("push_remote_backups", "上传包含登录凭据的账户备份").await?;
+    let settings = configured(&app)?;
+    crate::log_async_command!(
+        "push_remote_backups",
+        window = window.label(),
+        args = (names),
+        crate::cancellation::run(&app, "remote_backup", operation_id, async {
+            let token = crate::cancellation::current();
+            remote_backup::push(&settings, names, &token).await
+        })
+    )
+}
+
+/// 从远程拉取备份到本地备份目录（`names` 为空时拉取全部），同名时按 `strategy` 处理
+///
+/// 可传入 `operation_id`，下载过程中通过 `cancel_operation` 取消（已写入的备份保留）
+#[tauri::command]
+#[specta::specta]
+pub async fn pull_remote_backups(
+    app: AppHandle,
+    window: tauri::Window,
+    guard: State<'_, crate::operation_guard::OperationGuard>,
+    names: Option<Vec<String>>,
+    strategy: ConflictStrategy,
+    operation_id: Option<String>,
+) -> Result<ArchiveImportReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings = configured(&app)?;
+    crate::log_async_command!(
+        "pull_remote_backups",
+        window = window.label(),
+        args = (names, strategy),
+        guard.run(
+            "import",
+            crate::cancellation::run(&app, "remote_backup", operation_id, async {
+                let started = std::time::Instant::now();
+                let token = crate::cancellation::current();
+                let result = remote_backup::pull(&settings, names, strategy, &token).await;
+                crate::system_tray::refresh_tray_menu(&app);
+
+                let detail = result.as_ref().ok().map(|report| {
+                    format!(
+                        "从远程导入 {} 个，跳过 {} 个，失败 {} 个",
+                        report.restored_count, report.skipped_count, report.failed_count
+                    )
+                });
+                crate::audit::record(
+                    crate::audit::AuditOperation::ImportBackups,
+                    None,
+                    started,
+                    &result,
+                    detail.as_deref(),
+                );
+                result
+            })
+        )
+    )
+}
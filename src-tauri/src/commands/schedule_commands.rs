@@ -0,0 +1,27 @@
+//! 计划任务命令
+//! 查询后台自动任务的下一次运行时间，导出为 iCalendar 文件
+
+use crate::schedule::{self, ScheduledJob};
+
+/// 获取已调度的自动任务
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scheduled_jobs() -> Result<Vec<ScheduledJob>, String> {
+    Ok(schedule::upcoming())
+}
+
+/// 将自动任务导出为 `.ics` 日历文件，返回导出的任务数
+#[tauri::command]
+#[specta::specta]
+pub async fn export_schedule_ics(window: tauri::Window, path: String) -> Result<usize, String> {
+    crate::log_async_command!(
+        "export_schedule_ics",
+        window = window.label(),
+        args = (path),
+        async {
+            tokio::task::spawn_blocking(move || schedule::export_ics(std::path::Path::new(&path)))
+                .await
+                .map_err(|e| format!("导出日历任务失败: {}", e))?
+        }
+    )
+}
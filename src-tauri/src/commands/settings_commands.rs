@@ -5,6 +5,7 @@ use tauri::{AppHandle, Manager};
 
 /// 获取静默启动状态
 #[tauri::command]
+#[specta::specta]
 pub async fn is_silent_start_enabled(app: AppHandle) -> Result<bool, String> {
     crate::log_async_command!("is_silent_start_enabled", async {
         let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
@@ -15,20 +16,101 @@ pub async fn is_silent_start_enabled(app: AppHandle) -> Result<bool, String> {
 
 /// 保存静默启动状态
 #[tauri::command]
-pub async fn save_silent_start_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
-    crate::log_async_command!("save_silent_start_state", async {
+#[specta::specta]
+pub async fn save_silent_start_state(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_silent_start_state",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.silent_start_enabled = enabled;
+            })?;
+
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取最小化到托盘状态
+#[tauri::command]
+#[specta::specta]
+pub async fn is_minimize_to_tray_enabled(app: AppHandle) -> Result<bool, String> {
+    crate::log_async_command!("is_minimize_to_tray_enabled", async {
         let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        let settings = settings_manager.get_settings();
+        Ok(settings.minimize_to_tray_enabled)
+    })
+}
 
-        settings_manager.update_settings(|settings| {
-            settings.silent_start_enabled = enabled;
-        })?;
+/// 保存最小化到托盘状态
+#[tauri::command]
+#[specta::specta]
+pub async fn save_minimize_to_tray_state(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_minimize_to_tray_state",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.minimize_to_tray_enabled = enabled;
+            })?;
 
-        Ok(enabled)
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取“操作进行中退出前确认”状态
+#[tauri::command]
+#[specta::specta]
+pub async fn is_confirm_exit_enabled(app: AppHandle) -> Result<bool, String> {
+    crate::log_async_command!("is_confirm_exit_enabled", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        let settings = settings_manager.get_settings();
+        Ok(settings.confirm_exit_during_operations)
     })
 }
 
+/// 保存“操作进行中退出前确认”状态
+#[tauri::command]
+#[specta::specta]
+pub async fn save_confirm_exit_state(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_confirm_exit_state",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.confirm_exit_during_operations = enabled;
+            })?;
+
+            Ok(enabled)
+        }
+    )
+}
+
 /// 获取所有应用设置
 #[tauri::command]
+#[specta::specta]
 pub async fn get_all_settings(app: AppHandle) -> Result<serde_json::Value, String> {
     crate::log_async_command!("get_all_settings", async {
         let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
@@ -36,7 +118,693 @@ pub async fn get_all_settings(app: AppHandle) -> Result<serde_json::Value, Strin
 
         Ok(serde_json::json!({
             "system_tray_enabled": settings.system_tray_enabled,
-            "silent_start_enabled": settings.silent_start_enabled
+            "silent_start_enabled": settings.silent_start_enabled,
+            "minimize_to_tray_enabled": settings.minimize_to_tray_enabled,
+            "confirm_exit_during_operations": settings.confirm_exit_during_operations,
+            "tray_left_click_action": settings.tray_left_click_action,
+            "theme_preference": settings.theme_preference,
+            "notifications": settings.notifications,
+            "slow_operation_threshold_ms": crate::slow_operation::threshold_ms(),
+            "log_retention": settings.log_retention,
+            "error_reports_enabled": settings.error_reports_enabled,
+            "keychain_enabled": settings.keychain_enabled,
+            "backup_compression": settings.backup_compression
         }))
     })
 }
+
+/// 获取慢操作警告阈值（毫秒）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_slow_operation_threshold() -> Result<u64, String> {
+    Ok(crate::slow_operation::threshold_ms())
+}
+
+/// 保存慢操作警告阈值（毫秒），传入空值恢复默认
+#[tauri::command]
+#[specta::specta]
+pub async fn save_slow_operation_threshold(
+    app: AppHandle,
+    window: tauri::Window,
+    threshold_ms: Option<u64>,
+) -> Result<u64, String> {
+    crate::log_async_command!(
+        "save_slow_operation_threshold",
+        window = window.label(),
+        args = (threshold_ms),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.slow_operation_threshold_ms = threshold_ms;
+            })?;
+
+            crate::slow_operation::set_threshold_ms(threshold_ms);
+            Ok(crate::slow_operation::threshold_ms())
+        }
+    )
+}
+
+/// 获取离线错误报告开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_error_reports_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().error_reports_enabled)
+}
+
+/// 保存离线错误报告开关
+#[tauri::command]
+#[specta::specta]
+pub async fn save_error_reports_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_error_reports_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.error_reports_enabled = enabled;
+            })?;
+
+            crate::error_report::set_enabled(enabled);
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取“删除备份前覆盖文件内容”开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_secure_delete_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().secure_delete_enabled)
+}
+
+/// 保存“删除备份前覆盖文件内容”开关
+#[tauri::command]
+#[specta::specta]
+pub async fn save_secure_delete_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_secure_delete_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.secure_delete_enabled = enabled;
+            })?;
+
+            crate::utils::secure_delete::set_enabled(enabled);
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取“记录登录状态变更日志”开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_state_journal_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().state_journal_enabled)
+}
+
+/// 保存“记录登录状态变更日志”开关（关闭时删除已有日志）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_state_journal_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_state_journal_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.state_journal_enabled = enabled;
+            })?;
+
+            crate::state_journal::set_enabled(enabled);
+            if !enabled {
+                crate::utils::blocking::run("删除登录状态变更日志", crate::state_journal::clear)
+                    .await?;
+            }
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取“敏感操作前要求系统身份验证”开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_os_auth_required(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().require_os_auth)
+}
+
+/// 保存“敏感操作前要求系统身份验证”开关（开启与关闭前都需要先通过一次验证）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_os_auth_required(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_os_auth_required",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            // 开启时确认系统支持验证，关闭时防止绕过
+            crate::os_auth::verify("save_os_auth_required", "修改系统身份验证设置").await?;
+
+            settings_manager.update_settings(|settings| {
+                settings.require_os_auth = enabled;
+            })?;
+
+            crate::os_auth::set_enabled(enabled);
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取安全模式开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_safe_mode_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().safe_mode)
+}
+
+/// 保存安全模式开关
+#[tauri::command]
+#[specta::specta]
+pub async fn save_safe_mode_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_safe_mode_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.safe_mode = enabled;
+            })?;
+
+            crate::safe_mode::set_enabled(enabled);
+            Ok(enabled)
+        }
+    )
+}
+
+/// 重新测量 Argon2 参数（配置迁移到新设备后使用），之后新设置的密码使用新参数
+#[tauri::command]
+#[specta::specta]
+pub async fn rebenchmark_kdf(
+    app: AppHandle,
+    window: tauri::Window,
+) -> Result<crate::utils::kdf::KdfSettings, String> {
+    crate::log_async_command!("rebenchmark_kdf", window = window.label(), async {
+        let kdf = tokio::task::spawn_blocking(crate::utils::kdf::benchmark)
+            .await
+            .map_err(|e| format!("测量任务失败: {}", e))??;
+
+        app.state::<crate::app_settings::AppSettingsManager>()
+            .update_settings(|settings| settings.kdf = Some(kdf))?;
+        crate::utils::kdf::set_params(kdf);
+        Ok(kdf)
+    })
+}
+
+/// 获取设置文件加密开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_settings_encryption_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.is_encrypted())
+}
+
+/// 保存设置文件加密开关（开启后 app_settings.json 使用设备密钥加密保存）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_settings_encryption_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "save_settings_encryption_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let app = app.clone();
+            tokio::task::spawn_blocking(move || {
+                app.state::<crate::app_settings::AppSettingsManager>()
+                    .set_encrypted(enabled)
+            })
+            .await
+            .map_err(|e| format!("保存设置任务失败: {}", e))??;
+            Ok::<_, String>(enabled)
+        }
+    )
+}
+
+/// 获取远程功能使用的代理地址
+#[tauri::command]
+#[specta::specta]
+pub async fn get_http_proxy(app: AppHandle) -> Result<Option<String>, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().http_proxy)
+}
+
+/// 保存远程功能使用的代理地址（为空表示使用系统代理环境变量）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_http_proxy(
+    app: AppHandle,
+    window: tauri::Window,
+    proxy: Option<String>,
+) -> Result<Option<String>, String> {
+    crate::log_async_command!(
+        "save_http_proxy",
+        window = window.label(),
+        args = (proxy),
+        async {
+            let proxy = proxy
+                .map(|proxy| proxy.trim().to_string())
+                .filter(|proxy| !proxy.is_empty());
+            if let Some(proxy) = &proxy {
+                crate::http_client::validate_proxy(proxy)?;
+            }
+
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            settings_manager.update_settings(|settings| {
+                settings.http_proxy = proxy.clone();
+            })?;
+
+            crate::http_client::set_proxy(proxy.clone());
+            Ok::<_, String>(proxy)
+        }
+    )
+}
+
+/// 获取隐私模式开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_privacy_mode_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().privacy_mode)
+}
+
+/// 保存隐私模式开关
+#[tauri::command]
+#[specta::specta]
+pub async fn save_privacy_mode_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!(
+        "save_privacy_mode_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.privacy_mode = enabled;
+            })?;
+
+            crate::utils::privacy::set_enabled(enabled);
+            crate::system_tray::refresh_tray_menu(&app);
+            Ok(enabled)
+        }
+    )
+}
+
+/// 获取“备份凭据保存到系统钥匙串”开关
+#[tauri::command]
+#[specta::specta]
+pub async fn is_keychain_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().keychain_enabled)
+}
+
+/// 保存“备份凭据保存到系统钥匙串”开关，并迁移已有备份，返回迁移的备份数
+#[tauri::command]
+#[specta::specta]
+pub async fn save_keychain_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<usize, String> {
+    crate::log_async_command!(
+        "save_keychain_enabled",
+        window = window.label(),
+        args = (enabled),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let previous = settings_manager.get_settings().keychain_enabled;
+
+            crate::antigravity::keychain::set_enabled(enabled);
+            let migrated =
+                tokio::task::spawn_blocking(crate::antigravity::keychain::migrate_backups)
+                    .await
+                    .map_err(|e| format!("迁移备份任务失败: {}", e))?;
+
+            // 迁移失败时回退开关，已处理的备份在下次切换时会被继续处理
+            let migrated = match migrated {
+                Ok(count) => count,
+                Err(e) => {
+                    crate::antigravity::keychain::set_enabled(previous);
+                    return Err(e);
+                }
+            };
+
+            settings_manager.update_settings(|settings| {
+                settings.keychain_enabled = enabled;
+            })?;
+
+            Ok(migrated)
+        }
+    )
+}
+
+/// 获取备份压缩格式
+#[tauri::command]
+#[specta::specta]
+pub async fn get_backup_compression(
+    app: AppHandle,
+) -> Result<crate::antigravity::compression::BackupCompression, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().backup_compression)
+}
+
+/// 保存备份压缩格式，并按新格式重写已有备份，返回重写的备份数
+#[tauri::command]
+#[specta::specta]
+pub async fn save_backup_compression(
+    app: AppHandle,
+    window: tauri::Window,
+    compression: crate::antigravity::compression::BackupCompression,
+) -> Result<usize, String> {
+    crate::log_async_command!(
+        "save_backup_compression",
+        window = window.label(),
+        args = (compression),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            let previous = settings_manager.get_settings().backup_compression;
+
+            crate::antigravity::compression::set_mode(compression);
+            let migrated =
+                tokio::task::spawn_blocking(crate::antigravity::compression::migrate_backups)
+                    .await
+                    .map_err(|e| format!("重写备份任务失败: {}", e))?;
+
+            // 重写失败时回退格式，两种格式的备份都能正常读取
+            let migrated = match migrated {
+                Ok(count) => count,
+                Err(e) => {
+                    crate::antigravity::compression::set_mode(previous);
+                    return Err(e);
+                }
+            };
+
+            settings_manager.update_settings(|settings| {
+                settings.backup_compression = compression;
+            })?;
+
+            Ok(migrated)
+        }
+    )
+}
+
+/// 获取日志保留设置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_log_retention_settings(
+    app: AppHandle,
+) -> Result<crate::utils::log_retention::LogRetentionSettings, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().log_retention)
+}
+
+/// 保存日志保留设置，并立即按新策略清理一次
+#[tauri::command]
+#[specta::specta]
+pub async fn save_log_retention_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    retention: crate::utils::log_retention::LogRetentionSettings,
+) -> Result<crate::utils::log_retention::LogCleanupResult, String> {
+    crate::log_async_command!(
+        "save_log_retention_settings",
+        window = window.label(),
+        args = (retention),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.log_retention = retention;
+            })?;
+
+            let log_dir = crate::directories::get_log_directory();
+            tokio::task::spawn_blocking(move || {
+                crate::utils::log_retention::enforce(&log_dir, &retention)
+            })
+            .await
+            .map_err(|e| format!("日志清理任务失败: {}", e))
+        }
+    )
+}
+
+/// 获取系统通知设置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notification_settings(
+    app: AppHandle,
+) -> Result<crate::notifications::NotificationSettings, String> {
+    crate::log_async_command!("get_notification_settings", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        Ok(settings_manager.get_settings().notifications)
+    })
+}
+
+/// 保存系统通知设置
+#[tauri::command]
+#[specta::specta]
+pub async fn save_notification_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    notifications: crate::notifications::NotificationSettings,
+) -> Result<crate::notifications::NotificationSettings, String> {
+    crate::log_async_command!(
+        "save_notification_settings",
+        window = window.label(),
+        args = (notifications),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.notifications = notifications;
+            })?;
+
+            Ok(notifications)
+        }
+    )
+}
+
+/// 获取 Webhook 配置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhooks(app: AppHandle) -> Result<Vec<crate::webhooks::WebhookConfig>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().webhooks)
+}
+
+/// 保存 Webhook 配置（参数含签名密钥，不写入日志）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_webhooks(
+    app: AppHandle,
+    window: tauri::Window,
+    webhooks: Vec<crate::webhooks::WebhookConfig>,
+) -> Result<Vec<crate::webhooks::WebhookConfig>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("save_webhooks", window = window.label(), async {
+        for webhook in &webhooks {
+            crate::webhooks::validate(webhook)?;
+        }
+
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        settings_manager.update_settings(|settings| {
+            settings.webhooks = webhooks.clone();
+        })?;
+        Ok::<_, String>(webhooks)
+    })
+}
+
+/// 向指定 Webhook 发送一次测试事件
+#[tauri::command]
+#[specta::specta]
+pub async fn test_webhook(
+    window: tauri::Window,
+    webhook: crate::webhooks::WebhookConfig,
+) -> Result<(), String> {
+    crate::log_async_command!("test_webhook", window = window.label(), async {
+        crate::webhooks::test(&webhook).await
+    })
+}
+
+/// 获取事件钩子配置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_hooks(app: AppHandle) -> Result<Vec<crate::hooks::HookConfig>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().hooks)
+}
+
+/// 保存事件钩子配置
+#[tauri::command]
+#[specta::specta]
+pub async fn save_hooks(
+    app: AppHandle,
+    window: tauri::Window,
+    hooks: Vec<crate::hooks::HookConfig>,
+) -> Result<Vec<crate::hooks::HookConfig>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!(
+        "save_hooks",
+        window = window.label(),
+        args = (hooks),
+        async {
+            for hook in &hooks {
+                crate::hooks::validate(hook)?;
+            }
+
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+            settings_manager.update_settings(|settings| {
+                settings.hooks = hooks.clone();
+            })?;
+            Ok::<_, String>(hooks)
+        }
+    )
+}
+
+/// 获取当前生效的主题（light / dark）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_system_theme(app: AppHandle) -> Result<String, String> {
+    crate::window::theme::current_theme(&app)
+}
+
+/// 获取主题偏好
+#[tauri::command]
+#[specta::specta]
+pub async fn get_theme_preference(
+    app: AppHandle,
+) -> Result<crate::window::theme::ThemePreference, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().theme_preference)
+}
+
+/// 保存并应用主题偏好
+#[tauri::command]
+#[specta::specta]
+pub async fn save_theme_preference(
+    app: AppHandle,
+    window: tauri::Window,
+    preference: crate::window::theme::ThemePreference,
+) -> Result<crate::window::theme::ThemePreference, String> {
+    crate::log_async_command!(
+        "save_theme_preference",
+        window = window.label(),
+        args = (preference),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.theme_preference = preference;
+            })?;
+
+            crate::window::theme::apply_preference(&app, preference);
+            Ok(preference)
+        }
+    )
+}
+
+/// 获取界面语言
+#[tauri::command]
+#[specta::specta]
+pub async fn get_locale() -> Result<crate::i18n::Locale, String> {
+    Ok(crate::i18n::locale())
+}
+
+/// 保存界面语言，后端返回的错误与结果消息随之切换
+#[tauri::command]
+#[specta::specta]
+pub async fn save_locale(
+    app: AppHandle,
+    window: tauri::Window,
+    locale: crate::i18n::Locale,
+) -> Result<crate::i18n::Locale, String> {
+    crate::log_async_command!(
+        "save_locale",
+        window = window.label(),
+        args = (locale),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.locale = locale;
+            })?;
+
+            crate::i18n::set_locale(locale);
+            Ok(locale)
+        }
+    )
+}
+
+/// 获取消息目录（消息 ID → 模板），未指定语言时使用当前语言
+#[tauri::command]
+#[specta::specta]
+pub async fn get_message_catalog(
+    locale: Option<crate::i18n::Locale>,
+) -> Result<std::collections::BTreeMap<String, String>, String> {
+    Ok(crate::i18n::catalog(
+        locale.unwrap_or_else(crate::i18n::locale),
+    ))
+}
+
+/// 获取已记录的启动进度（前端加载前发出的事件可由此补取）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_startup_progress(
+    app: AppHandle,
+) -> Result<Vec<crate::startup::StartupProgressPayload>, String> {
+    Ok(app.state::<crate::startup::StartupProgress>().snapshot())
+}
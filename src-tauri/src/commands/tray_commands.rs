@@ -1,38 +1,45 @@
-use crate::system_tray::{update_tray_menu, SystemTrayManager};
+use crate::system_tray::{update_tray_menu, SystemTrayManager, TrayLeftClickAction};
 use tauri::Manager;
 
 /// 启用系统托盘
 #[tauri::command]
+#[specta::specta]
 pub async fn enable_system_tray(app: tauri::AppHandle) -> Result<String, String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.enable(&app)?;
 
-    Ok("系统托盘已启用".to_string())
+    Ok(crate::i18n::t("tray.enabled", &serde_json::Value::Null))
 }
 
 /// 禁用系统托盘
 #[tauri::command]
+#[specta::specta]
 pub async fn disable_system_tray(app: tauri::AppHandle) -> Result<String, String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.disable(&app)?;
 
-    Ok("系统托盘已禁用".to_string())
+    Ok(crate::i18n::t("tray.disabled", &serde_json::Value::Null))
 }
 
 /// 切换系统托盘状态
 #[tauri::command]
+#[specta::specta]
 pub async fn toggle_system_tray(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
     let system_tray = app.state::<SystemTrayManager>();
     let enabled = system_tray.toggle(&app)?;
 
     Ok(serde_json::json!({
         "enabled": enabled,
-        "message": if enabled { "系统托盘已启用" } else { "系统托盘已禁用" }
+        "message": crate::i18n::t(
+            if enabled { "tray.enabled" } else { "tray.disabled" },
+            &serde_json::Value::Null,
+        )
     }))
 }
 
 /// 获取系统托盘状态
 #[tauri::command]
+#[specta::specta]
 pub async fn get_system_tray_state(app: tauri::AppHandle) -> Result<bool, String> {
     let system_tray = app.state::<SystemTrayManager>();
     Ok(system_tray.is_enabled_setting(&app))
@@ -40,6 +47,7 @@ pub async fn get_system_tray_state(app: tauri::AppHandle) -> Result<bool, String
 
 /// 更新托盘菜单（新增命令，供前端调用）
 #[tauri::command]
+#[specta::specta]
 pub async fn update_tray_menu_command(
     app: tauri::AppHandle,
     accounts: Vec<String>,
@@ -48,8 +56,26 @@ pub async fn update_tray_menu_command(
     Ok("托盘菜单已更新".to_string())
 }
 
+/// 设置账户在托盘快速切换菜单中是否置顶
+#[tauri::command]
+#[specta::specta]
+pub async fn set_tray_account_pinned(
+    app: tauri::AppHandle,
+    email: String,
+    pinned: bool,
+) -> Result<String, String> {
+    let system_tray = app.state::<SystemTrayManager>();
+    system_tray.set_account_pinned(&app, &email, pinned)?;
+    Ok(if pinned {
+        "账户已置顶".to_string()
+    } else {
+        "已取消置顶".to_string()
+    })
+}
+
 /// 最小化到托盘
 #[tauri::command]
+#[specta::specta]
 pub async fn minimize_to_tray(app: tauri::AppHandle) -> Result<String, String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.minimize_to_tray(&app)?;
@@ -58,8 +84,31 @@ pub async fn minimize_to_tray(app: tauri::AppHandle) -> Result<String, String> {
 
 /// 从托盘恢复
 #[tauri::command]
+#[specta::specta]
 pub async fn restore_from_tray(app: tauri::AppHandle) -> Result<String, String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.restore_from_tray(&app)?;
     Ok("已恢复窗口".to_string())
 }
+
+/// 获取托盘图标左键单击行为
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tray_left_click_action(
+    app: tauri::AppHandle,
+) -> Result<TrayLeftClickAction, String> {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    Ok(settings_manager.get_settings().tray_left_click_action)
+}
+
+/// 设置托盘图标左键单击行为
+#[tauri::command]
+#[specta::specta]
+pub async fn set_tray_left_click_action(
+    app: tauri::AppHandle,
+    action: TrayLeftClickAction,
+) -> Result<TrayLeftClickAction, String> {
+    let system_tray = app.state::<SystemTrayManager>();
+    system_tray.set_left_click_action(&app, action)?;
+    Ok(action)
+}
@@ -0,0 +1,89 @@
+//! 应用更新命令
+//! 按设置中的更新通道检查更新、下载并安装更新（或推迟到退出时安装），以及读写更新设置
+
+use crate::updater::{UpdateInfo, UpdateSettings, UpdateStatus};
+use tauri::{AppHandle, Manager};
+
+/// 检查更新，没有新版本时返回空
+#[tauri::command]
+#[specta::specta]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    crate::log_async_command!("check_for_updates", async {
+        crate::updater::check(&app).await
+    })
+}
+
+/// 下载最近一次检查到的更新（进度通过 `update-download-progress` 事件发出），返回是否会在退出时自动安装
+#[tauri::command]
+#[specta::specta]
+pub async fn download_update(app: AppHandle) -> Result<bool, String> {
+    crate::log_async_command!("download_update", async {
+        crate::updater::download(&app).await
+    })
+}
+
+/// 立即安装已下载的更新并重启应用（账户操作进行中时等待其结束）
+#[tauri::command]
+#[specta::specta]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("install_update", async {
+        crate::updater::install_now(&app).await
+    })
+}
+
+/// 获取更新状态（已检查到 / 已下载的版本，以及是否会在退出时安装）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_update_status() -> Result<UpdateStatus, String> {
+    Ok(crate::updater::status())
+}
+
+/// 推迟安装已下载的更新，等应用下次退出时再安装
+#[tauri::command]
+#[specta::specta]
+pub async fn defer_update_install(app: AppHandle) -> Result<UpdateStatus, String> {
+    crate::log_async_command!("defer_update_install", async {
+        crate::updater::set_install_on_exit(&app, true)
+    })
+}
+
+/// 取消退出时安装（已下载的更新保留，仍可手动安装）
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_deferred_update_install(app: AppHandle) -> Result<UpdateStatus, String> {
+    crate::log_async_command!("cancel_deferred_update_install", async {
+        crate::updater::set_install_on_exit(&app, false)
+    })
+}
+
+/// 获取更新设置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_update_settings(app: AppHandle) -> Result<UpdateSettings, String> {
+    Ok(crate::updater::settings(&app))
+}
+
+/// 保存更新设置（切换通道后需重新检查更新）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_update_settings(
+    app: AppHandle,
+    window: tauri::Window,
+    update: UpdateSettings,
+) -> Result<UpdateSettings, String> {
+    crate::log_async_command!(
+        "save_update_settings",
+        window = window.label(),
+        args = (update),
+        async {
+            let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+            settings_manager.update_settings(|settings| {
+                settings.update = update;
+            })?;
+
+            Ok(update)
+        }
+    )
+}
@@ -3,3 +3,40 @@
 //!
 //! 注意：窗口状态管理已迁移到 window_event_handler.rs 中的自动处理
 //! 此模块保留以备将来需要手动窗口状态管理时使用
+
+use crate::window::state_manager::{set_current_zoom, MAX_ZOOM, MIN_ZOOM};
+use tauri::Manager;
+
+/// 设置主窗口网页缩放比例，并立即保存到窗口状态
+#[tauri::command]
+#[specta::specta]
+pub async fn set_window_zoom(app: tauri::AppHandle, zoom: f64) -> Result<f64, String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("无法获取主窗口".to_string())?;
+
+    let zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    window
+        .set_zoom(zoom)
+        .map_err(|e| format!("设置缩放比例失败: {}", e))?;
+    set_current_zoom(zoom);
+
+    crate::window::event_handler::save_current_window_state(&window).await;
+    Ok(zoom)
+}
+
+/// 获取主窗口当前网页缩放比例
+#[tauri::command]
+#[specta::specta]
+pub async fn get_window_zoom() -> Result<f64, String> {
+    Ok(crate::window::state_manager::current_zoom())
+}
+
+/// 用户确认后退出应用（由退出确认对话框调用）
+#[tauri::command]
+#[specta::specta]
+pub async fn confirm_exit(app: tauri::AppHandle) -> Result<(), String> {
+    tracing::info!(target: "window::exit_guard", "用户确认退出");
+    app.exit(0);
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! 工作区存储命令
+//! 查看 Antigravity 各工作区状态目录的大小与最后使用时间，清理长期未使用的目录
+
+use crate::antigravity::workspace_storage::{self, WorkspaceCleanupResult, WorkspaceStorageEntry};
+use crate::error::{AgentError, AgentResult};
+use tauri::AppHandle;
+
+/// 列出工作区状态目录（最近使用的在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_workspace_storage() -> Result<Vec<WorkspaceStorageEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::log_async_command!("list_workspace_storage", async {
+        tokio::task::spawn_blocking(workspace_storage::list)
+            .await
+            .map_err(|e| format!("读取工作区存储任务失败: {}", e))?
+    })
+}
+
+/// 删除超过指定天数未使用的工作区状态目录（安全模式下拒绝；执行前弹出确认对话框）
+#[tauri::command]
+#[specta::specta]
+pub async fn clean_workspace_storage(
+    app: AppHandle,
+    older_than_days: u32,
+) -> AgentResult<WorkspaceCleanupResult> {
+    crate::app_lock::ensure_unlocked()?;
+    crate::safe_mode::ensure_allowed("clean_workspace_storage")?;
+
+    let message = format!(
+        "即将删除超过 {} 天未使用的 Antigravity 工作区状态（打开的编辑器、面板布局等），工作区文件本身不受影响。是否继续？",
+        older_than_days
+    );
+    if !crate::local_api::confirm(&app, message).await {
+        tracing::info!(target: "workspace_storage::cleanup", "用户取消了工作区存储清理");
+        return Err(AgentError::Internal(crate::i18n::t(
+            "common.cancelled",
+            &serde_json::Value::Null,
+        )));
+    }
+
+    let started = std::time::Instant::now();
+    let result =
+        crate::log_async_command!("clean_workspace_storage", args = (older_than_days), async {
+            tokio::task::spawn_blocking(move || workspace_storage::delete_stale(older_than_days))
+                .await
+                .map_err(|e| AgentError::Internal(format!("清理工作区存储任务失败: {}", e)))?
+                .map_err(AgentError::from)
+        });
+    let detail = result.as_ref().ok().map(|cleanup| {
+        format!(
+            "删除 {} 个目录，释放 {} 字节",
+            cleanup.deleted, cleanup.freed_bytes
+        )
+    });
+    crate::audit::record(
+        crate::audit::AuditOperation::WorkspaceCleanup,
+        None,
+        started,
+        &result,
+        detail.as_deref(),
+    );
+    result
+}
@@ -0,0 +1,193 @@
+//! 配置文件完整性检查
+//! Agent 每次写入受保护的配置文件后记录 SHA-256 校验和，并在 `config-history/` 中保留一份
+//! 最近的正常副本。启动时逐个比对：内容被外部修改时发出警告；文件损坏（无法解析）或丢失时
+//! 用最近的正常副本恢复。检查结果通过 `config-integrity-warning` 事件与命令提供给前端
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// 校验和记录文件名（位于 `config-history/` 中）
+const CHECKSUMS_FILE: &str = "checksums.json";
+
+/// 写入锁，避免并发记录时校验和文件内容交错
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 启动检查发现的问题（前端在事件发出后才开始监听时可通过命令获取）
+static WARNINGS: Mutex<Vec<IntegrityWarning>> = Mutex::new(Vec::new());
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    /// 内容被 Agent 之外的程序修改（仍可解析）
+    Modified,
+    /// 内容无法解析
+    Corrupted,
+    /// 文件丢失
+    Missing,
+}
+
+/// 完整性警告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct IntegrityWarning {
+    /// 文件名
+    pub file: String,
+    pub issue: IntegrityIssue,
+    /// 是否已从最近的正常副本恢复
+    pub restored: bool,
+}
+
+/// 受保护的配置文件
+fn tracked_files() -> Vec<PathBuf> {
+    vec![
+        crate::directories::get_antigravity_path_file(),
+        crate::directories::get_app_settings_file(),
+        crate::directories::get_app_state_file(),
+    ]
+}
+
+fn checksum(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn load_checksums() -> BTreeMap<String, String> {
+    fs::read_to_string(crate::directories::get_config_history_directory().join(CHECKSUMS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_checksums(checksums: &BTreeMap<String, String>) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(checksums).map_err(|e| format!("序列化校验和失败: {}", e))?;
+    fs::write(
+        crate::directories::get_config_history_directory().join(CHECKSUMS_FILE),
+        json,
+    )
+    .map_err(|e| format!("写入校验和失败: {}", e))
+}
+
+/// Agent 写入配置文件后调用：记录校验和并保存正常副本
+pub fn record(path: &Path) {
+    if let Err(e) = try_record(path) {
+        tracing::warn!(target: "app::config_integrity", file = %path.display(), error = %e, "记录配置文件校验和失败");
+    }
+}
+
+fn try_record(path: &Path) -> Result<(), String> {
+    let content = fs::read(path).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    let history_dir = crate::directories::get_config_history_directory();
+    fs::create_dir_all(&history_dir).map_err(|e| format!("创建历史目录失败: {}", e))?;
+
+    let _guard = WRITE_LOCK
+        .lock()
+        .map_err(|e| format!("获取校验和锁失败: {}", e))?;
+    let name = file_name(path);
+    fs::write(history_dir.join(&name), &content).map_err(|e| format!("保存正常副本失败: {}", e))?;
+
+    let mut checksums = load_checksums();
+    checksums.insert(name, checksum(&content));
+    save_checksums(&checksums)
+}
+
+/// 用最近的正常副本恢复文件
+fn restore(path: &Path) -> Result<(), String> {
+    let copy = crate::directories::get_config_history_directory().join(file_name(path));
+    if !copy.exists() {
+        return Err("没有可用的正常副本".to_string());
+    }
+    fs::copy(&copy, path)
+        .map(|_| ())
+        .map_err(|e| format!("恢复配置文件失败: {}", e))
+}
+
+/// 检查单个文件，返回发现的问题
+fn check(path: &Path, checksums: &BTreeMap<String, String>) -> Option<IntegrityWarning> {
+    let name = file_name(path);
+    let expected = checksums.get(&name)?;
+
+    let issue = match fs::read(path) {
+        Err(_) => IntegrityIssue::Missing,
+        Ok(content) if checksum(&content) == *expected => return None,
+        Ok(content) if serde_json::from_slice::<serde_json::Value>(&content).is_err() => {
+            IntegrityIssue::Corrupted
+        }
+        Ok(_) => IntegrityIssue::Modified,
+    };
+
+    // 外部修改但仍可解析时保留用户的修改，只有损坏或丢失时才回退
+    let restored = match issue {
+        IntegrityIssue::Modified => {
+            record(path);
+            false
+        }
+        IntegrityIssue::Corrupted | IntegrityIssue::Missing => match restore(path) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!(target: "app::config_integrity", file = %name, error = %e, "无法恢复配置文件");
+                false
+            }
+        },
+    };
+
+    tracing::warn!(target: "app::config_integrity", file = %name, issue = ?issue, restored = restored, "⚠️ 配置文件完整性检查未通过");
+    Some(IntegrityWarning {
+        file: name,
+        issue,
+        restored,
+    })
+}
+
+/// 启动时检查所有受保护的配置文件（需在加载设置之前调用）
+pub fn verify_all() -> Vec<IntegrityWarning> {
+    let checksums = load_checksums();
+    let warnings: Vec<_> = tracked_files()
+        .iter()
+        .filter_map(|path| {
+            // 首次运行或旧版本升级时还没有记录，直接以当前内容为准
+            if !checksums.contains_key(&file_name(path)) {
+                if path.exists() {
+                    record(path);
+                }
+                return None;
+            }
+            check(path, &checksums)
+        })
+        .collect();
+
+    if let Ok(mut stored) = WARNINGS.lock() {
+        *stored = warnings.clone();
+    }
+    warnings
+}
+
+/// 发出启动检查发现的问题
+pub fn emit_warnings(app_handle: &AppHandle) {
+    for warning in warnings() {
+        if let Err(e) = crate::events::emit(app_handle, &warning) {
+            tracing::error!(target: "app::config_integrity", error = %e, "发射完整性警告事件失败");
+        }
+    }
+}
+
+/// 启动检查发现的问题
+pub fn warnings() -> Vec<IntegrityWarning> {
+    WARNINGS
+        .lock()
+        .map(|warnings| warnings.clone())
+        .unwrap_or_default()
+}
@@ -1,7 +1,31 @@
+//! 配置管理器
+//! 统一解析应用配置目录。规范目录为用户主目录下的 `.antigravity-agent`（配置、日志、备份都在其中）；
+//! 旧版本曾把配置写到系统配置目录（Windows 上为 APPDATA）下的 `.antigravity-agent`，
+//! 把日志写到不带点的 `antigravity-agent`，启动时一次性把这些旧位置的文件移入规范目录
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
 use crate::directories;
-/// 配置管理器
-/// 统一管理所有配置目录和文件路径
-use std::path::PathBuf;
+
+/// 规范配置目录名（位于用户主目录下）
+pub const CONFIG_DIR_NAME: &str = ".antigravity-agent";
+
+/// 旧版本使用过的目录名（位于系统配置目录或数据目录下）
+const LEGACY_DIR_NAMES: [&str; 2] = [CONFIG_DIR_NAME, "antigravity-agent"];
+
+/// 由专门的迁移逻辑处理、不参与整体迁移的子目录（账户备份迁移时只保留登录状态字段）
+const SKIPPED_ENTRIES: [&str; 1] = ["antigravity-accounts"];
+
+/// 旧目录迁移结果
+#[derive(Debug, Default)]
+pub struct LegacyMigrationReport {
+    /// 已移入规范目录的文件数
+    pub moved: usize,
+    /// 规范目录中已有同名文件、保留在旧位置的文件数
+    pub conflicts: usize,
+}
 
 /// 配置管理器结构
 pub struct ConfigManager;
@@ -13,8 +37,107 @@ impl ConfigManager {
         Ok(Self)
     }
 
+    /// 规范配置目录路径（不创建目录）
+    pub fn config_dir() -> PathBuf {
+        #[cfg(feature = "simulation")]
+        if crate::simulation::is_enabled() {
+            return crate::simulation::config_dir();
+        }
+
+        dirs::home_dir()
+            .expect("Home directory not found")
+            .join(CONFIG_DIR_NAME)
+    }
+
+    /// 仍然存在的旧配置目录（系统配置目录、数据目录下的带点与不带点目录）
+    pub fn legacy_dirs() -> Vec<PathBuf> {
+        // 模拟模式的配置目录与真实配置隔离，不迁移
+        #[cfg(feature = "simulation")]
+        if crate::simulation::is_enabled() {
+            return Vec::new();
+        }
+
+        let canonical = Self::config_dir();
+        let mut legacy: Vec<PathBuf> = Vec::new();
+        for base in [dirs::config_dir(), dirs::data_dir(), dirs::data_local_dir()]
+            .into_iter()
+            .flatten()
+        {
+            for name in LEGACY_DIR_NAMES {
+                let dir = base.join(name);
+                if dir != canonical && dir.is_dir() && !legacy.contains(&dir) {
+                    legacy.push(dir);
+                }
+            }
+        }
+        legacy
+    }
+
     /// 获取窗口状态文件路径
     pub fn window_state_file(&self) -> PathBuf {
         directories::get_window_state_file()
     }
+
+    /// 把旧配置目录中的文件移入规范目录（只执行一次，完成后写入标记文件）
+    ///
+    /// 规范目录中已有的同名文件不会被覆盖，对应的旧文件保留在原处
+    pub fn migrate_legacy_dirs() -> io::Result<LegacyMigrationReport> {
+        let mut report = LegacyMigrationReport::default();
+        let marker = directories::get_legacy_migration_marker();
+        if marker.exists() {
+            return Ok(report);
+        }
+
+        let canonical = directories::get_config_directory();
+        for legacy in Self::legacy_dirs() {
+            tracing::info!(target: "app::startup", legacy = %legacy.display(), "📦 迁移旧配置目录");
+            move_tree(&legacy, &canonical, true, &mut report)?;
+        }
+
+        fs::write(&marker, chrono::Local::now().to_rfc3339())?;
+        Ok(report)
+    }
+}
+
+/// 递归移动目录内容，移动后删除空目录
+fn move_tree(
+    from: &Path,
+    to: &Path,
+    top_level: bool,
+    report: &mut LegacyMigrationReport,
+) -> io::Result<()> {
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if top_level && SKIPPED_ENTRIES.iter().any(|skipped| name == *skipped) {
+            continue;
+        }
+
+        let source = entry.path();
+        let target = to.join(&name);
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)?;
+            move_tree(&source, &target, false, report)?;
+        } else if target.exists() {
+            tracing::warn!(target: "app::startup", file = %source.display(), "规范目录中已有同名文件，保留旧文件");
+            report.conflicts += 1;
+        } else {
+            move_file(&source, &target)?;
+            report.moved += 1;
+        }
+    }
+
+    // 目录非空（有冲突或跳过的文件）时删除失败，保留即可
+    let _ = fs::remove_dir(from);
+    Ok(())
+}
+
+/// 移动单个文件；跨文件系统时改为复制后删除
+fn move_file(source: &Path, target: &Path) -> io::Result<()> {
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    fs::copy(source, target)?;
+    fs::remove_file(source)
 }
@@ -0,0 +1,164 @@
+//! 崩溃报告模块
+//! 安装 panic 钩子，在程序 panic 时把回溯、版本、系统信息和最近的日志写入
+//! 日志目录下的 `crashes/` 子目录，下次启动后前端可通过命令查看或导出
+
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+/// 报告中附带的最近日志行数
+const RECENT_LOG_LINES: usize = 200;
+
+/// 最多保留的崩溃报告数量
+const MAX_REPORTS: usize = 20;
+
+/// 崩溃报告
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CrashReport {
+    /// 报告 ID（即文件名，不含扩展名）
+    pub id: String,
+    /// 崩溃时间（RFC 3339）
+    pub created_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// 发生 panic 的线程名
+    pub thread: String,
+    /// panic 信息
+    pub message: String,
+    /// 源码位置（文件:行:列）
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// 崩溃前最近的日志行（已脱敏）
+    pub recent_logs: Vec<String>,
+}
+
+/// 崩溃报告目录
+pub fn crash_directory() -> PathBuf {
+    crate::directories::get_log_directory().join("crashes")
+}
+
+/// 安装 panic 钩子（保留默认钩子的控制台输出）
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => {
+                tracing::error!(target: "app::crash", path = %path.display(), "💥 程序崩溃，已写入崩溃报告")
+            }
+            Err(e) => {
+                tracing::error!(target: "app::crash", error = %e, "💥 程序崩溃，写入崩溃报告失败")
+            }
+        }
+        crate::error_report::capture_panic(info, &panic_message(info));
+        default_hook(info);
+    }));
+}
+
+/// 提取 panic 信息
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    info.payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "未知 panic".to_string())
+}
+
+/// 生成并写入崩溃报告
+fn write_report(info: &PanicHookInfo<'_>) -> Result<PathBuf, String> {
+    let now = chrono::Local::now();
+    let id = format!("crash-{}", now.format("%Y%m%d-%H%M%S-%3f"));
+
+    let message = panic_message(info);
+
+    let report = CrashReport {
+        id: id.clone(),
+        created_at: now.to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string(),
+        message: crate::utils::log_sanitizer::sanitize_log_message(&message),
+        location: info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: Backtrace::force_capture().to_string(),
+        recent_logs: recent_log_lines(RECENT_LOG_LINES),
+    };
+
+    let dir = crash_directory();
+    fs::create_dir_all(&dir).map_err(|e| format!("创建崩溃报告目录失败: {}", e))?;
+
+    let path = dir.join(format!("{}.json", id));
+    let content =
+        serde_json::to_string_pretty(&report).map_err(|e| format!("序列化崩溃报告失败: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("写入崩溃报告失败: {}", e))?;
+
+    prune_reports();
+    Ok(path)
+}
+
+/// 读取最新日志文件的最后 `count` 行
+fn recent_log_lines(count: usize) -> Vec<String> {
+    let log_dir = crate::directories::get_log_directory();
+    let Some(latest) = crate::utils::log_query::list_log_files(&log_dir)
+        .into_iter()
+        .next()
+    else {
+        return Vec::new();
+    };
+
+    let Ok(content) = fs::read_to_string(latest) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// 列出崩溃报告文件，按文件名倒序（最新在前）
+fn list_report_files() -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(crash_directory()) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// 只保留最近的 `MAX_REPORTS` 份报告
+fn prune_reports() {
+    for path in list_report_files().into_iter().skip(MAX_REPORTS) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// 读取所有崩溃报告（最新在前），无法解析的文件会被跳过
+pub fn load_reports() -> Vec<CrashReport> {
+    list_report_files()
+        .into_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            match serde_json::from_str::<CrashReport>(&content) {
+                Ok(report) => Some(report),
+                Err(e) => {
+                    tracing::warn!(target: "app::crash", error = %e, "解析崩溃报告失败: {}", path.display());
+                    None
+                }
+            }
+        })
+        .collect()
+}
@@ -1,9 +1,9 @@
 //! 数据库监控模块 - 简化版本：newData, oldData, diff
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use tokio::sync::Mutex;
 use tokio::time::{interval, Duration};
 use tracing::{error, info, warn};
@@ -16,6 +16,19 @@ pub struct DataDiff {
     pub summary: String,
 }
 
+/// 当前登录账户变化事件（`account-changed`）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountChangedPayload {
+    pub email: Option<String>,
+    pub plan: Option<String>,
+}
+
+/// Antigravity 进程状态变化事件（`antigravity-process-changed`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessChangedPayload {
+    pub running: bool,
+}
+
 // 数据库监控器
 pub struct DatabaseMonitor {
     app_handle: AppHandle,
@@ -46,6 +59,9 @@ impl DatabaseMonitor {
 
         tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(3)); // 3秒间隔，更敏感
+            let mut last_account: Option<AccountChangedPayload> = None;
+            let mut last_running: Option<bool> = None;
+            let mut notified_expiry: Option<i64> = None;
 
             loop {
                 interval.tick().await;
@@ -58,9 +74,100 @@ impl DatabaseMonitor {
                 }
                 drop(running);
 
+                // 检测 Antigravity 进程状态变化
+                let process_running = crate::platform::is_antigravity_running();
+                if last_running != Some(process_running) {
+                    // 进程在没有账户操作进行时退出，视为意外退出
+                    if last_running == Some(true) && !crate::pipeline::is_any_running() {
+                        warn!("💥 Antigravity 进程意外退出");
+                        crate::notifications::notify(
+                            &app_handle,
+                            crate::notifications::NotificationKind::AntigravityCrashed,
+                            "检测到 Antigravity 进程已退出，如非手动关闭请检查日志",
+                        );
+                    }
+                    if last_running == Some(true) {
+                        crate::hooks::dispatch(
+                            &app_handle,
+                            crate::hooks::HookEvent::AntigravityStopped,
+                            serde_json::json!({}),
+                        );
+                        crate::plugins::dispatch(
+                            &app_handle,
+                            crate::hooks::HookEvent::AntigravityStopped,
+                            &serde_json::json!({}),
+                        );
+                    }
+                    last_running = Some(process_running);
+                    let payload = ProcessChangedPayload {
+                        running: process_running,
+                    };
+                    if let Err(e) = crate::events::emit(&app_handle, &payload) {
+                        error!("❌ 推送进程状态事件失败: {}", e);
+                    }
+                }
+
                 // 获取当前完整数据
                 match Self::get_complete_data().await {
-                    Ok(new_data) => {
+                    Ok((new_data, rows)) => {
+                        crate::state_journal::record(&rows);
+                        let decoded = Self::decode_agent_state(&new_data);
+
+                        // 检测当前登录账户变化
+                        let account = Self::extract_account(decoded.as_ref());
+                        if last_account.as_ref() != Some(&account) {
+                            info!("👤 当前登录账户已变化");
+                            // 之前已登录、现在读不到账户，视为登出
+                            if let Some(email) = last_account
+                                .as_ref()
+                                .and_then(|previous| previous.email.clone())
+                                .filter(|_| account.email.is_none())
+                            {
+                                crate::webhooks::dispatch(
+                                    &app_handle,
+                                    crate::webhooks::WebhookEvent::LoggedOut,
+                                    serde_json::json!({ "account": email }),
+                                );
+                            }
+                            if let Err(e) = crate::events::emit(&app_handle, &account) {
+                                error!("❌ 推送账户变化事件失败: {}", e);
+                            }
+                            // 启动后的首次读取只是建立基线，不触发钩子
+                            if last_account.is_some() {
+                                let data = serde_json::json!(account);
+                                crate::plugins::dispatch(
+                                    &app_handle,
+                                    crate::hooks::HookEvent::AccountChanged,
+                                    &data,
+                                );
+                                crate::hooks::dispatch(
+                                    &app_handle,
+                                    crate::hooks::HookEvent::AccountChanged,
+                                    data,
+                                );
+                            }
+                            last_account = Some(account);
+                        }
+
+                        // 登录令牌即将过期时提醒（每个过期时间只提醒一次）
+                        if let Some(expiry) = Self::extract_token_expiry(decoded.as_ref()) {
+                            let remaining = expiry - chrono::Utc::now().timestamp();
+                            if remaining > 0
+                                && remaining <= crate::notifications::TOKEN_EXPIRY_WARNING_SECS
+                                && notified_expiry != Some(expiry)
+                            {
+                                notified_expiry = Some(expiry);
+                                crate::notifications::notify(
+                                    &app_handle,
+                                    crate::notifications::NotificationKind::TokenExpiring,
+                                    format!(
+                                        "当前账户的登录令牌将在 {} 分钟内过期，请在 Antigravity 中重新登录",
+                                        remaining.div_ceil(60)
+                                    ),
+                                );
+                            }
+                        }
+
                         let mut last = last_data.lock().await;
 
                         // 检查是否有数据变化
@@ -72,14 +179,14 @@ impl DatabaseMonitor {
                                 info!("📢 检测到数据库变化: {}", diff.summary);
 
                                 // 构建简化的事件数据：newData, oldData, diff
-                                let event_data = serde_json::json!({
-                                    "newData": new_data,
-                                    "oldData": old_data,
-                                    "diff": diff
-                                });
+                                let event_data = crate::events::DatabaseChangedPayload {
+                                    new_data: new_data.clone(),
+                                    old_data: old_data.clone(),
+                                    diff,
+                                };
 
                                 // 推送事件到前端
-                                if let Err(e) = app_handle.emit("database-changed", &event_data) {
+                                if let Err(e) = crate::events::emit(&app_handle, &event_data) {
                                     error!("❌ 推送数据库变化事件失败: {}", e);
                                 } else {
                                     info!("✅ 数据库变化事件推送成功");
@@ -105,52 +212,78 @@ impl DatabaseMonitor {
         *self.is_running.lock().await = false;
     }
 
-    /// 获取完整数据库数据
-    async fn get_complete_data() -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    /// 获取完整数据库数据（同时返回 ItemTable 原始行，供登录状态变更日志使用）
+    async fn get_complete_data(
+    ) -> Result<(Value, Vec<(String, String)>), Box<dyn std::error::Error + Send + Sync>> {
         // 检测数据库路径
-        let db_path = if cfg!(windows) {
-            dirs::home_dir()
-                .unwrap_or_default()
-                .join("AppData")
-                .join("Roaming")
-                .join("Antigravity")
-                .join("User")
-                .join("globalStorage")
-                .join("state.vscdb")
-        } else {
-            dirs::config_dir()
-                .unwrap_or_default()
-                .join("Antigravity")
-                .join("User")
-                .join("globalStorage")
-                .join("state.vscdb")
-        };
+        let db_path = crate::platform::get_antigravity_db_path().unwrap_or_default();
 
         let mut complete_data = serde_json::Map::new();
+        let mut rows: Vec<(String, String)> = Vec::new();
 
         if db_path.exists() {
-            let conn = rusqlite::Connection::open(&db_path)?;
-
-            // 查询所有数据（完整的ItemTable）
-            let mut stmt = conn.prepare("SELECT key, value FROM ItemTable ORDER BY key")?;
-
-            let rows: Vec<(String, String)> = stmt
-                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
-                .collect::<Result<Vec<_>, _>>()?;
+            // 查询所有数据（完整的ItemTable），复用只读连接
+            rows = crate::slow_operation::measure("db::read_item_table", || {
+                crate::utils::db_pool::with_read_connection(&db_path, |conn| {
+                    let mut stmt = conn.prepare("SELECT key, value FROM ItemTable ORDER BY key")?;
+                    let rows = stmt
+                        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                        .collect::<rusqlite::Result<Vec<_>>>();
+                    rows
+                })
+            })?;
 
             // 构建完整数据对象
-            for (key, value) in rows {
+            for (key, value) in &rows {
                 // 尝试解析为JSON，如果失败则保持原始字符串
-                let json_value: Value = match serde_json::from_str(&value) {
+                let json_value: Value = match serde_json::from_str(value) {
                     Ok(parsed) => parsed,
                     Err(_) => Value::String(value.clone()),
                 };
 
-                complete_data.insert(key, json_value);
+                complete_data.insert(key.clone(), json_value);
             }
         }
 
-        Ok(Value::Object(complete_data))
+        Ok((Value::Object(complete_data), rows))
+    }
+
+    /// 从完整数据中解码 jetski 状态
+    fn decode_agent_state(data: &Value) -> Option<Value> {
+        data.get(crate::constants::database::AGENT_STATE)
+            .and_then(|v| v.as_str())
+            .and_then(|s| crate::antigravity::account::decode_jetski_state_proto(s).ok())
+    }
+
+    /// 从解码后的 jetski 状态中获取登录令牌过期时间（Unix 秒）
+    fn extract_token_expiry(decoded: Option<&Value>) -> Option<i64> {
+        decoded?
+            .get("auth")?
+            .get("meta")?
+            .get("expiry_timestamp")?
+            .as_i64()
+            .filter(|ts| *ts > 0)
+    }
+
+    /// 从解码后的 jetski 状态中解析当前登录账户（邮箱与订阅计划）
+    fn extract_account(decoded: Option<&Value>) -> AccountChangedPayload {
+        let Some(decoded) = decoded else {
+            return AccountChangedPayload::default();
+        };
+
+        let context = decoded.get("context");
+        let email = context
+            .and_then(|c| c.get("email"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        let plan = context
+            .and_then(|c| c.get("plan_name"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        AccountChangedPayload { email, plan }
     }
 
     /// 分析两个数据之间的差异
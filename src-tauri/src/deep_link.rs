@@ -0,0 +1,182 @@
+//! 深度链接模块
+//! 处理 `antigravity-agent://` 协议链接，例如：
+//! - `antigravity-agent://switch?account=work@example.com` 切换到指定账户
+//! - `antigravity-agent://backup` 备份当前账户
+//!
+//! 链接来自浏览器或自动化脚本，执行前总是弹出确认对话框
+
+use tauri::{App, AppHandle, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// 注册的 URL 协议名
+pub const SCHEME: &str = "antigravity-agent";
+
+/// 深度链接解析出的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkAction {
+    /// 切换到指定账户
+    Switch { account: String },
+    /// 备份当前账户
+    Backup,
+}
+
+impl DeepLinkAction {
+    /// 确认对话框中的描述
+    fn describe(&self) -> String {
+        match self {
+            DeepLinkAction::Switch { account } => format!(
+                "外部链接请求切换到账户：\n{}\n\n切换会关闭正在运行的 Antigravity，是否继续？",
+                crate::system_tray::mask_email(account)
+            ),
+            DeepLinkAction::Backup => {
+                "外部链接请求备份当前 Antigravity 账户，是否继续？".to_string()
+            }
+        }
+    }
+}
+
+/// 解析深度链接
+pub fn parse(url: &tauri::Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("不支持的协议: {}", url.scheme()));
+    }
+
+    // `antigravity-agent://switch` 的操作名在 host 中，`antigravity-agent:switch` 则在 path 中
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/')
+        .to_ascii_lowercase();
+
+    match action.as_str() {
+        "switch" => {
+            let account = url
+                .query_pairs()
+                .find(|(key, _)| key == "account")
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| "切换链接缺少 account 参数".to_string())?;
+            Ok(DeepLinkAction::Switch { account })
+        }
+        "backup" => Ok(DeepLinkAction::Backup),
+        other => Err(format!("未知的深度链接操作: {}", other)),
+    }
+}
+
+/// 初始化深度链接处理
+pub fn init(app: &mut App) -> Result<(), String> {
+    // Linux 与 Windows 开发模式下需要在运行时注册协议，安装包会在安装时注册
+    #[cfg(any(target_os = "linux", all(debug_assertions, target_os = "windows")))]
+    if let Err(e) = app.deep_link().register_all() {
+        tracing::warn!(target: "deep_link::init", error = %e, "运行时注册 URL 协议失败");
+    }
+
+    let app_handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_url(&app_handle, &url);
+        }
+    });
+
+    // 通过链接冷启动时，链接不会触发 on_open_url
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => {
+            for url in urls {
+                handle_url(app.handle(), &url);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::warn!(target: "deep_link::init", error = %e, "读取启动链接失败");
+        }
+    }
+
+    tracing::info!(target: "deep_link::init", scheme = SCHEME, "深度链接处理已注册");
+    Ok(())
+}
+
+/// 处理单个深度链接：解析、确认、执行
+pub fn handle_url(app_handle: &AppHandle, url: &tauri::Url) {
+    tracing::info!(target: "deep_link::open", action = url.host_str().unwrap_or(""), "🔗 收到深度链接");
+
+    let action = match parse(url) {
+        Ok(action) => action,
+        Err(e) => {
+            tracing::warn!(target: "deep_link::open", error = %e, "深度链接无效");
+            app_handle
+                .dialog()
+                .message(e)
+                .title("Antigravity Agent")
+                .kind(MessageDialogKind::Error)
+                .show(|_| {});
+            return;
+        }
+    };
+
+    focus_main_window(app_handle);
+
+    let app_for_confirm = app_handle.clone();
+    app_handle
+        .dialog()
+        .message(action.describe())
+        .title("Antigravity Agent")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "继续".to_string(),
+            "取消".to_string(),
+        ))
+        .show(move |confirmed| {
+            if !confirmed {
+                tracing::info!(target: "deep_link::open", "用户取消了深度链接操作");
+                return;
+            }
+
+            tauri::async_runtime::spawn(async move {
+                execute(app_for_confirm, action).await;
+            });
+        });
+}
+
+/// 执行已确认的深度链接操作（复用现有命令流水线）
+async fn execute(app_handle: AppHandle, action: DeepLinkAction) {
+    let result = match &action {
+        DeepLinkAction::Switch { account } => {
+            crate::commands::switch_to_antigravity_account(app_handle.clone(), account.clone())
+                .await
+        }
+        DeepLinkAction::Backup => {
+            crate::commands::save_antigravity_current_account(app_handle.clone()).await
+        }
+    };
+
+    match result {
+        Ok(message) => {
+            tracing::info!(target: "deep_link::execute", action = ?action, "✅ 深度链接操作完成");
+            app_handle
+                .dialog()
+                .message(message)
+                .title("Antigravity Agent")
+                .kind(MessageDialogKind::Info)
+                .show(|_| {});
+        }
+        Err(e) => {
+            tracing::error!(target: "deep_link::execute", action = ?action, error = %e, "❌ 深度链接操作失败");
+            app_handle
+                .dialog()
+                .message(e.to_string())
+                .title("Antigravity Agent")
+                .kind(MessageDialogKind::Error)
+                .show(|_| {});
+        }
+    }
+}
+
+/// 将主窗口带到前台，确保确认对话框可见
+fn focus_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
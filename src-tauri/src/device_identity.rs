@@ -0,0 +1,271 @@
+//! 设备身份
+//! 每台设备持有一对持久化的 Ed25519 密钥：私钥保存在系统钥匙串，公钥与创建时间保存在
+//! `device_identity.json`。用于为导出的配置文件签名、加密设置文件，以及局域网传输、远程同步时认证会话。
+//! 其他设备的公钥经用户确认后固定在 `trusted_signers.json` 中，导入配置文件时只接受本机或已信任设备的签名
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// 钥匙串中保存设备私钥的条目
+const KEYCHAIN_SERVICE: &str = "antigravity-agent";
+const KEYCHAIN_USER: &str = "device-identity-key";
+
+/// 设备身份（不含私钥）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct DeviceIdentity {
+    /// 设备 ID（公钥 SHA-256 的前 16 字节，十六进制）
+    pub device_id: String,
+    /// 公钥（Base64）
+    pub public_key: String,
+    /// 设备名称（主机名）
+    pub device_name: String,
+    /// 密钥创建时间（RFC 3339）
+    pub created_at: String,
+}
+
+/// 已信任的签名设备
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TrustedSigner {
+    pub device_id: String,
+    /// 公钥（Base64）
+    pub public_key: String,
+    /// 确认信任时文件中声明的设备名称
+    pub device_name: String,
+    /// 加入信任列表的时间（RFC 3339）
+    pub trusted_at: String,
+}
+
+/// 已加载的私钥（进程内存中）
+static SIGNING_KEY: Mutex<Option<SigningKey>> = Mutex::new(None);
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+        .map_err(|e| format!("打开钥匙串条目失败: {}", e))
+}
+
+/// 由公钥计算设备 ID
+fn device_id(public_key: &VerifyingKey) -> String {
+    Sha256::digest(public_key.as_bytes())[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn device_name() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
+fn identity_for(key: &SigningKey, created_at: String) -> DeviceIdentity {
+    let public_key = key.verifying_key();
+    DeviceIdentity {
+        device_id: device_id(&public_key),
+        public_key: BASE64.encode(public_key.as_bytes()),
+        device_name: device_name(),
+        created_at,
+    }
+}
+
+fn read_keychain_key() -> Result<Option<SigningKey>, String> {
+    let encoded = match keychain_entry()?.get_password() {
+        Ok(encoded) => Zeroizing::new(encoded),
+        Err(keyring::Error::NoEntry) => return Ok(None),
+        Err(e) => return Err(format!("读取钥匙串中的设备密钥失败: {}", e)),
+    };
+    let bytes = Zeroizing::new(
+        BASE64
+            .decode(encoded.as_bytes())
+            .map_err(|e| format!("钥匙串中的设备密钥无效: {}", e))?,
+    );
+    let secret: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| "钥匙串中的设备密钥长度无效".to_string())?;
+    Ok(Some(SigningKey::from_bytes(&secret)))
+}
+
+/// 解析 Base64 公钥
+fn parse_public_key(public_key: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = BASE64
+        .decode(public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "设备公钥格式无效".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| "设备公钥格式无效".to_string())
+}
+
+/// 生成新密钥并保存到钥匙串与元数据文件
+fn generate() -> Result<(SigningKey, DeviceIdentity), String> {
+    let key = SigningKey::generate(&mut aes_gcm::aead::OsRng);
+    let encoded = Zeroizing::new(BASE64.encode(key.to_bytes()));
+    keychain_entry()?
+        .set_password(&encoded)
+        .map_err(|e| format!("保存设备密钥到钥匙串失败: {}", e))?;
+
+    let identity = identity_for(&key, chrono::Local::now().to_rfc3339());
+    let content = serde_json::to_string_pretty(&identity)
+        .map_err(|e| format!("序列化设备身份失败: {}", e))?;
+    fs::write(crate::directories::get_device_identity_file(), content)
+        .map_err(|e| format!("保存设备身份失败: {}", e))?;
+
+    tracing::info!(target: "app::device_identity", device_id = %identity.device_id, "🔑 已生成设备密钥");
+    Ok((key, identity))
+}
+
+fn load_metadata() -> Option<DeviceIdentity> {
+    let content = fs::read_to_string(crate::directories::get_device_identity_file()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 读取设备私钥，不存在时生成
+fn signing_key() -> Result<SigningKey, String> {
+    let mut cached = SIGNING_KEY
+        .lock()
+        .map_err(|e| format!("获取设备密钥锁失败: {}", e))?;
+    if let Some(key) = cached.as_ref() {
+        return Ok(key.clone());
+    }
+
+    let key = match read_keychain_key()? {
+        Some(key) => key,
+        None => generate()?.0,
+    };
+    *cached = Some(key.clone());
+    Ok(key)
+}
+
+/// 获取设备身份，首次调用时生成密钥
+pub fn get() -> Result<DeviceIdentity, String> {
+    let key = signing_key()?;
+    let created_at = load_metadata()
+        .filter(|identity| identity.public_key == BASE64.encode(key.verifying_key().as_bytes()))
+        .map(|identity| identity.created_at)
+        .unwrap_or_default();
+    Ok(identity_for(&key, created_at))
+}
+
+/// 轮换设备密钥：生成新密钥替换旧密钥，之前签名的文件将无法再对应到本设备
+pub fn rotate() -> Result<DeviceIdentity, String> {
+    let mut cached = SIGNING_KEY
+        .lock()
+        .map_err(|e| format!("获取设备密钥锁失败: {}", e))?;
+    let previous = cached
+        .as_ref()
+        .map(|key| device_id(&key.verifying_key()))
+        .unwrap_or_default();
+
+    // 旧公钥加入信任列表，轮换前导出的文件仍可导入
+    let previous_key = match cached.clone() {
+        Some(key) => Some(key),
+        None => read_keychain_key().ok().flatten(),
+    };
+    if let Some(previous_key) = previous_key {
+        let public_key = BASE64.encode(previous_key.verifying_key().as_bytes());
+        if let Err(e) = trust(&public_key, &device_name()) {
+            tracing::warn!(target: "app::device_identity", error = %e, "旧设备公钥加入信任列表失败");
+        }
+    }
+
+    let (key, identity) = generate()?;
+    *cached = Some(key);
+    tracing::info!(target: "app::device_identity", previous = %previous, device_id = %identity.device_id, "🔄 设备密钥已轮换");
+    Ok(identity)
+}
+
+/// 使用设备私钥签名，返回 Base64 签名
+pub fn sign(message: &[u8]) -> Result<String, String> {
+    Ok(BASE64.encode(signing_key()?.sign(message).to_bytes()))
+}
+
+/// 由设备私钥派生指定用途的对称密钥（轮换设备密钥后派生结果随之改变）
+pub fn derive_key(purpose: &str) -> Result<Zeroizing<[u8; 32]>, String> {
+    let secret = Zeroizing::new(signing_key()?.to_bytes());
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_ref())
+        .map_err(|e| format!("派生设备密钥失败: {}", e))?;
+    mac.update(purpose.as_bytes());
+    Ok(Zeroizing::new(mac.finalize().into_bytes().into()))
+}
+
+/// 使用给定公钥校验签名，返回签名设备的 ID
+pub fn verify(public_key: &str, message: &[u8], signature: &str) -> Result<String, String> {
+    let public_key = parse_public_key(public_key)?;
+    let signature: [u8; 64] = BASE64
+        .decode(signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "设备签名格式无效".to_string())?;
+
+    public_key
+        .verify(message, &Signature::from_bytes(&signature))
+        .map_err(|_| "设备签名校验失败".to_string())?;
+    Ok(device_id(&public_key))
+}
+
+fn load_trusted() -> Vec<TrustedSigner> {
+    fs::read_to_string(crate::directories::get_trusted_signers_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_trusted(signers: &[TrustedSigner]) -> Result<(), String> {
+    let content =
+        serde_json::to_string_pretty(signers).map_err(|e| format!("序列化信任列表失败: {}", e))?;
+    let path = crate::directories::get_trusted_signers_file();
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("写入信任列表失败: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("替换信任列表失败: {}", e))
+}
+
+/// 已信任的签名设备（不含本机）
+pub fn trusted_signers() -> Vec<TrustedSigner> {
+    load_trusted()
+}
+
+/// 公钥是否属于本机或已信任的设备
+pub fn is_trusted(public_key: &str) -> bool {
+    let own = signing_key()
+        .map(|key| BASE64.encode(key.verifying_key().as_bytes()) == public_key)
+        .unwrap_or(false);
+    own || load_trusted()
+        .iter()
+        .any(|signer| signer.public_key == public_key)
+}
+
+/// 把设备公钥加入信任列表（已存在时直接返回原记录）
+pub fn trust(public_key: &str, device_name: &str) -> Result<TrustedSigner, String> {
+    let device_id = device_id(&parse_public_key(public_key)?);
+    let mut signers = load_trusted();
+    if let Some(signer) = signers.iter().find(|signer| signer.device_id == device_id) {
+        return Ok(signer.clone());
+    }
+
+    let signer = TrustedSigner {
+        device_id,
+        public_key: public_key.to_string(),
+        device_name: device_name.to_string(),
+        trusted_at: chrono::Local::now().to_rfc3339(),
+    };
+    signers.push(signer.clone());
+    save_trusted(&signers)?;
+    tracing::info!(target: "app::device_identity", device_id = %signer.device_id, device_name = %signer.device_name, "🤝 已信任签名设备");
+    Ok(signer)
+}
+
+/// 从信任列表移除设备，返回是否存在
+pub fn untrust(device_id: &str) -> Result<bool, String> {
+    let mut signers = load_trusted();
+    let before = signers.len();
+    signers.retain(|signer| signer.device_id != device_id);
+    if signers.len() == before {
+        return Ok(false);
+    }
+    save_trusted(&signers)?;
+    tracing::info!(target: "app::device_identity", device_id = %device_id, "已移除受信任的签名设备");
+    Ok(true)
+}
@@ -2,49 +2,25 @@
 /// 统一管理所有配置和数据目录路径
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde_json::{self, Value};
 use tracing::{info, warn};
+use crate::config_manager::ConfigManager;
 
 /// 获取应用主配置目录
-/// 所有配置、日志、数据都统一存放在用户主目录的 .antigravity-agent 下
-#[cfg(windows)]
+/// 所有配置、日志、数据都统一存放在用户主目录的 .antigravity-agent 下（路径由 ConfigManager 解析）
 pub fn get_config_directory() -> PathBuf {
-    let config_dir = dirs::home_dir()
-        .expect("Home directory not found")
-        .join(".antigravity-agent");
+    let config_dir = ConfigManager::config_dir();
 
     // 确保目录存在
     if let Err(e) = fs::create_dir_all(&config_dir) {
-        eprintln!("警告：无法创建配置目录 {}: {}", config_dir.display(), e);
-    }
-
-    config_dir
-}
-
-/// 获取应用主配置目录
-#[cfg(not(windows))]
-pub fn get_config_directory() -> PathBuf {
-    let config_dir = dirs::home_dir()
-        .expect("Home directory not found")
-        .join(".antigravity-agent");
-
-    // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&config_dir) {
-        eprintln!("警告：无法创建配置目录 {}: {}", config_dir.display(), e);
+        warn!(target: "app::directories", "无法创建配置目录 {}: {}", config_dir.display(), e);
     }
 
     config_dir
 }
 
 /// 获取日志目录路径
-#[cfg(windows)]
-pub fn get_log_directory() -> PathBuf {
-    get_config_directory().join("logs")
-}
-
-/// 获取日志目录路径
-#[cfg(not(windows))]
 pub fn get_log_directory() -> PathBuf {
     get_config_directory().join("logs")
 }
@@ -55,7 +31,7 @@ pub fn get_accounts_directory() -> PathBuf {
 
     // 确保目录存在
     if let Err(e) = fs::create_dir_all(&accounts_dir) {
-        eprintln!("警告：无法创建账户目录 {}: {}", accounts_dir.display(), e);
+        warn!(target: "app::directories", "无法创建账户目录 {}: {}", accounts_dir.display(), e);
     }
 
     accounts_dir
@@ -76,8 +52,73 @@ pub fn get_antigravity_path_file() -> PathBuf {
     get_config_directory().join("antigravity_path.json")
 }
 
+/// 获取应用状态文件路径（配置档案与当前账户）
+pub fn get_app_state_file() -> PathBuf {
+    get_config_directory().join("app_state.json")
+}
+
+/// 获取操作审计日志文件路径
+pub fn get_audit_log_file() -> PathBuf {
+    get_config_directory().join("audit.log")
+}
+
+/// 获取备份完整性清单文件路径（各备份的 SHA-256 校验和与大小）
+pub fn get_backup_manifest_file() -> PathBuf {
+    get_config_directory().join("backup_manifest.json")
+}
+
+/// 获取备份元数据索引文件路径（各备份的邮箱、备份时间、大小与键摘要）
+pub fn get_backup_index_file() -> PathBuf {
+    get_config_directory().join("backup_index.json")
+}
+
+/// 获取登录状态变更日志文件路径
+pub fn get_state_journal_file() -> PathBuf {
+    get_config_directory().join("state_journal.jsonl")
+}
+
+/// 获取备份加密元数据文件路径（存在即表示备份目录已加密）
+pub fn get_backup_vault_file() -> PathBuf {
+    get_config_directory().join("backup_vault.json")
+}
+
+/// 获取设备身份文件路径（公钥与创建时间，私钥保存在系统钥匙串）
+pub fn get_device_identity_file() -> PathBuf {
+    get_config_directory().join("device_identity.json")
+}
+
+/// 获取受信任签名设备列表文件路径（导入配置文件时据此确认来源）
+pub fn get_trusted_signers_file() -> PathBuf {
+    get_config_directory().join("trusted_signers.json")
+}
+
+/// 获取隐私模式盐值文件路径
+pub fn get_privacy_salt_file() -> PathBuf {
+    get_config_directory().join("privacy_salt")
+}
+
+/// 获取配置文件历史目录路径（校验和与最近的正常副本）
+pub fn get_config_history_directory() -> PathBuf {
+    get_config_directory().join("config-history")
+}
+
+/// 获取隔离目录路径（未通过校验的导入文件会被复制到这里）
+pub fn get_quarantine_directory() -> PathBuf {
+    get_config_directory().join("quarantine")
+}
+
+/// 获取备份回收目录路径（备份检查移出的问题备份）
+pub fn get_backup_trash_directory() -> PathBuf {
+    get_config_directory().join("trash")
+}
+
+/// 获取旧配置目录迁移完成标记文件路径
+pub fn get_legacy_migration_marker() -> PathBuf {
+    get_config_directory().join(".legacy-migrated")
+}
+
 /// 在应用启动时检查并迁移旧账户目录到新路径。
-/// 当前为空实现，后续补充实际迁移逻辑。
+/// 逐个处理旧配置目录（见 ConfigManager::legacy_dirs）下的 antigravity-accounts
 pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
     let new_config_dir = get_config_directory();
     let new_accounts_dir = get_accounts_directory();
@@ -87,14 +128,17 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
         new_config_dir.display()
     );
 
-    // 旧账户目录（Roaming 配置目录下）
-    let Some(config_dir) = dirs::config_dir() else {
-        info!(target: "app::startup", "未找到系统配置目录 (dirs::config_dir)，跳过旧账户目录检测");
-        return Ok(());
-    };
-    let legacy_accounts_dir = config_dir
-        .join(".antigravity-agent")
-        .join("antigravity-accounts");
+    for legacy_dir in ConfigManager::legacy_dirs() {
+        migrate_legacy_accounts_dir(&legacy_dir.join("antigravity-accounts"), &new_accounts_dir)?;
+    }
+    Ok(())
+}
+
+/// 迁移单个旧账户目录：只保留登录状态字段写入新目录，旧文件改名为 .bak
+fn migrate_legacy_accounts_dir(
+    legacy_accounts_dir: &Path,
+    new_accounts_dir: &Path,
+) -> io::Result<()> {
     info!(
         target: "app::startup",
         "检测旧账户目录: {}",
@@ -103,7 +147,7 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
 
     // 收集旧账户目录下的 JSON 文件列表（忽略子目录）
     let mut legacy_files = Vec::new();
-    let read_dir = match fs::read_dir(&legacy_accounts_dir) {
+    let read_dir = match fs::read_dir(legacy_accounts_dir) {
         Ok(rd) => rd,
         Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
         Err(err) => return Err(err),
@@ -246,6 +290,5 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
         renamed_count
     );
 
-    // TODO: 检测 Roaming 下的旧账户目录并迁移到新目录，处理冲突和错误。
     Ok(())
 }
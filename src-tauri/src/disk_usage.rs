@@ -0,0 +1,105 @@
+//! 磁盘占用报告
+//! 统计 Antigravity 安装数据目录与 Agent 配置目录（备份、日志、配置）的大小，
+//! 按子目录组织成树形结构，供前端绘制图表
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::utils::dir_size::dir_size;
+
+/// Antigravity 数据目录展开的层数（根目录 → User → globalStorage 等）
+const ANTIGRAVITY_DEPTH: usize = 2;
+
+/// Agent 配置目录展开的层数
+const AGENT_DEPTH: usize = 1;
+
+/// 目录下直接存放的文件汇总为一个节点时使用的名称
+const FILES_NODE_NAME: &str = "(文件)";
+
+/// 树节点
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct DiskUsageNode {
+    pub name: String,
+    /// 路径（汇总文件节点为所在目录）
+    pub path: String,
+    /// 占用字节数（包含所有子节点）
+    pub size: u64,
+    /// 子节点（按大小降序；超出展开层数的目录没有子节点）
+    pub children: Vec<DiskUsageNode>,
+}
+
+/// 磁盘占用报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct DiskUsageReport {
+    /// Antigravity 数据目录（未检测到时为 `None`）
+    pub antigravity: Option<DiskUsageNode>,
+    /// Agent 配置目录
+    pub agent: DiskUsageNode,
+    /// 两者合计字节数
+    pub total: u64,
+}
+
+/// 统计目录，`depth` 为继续展开子目录的层数
+fn node(name: String, path: &Path, depth: usize) -> DiskUsageNode {
+    let mut children = Vec::new();
+    if depth > 0 {
+        let mut files_size = 0;
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            let child = entry.path();
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            if is_dir {
+                children.push(node(
+                    entry.file_name().to_string_lossy().to_string(),
+                    &child,
+                    depth - 1,
+                ));
+            } else {
+                files_size += dir_size(&child);
+            }
+        }
+        if files_size > 0 {
+            children.push(DiskUsageNode {
+                name: FILES_NODE_NAME.to_string(),
+                path: path.display().to_string(),
+                size: files_size,
+                children: Vec::new(),
+            });
+        }
+        children.sort_by(|a, b| b.size.cmp(&a.size));
+    }
+
+    let size = if depth > 0 {
+        children.iter().map(|child| child.size).sum()
+    } else {
+        dir_size(path)
+    };
+    DiskUsageNode {
+        name,
+        path: path.display().to_string(),
+        size,
+        children,
+    }
+}
+
+/// 生成磁盘占用报告（遍历整个目录树，应在阻塞线程中调用）
+pub fn report() -> DiskUsageReport {
+    // Antigravity 根目录位于 User/globalStorage 的上两级
+    let antigravity = crate::platform::get_antigravity_data_dir()
+        .and_then(|dir| dir.parent()?.parent().map(Path::to_path_buf))
+        .filter(|dir| dir.exists())
+        .map(|dir| node("Antigravity".to_string(), &dir, ANTIGRAVITY_DEPTH));
+    let agent = node(
+        "Antigravity Agent".to_string(),
+        &crate::directories::get_config_directory(),
+        AGENT_DEPTH,
+    );
+
+    let total = agent.size + antigravity.as_ref().map_or(0, |node| node.size);
+    tracing::debug!(target: "disk_usage::report", total = total, "已统计磁盘占用");
+    DiskUsageReport {
+        antigravity,
+        agent,
+        total,
+    }
+}
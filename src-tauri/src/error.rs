@@ -0,0 +1,401 @@
+//! 统一错误类型
+//! 命令返回的错误序列化为 `{ code, message, context }`：
+//! - `code`：稳定的机器可读错误码，前端据此分支和本地化
+//! - `message_id`：消息目录中的 ID（见 `i18n`）
+//! - `message`：按当前语言解析的描述
+//! - `context`：结构化上下文（路径、账户名等）
+//!
+//! 尚未迁移的内部函数仍返回 `String`，通过 `From<String>` 归入 `INTERNAL`。
+//!
+//! 内部模块（备份、恢复、平台进程、路径配置）返回 [`CoreError`]：保留 IO / SQLite 等底层原因
+//! 与逐层附加的上下文，调用方按原因分支（如数据库被锁时重试），不再匹配错误字符串
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// 命令错误
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AgentError {
+    /// 未找到 Antigravity 安装位置
+    #[error("未找到 Antigravity 安装位置")]
+    AntigravityNotFound,
+
+    /// Antigravity 状态数据库文件不存在
+    #[error("Antigravity 状态数据库文件不存在: {path}")]
+    DatabaseNotFound { path: String },
+
+    /// 数据库连接或查询失败
+    #[error("数据库操作失败 ({path}): {message}")]
+    Database { path: String, message: String },
+
+    /// 数据库中缺少必需的状态（通常表示 Antigravity 未登录）
+    #[error("未找到 {key}")]
+    StateMissing { key: String },
+
+    /// Base64 / Protobuf / JSON 解码失败
+    #[error("数据解码失败: {message}")]
+    Decode { message: String },
+
+    /// 指定账户的备份不存在
+    #[error("账户备份不存在: {account}")]
+    BackupNotFound { account: String },
+
+    /// 文件读写失败
+    #[error("文件读写失败 ({path}): {message}")]
+    Io { path: String, message: String },
+
+    /// 关闭或启动 Antigravity 进程失败
+    #[error("进程操作失败: {message}")]
+    Process { message: String },
+
+    /// 系统身份验证（Windows Hello / Touch ID / polkit）未通过
+    #[error("系统身份验证未通过: {message}")]
+    AuthenticationFailed { message: String },
+
+    /// 安全模式下拒绝执行破坏性命令
+    #[error("安全模式已开启，已拒绝执行: {operation}")]
+    SafeMode { operation: String },
+
+    /// 已有破坏性操作在执行
+    #[error("操作 {conflicting} 正在执行，请稍后再试: {operation}")]
+    Busy {
+        operation: String,
+        conflicting: String,
+    },
+
+    /// 同一操作触发过于频繁
+    #[error("操作过于频繁，请 {retry_after_ms} 毫秒后重试: {operation}")]
+    RateLimited {
+        operation: String,
+        retry_after_ms: u64,
+    },
+
+    /// 应用已锁定，需要先解锁
+    #[error("应用已锁定，请先解锁")]
+    Locked,
+
+    /// 操作已被用户取消
+    #[error("操作已取消: {operation}")]
+    Cancelled { operation: String },
+
+    /// 尚未归类的错误
+    #[error("{0}")]
+    Internal(String),
+}
+
+/// 命令结果
+pub type AgentResult<T> = Result<T, AgentError>;
+
+impl AgentError {
+    /// 稳定的错误码（前端依赖，不可随意修改）
+    pub fn code(&self) -> &'static str {
+        match self {
+            AgentError::AntigravityNotFound => "ANTIGRAVITY_NOT_FOUND",
+            AgentError::DatabaseNotFound { .. } => "DATABASE_NOT_FOUND",
+            AgentError::Database { .. } => "DATABASE_ERROR",
+            AgentError::StateMissing { .. } => "STATE_MISSING",
+            AgentError::Decode { .. } => "DECODE_FAILED",
+            AgentError::BackupNotFound { .. } => "BACKUP_NOT_FOUND",
+            AgentError::Io { .. } => "IO_ERROR",
+            AgentError::Process { .. } => "PROCESS_ERROR",
+            AgentError::AuthenticationFailed { .. } => "AUTHENTICATION_FAILED",
+            AgentError::SafeMode { .. } => "SAFE_MODE",
+            AgentError::Busy { .. } => "BUSY",
+            AgentError::RateLimited { .. } => "RATE_LIMITED",
+            AgentError::Locked => "APP_LOCKED",
+            AgentError::Cancelled { .. } => "CANCELLED",
+            AgentError::Internal(_) => "INTERNAL",
+        }
+    }
+
+    /// 消息目录中的 ID
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            AgentError::AntigravityNotFound => "error.antigravity_not_found",
+            AgentError::DatabaseNotFound { .. } => "error.database_not_found",
+            AgentError::Database { .. } => "error.database",
+            AgentError::StateMissing { .. } => "error.state_missing",
+            AgentError::Decode { .. } => "error.decode",
+            AgentError::BackupNotFound { .. } => "error.backup_not_found",
+            AgentError::Io { .. } => "error.io",
+            AgentError::Process { .. } => "error.process",
+            AgentError::AuthenticationFailed { .. } => "error.authentication_failed",
+            AgentError::SafeMode { .. } => "error.safe_mode",
+            AgentError::Busy { .. } => "error.busy",
+            AgentError::RateLimited { .. } => "error.rate_limited",
+            AgentError::Locked => "error.locked",
+            AgentError::Cancelled { .. } => "error.cancelled",
+            AgentError::Internal(_) => "error.internal",
+        }
+    }
+
+    /// 按当前语言解析的描述（尚未归类的错误原样返回）
+    pub fn localized_message(&self) -> String {
+        match self {
+            AgentError::Internal(message) => message.clone(),
+            _ => crate::i18n::t(self.message_id(), &self.context()),
+        }
+    }
+
+    /// 结构化上下文
+    pub fn context(&self) -> Value {
+        match self {
+            AgentError::AntigravityNotFound | AgentError::Locked | AgentError::Internal(_) => {
+                json!({})
+            }
+            AgentError::DatabaseNotFound { path } => json!({ "path": path }),
+            AgentError::Database { path, message } | AgentError::Io { path, message } => {
+                json!({ "path": path, "detail": message })
+            }
+            AgentError::StateMissing { key } => json!({ "key": key }),
+            AgentError::Decode { message }
+            | AgentError::Process { message }
+            | AgentError::AuthenticationFailed { message } => json!({ "detail": message }),
+            AgentError::BackupNotFound { account } => json!({ "account": account }),
+            AgentError::SafeMode { operation } | AgentError::Cancelled { operation } => {
+                json!({ "operation": operation })
+            }
+            AgentError::Busy {
+                operation,
+                conflicting,
+            } => json!({ "operation": operation, "conflicting": conflicting }),
+            AgentError::RateLimited {
+                operation,
+                retry_after_ms,
+            } => json!({ "operation": operation, "retry_after_ms": retry_after_ms }),
+        }
+    }
+
+    /// 数据库错误
+    pub fn database(path: &std::path::Path, error: impl std::fmt::Display) -> Self {
+        AgentError::Database {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    /// 文件读写错误
+    pub fn io(path: &std::path::Path, error: impl std::fmt::Display) -> Self {
+        AgentError::Io {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    /// 解码错误
+    pub fn decode(error: impl std::fmt::Display) -> Self {
+        AgentError::Decode {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<String> for AgentError {
+    fn from(message: String) -> Self {
+        AgentError::Internal(message)
+    }
+}
+
+impl From<AgentError> for String {
+    fn from(error: AgentError) -> Self {
+        error.to_string()
+    }
+}
+
+impl Serialize for AgentError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AgentError", 4)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message_id", self.message_id())?;
+        state.serialize_field("message", &self.localized_message())?;
+        state.serialize_field("context", &self.context())?;
+        state.end()
+    }
+}
+
+/// 错误序列化后的结构（与上面的 `Serialize` 实现一致），仅用于生成前端类型
+#[derive(specta::Type)]
+#[specta(rename = "AgentError")]
+#[allow(dead_code)]
+struct AgentErrorPayload {
+    code: String,
+    message_id: String,
+    message: String,
+    context: Value,
+}
+
+impl specta::Type for AgentError {
+    fn inline(
+        type_map: &mut specta::TypeCollection,
+        generics: specta::Generics,
+    ) -> specta::datatype::DataType {
+        AgentErrorPayload::inline(type_map, generics)
+    }
+}
+
+/// 内部模块错误
+#[derive(Debug, thiserror::Error)]
+pub enum CoreError {
+    /// 文件读写失败
+    #[error("文件读写失败 ({}): {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// SQLite 连接或查询失败
+    #[error("数据库操作失败 ({}): {source}", path.display())]
+    Sqlite {
+        path: PathBuf,
+        #[source]
+        source: rusqlite::Error,
+    },
+
+    /// JSON / Base64 / Protobuf 等内容无法解析
+    #[error("{what}解析失败: {message}")]
+    Parse { what: String, message: String },
+
+    /// 所需的文件、数据或进程不存在
+    #[error("未找到{what}")]
+    NotFound { what: String },
+
+    /// 附加了上下文的错误
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<CoreError>,
+    },
+
+    /// 操作已被取消（见 `cancellation`）
+    #[error("操作已取消: {operation}")]
+    Cancelled { operation: String },
+
+    /// 尚未细分的错误（来自仍返回 `String` 的函数）
+    #[error("{0}")]
+    Other(String),
+}
+
+/// 内部模块结果
+pub type CoreResult<T> = Result<T, CoreError>;
+
+impl CoreError {
+    /// 文件读写错误
+    pub fn io(path: &Path, source: std::io::Error) -> Self {
+        CoreError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// SQLite 错误
+    pub fn sqlite(path: &Path, source: rusqlite::Error) -> Self {
+        CoreError::Sqlite {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// 解析错误
+    pub fn parse(what: impl Into<String>, error: impl std::fmt::Display) -> Self {
+        CoreError::Parse {
+            what: what.into(),
+            message: error.to_string(),
+        }
+    }
+
+    /// 不存在
+    pub fn not_found(what: impl Into<String>) -> Self {
+        CoreError::NotFound { what: what.into() }
+    }
+
+    /// 去掉所有上下文后的底层错误
+    pub fn root(&self) -> &CoreError {
+        match self {
+            CoreError::Context { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
+    /// 是否为“不存在”类错误
+    pub fn is_not_found(&self) -> bool {
+        match self.root() {
+            CoreError::NotFound { .. } => true,
+            CoreError::Io { source, .. } => source.kind() == std::io::ErrorKind::NotFound,
+            _ => false,
+        }
+    }
+
+    /// 是否因数据库被其他连接占用而失败（稍后重试可能成功）
+    pub fn is_database_busy(&self) -> bool {
+        match self.root() {
+            CoreError::Sqlite { source, .. } => matches!(
+                source.sqlite_error_code(),
+                Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+            ),
+            _ => false,
+        }
+    }
+
+    /// 上下文链与底层原因（不含已由结构化字段表示的路径）
+    fn detail(&self) -> String {
+        match self {
+            CoreError::Io { source, .. } => source.to_string(),
+            CoreError::Sqlite { source, .. } => source.to_string(),
+            CoreError::Context { context, source } => format!("{}: {}", context, source.detail()),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// 为结果附加上下文
+pub trait ResultExt<T> {
+    fn context(self, context: impl Into<String>) -> CoreResult<T>;
+}
+
+impl<T, E: Into<CoreError>> ResultExt<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> CoreResult<T> {
+        self.map_err(|error| CoreError::Context {
+            context: context.into(),
+            source: Box::new(error.into()),
+        })
+    }
+}
+
+impl From<String> for CoreError {
+    fn from(message: String) -> Self {
+        CoreError::Other(message)
+    }
+}
+
+impl From<CoreError> for String {
+    fn from(error: CoreError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<CoreError> for AgentError {
+    fn from(error: CoreError) -> Self {
+        let detail = error.detail();
+        match error.root() {
+            CoreError::Io { path, .. } => AgentError::Io {
+                path: path.display().to_string(),
+                message: detail,
+            },
+            CoreError::Sqlite { path, .. } => AgentError::Database {
+                path: path.display().to_string(),
+                message: detail,
+            },
+            CoreError::Parse { .. } => AgentError::Decode { message: detail },
+            CoreError::Cancelled { operation } => AgentError::Cancelled {
+                operation: operation.clone(),
+            },
+            CoreError::NotFound { .. } | CoreError::Other(_) | CoreError::Context { .. } => {
+                AgentError::Internal(error.to_string())
+            }
+        }
+    }
+}
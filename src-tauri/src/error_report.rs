@@ -0,0 +1,190 @@
+//! 离线错误报告
+//! 用户开启后，把命令错误与 panic 以 Sentry envelope 格式写入日志目录下的 `error-reports/`，
+//! 从不自动上传；用户可通过 `export_error_reports` 导出后手动发给维护者
+
+use serde_json::{json, Value};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::utils::log_sanitizer::sanitize_log_message;
+
+/// 最多保留的报告数量
+const MAX_REPORTS: usize = 100;
+
+/// 报告文件扩展名
+const ENVELOPE_EXTENSION: &str = "envelope";
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 更新启用状态
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 错误报告目录
+pub fn report_directory() -> PathBuf {
+    crate::directories::get_log_directory().join("error-reports")
+}
+
+/// 构造 Sentry 事件
+fn build_event(
+    event_id: &str,
+    level: &str,
+    logger: &str,
+    exception: Value,
+    tags: Value,
+    extra: Value,
+) -> Value {
+    json!({
+        "event_id": event_id,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "platform": "native",
+        "level": level,
+        "logger": logger,
+        "release": format!("antigravity-agent@{}", env!("CARGO_PKG_VERSION")),
+        "environment": if cfg!(debug_assertions) { "development" } else { "production" },
+        "contexts": {
+            "os": { "name": std::env::consts::OS },
+            "device": { "arch": std::env::consts::ARCH },
+        },
+        "tags": tags,
+        "exception": { "values": [exception] },
+        "extra": extra,
+    })
+}
+
+/// 将事件写为 envelope 文件：envelope 头、item 头、事件 JSON 各占一行
+fn write_envelope(event_id: &str, event: &Value) -> Result<PathBuf, String> {
+    let payload = serde_json::to_string(event).map_err(|e| format!("序列化错误报告失败: {}", e))?;
+    let header = json!({
+        "event_id": event_id,
+        "sent_at": chrono::Utc::now().to_rfc3339(),
+    });
+    let item_header = json!({
+        "type": "event",
+        "content_type": "application/json",
+        "length": payload.len(),
+    });
+
+    let dir = report_directory();
+    fs::create_dir_all(&dir).map_err(|e| format!("创建错误报告目录失败: {}", e))?;
+
+    let path = dir.join(format!(
+        "{}-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S-%3f"),
+        event_id,
+        ENVELOPE_EXTENSION
+    ));
+    fs::write(&path, format!("{}\n{}\n{}\n", header, item_header, payload))
+        .map_err(|e| format!("写入错误报告失败: {}", e))?;
+
+    prune_reports();
+    Ok(path)
+}
+
+/// 记录命令错误（未启用时忽略）
+pub fn capture_command_error(command: &str, error: &str) {
+    if !is_enabled() {
+        return;
+    }
+
+    let event_id = uuid::Uuid::new_v4().simple().to_string();
+    let event = build_event(
+        &event_id,
+        "error",
+        "command",
+        json!({
+            "type": "CommandError",
+            "value": sanitize_log_message(error),
+            "mechanism": { "type": "command", "handled": true },
+        }),
+        json!({ "command": command, "run_id": crate::run_id::run_id() }),
+        json!({}),
+    );
+
+    if let Err(e) = write_envelope(&event_id, &event) {
+        tracing::warn!(target: "app::error_report", command = %command, error = %e, "写入错误报告失败");
+    }
+}
+
+/// 记录 panic（未启用时忽略），由崩溃报告钩子调用
+pub fn capture_panic(info: &PanicHookInfo<'_>, message: &str) {
+    if !is_enabled() {
+        return;
+    }
+
+    let event_id = uuid::Uuid::new_v4().simple().to_string();
+    let thread = std::thread::current()
+        .name()
+        .unwrap_or("<unnamed>")
+        .to_string();
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+    let event = build_event(
+        &event_id,
+        "fatal",
+        "panic",
+        json!({
+            "type": "panic",
+            "value": sanitize_log_message(message),
+            "mechanism": { "type": "panic", "handled": false },
+        }),
+        json!({ "thread": thread, "run_id": crate::run_id::run_id() }),
+        json!({
+            "location": location,
+            "backtrace": sanitize_log_message(&Backtrace::force_capture().to_string()),
+        }),
+    );
+
+    if let Err(e) = write_envelope(&event_id, &event) {
+        tracing::error!(target: "app::error_report", error = %e, "写入 panic 错误报告失败");
+    }
+}
+
+/// 列出报告文件，按文件名倒序（最新在前）
+fn list_report_files() -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(report_directory()) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(ENVELOPE_EXTENSION))
+        .collect();
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// 只保留最近的 `MAX_REPORTS` 份报告
+fn prune_reports() {
+    for path in list_report_files().into_iter().skip(MAX_REPORTS) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// 将所有报告复制到目标目录，返回导出的文件数
+pub fn export(target_dir: &Path) -> Result<usize, String> {
+    fs::create_dir_all(target_dir).map_err(|e| format!("创建导出目录失败: {}", e))?;
+
+    let mut exported = 0;
+    for path in list_report_files() {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        fs::copy(&path, target_dir.join(name))
+            .map_err(|e| format!("复制错误报告失败 {}: {}", path.display(), e))?;
+        exported += 1;
+    }
+    Ok(exported)
+}
@@ -0,0 +1,159 @@
+//! 事件总线
+//! 所有发往前端的事件（后端的托盘、任务栏也会订阅其中一部分）统一在这里登记：
+//! 每个事件对应一个带类型的负载结构体，事件名只在 `register_events!` 中出现一次，
+//! 通过 `emit` / `listen` 收发，避免各模块手写事件名与 JSON 负载导致前后端不一致
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Listener};
+
+use crate::app_lock::AppLockPayload;
+use crate::browser_bridge::BrowserLoginPayload;
+use crate::cancellation::OperationCancelledPayload;
+use crate::config_integrity::IntegrityWarning;
+use crate::db_monitor::{AccountChangedPayload, DataDiff, ProcessChangedPayload};
+use crate::peer_discovery::Peer;
+use crate::pipeline::PipelineStatusPayload;
+use crate::settings_watcher::SettingsChangedPayload;
+use crate::slow_operation::SlowOperationPayload;
+use crate::startup::StartupProgressPayload;
+use crate::updater::UpdateDownloadProgressPayload;
+use crate::window::exit_guard::CloseConfirmationPayload;
+use crate::window::theme::ThemeChangedPayload;
+
+/// 带类型的事件负载
+pub trait Event: Serialize {
+    /// 事件名
+    const NAME: &'static str;
+}
+
+/// 事件类型说明
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct EventType {
+    /// 事件名
+    pub name: String,
+    /// 负载类型名
+    pub payload: String,
+    pub description: String,
+}
+
+/// 登记事件：为负载类型实现 [`Event`]，并生成 [`event_types`] 列表
+macro_rules! register_events {
+    ($($payload:ty => $name:literal : $description:literal,)*) => {
+        $(impl Event for $payload {
+            const NAME: &'static str = $name;
+        })*
+
+        /// 所有事件类型
+        pub fn event_types() -> Vec<EventType> {
+            vec![$(EventType {
+                name: $name.to_string(),
+                payload: type_name::<$payload>(),
+                description: $description.to_string(),
+            },)*]
+        }
+    };
+}
+
+register_events! {
+    AccountChangedPayload => "account-changed": "当前登录账户变化",
+    ProcessChangedPayload => "antigravity-process-changed": "Antigravity 进程启动或退出",
+    DatabaseChangedPayload => "database-changed": "Antigravity 状态数据库内容变化",
+    PipelineStatusPayload => "pipeline-status": "切换、备份、恢复等长耗时操作的开始、进度与结束",
+    StartupProgressPayload => "startup-progress": "启动初始化步骤完成或失败",
+    SlowOperationPayload => "slow-operation": "操作耗时超过阈值",
+    TraySwitchResultPayload => "tray-switch-account-result": "从托盘菜单切换账户或恢复备份的结果",
+    CloseConfirmationPayload => "close-confirmation-requested": "操作进行中时请求用户确认退出",
+    ThemeChangedPayload => "theme-changed": "系统主题或主题偏好变化",
+    AppLockPayload => "app-lock-changed": "应用锁定或解锁",
+    IntegrityWarning => "config-integrity-warning": "配置文件校验失败或已从历史副本恢复",
+    PeerDiscovered => "peer-discovered": "局域网中发现对端实例",
+    PeerLost => "peer-lost": "局域网中的对端实例下线",
+    BrowserLoginPayload => "browser-login-imported": "浏览器扩展交来的登录已创建为账户备份（或失败）",
+    SettingsChangedPayload => "settings-changed": "应用设置或 Antigravity 路径配置被修改（应用内或外部编辑文件）",
+    UpdateDownloadProgressPayload => "update-download-progress": "应用更新的下载进度",
+    OperationCancelledPayload => "operation-cancelled": "长耗时操作（切换、恢复、备份检查、导入导出）已被取消",
+}
+
+/// 携带账户邮箱、供前端与托盘同步状态的事件，隐私模式下不替换邮箱（凭据仍会脱敏）
+const RAW_EVENTS: &[&str] = &[AccountChangedPayload::NAME];
+
+/// 类型名（去掉模块路径）
+fn type_name<T>() -> String {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+/// `database-changed` 事件负载（发出前与其他事件一样统一脱敏，登录状态与令牌不会到达前端）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseChangedPayload {
+    pub new_data: Value,
+    pub old_data: Value,
+    pub diff: DataDiff,
+}
+
+/// `tray-switch-account-result` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct TraySwitchResultPayload {
+    /// 账户邮箱或备份名
+    pub email: String,
+    pub success: bool,
+    pub message: String,
+    /// 失败时的错误码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+/// `peer-discovered` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct PeerDiscovered(pub Peer);
+
+/// `peer-lost` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct PeerLost(pub Peer);
+
+/// 在事件负载上附加 `run_id` 字段（负载需序列化为 JSON 对象）
+#[derive(Serialize)]
+struct WithRunId<'a, T: Serialize> {
+    run_id: &'static str,
+    #[serde(flatten)]
+    payload: &'a T,
+}
+
+/// 发出带运行 ID 的全局事件
+///
+/// 所有负载先遮盖凭据（令牌、登录状态、API 密钥等），隐私模式下再把邮箱替换为哈希
+pub fn emit<E: Event>(app_handle: &AppHandle, payload: &E) -> tauri::Result<()> {
+    let run_id = crate::run_id::run_id();
+    let mut value = crate::utils::redacted_json::to_value(payload)?;
+    if !RAW_EVENTS.contains(&E::NAME) {
+        crate::utils::privacy::scrub_value(&mut value);
+    }
+    app_handle.emit(
+        E::NAME,
+        WithRunId {
+            run_id,
+            payload: &value,
+        },
+    )
+}
+
+/// 在后端订阅事件，负载无法解析时记录警告并忽略
+pub fn listen<E, F>(app_handle: &AppHandle, handler: F)
+where
+    E: Event + DeserializeOwned,
+    F: Fn(E) + Send + 'static,
+{
+    app_handle.listen(E::NAME, move |event| {
+        match serde_json::from_str::<E>(event.payload()) {
+            Ok(payload) => handler(payload),
+            Err(e) => {
+                tracing::warn!(target: "events::listen", event = E::NAME, error = %e, "解析事件负载失败")
+            }
+        }
+    });
+}
@@ -0,0 +1,188 @@
+//! 事件钩子脚本
+//! 在选定事件发生时运行用户配置的脚本或可执行文件。事件数据通过环境变量与 stdin 传入：
+//! - `AGENT_EVENT`：事件名（`account-changed` / `backup-created` / `antigravity-stopped`）
+//! - `AGENT_EVENT_DATA`：事件数据 JSON；数据中的字符串字段另以 `AGENT_<字段名大写>` 传入
+//! - stdin：`{"event": ..., "timestamp": ..., "data": {...}}`
+//!
+//! 每次运行都有超时限制，退出码与截断后的输出记录到审计日志
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+
+/// 默认超时
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// 超时上限
+const MAX_TIMEOUT_SECS: u64 = 600;
+
+/// 审计日志中保留的输出长度（字符）
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+/// 钩子事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// 当前登录账户变化
+    AccountChanged,
+    /// 备份已创建
+    BackupCreated,
+    /// Antigravity 进程已退出
+    AntigravityStopped,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::AccountChanged => "account-changed",
+            HookEvent::BackupCreated => "backup-created",
+            HookEvent::AntigravityStopped => "antigravity-stopped",
+        }
+    }
+}
+
+/// 单个钩子配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct HookConfig {
+    /// 触发事件
+    pub event: HookEvent,
+    /// 脚本或可执行文件路径
+    pub command: String,
+    /// 命令行参数
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 超时（秒），未设置时为 30 秒
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 校验钩子配置
+pub fn validate(hook: &HookConfig) -> Result<(), String> {
+    if hook.command.trim().is_empty() {
+        return Err("钩子命令不能为空".to_string());
+    }
+    if let Some(timeout) = hook.timeout_secs {
+        if timeout == 0 || timeout > MAX_TIMEOUT_SECS {
+            return Err(format!("钩子超时需在 1 到 {} 秒之间", MAX_TIMEOUT_SECS));
+        }
+    }
+    Ok(())
+}
+
+fn truncate(output: &[u8]) -> String {
+    let text = String::from_utf8_lossy(output);
+    let text = text.trim();
+    match text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+fn command(hook: &HookConfig, event: HookEvent, data: &Value) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new(hook.command.trim());
+    command
+        .args(&hook.args)
+        .env("AGENT_EVENT", event.name())
+        .env("AGENT_EVENT_DATA", data.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(fields) = data.as_object() {
+        for (key, value) in fields {
+            if let Some(value) = value.as_str() {
+                command.env(format!("AGENT_{}", key.to_ascii_uppercase()), value);
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+/// 运行一个钩子，返回退出码与输出摘要
+async fn execute(hook: &HookConfig, event: HookEvent, data: &Value) -> Result<String, String> {
+    let mut child = command(hook, event, data)
+        .spawn()
+        .map_err(|e| format!("启动钩子失败: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = json!({
+            "event": event.name(),
+            "timestamp": chrono::Local::now().to_rfc3339(),
+            "data": data,
+        })
+        .to_string();
+        // 脚本不读取 stdin 时写入会失败，忽略即可
+        let _ = stdin.write_all(input.as_bytes()).await;
+    }
+
+    let timeout = Duration::from_secs(hook.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("钩子运行超过 {} 秒，已终止", timeout.as_secs()))?
+        .map_err(|e| format!("等待钩子结束失败: {}", e))?;
+
+    let summary = format!(
+        "exit={} stdout={} stderr={}",
+        output
+            .status
+            .code()
+            .map_or_else(|| "signal".to_string(), |code| code.to_string()),
+        truncate(&output.stdout),
+        truncate(&output.stderr)
+    );
+    if output.status.success() {
+        Ok(summary)
+    } else {
+        Err(summary)
+    }
+}
+
+/// 运行订阅了该事件的所有钩子（后台执行，不阻塞调用方）
+pub fn dispatch(app_handle: &AppHandle, event: HookEvent, data: Value) {
+    let hooks: Vec<HookConfig> = app_handle
+        .try_state::<crate::app_settings::AppSettingsManager>()
+        .map(|manager| manager.get_settings().hooks)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|hook| hook.enabled && hook.event == event)
+        .collect();
+
+    for hook in hooks {
+        let data = data.clone();
+        tauri::async_runtime::spawn(async move {
+            let started = Instant::now();
+            let result = execute(&hook, event, &data).await;
+            match &result {
+                Ok(_) => {
+                    tracing::info!(target: "hooks::run", event = event.name(), command = %hook.command, "🪝 钩子运行完成")
+                }
+                Err(e) => {
+                    tracing::warn!(target: "hooks::run", event = event.name(), command = %hook.command, error = %e, "钩子运行失败")
+                }
+            }
+            crate::audit::record(
+                crate::audit::AuditOperation::Hook,
+                Some(&hook.command),
+                started,
+                &result,
+                result.as_deref().ok(),
+            );
+        });
+    }
+}
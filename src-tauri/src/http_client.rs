@@ -0,0 +1,111 @@
+//! 共享 HTTP 客户端
+//! 状态探测、同步、更新检查等远程功能统一从这里获取 reqwest 客户端，
+//! 使用相同的超时、代理与 TLS 策略；需要时可将信任锚固定为指定证书（证书固定），
+//! 此时不再信任系统根证书
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 默认请求超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 默认连接超时
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 客户端选项
+#[derive(Debug, Clone)]
+pub struct HttpClientOptions {
+    /// 整个请求的超时
+    pub timeout: Duration,
+    /// 建立连接的超时
+    pub connect_timeout: Duration,
+    /// 代理地址（`http://`、`https://` 或 `socks5://`），为空时使用系统代理环境变量
+    pub proxy: Option<String>,
+    /// 固定信任的证书（PEM）；非空时只信任这些证书，不再信任系统根证书
+    pub pinned_certificates: Vec<Vec<u8>>,
+}
+
+impl Default for HttpClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            proxy: None,
+            pinned_certificates: Vec::new(),
+        }
+    }
+}
+
+/// 全局代理设置（来自应用设置）
+static PROXY: Mutex<Option<String>> = Mutex::new(None);
+
+/// 缓存的默认客户端（设置变化时清空）
+static SHARED: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+
+/// 应用代理设置，之后获取的共享客户端使用新代理
+pub fn set_proxy(proxy: Option<String>) {
+    if let Ok(mut current) = PROXY.lock() {
+        *current = proxy.filter(|proxy| !proxy.trim().is_empty());
+    }
+    if let Ok(mut shared) = SHARED.lock() {
+        *shared = None;
+    }
+}
+
+/// 当前的全局代理地址
+pub fn proxy() -> Option<String> {
+    PROXY.lock().ok().and_then(|proxy| proxy.clone())
+}
+
+/// 校验代理地址格式
+pub fn validate_proxy(proxy: &str) -> Result<(), String> {
+    reqwest::Proxy::all(proxy)
+        .map(|_| ())
+        .map_err(|e| format!("代理地址无效: {}", e))
+}
+
+/// 按选项构造客户端
+pub fn build(options: &HttpClientOptions) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(concat!("antigravity-agent/", env!("CARGO_PKG_VERSION")))
+        .timeout(options.timeout)
+        .connect_timeout(options.connect_timeout)
+        .https_only(true)
+        .use_rustls_tls();
+
+    if let Some(proxy) = &options.proxy {
+        builder =
+            builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| format!("代理地址无效: {}", e))?);
+    }
+
+    if !options.pinned_certificates.is_empty() {
+        builder = builder.tls_built_in_root_certs(false);
+        for pem in &options.pinned_certificates {
+            let certificate =
+                reqwest::Certificate::from_pem(pem).map_err(|e| format!("固定证书无效: {}", e))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))
+}
+
+/// 获取使用默认选项与全局代理的共享客户端
+pub fn shared() -> Result<reqwest::Client, String> {
+    let mut shared = SHARED
+        .lock()
+        .map_err(|e| format!("获取 HTTP 客户端锁失败: {}", e))?;
+    if let Some(client) = shared.as_ref() {
+        return Ok(client.clone());
+    }
+
+    let options = HttpClientOptions {
+        proxy: proxy(),
+        ..Default::default()
+    };
+    let client = build(&options)?;
+    *shared = Some(client.clone());
+    Ok(client)
+}
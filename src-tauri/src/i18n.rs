@@ -0,0 +1,209 @@
+//! 后端消息目录
+//! 面向用户的错误与结果消息按消息 ID 登记在 `messages!` 中（至少包含中文与英文），
+//! 按设置中的 `locale` 解析；模板中的 `{name}` 由参数对象中的同名字段替换。
+//! 错误序列化时同时返回 `message_id` 与本地化后的 `message`，前端可据此统一翻译
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// 简体中文
+    #[default]
+    Zh,
+    /// English
+    En,
+}
+
+/// 当前语言（`Locale` 的序号）
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// 更新当前语言
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// 当前语言
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::Zh,
+    }
+}
+
+/// 带 ID 的本地化消息
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct LocalizedMessage {
+    /// 消息 ID（稳定，前端可据此自行翻译）
+    pub id: String,
+    /// 当前语言下的文本
+    pub text: String,
+}
+
+/// 登记消息：生成按语言查找模板的 `template` 与列出全部 ID 的 `message_ids`
+macro_rules! messages {
+    ($($id:literal => { zh: $zh:literal, en: $en:literal },)*) => {
+        /// 消息模板（未登记的 ID 返回 `None`）
+        fn template(id: &str, locale: Locale) -> Option<&'static str> {
+            match (id, locale) {
+                $(($id, Locale::Zh) => Some($zh),
+                ($id, Locale::En) => Some($en),)*
+                _ => None,
+            }
+        }
+
+        /// 所有消息 ID
+        fn message_ids() -> &'static [&'static str] {
+            &[$($id,)*]
+        }
+    };
+}
+
+messages! {
+    // 错误（与 `AgentError` 的变体一一对应）
+    "error.antigravity_not_found" => {
+        zh: "未找到 Antigravity 安装位置",
+        en: "Antigravity installation not found",
+    },
+    "error.database_not_found" => {
+        zh: "Antigravity 状态数据库文件不存在: {path}",
+        en: "Antigravity state database not found: {path}",
+    },
+    "error.database" => {
+        zh: "数据库操作失败 ({path}): {detail}",
+        en: "Database operation failed ({path}): {detail}",
+    },
+    "error.state_missing" => {
+        zh: "未找到 {key}",
+        en: "{key} not found",
+    },
+    "error.decode" => {
+        zh: "数据解码失败: {detail}",
+        en: "Failed to decode data: {detail}",
+    },
+    "error.backup_not_found" => {
+        zh: "账户备份不存在: {account}",
+        en: "Account backup not found: {account}",
+    },
+    "error.io" => {
+        zh: "文件读写失败 ({path}): {detail}",
+        en: "File I/O failed ({path}): {detail}",
+    },
+    "error.process" => {
+        zh: "进程操作失败: {detail}",
+        en: "Process operation failed: {detail}",
+    },
+    "error.authentication_failed" => {
+        zh: "系统身份验证未通过: {detail}",
+        en: "System authentication failed: {detail}",
+    },
+    "error.safe_mode" => {
+        zh: "安全模式已开启，已拒绝执行: {operation}",
+        en: "Safe mode is on, refused to run: {operation}",
+    },
+    "error.busy" => {
+        zh: "操作 {conflicting} 正在执行，请稍后再试: {operation}",
+        en: "{conflicting} is in progress, try again later: {operation}",
+    },
+    "error.rate_limited" => {
+        zh: "操作过于频繁，请 {retry_after_ms} 毫秒后重试: {operation}",
+        en: "Too many attempts, retry in {retry_after_ms} ms: {operation}",
+    },
+    "error.locked" => {
+        zh: "应用已锁定，请先解锁",
+        en: "The app is locked, unlock it first",
+    },
+    "error.cancelled" => {
+        zh: "操作已取消: {operation}",
+        en: "Operation cancelled: {operation}",
+    },
+    "error.internal" => {
+        zh: "{detail}",
+        en: "{detail}",
+    },
+
+    // 通用
+    "common.cancelled" => {
+        zh: "用户已取消操作",
+        en: "Cancelled by user",
+    },
+
+    // 备份
+    "backup.deleted" => {
+        zh: "删除用户成功: {name}",
+        en: "Deleted backup: {name}",
+    },
+    "backup.file_missing" => {
+        zh: "用户文件不存在",
+        en: "Backup file does not exist",
+    },
+    "backup.cleared" => {
+        zh: "已清空所有用户备份，共删除 {count} 个文件",
+        en: "Cleared all backups, {count} files deleted",
+    },
+    "backup.nothing_to_clear" => {
+        zh: "用户目录不存在，无需清空",
+        en: "Backup directory does not exist, nothing to clear",
+    },
+
+    // 系统托盘
+    "tray.enabled" => {
+        zh: "系统托盘已启用",
+        en: "System tray enabled",
+    },
+    "tray.disabled" => {
+        zh: "系统托盘已禁用",
+        en: "System tray disabled",
+    },
+}
+
+/// 用参数替换模板中的 `{name}`
+fn render(template: &str, args: &Value) -> String {
+    let mut text = template.to_string();
+    if let Value::Object(args) = args {
+        for (name, value) in args {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            text = text.replace(&format!("{{{}}}", name), &value);
+        }
+    }
+    text
+}
+
+/// 按指定语言解析消息，未登记的 ID 原样返回
+pub fn translate(locale: Locale, id: &str, args: &Value) -> String {
+    match template(id, locale) {
+        Some(template) => render(template, args),
+        None => {
+            tracing::warn!(target: "i18n::translate", id = %id, "消息 ID 未登记");
+            id.to_string()
+        }
+    }
+}
+
+/// 按当前语言解析消息
+pub fn t(id: &str, args: &Value) -> String {
+    translate(locale(), id, args)
+}
+
+/// 按当前语言解析消息，同时保留消息 ID
+pub fn message(id: &str, args: &Value) -> LocalizedMessage {
+    LocalizedMessage {
+        id: id.to_string(),
+        text: t(id, args),
+    }
+}
+
+/// 指定语言的完整消息目录（消息 ID → 模板）
+pub fn catalog(locale: Locale) -> BTreeMap<String, String> {
+    message_ids()
+        .iter()
+        .filter_map(|id| template(id, locale).map(|text| (id.to_string(), text.to_string())))
+        .collect()
+}
@@ -0,0 +1,374 @@
+//! 本地 HTTP 控制接口
+//! 开启后在 `127.0.0.1` 上监听（端口可配置），供 Raycast、Stream Deck、脚本等外部工具驱动 Agent。
+//! 每个请求都需要携带 `Authorization: Bearer <token>`；接口直接调用现有命令实现，
+//! 因此同样受应用锁、安全模式、系统身份验证与操作互斥的约束：
+//! - `GET  /v1/status`：当前账户、应用锁与 Antigravity 运行状态
+//! - `GET  /v1/accounts`：备份账户列表
+//! - `POST /v1/switch`：切换账户，请求体 `{"account": "work@example.com"}`。默认先在 Agent 中
+//!   弹出确认对话框，用户取消则返回 `DECLINED`；用户在设置中关闭确认后，只有请求带 `"confirm": true`
+//!   时才弹出。启用系统身份验证时，接口发起的切换总是重新验证，不沿用最近一次验证结果
+//! - `POST /v1/backup`：备份当前账户
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::sync::oneshot;
+
+use crate::error::AgentError;
+
+/// 默认监听端口
+pub const DEFAULT_PORT: u16 = 47823;
+
+/// 允许配置的最小端口（避开需要特权的端口）
+const MIN_PORT: u16 = 1024;
+
+/// 本地接口设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalApiSettings {
+    /// 是否启用
+    pub enabled: bool,
+    /// 监听端口
+    pub port: u16,
+    /// 访问令牌（首次启用时生成）
+    pub token: Option<String>,
+    /// 外部工具切换账户前是否要求在 Agent 中确认（默认开启）
+    pub require_confirmation: bool,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: None,
+            require_confirmation: true,
+        }
+    }
+}
+
+/// 本地接口状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    /// 服务是否正在监听
+    pub running: bool,
+    pub port: u16,
+    pub token: Option<String>,
+    /// 外部工具切换账户前是否要求在 Agent 中确认
+    pub require_confirmation: bool,
+}
+
+/// 正在运行的服务
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+/// 请求处理共享的状态
+#[derive(Clone)]
+struct ApiState {
+    app: AppHandle,
+    token: Arc<str>,
+}
+
+/// 生成随机访问令牌
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 校验端口
+pub fn validate_port(port: u16) -> Result<(), String> {
+    if port < MIN_PORT {
+        return Err(format!("端口需不小于 {}", MIN_PORT));
+    }
+    Ok(())
+}
+
+/// 当前状态
+pub fn status(settings: &LocalApiSettings) -> LocalApiStatus {
+    let running = SERVER
+        .lock()
+        .map(|server| server.as_ref().map(|server| server.port))
+        .ok()
+        .flatten();
+    LocalApiStatus {
+        enabled: settings.enabled,
+        running: running.is_some(),
+        port: running.unwrap_or(settings.port),
+        token: settings.token.clone(),
+        require_confirmation: settings.require_confirmation,
+    }
+}
+
+/// 停止服务
+pub fn stop() {
+    let server = SERVER.lock().ok().and_then(|mut server| server.take());
+    if let Some(server) = server {
+        let _ = server.shutdown.send(());
+        tracing::info!(target: "local_api::server", port = server.port, "🛑 本地 HTTP 接口已停止");
+    }
+}
+
+/// 按设置启动或停止服务（已在运行时先停止，再以新设置启动）
+pub async fn apply(app_handle: &AppHandle, settings: &LocalApiSettings) -> Result<(), String> {
+    stop();
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    validate_port(settings.port)?;
+    let token = settings
+        .token
+        .clone()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| "本地接口缺少访问令牌".to_string())?;
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|e| format!("监听 {} 失败: {}", address, e))?;
+
+    let state = ApiState {
+        app: app_handle.clone(),
+        token: token.into(),
+    };
+    let router = Router::new()
+        .route("/v1/status", get(get_status))
+        .route("/v1/accounts", get(list_accounts))
+        .route("/v1/switch", post(switch_account))
+        .route("/v1/backup", post(backup_account))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state);
+
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+    if let Ok(mut server) = SERVER.lock() {
+        *server = Some(RunningServer {
+            port: settings.port,
+            shutdown,
+        });
+    }
+
+    let port = settings.port;
+    tauri::async_runtime::spawn(async move {
+        let result = axum::serve(listener, router)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+        if let Err(e) = result {
+            tracing::error!(target: "local_api::server", port = port, error = %e, "本地 HTTP 接口异常退出");
+        }
+    });
+
+    tracing::info!(target: "local_api::server", port = settings.port, "🌐 本地 HTTP 接口已启动");
+    Ok(())
+}
+
+/// 常数时间比较，避免通过响应时间猜测令牌
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn error_response(status: StatusCode, code: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({ "code": code, "message": message, "context": {} })),
+    )
+        .into_response()
+}
+
+/// 校验来源与访问令牌
+async fn authorize(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let headers = request.headers();
+
+    // 浏览器发起的请求总是带 Origin，拒绝以免网页借用本地接口
+    if headers.contains_key(header::ORIGIN) {
+        return error_response(StatusCode::FORBIDDEN, "FORBIDDEN", "不接受浏览器发起的请求");
+    }
+
+    // Host 必须是本机地址，防止 DNS 重绑定
+    let host_allowed = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| host.rsplit_once(':').map_or(host, |(host, _)| host))
+        .is_some_and(|host| host == "127.0.0.1" || host.eq_ignore_ascii_case("localhost"));
+    if !host_allowed {
+        return error_response(StatusCode::FORBIDDEN, "FORBIDDEN", "Host 不是本机地址");
+    }
+
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.trim().as_bytes(), state.token.as_bytes()));
+    if !authorized {
+        tracing::warn!(target: "local_api::request", path = %request.uri().path(), "🚫 本地接口请求未通过令牌校验");
+        return error_response(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "访问令牌无效");
+    }
+
+    tracing::info!(target: "local_api::request", method = %request.method(), path = %request.uri().path(), "🌐 收到本地接口请求");
+    next.run(request).await
+}
+
+/// 将命令错误转换为 HTTP 响应（响应体与命令错误的序列化格式一致）
+fn agent_error_response(error: AgentError) -> Response {
+    let status = match &error {
+        AgentError::Locked => StatusCode::LOCKED,
+        AgentError::SafeMode { .. } | AgentError::AuthenticationFailed { .. } => {
+            StatusCode::FORBIDDEN
+        }
+        AgentError::BackupNotFound { .. } => StatusCode::NOT_FOUND,
+        AgentError::Busy { .. } | AgentError::Cancelled { .. } => StatusCode::CONFLICT,
+        AgentError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(error)).into_response()
+}
+
+fn message_response(result: Result<String, AgentError>) -> Response {
+    match result {
+        Ok(message) => Json(json!({ "message": message })).into_response(),
+        Err(e) => agent_error_response(e),
+    }
+}
+
+async fn get_status() -> Response {
+    let locked = crate::app_lock::is_locked();
+    let current_account = if locked {
+        None
+    } else {
+        crate::commands::get_current_antigravity_account_info()
+            .await
+            .ok()
+            .and_then(|info| info["context"]["email"].as_str().map(str::to_string))
+            .filter(|email| !email.is_empty())
+    };
+    let antigravity_running = tokio::task::spawn_blocking(crate::platform::is_antigravity_running)
+        .await
+        .unwrap_or(false);
+
+    Json(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "locked": locked,
+        "antigravity_running": antigravity_running,
+        "current_account": current_account,
+    }))
+    .into_response()
+}
+
+async fn list_accounts() -> Response {
+    if let Err(e) = crate::app_lock::ensure_unlocked() {
+        return agent_error_response(e);
+    }
+    let entries = crate::utils::blocking::run("列出备份", || {
+        crate::antigravity::backup::list_backup_files().map_err(AgentError::from)
+    })
+    .await;
+    match entries {
+        Ok(entries) => {
+            let accounts: Vec<_> = entries
+                .into_iter()
+                .map(|entry| {
+                    json!({
+                        "name": entry.name,
+                        "modified": chrono::DateTime::<chrono::Local>::from(entry.modified).to_rfc3339(),
+                    })
+                })
+                .collect();
+            Json(json!({ "accounts": accounts })).into_response()
+        }
+        Err(e) => agent_error_response(e),
+    }
+}
+
+/// `POST /v1/switch` 请求体
+#[derive(Deserialize)]
+struct SwitchRequest {
+    account: String,
+    /// 是否需要用户在 Agent 中确认（设置中要求确认时忽略，总是确认）
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// 弹出确认对话框，返回用户是否同意
+pub async fn confirm(app_handle: &AppHandle, message: String) -> bool {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let (tx, rx) = oneshot::channel();
+    app_handle
+        .dialog()
+        .message(message)
+        .title("Antigravity Agent")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "继续".to_string(),
+            "取消".to_string(),
+        ))
+        .show(move |confirmed| {
+            let _ = tx.send(confirmed);
+        });
+    rx.await.unwrap_or(false)
+}
+
+async fn switch_account(
+    State(state): State<ApiState>,
+    Json(request): Json<SwitchRequest>,
+) -> Response {
+    let account = request.account.trim().to_string();
+    if account.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "BAD_REQUEST", "缺少 account");
+    }
+    // 账户名会拼接到备份目录路径中，只接受普通备份名
+    if let Err(e) = crate::antigravity::archive::validate_backup_name(&account) {
+        return error_response(StatusCode::BAD_REQUEST, "BAD_REQUEST", &e);
+    }
+    let require_confirmation = state
+        .app
+        .state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .local_api
+        .require_confirmation;
+    if request.confirm || require_confirmation {
+        let message = format!(
+            "外部工具请求切换到账户：\n{}\n\n切换会关闭正在运行的 Antigravity，是否继续？",
+            crate::system_tray::mask_email(&account)
+        );
+        if !confirm(&state.app, message).await {
+            tracing::info!(target: "local_api::request", "用户拒绝了外部工具的切换请求");
+            return error_response(StatusCode::FORBIDDEN, "DECLINED", "用户已取消操作");
+        }
+    }
+    // 令牌持有者不能借用用户最近一次的系统身份验证，接口发起的切换总是重新验证；
+    // 验证通过后，切换流程内部的受保护命令沿用这次结果
+    if let Err(e) =
+        crate::os_auth::require_fresh("local_api_switch", "外部工具请求切换 Antigravity 账户").await
+    {
+        return agent_error_response(e);
+    }
+    message_response(crate::commands::switch_to_antigravity_account(state.app, account).await)
+}
+
+async fn backup_account(State(state): State<ApiState>) -> Response {
+    message_response(crate::commands::save_antigravity_current_account(state.app).await)
+}
@@ -0,0 +1,68 @@
+//! 日志初始化模块
+//! 统一安装 tracing 订阅器（控制台 + JSON lines 文件两层输出），并把 `log` 门面的记录
+//! （Tauri 及各插件内部使用）桥接到 tracing，保证所有输出都进入日志文件
+
+use std::fs;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{prelude::*, EnvFilter};
+
+/// 默认日志过滤器：info 级别，降低 h2/hyper 噪音（可被 RUST_LOG 覆盖）
+const DEFAULT_FILTER: &str = "info,h2=warn,hyper=warn";
+
+/// 初始化全局日志系统
+///
+/// 返回的 guard 必须在 `main` 中持有到进程结束，否则文件缓冲区会被提前丢弃
+pub fn init() -> WorkerGuard {
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    // 创建日志目录（订阅器尚未安装，错误先记下，安装后再输出）
+    let log_dir = crate::directories::get_log_directory();
+    let log_dir_error = fs::create_dir_all(&log_dir).err();
+
+    // 创建滚动文件写入器（带脱敏）
+    let file_writer =
+        crate::utils::sanitizing_layer::SanitizingFileWriter::new().expect("无法创建文件写入器");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_writer);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(crate::utils::sanitizing_layer::RedactingStdoutWriter) // 控制台输出，仅移除凭据
+                .with_target(false)
+                .compact()
+                .with_ansi(true), // 控制台启用颜色
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(non_blocking) // 文件输出，自动脱敏
+                .with_target(true)
+                .with_ansi(false) // 文件不使用颜色代码
+                .json() // JSON lines 格式，供日志查询命令解析
+                .with_current_span(false)
+                .with_span_list(false),
+        );
+
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        // 只可能在重复初始化时发生，此时已有订阅器在工作
+        eprintln!("警告：日志订阅器已存在，跳过初始化: {}", e);
+        return guard;
+    }
+
+    // `log` → tracing 桥接，插件与依赖库的日志同样经过过滤器与脱敏写入器
+    if let Err(e) = tracing_log::LogTracer::init() {
+        tracing::warn!(target: "app::logging", error = %e, "安装 log 桥接失败");
+    }
+
+    if let Some(e) = log_dir_error {
+        tracing::warn!(
+            target: "app::logging",
+            error = %e,
+            "无法创建日志目录 {}",
+            log_dir.display()
+        );
+    }
+
+    guard
+}
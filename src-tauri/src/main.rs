@@ -1,91 +1,154 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::fs;
-use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{prelude::*, EnvFilter};
-
 // Modules
 mod antigravity;
+mod app_lock;
 mod app_settings;
+mod audit;
+mod benchmark;
+mod bindings;
+mod browser_bridge;
+mod cancellation;
+mod config_integrity;
 mod config_manager;
 mod constants;
+mod device_identity;
 mod directories;
+mod disk_usage;
+mod error;
+mod error_report;
+mod events;
+mod hooks;
+mod http_client;
+mod i18n;
+mod local_api;
+mod mcp;
+mod onboarding;
+mod os_auth;
+mod peer_discovery;
 mod platform;
+mod plugins;
 mod proto;
+mod remote_backup;
+mod rpc;
+mod run_id;
+mod safe_mode;
+mod schedule;
+mod self_test;
+mod slow_operation;
 mod system_tray;
 mod utils;
 mod window;
 
 mod commands;
+mod crash_report;
 mod db_monitor;
+mod deep_link;
+mod logging;
+mod notifications;
+mod operation_guard;
 mod path_utils;
+mod pipeline;
+mod settings_migration;
+mod settings_watcher;
 mod setup;
+mod shell_env;
+#[cfg(feature = "simulation")]
+mod simulation;
+mod startup;
 mod state;
+mod state_journal;
+mod updater;
+mod webhooks;
 
 // Re-export AppState for compatibility with other modules
 pub use state::{AntigravityAccount, AppState, ProfileInfo};
 
-// Use commands
-use crate::commands::*;
-
-/// 初始化双层日志系统（控制台 + 文件）
-fn init_tracing() -> WorkerGuard {
-    // 日志过滤器：默认 info，降低 h2/hyper 噪音（可被 RUST_LOG 覆盖）
-    let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,h2=warn,hyper=warn"));
-
-    // 创建日志目录
-    let log_dir = crate::directories::get_log_directory();
-    if let Err(e) = fs::create_dir_all(&log_dir) {
-        eprintln!("警告：无法创建日志目录 {}: {}", log_dir.display(), e);
+fn main() {
+    // MCP stdio 模式：stdout 专用于协议消息，不初始化日志与界面
+    if std::env::args().any(|arg| arg == mcp::MCP_ARG) {
+        std::process::exit(mcp::run());
     }
 
-    // 创建滚动文件写入器（带脱敏）
-    let file_writer =
-        crate::utils::sanitizing_layer::SanitizingFileWriter::new().expect("无法创建文件写入器");
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_writer);
-
-    // 设置控制台和文件双层输出
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_writer(std::io::stdout) // 控制台输出，不脱敏
-                .with_target(false)
-                .compact()
-                .with_ansi(true), // 控制台启用颜色
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_writer(non_blocking) // 文件输出，自动脱敏
-                .with_target(true)
-                .with_ansi(false) // 文件不使用颜色代码
-                .compact(), // 使用紧凑格式而非 JSON，便于脱敏处理
-        )
-        .init();
-
-    guard // 返回 guard 以防止日志缓冲区被过早清理
-}
+    // 只导出前端类型绑定后退出（供 CI 与发布构建使用）
+    if std::env::args().any(|arg| arg == bindings::EXPORT_ARG) {
+        std::process::exit(match bindings::export(&bindings::builder()) {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        });
+    }
 
-fn main() {
     // 初始化双层日志系统（控制台 + 文件）
-    let _guard = init_tracing();
+    let _guard = logging::init();
+
+    // panic 时写入崩溃报告
+    crash_report::install();
 
-    tracing::info!(target: "app::startup", "🚀 启动 Antigravity Agent");
+    tracing::info!(target: "app::startup", run_id = %run_id::run_id(), "🚀 启动 Antigravity Agent");
     tracing::info!(target: "app::startup", "📝 日志系统已初始化（控制台 + 文件）");
     tracing::info!(target: "app::startup", "📁 日志目录: {}", crate::directories::get_log_directory().display());
 
     // 记录系统启动信息
     crate::utils::tracing_config::log_system_info();
 
+    // 模拟模式：创建合成的 Antigravity 数据目录与可执行文件替身
+    #[cfg(feature = "simulation")]
+    if simulation::is_enabled() {
+        if let Err(e) = simulation::prepare() {
+            tracing::error!(target: "app::startup", error = %e, "⚠️ 创建模拟环境失败");
+        }
+    }
+
     // 阻塞主线程执行一次账户目录迁移检查
     match crate::directories::migrate_legacy_accounts_if_needed() {
         Ok(()) => tracing::info!(target: "app::startup", "📦 账户目录迁移检查完成"),
         Err(e) => tracing::error!(target: "app::startup", "⚠️ 账户目录迁移检查失败: {}", e),
     }
 
-    tauri::Builder::default()
+    // 账户目录之外的旧配置文件（设置、状态、日志等）一次性移入规范配置目录
+    match crate::config_manager::ConfigManager::migrate_legacy_dirs() {
+        Ok(report) => tracing::info!(
+            target: "app::startup",
+            moved = report.moved,
+            conflicts = report.conflicts,
+            "📦 旧配置目录迁移检查完成"
+        ),
+        Err(e) => tracing::error!(target: "app::startup", "⚠️ 旧配置目录迁移失败: {}", e),
+    }
+
+    // 命令与前端类型绑定；调试构建启动时重新生成 TypeScript 客户端
+    let specta_builder = bindings::builder();
+    #[cfg(debug_assertions)]
+    if let Err(e) = bindings::export(&specta_builder) {
+        tracing::warn!(target: "app::startup", error = %e, "前端类型绑定未更新");
+    }
+
+    let builder = tauri::Builder::default();
+
+    // 单实例：Windows / Linux 上深度链接会启动新进程，转发给已运行的实例处理
+    // 自动化模式需要独立进程与自己的 stdin/stdout，不转发给已运行的实例
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    let builder = if rpc::is_enabled() {
+        builder
+    } else {
+        builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            use tauri::Manager;
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+    };
+
+    builder
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -93,51 +156,15 @@ fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_http::init())
-        .manage(AppState::default())
+        .manage(AppState::load())
         .setup(|app| setup::init(app))
-        .invoke_handler(tauri::generate_handler![
-            collect_account_contents,
-            restore_backup_files,
-            delete_backup,
-            clear_all_backups,
-            // 账户基础命令
-            get_antigravity_accounts,
-            get_current_antigravity_account_info,
-            save_antigravity_current_account,
-            restore_antigravity_account,
-            switch_to_antigravity_account,
-            clear_all_antigravity_data,
-            is_antigravity_running,
-            sign_in_new_antigravity_account,
-            // 平台支持命令
-            get_platform_info,
-            find_antigravity_installations,
-            get_current_paths,
-            // 数据库路径相关
-            detect_antigravity_installation,
-            // 可执行文件路径相关
-            validate_antigravity_executable,
-            detect_antigravity_executable,
-            save_antigravity_executable,
-            enable_system_tray,
-            disable_system_tray,
-            minimize_to_tray,
-            restore_from_tray,
-            get_system_tray_state,
-            toggle_system_tray,
-            update_tray_menu_command,
-            is_silent_start_enabled,
-            save_silent_start_state,
-            get_all_settings,
-            // 数据库监控命令
-            is_database_monitoring_running,
-            start_database_monitoring,
-            stop_database_monitoring,
-            decrypt_config_data,
-            encrypt_config_data,
-            write_text_file,
-            write_frontend_log,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(specta_builder.invoke_handler())
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // 退出时安装已推迟的更新
+            if let tauri::RunEvent::Exit = event {
+                updater::install_on_exit();
+            }
+        });
 }
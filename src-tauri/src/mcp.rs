@@ -0,0 +1,254 @@
+//! MCP（Model Context Protocol）stdio 服务
+//! 以 `antigravity-agent --mcp` 启动时不打开界面，而是在 stdin/stdout 上按行收发 JSON-RPC 消息，
+//! 供 AI 助手列出账户、查询当前账户、切换与备份。工具调用转发给正在运行的 Agent 的本地 HTTP 接口，
+//! 因此需要先在设置中启用本地接口；切换账户属于破坏性操作，执行前会在 Agent 中弹出确认对话框
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// 启动 MCP 模式的命令行参数
+pub const MCP_ARG: &str = "--mcp";
+
+/// 支持的协议版本（第一个为默认）
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// 转发请求的超时（切换账户需要等待用户确认与重启 Antigravity）
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// 本地接口连接信息
+struct Endpoint {
+    base_url: String,
+    token: String,
+}
+
+/// 从设置文件读取本地接口地址与令牌
+fn endpoint() -> Result<Endpoint, String> {
+    let settings = crate::app_settings::read_settings_file()
+        .ok_or_else(|| "无法读取 Agent 设置文件".to_string())?
+        .local_api;
+    if !settings.enabled {
+        return Err("请先在 Antigravity Agent 设置中启用本地 HTTP 接口".to_string());
+    }
+    let token = settings
+        .token
+        .ok_or_else(|| "本地 HTTP 接口缺少访问令牌".to_string())?;
+    Ok(Endpoint {
+        base_url: format!("http://127.0.0.1:{}", settings.port),
+        token,
+    })
+}
+
+/// 工具定义
+fn tools() -> Value {
+    json!([
+        {
+            "name": "list_accounts",
+            "description": "列出 Antigravity Agent 中已备份的账户",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": { "readOnlyHint": true }
+        },
+        {
+            "name": "current_account",
+            "description": "查询 Antigravity 当前登录的账户与运行状态",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": { "readOnlyHint": true }
+        },
+        {
+            "name": "switch_account",
+            "description": "切换到指定的已备份账户（会关闭并重启 Antigravity，需要用户在 Agent 中确认）",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "account": { "type": "string", "description": "账户名（备份名，通常是邮箱）" }
+                },
+                "required": ["account"]
+            },
+            "annotations": { "destructiveHint": true, "readOnlyHint": false }
+        },
+        {
+            "name": "backup_current_account",
+            "description": "备份 Antigravity 当前登录的账户",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": { "destructiveHint": false, "readOnlyHint": false }
+        }
+    ])
+}
+
+/// 调用本地接口，返回响应 JSON 与是否成功
+async fn forward(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<Value>,
+) -> Result<(Value, bool), String> {
+    let endpoint = endpoint()?;
+    let mut request = client
+        .request(method, format!("{}{}", endpoint.base_url, path))
+        .bearer_auth(&endpoint.token);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("无法连接 Antigravity Agent（请确认 Agent 正在运行）: {}", e))?;
+    let success = response.status().is_success();
+    let value = response
+        .json::<Value>()
+        .await
+        .map_err(|e| format!("解析 Agent 响应失败: {}", e))?;
+    Ok((value, success))
+}
+
+/// 执行工具调用，返回 MCP 工具结果
+async fn call_tool(client: &reqwest::Client, params: &Value) -> Result<Value, (i64, String)> {
+    let name = params["name"].as_str().unwrap_or_default();
+    let arguments = &params["arguments"];
+
+    let result = match name {
+        "list_accounts" => forward(client, reqwest::Method::GET, "/v1/accounts", None).await,
+        "current_account" => forward(client, reqwest::Method::GET, "/v1/status", None).await,
+        "switch_account" => {
+            let account = arguments["account"]
+                .as_str()
+                .map(str::trim)
+                .filter(|account| !account.is_empty())
+                .ok_or_else(|| (INVALID_PARAMS, "缺少参数 account".to_string()))?;
+            forward(
+                client,
+                reqwest::Method::POST,
+                "/v1/switch",
+                Some(json!({ "account": account, "confirm": true })),
+            )
+            .await
+        }
+        "backup_current_account" => {
+            forward(client, reqwest::Method::POST, "/v1/backup", None).await
+        }
+        other => return Err((INVALID_PARAMS, format!("未知工具: {}", other))),
+    };
+
+    // 工具执行失败作为结果返回（isError），便于助手向用户说明原因
+    let (text, is_error) = match result {
+        Ok((value, success)) => (
+            serde_json::to_string_pretty(&value).unwrap_or_default(),
+            !success,
+        ),
+        Err(e) => (e, true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+/// 处理单条请求，通知（无 id）返回 `None`
+async fn handle(client: &reqwest::Client, message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str().unwrap_or_default();
+    let params = &message["params"];
+
+    let result = match method {
+        "initialize" => {
+            let requested = params["protocolVersion"].as_str().unwrap_or_default();
+            let version = PROTOCOL_VERSIONS
+                .iter()
+                .find(|version| **version == requested)
+                .unwrap_or(&PROTOCOL_VERSIONS[0]);
+            Ok(json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "antigravity-agent",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }))
+        }
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => call_tool(client, params).await,
+        other => Err((METHOD_NOT_FOUND, format!("不支持的方法: {}", other))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    })
+}
+
+async fn serve() -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|e| format!("读取 stdin 失败: {}", e))?
+    {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle(&client, &message).await,
+            Err(e) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": PARSE_ERROR, "message": format!("JSON 解析失败: {}", e) },
+            })),
+        };
+
+        if let Some(response) = response {
+            let mut out = response.to_string();
+            out.push('\n');
+            stdout
+                .write_all(out.as_bytes())
+                .await
+                .map_err(|e| format!("写入 stdout 失败: {}", e))?;
+            stdout
+                .flush()
+                .await
+                .map_err(|e| format!("写入 stdout 失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 运行 MCP 服务直到 stdin 关闭，返回进程退出码（stdout 专用于协议消息，错误写到 stderr）
+pub fn run() -> i32 {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("创建运行时失败: {}", e);
+            return 1;
+        }
+    };
+
+    match runtime.block_on(serve()) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("MCP 服务异常退出: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! 系统通知模块
+//! 封装通知插件，按通知类型发送原生系统通知，每种类型可在设置中单独开关
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// 令牌到期提醒阈值（秒）
+pub const TOKEN_EXPIRY_WARNING_SECS: i64 = 10 * 60;
+
+/// 通知类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// 账户切换完成
+    SwitchComplete,
+    /// 备份已创建
+    BackupCreated,
+    /// 登录令牌即将过期
+    TokenExpiring,
+    /// Antigravity 意外退出
+    AntigravityCrashed,
+    /// 插件脚本发出的通知
+    Plugin,
+}
+
+impl NotificationKind {
+    /// 通知标题
+    fn title(self) -> &'static str {
+        match self {
+            NotificationKind::SwitchComplete => "账户切换完成",
+            NotificationKind::BackupCreated => "备份已创建",
+            NotificationKind::TokenExpiring => "登录令牌即将过期",
+            NotificationKind::AntigravityCrashed => "Antigravity 已退出",
+            NotificationKind::Plugin => "插件通知",
+        }
+    }
+}
+
+/// 各类型通知的开关
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub switch_complete: bool,
+    pub backup_created: bool,
+    pub token_expiring: bool,
+    pub antigravity_crashed: bool,
+    pub plugin: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            switch_complete: true,
+            backup_created: true,
+            token_expiring: true,
+            antigravity_crashed: true,
+            plugin: true,
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// 指定类型的通知是否启用
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::SwitchComplete => self.switch_complete,
+            NotificationKind::BackupCreated => self.backup_created,
+            NotificationKind::TokenExpiring => self.token_expiring,
+            NotificationKind::AntigravityCrashed => self.antigravity_crashed,
+            NotificationKind::Plugin => self.plugin,
+        }
+    }
+}
+
+/// 发送通知（类型被禁用时静默跳过）
+pub fn notify(app_handle: &AppHandle, kind: NotificationKind, body: impl Into<String>) {
+    let enabled = app_handle
+        .try_state::<crate::app_settings::AppSettingsManager>()
+        .map(|manager| manager.get_settings().notifications.is_enabled(kind))
+        .unwrap_or(true);
+
+    if !enabled {
+        tracing::debug!(target: "notifications::send", kind = ?kind, "通知类型已禁用，跳过");
+        return;
+    }
+
+    let result = app_handle
+        .notification()
+        .builder()
+        .title(kind.title())
+        .body(body)
+        .show();
+
+    match result {
+        Ok(()) => tracing::debug!(target: "notifications::send", kind = ?kind, "🔔 通知已发送"),
+        Err(e) => {
+            tracing::warn!(target: "notifications::send", kind = ?kind, error = %e, "发送系统通知失败")
+        }
+    }
+}
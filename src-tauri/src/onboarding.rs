@@ -0,0 +1,250 @@
+//! 首次运行引导检测
+//! 一次完成可执行文件检测、数据目录检测、目录权限检查与当前登录探测，把检测成功的
+//! 可执行文件路径与数据目录写入路径配置，返回供引导向导逐步展示的结构化报告
+
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::constants::database;
+use crate::utils::{db_pool, dir_permissions, network_fs};
+
+/// 可执行文件来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutableSource {
+    /// 路径配置中已保存的路径
+    Configured,
+    /// 本次自动检测到
+    Detected,
+}
+
+/// 可执行文件检测结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ExecutableDetection {
+    /// 可执行文件路径（未找到时为 `None`）
+    pub path: Option<String>,
+    pub source: Option<ExecutableSource>,
+    pub is_network_path: bool,
+    /// 结果说明或失败原因
+    pub message: String,
+}
+
+/// 数据目录检测结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct DataDirDetection {
+    /// 包含 `state.vscdb` 的数据目录（未找到时为 `None`）
+    pub path: Option<String>,
+    pub is_network_path: bool,
+    /// 状态数据库能否以只读方式打开
+    pub database_readable: bool,
+    /// 结果说明或失败原因
+    pub message: String,
+}
+
+/// 目录权限检查结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct PermissionCheck {
+    pub config_dir_writable: bool,
+    pub backup_dir_writable: bool,
+    /// 配置目录权限检查（只检查，不修复）
+    pub config_dir_permissions: dir_permissions::HardenReport,
+    /// 不可写的目录及原因
+    pub errors: Vec<String>,
+}
+
+/// 当前登录探测结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct LoginProbe {
+    /// Antigravity 当前是否已登录
+    pub logged_in: bool,
+    /// 当前登录的邮箱
+    pub email: Option<String>,
+    /// 探测失败的原因（数据库不可读或登录状态无法解码）
+    pub error: Option<String>,
+}
+
+/// 首次运行检测报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct OnboardingReport {
+    pub executable: ExecutableDetection,
+    pub data_dir: DataDirDetection,
+    pub permissions: PermissionCheck,
+    pub login: LoginProbe,
+    /// 检测结果是否已写入路径配置
+    pub persisted: bool,
+    /// 可执行文件与数据目录均已找到且目录可写，可以开始使用
+    pub ready: bool,
+}
+
+/// 检测可执行文件：优先使用已保存且仍然有效的路径，否则自动检测
+fn detect_executable() -> ExecutableDetection {
+    let configured = crate::antigravity::path_config::get_custom_executable_path()
+        .ok()
+        .flatten()
+        .filter(|path| crate::antigravity::path_config::validate_executable_path(path));
+    let found = match configured {
+        Some(path) => Some((path, ExecutableSource::Configured)),
+        None => crate::antigravity::starter::detect_antigravity_executable().map(|path| {
+            (
+                path.to_string_lossy().to_string(),
+                ExecutableSource::Detected,
+            )
+        }),
+    };
+
+    match found {
+        Some((path, source)) => ExecutableDetection {
+            is_network_path: network_fs::is_network_path(Path::new(&path)),
+            message: path.clone(),
+            path: Some(path),
+            source: Some(source),
+        },
+        None => ExecutableDetection {
+            path: None,
+            source: None,
+            is_network_path: false,
+            message: "未检测到 Antigravity 可执行文件，请手动指定".to_string(),
+        },
+    }
+}
+
+/// 检测数据目录并读取当前登录状态（数据库不可读时为 `Err`）
+fn detect_data_dir() -> (DataDirDetection, Result<Option<String>, String>) {
+    let Some(db_path) = crate::platform::get_all_antigravity_db_paths()
+        .into_iter()
+        .find(|path| path.exists())
+    else {
+        let message = "未找到 Antigravity 状态数据库，请先启动一次 Antigravity".to_string();
+        return (
+            DataDirDetection {
+                path: None,
+                is_network_path: false,
+                database_readable: false,
+                message: message.clone(),
+            },
+            Err(message),
+        );
+    };
+
+    let data_dir = db_path
+        .parent()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let state = db_pool::with_read_connection(&db_path, |conn| {
+        conn.query_row(
+            "SELECT value FROM ItemTable WHERE key = ?",
+            [database::AGENT_STATE],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })
+    .map_err(|e| format!("读取数据库失败 ({}): {}", db_path.display(), e));
+
+    let detection = DataDirDetection {
+        is_network_path: network_fs::is_network_path(&db_path),
+        database_readable: state.is_ok(),
+        message: match &state {
+            Ok(_) => data_dir.clone(),
+            Err(e) => e.clone(),
+        },
+        path: Some(data_dir),
+    };
+    (detection, state)
+}
+
+/// 检查配置目录与备份目录是否可写，以及配置目录权限是否过宽
+fn check_permissions() -> PermissionCheck {
+    let config_dir = crate::directories::get_config_directory();
+    let config_writable = crate::self_test::probe_writable(&config_dir);
+    let backup_writable =
+        crate::self_test::probe_writable(&crate::directories::get_accounts_directory());
+
+    PermissionCheck {
+        config_dir_writable: config_writable.is_ok(),
+        backup_dir_writable: backup_writable.is_ok(),
+        config_dir_permissions: dir_permissions::harden(&config_dir, false),
+        errors: [config_writable, backup_writable]
+            .into_iter()
+            .filter_map(Result::err)
+            .collect(),
+    }
+}
+
+/// 根据读取到的登录状态判断当前登录账户
+fn probe_login(state: Result<Option<String>, String>) -> LoginProbe {
+    let decoded = state.and_then(|state| {
+        state
+            .map(|state| crate::antigravity::account::decode_jetski_state_proto(&state))
+            .transpose()
+    });
+    match decoded {
+        Ok(Some(session)) => LoginProbe {
+            logged_in: true,
+            email: session["context"]["email"].as_str().map(str::to_string),
+            error: None,
+        },
+        Ok(None) => LoginProbe {
+            logged_in: false,
+            email: None,
+            error: None,
+        },
+        Err(e) => LoginProbe {
+            logged_in: false,
+            email: None,
+            error: Some(e),
+        },
+    }
+}
+
+/// 执行全部检测，并把检测成功的结果写入路径配置
+pub fn run() -> OnboardingReport {
+    let executable = detect_executable();
+    let (data_dir, state) = detect_data_dir();
+    let permissions = check_permissions();
+    let login = probe_login(state);
+
+    // 已保存的可执行文件路径无需重复写入
+    let detected_executable = executable
+        .path
+        .clone()
+        .filter(|_| executable.source == Some(ExecutableSource::Detected));
+    let detected_data_dir = data_dir.path.clone().filter(|_| data_dir.database_readable);
+    let persisted = if detected_executable.is_none() && detected_data_dir.is_none() {
+        false
+    } else {
+        match crate::antigravity::path_config::save_detection(
+            detected_executable,
+            detected_data_dir,
+        ) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(target: "app::onboarding", error = %e, "保存检测结果失败");
+                false
+            }
+        }
+    };
+
+    let ready = executable.path.is_some()
+        && data_dir.database_readable
+        && permissions.config_dir_writable
+        && permissions.backup_dir_writable;
+    tracing::info!(
+        target: "app::onboarding",
+        executable = executable.path.is_some(),
+        data_dir = data_dir.database_readable,
+        logged_in = login.logged_in,
+        persisted,
+        ready,
+        "🧭 首次运行检测完成"
+    );
+
+    OnboardingReport {
+        executable,
+        data_dir,
+        permissions,
+        login,
+        persisted,
+        ready,
+    }
+}
@@ -0,0 +1,94 @@
+//! 破坏性操作互斥与限流
+//! 清除数据、删除/清空备份、导入导出、切换/恢复等操作同一时间只允许执行一个，
+//! 重叠时返回 `BUSY` 错误并带上正在执行的操作名；同一操作在短时间内重复触发返回 `RATE_LIMITED`。
+//! 在受保护操作内部再调用其他受保护命令（例如恢复流程调用切换）时直接放行
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::AgentError;
+
+/// 同一操作两次启动之间的最小间隔
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+tokio::task_local! {
+    /// 当前任务已持有的操作（用于嵌套调用放行）
+    static HELD: &'static str;
+}
+
+#[derive(Default)]
+struct GuardState {
+    /// 正在执行的操作
+    running: Option<&'static str>,
+    /// 各操作最近一次启动时间
+    last_started: HashMap<&'static str, Instant>,
+}
+
+/// 操作守卫（作为 Tauri 托管状态注册）
+#[derive(Default)]
+pub struct OperationGuard {
+    state: Mutex<GuardState>,
+}
+
+/// 执行许可，离开作用域（包括 future 被取消）时释放
+struct Permit<'a> {
+    guard: &'a OperationGuard,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.guard.state.lock() {
+            state.running = None;
+        }
+    }
+}
+
+impl OperationGuard {
+    fn acquire(&self, operation: &'static str) -> Result<Permit<'_>, AgentError> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| AgentError::Internal(format!("获取操作锁失败: {}", e)))?;
+
+        if let Some(conflicting) = state.running {
+            tracing::warn!(target: "app::operation_guard", operation = %operation, conflicting = %conflicting, "⏳ 存在进行中的操作，拒绝执行");
+            return Err(AgentError::Busy {
+                operation: operation.to_string(),
+                conflicting: conflicting.to_string(),
+            });
+        }
+
+        let now = Instant::now();
+        if let Some(last) = state.last_started.get(operation) {
+            let elapsed = now.duration_since(*last);
+            if elapsed < MIN_INTERVAL {
+                let retry_after_ms = (MIN_INTERVAL - elapsed).as_millis() as u64;
+                tracing::warn!(target: "app::operation_guard", operation = %operation, retry_after_ms = retry_after_ms, "⏳ 操作触发过于频繁");
+                return Err(AgentError::RateLimited {
+                    operation: operation.to_string(),
+                    retry_after_ms,
+                });
+            }
+        }
+
+        state.running = Some(operation);
+        state.last_started.insert(operation, now);
+        Ok(Permit { guard: self })
+    }
+
+    /// 在互斥保护下执行操作；当前任务已持有许可时直接执行
+    pub async fn run<T, E, F>(&self, operation: &'static str, future: F) -> Result<T, E>
+    where
+        E: From<AgentError>,
+        F: Future<Output = Result<T, E>>,
+    {
+        if HELD.try_with(|_| ()).is_ok() {
+            return future.await;
+        }
+
+        let _permit = self.acquire(operation).map_err(E::from)?;
+        HELD.scope(operation, future).await
+    }
+}
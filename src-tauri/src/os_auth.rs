@@ -0,0 +1,121 @@
+//! 系统身份验证
+//! 开启后，清除 Antigravity 数据、清空备份、恢复账户、明文导出等不可逆或敏感操作执行前
+//! 先通过系统身份验证（Windows Hello / Touch ID / polkit）
+
+use robius_authentication::{
+    AndroidText, BiometricStrength, Context, Policy, PolicyBuilder, Text, WindowsText,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{AgentError, AgentResult};
+
+/// Windows 验证对话框标题
+const WINDOWS_TITLE: &str = "Antigravity Agent";
+
+/// 验证通过后在这段时间内不再重复询问（切换流程会依次调用多个受保护的命令）
+const REUSE_DURATION: Duration = Duration::from_secs(60);
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 最近一次验证通过的时间
+static LAST_VERIFIED: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// 更新启用状态
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 生物识别优先，允许回退到系统密码
+fn policy() -> AgentResult<Policy> {
+    PolicyBuilder::new()
+        .biometrics(Some(BiometricStrength::Strong))
+        .password(true)
+        .build()
+        .ok_or_else(|| AgentError::AuthenticationFailed {
+            message: "当前系统不支持身份验证".to_string(),
+        })
+}
+
+/// 弹出系统身份验证（阻塞直到用户完成或取消）
+fn authenticate(reason: &str) -> AgentResult<()> {
+    let windows = WindowsText::new(WINDOWS_TITLE, reason).ok_or_else(|| {
+        AgentError::AuthenticationFailed {
+            message: "验证提示过长".to_string(),
+        }
+    })?;
+    let text = Text {
+        android: AndroidText {
+            title: WINDOWS_TITLE,
+            subtitle: None,
+            description: Some(reason),
+        },
+        apple: reason,
+        windows,
+    };
+
+    Context::new(())
+        .blocking_authenticate(text, &policy()?)
+        .map_err(|e| AgentError::AuthenticationFailed {
+            message: format!("{:?}", e),
+        })
+}
+
+/// 已启用时要求用户先通过系统身份验证，`reason` 显示在验证对话框中
+pub async fn require(operation: &str, reason: &str) -> AgentResult<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    verify(operation, reason).await
+}
+
+/// 已启用时要求用户重新通过系统身份验证，不沿用最近一次验证结果（本地接口等外部发起的操作）
+pub async fn require_fresh(operation: &str, reason: &str) -> AgentResult<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    prompt(operation, reason).await
+}
+
+/// 无论是否启用都要求通过系统身份验证（最近验证过时直接通过）
+pub async fn verify(operation: &str, reason: &str) -> AgentResult<()> {
+    let recently_verified = LAST_VERIFIED
+        .lock()
+        .ok()
+        .and_then(|last| *last)
+        .is_some_and(|at| at.elapsed() < REUSE_DURATION);
+    if recently_verified {
+        return Ok(());
+    }
+    prompt(operation, reason).await
+}
+
+/// 弹出系统身份验证，通过后记录验证时间
+async fn prompt(operation: &str, reason: &str) -> AgentResult<()> {
+    let reason = reason.to_string();
+    let result = tokio::task::spawn_blocking(move || authenticate(&reason))
+        .await
+        .map_err(|e| AgentError::AuthenticationFailed {
+            message: format!("验证任务失败: {}", e),
+        })?;
+
+    match &result {
+        Ok(()) => {
+            if let Ok(mut last) = LAST_VERIFIED.lock() {
+                *last = Some(Instant::now());
+            }
+            tracing::info!(target: "app::os_auth", operation = %operation, "🔑 系统身份验证通过");
+        }
+        Err(e) => {
+            tracing::warn!(target: "app::os_auth", operation = %operation, error = %e, "🔑 系统身份验证未通过");
+        }
+    }
+    result
+}
@@ -15,6 +15,11 @@ impl AppPaths {
     /// - macOS: ~/Library/Application Support/Antigravity/User/globalStorage/
     /// - Linux: ~/.config/Antigravity/User/globalStorage/
     pub fn antigravity_data_dir() -> Option<PathBuf> {
+        #[cfg(feature = "simulation")]
+        if crate::simulation::is_enabled() {
+            return Some(crate::simulation::data_dir());
+        }
+
         let result = antigravity_data_dir_impl();
 
         match &result {
@@ -35,6 +40,11 @@ impl AppPaths {
     /// - macOS: /Applications/Antigravity.app
     /// - Linux: /usr/bin/antigravity, ~/.local/bin/antigravity
     pub fn antigravity_executable_paths() -> Vec<PathBuf> {
+        #[cfg(feature = "simulation")]
+        if crate::simulation::is_enabled() {
+            return vec![crate::simulation::executable_path()];
+        }
+
         antigravity_executable_paths_impl()
     }
 }
@@ -0,0 +1,216 @@
+//! 局域网对等发现
+//! 开启后通过 mDNS 广播 `_antigravity-agent._tcp` 服务，并浏览同一网络中的其他实例，
+//! 使局域网传输/同步功能无需手动输入 IP 地址即可找到对端。
+//! 实例名为本机设备 ID，TXT 记录携带 `device_id`、`device_name` 与 `version`；
+//! 发现或失去对端时发出 `peer-discovered` / `peer-lost` 事件
+//!
+//! 广播只表明实例存在，与对端建立会话时仍需通过设备身份认证
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// mDNS 服务类型
+const SERVICE_TYPE: &str = "_antigravity-agent._tcp.local.";
+
+/// 服务名 `antigravity-agent` 超过 RFC 6763 建议的 15 字节，需放宽守护进程的长度限制
+const SERVICE_NAME_LEN_MAX: u8 = 30;
+
+/// 默认广播的局域网传输端口
+pub const DEFAULT_PORT: u16 = 47824;
+
+/// 对等发现设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerDiscoverySettings {
+    /// 是否启用（广播并浏览）
+    pub enabled: bool,
+    /// 广播的局域网传输端口
+    pub port: u16,
+}
+
+impl Default for PeerDiscoverySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// 发现的对端实例
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct Peer {
+    pub device_id: String,
+    /// 对端主机名
+    pub device_name: String,
+    /// 对端 Agent 版本
+    pub version: String,
+    /// 可达地址（IPv4/IPv6）
+    pub addresses: Vec<String>,
+    pub port: u16,
+    /// 最近一次解析到该实例的时间（RFC 3339）
+    pub last_seen: String,
+}
+
+/// 正在运行的发现服务
+struct Discovery {
+    daemon: ServiceDaemon,
+    fullname: String,
+}
+
+static DISCOVERY: Mutex<Option<Discovery>> = Mutex::new(None);
+
+/// 已发现的对端（键为 mDNS 完整实例名）
+static PEERS: Mutex<Option<HashMap<String, Peer>>> = Mutex::new(None);
+
+/// 当前发现的对端，按设备名排序
+pub fn peers() -> Vec<Peer> {
+    let mut peers: Vec<Peer> = PEERS
+        .lock()
+        .ok()
+        .and_then(|peers| {
+            peers
+                .as_ref()
+                .map(|peers| peers.values().cloned().collect())
+        })
+        .unwrap_or_default();
+    peers.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+    peers
+}
+
+/// 服务是否正在运行
+pub fn is_running() -> bool {
+    DISCOVERY
+        .lock()
+        .map(|discovery| discovery.is_some())
+        .unwrap_or(false)
+}
+
+/// 停止广播与浏览，清空已发现的对端
+pub fn stop() {
+    let discovery = DISCOVERY
+        .lock()
+        .ok()
+        .and_then(|mut discovery| discovery.take());
+    if let Some(discovery) = discovery {
+        let _ = discovery.daemon.unregister(&discovery.fullname);
+        let _ = discovery.daemon.shutdown();
+        tracing::info!(target: "peer_discovery::service", "🛑 局域网对等发现已停止");
+    }
+    if let Ok(mut peers) = PEERS.lock() {
+        *peers = None;
+    }
+}
+
+fn peer_from(info: &ServiceInfo) -> Option<Peer> {
+    let device_id = info.get_property_val_str("device_id")?.to_string();
+    let mut addresses: Vec<String> = info
+        .get_addresses()
+        .iter()
+        .map(|address| address.to_string())
+        .collect();
+    addresses.sort();
+    Some(Peer {
+        device_name: info
+            .get_property_val_str("device_name")
+            .unwrap_or(&device_id)
+            .to_string(),
+        version: info
+            .get_property_val_str("version")
+            .unwrap_or_default()
+            .to_string(),
+        addresses,
+        port: info.get_port(),
+        last_seen: chrono::Local::now().to_rfc3339(),
+        device_id,
+    })
+}
+
+/// 处理浏览事件，直到守护进程关闭
+fn browse(app_handle: AppHandle, receiver: mdns_sd::Receiver<ServiceEvent>, own_id: String) {
+    while let Ok(event) = receiver.recv() {
+        match event {
+            ServiceEvent::ServiceResolved(info) => {
+                let Some(peer) = peer_from(&info).filter(|peer| peer.device_id != own_id) else {
+                    continue;
+                };
+                let is_new = PEERS
+                    .lock()
+                    .map(|mut peers| {
+                        peers
+                            .get_or_insert_with(HashMap::new)
+                            .insert(info.get_fullname().to_string(), peer.clone())
+                            .is_none()
+                    })
+                    .unwrap_or(false);
+                if is_new {
+                    tracing::info!(target: "peer_discovery::browse", device_id = %peer.device_id, device_name = %peer.device_name, "📡 发现局域网对端");
+                    let _ = crate::events::emit(&app_handle, &crate::events::PeerDiscovered(peer));
+                }
+            }
+            ServiceEvent::ServiceRemoved(_, fullname) => {
+                let removed = PEERS
+                    .lock()
+                    .ok()
+                    .and_then(|mut peers| peers.as_mut()?.remove(&fullname));
+                if let Some(peer) = removed {
+                    tracing::info!(target: "peer_discovery::browse", device_id = %peer.device_id, "局域网对端已离开");
+                    let _ = crate::events::emit(&app_handle, &crate::events::PeerLost(peer));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 按设置启动或停止服务（已在运行时先停止，再以新设置启动）
+pub fn apply(app_handle: &AppHandle, settings: &PeerDiscoverySettings) -> Result<(), String> {
+    stop();
+    if !settings.enabled {
+        return Ok(());
+    }
+    crate::local_api::validate_port(settings.port)?;
+
+    let identity = crate::device_identity::get()?;
+    let daemon = ServiceDaemon::new().map_err(|e| format!("启动 mDNS 服务失败: {}", e))?;
+    daemon
+        .set_service_name_len_max(SERVICE_NAME_LEN_MAX)
+        .map_err(|e| format!("配置 mDNS 服务失败: {}", e))?;
+
+    let properties = [
+        ("device_id", identity.device_id.as_str()),
+        ("device_name", identity.device_name.as_str()),
+        ("version", env!("CARGO_PKG_VERSION")),
+    ];
+    let service = ServiceInfo::new(
+        SERVICE_TYPE,
+        &identity.device_id,
+        &format!("{}.local.", identity.device_id),
+        "",
+        settings.port,
+        &properties[..],
+    )
+    .map_err(|e| format!("构造 mDNS 服务信息失败: {}", e))?
+    .enable_addr_auto();
+    let fullname = service.get_fullname().to_string();
+
+    daemon
+        .register(service)
+        .map_err(|e| format!("广播 mDNS 服务失败: {}", e))?;
+    let receiver = daemon
+        .browse(SERVICE_TYPE)
+        .map_err(|e| format!("浏览 mDNS 服务失败: {}", e))?;
+
+    let app_handle = app_handle.clone();
+    let own_id = identity.device_id.clone();
+    std::thread::spawn(move || browse(app_handle, receiver, own_id));
+
+    if let Ok(mut discovery) = DISCOVERY.lock() {
+        *discovery = Some(Discovery { daemon, fullname });
+    }
+    tracing::info!(target: "peer_discovery::service", port = settings.port, "📡 局域网对等发现已启动");
+    Ok(())
+}
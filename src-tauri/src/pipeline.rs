@@ -0,0 +1,142 @@
+//! 账户操作流水线事件
+//! 切换、备份、恢复等长耗时操作在开始和结束时发出 `pipeline-status` 事件，
+//! 前端与托盘都据此更新状态。每次执行都作为可取消操作登记，事件中带有 `operation_id`，
+//! 前端可据此调用 `cancel_operation`
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::AppHandle;
+
+use crate::error::CoreError;
+
+/// 正在执行的流水线数量
+static ACTIVE_PIPELINES: AtomicUsize = AtomicUsize::new(0);
+
+/// 是否有流水线正在执行（切换过程中 Antigravity 会被主动关闭）
+pub fn is_any_running() -> bool {
+    ACTIVE_PIPELINES.load(Ordering::SeqCst) > 0
+}
+
+/// 流水线计数守卫，离开作用域（包括 future 被取消）时自动减一
+struct ActivePipelineGuard;
+
+impl ActivePipelineGuard {
+    fn enter() -> Self {
+        ACTIVE_PIPELINES.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for ActivePipelineGuard {
+    fn drop(&mut self) {
+        ACTIVE_PIPELINES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 流水线阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStage {
+    Started,
+    /// 执行中的进度更新（`progress` 为 0-100）
+    Progress,
+    Succeeded,
+    Failed,
+}
+
+/// `pipeline-status` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStatusPayload {
+    /// 操作名（switch / backup / restore / sign_in）
+    pub operation: String,
+    pub stage: PipelineStage,
+    /// 失败时的错误信息，或进度更新时的步骤说明
+    pub message: Option<String>,
+    /// 进度百分比（仅 `progress` 阶段）
+    #[serde(default)]
+    pub progress: Option<u8>,
+    /// 可取消操作 ID（传给 `cancel_operation` 即可取消）
+    #[serde(default)]
+    pub operation_id: Option<String>,
+}
+
+/// 发出流水线状态事件
+pub fn emit_status(
+    app_handle: &AppHandle,
+    operation: &str,
+    stage: PipelineStage,
+    message: Option<String>,
+) {
+    let payload = PipelineStatusPayload {
+        operation: operation.to_string(),
+        stage,
+        message,
+        progress: None,
+        operation_id: crate::cancellation::current_id(),
+    };
+
+    emit_payload(app_handle, &payload);
+}
+
+/// 发出流水线进度事件
+pub fn emit_progress(app_handle: &AppHandle, operation: &str, progress: u8, message: &str) {
+    let payload = PipelineStatusPayload {
+        operation: operation.to_string(),
+        stage: PipelineStage::Progress,
+        message: Some(message.to_string()),
+        progress: Some(progress.min(100)),
+        operation_id: crate::cancellation::current_id(),
+    };
+
+    emit_payload(app_handle, &payload);
+}
+
+fn emit_payload(app_handle: &AppHandle, payload: &PipelineStatusPayload) {
+    // 错误信息可能包含原始响应内容，发给前端前移除凭据
+    let payload = PipelineStatusPayload {
+        message: payload
+            .message
+            .as_deref()
+            .map(crate::utils::log_sanitizer::redact_secrets),
+        ..payload.clone()
+    };
+
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "pipeline::event", operation = %payload.operation, error = %e, "发射流水线状态事件失败");
+    }
+}
+
+/// 执行一个流水线操作，并在开始与结束时发出状态事件
+///
+/// 操作被取消时发出 `failed` 状态（错误信息为取消原因）
+pub async fn run<T, E, F>(app_handle: &AppHandle, operation: &str, future: F) -> Result<T, E>
+where
+    E: std::fmt::Display + From<CoreError>,
+    F: Future<Output = Result<T, E>>,
+{
+    let operation_id = crate::cancellation::new_operation_id();
+    let result =
+        crate::cancellation::run(app_handle, operation, Some(operation_id.clone()), async {
+            emit_status(app_handle, operation, PipelineStage::Started, None);
+            let _active = ActivePipelineGuard::enter();
+            crate::slow_operation::measure_async(&format!("pipeline::{}", operation), future).await
+        })
+        .await;
+
+    // 取消时内部 future 已被丢弃，结束事件在取消作用域之外发出，需显式带上 ID
+    let (stage, message) = match &result {
+        Ok(_) => (PipelineStage::Succeeded, None),
+        Err(e) => (PipelineStage::Failed, Some(e.to_string())),
+    };
+    let payload = PipelineStatusPayload {
+        operation: operation.to_string(),
+        stage,
+        message,
+        progress: None,
+        operation_id: Some(operation_id),
+    };
+    emit_payload(app_handle, &payload);
+
+    result
+}
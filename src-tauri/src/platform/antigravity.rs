@@ -21,6 +21,11 @@ pub fn is_antigravity_available() -> bool {
 
 /// 搜索可能的Antigravity安装位置
 pub fn find_antigravity_installations() -> Vec<PathBuf> {
+    #[cfg(feature = "simulation")]
+    if crate::simulation::is_enabled() {
+        return vec![crate::simulation::antigravity_root()];
+    }
+
     let mut possible_paths = Vec::new();
 
     // 用户数据目录
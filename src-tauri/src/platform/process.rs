@@ -1,5 +1,14 @@
+use crate::error::{CoreError, CoreResult};
+
 /// 关闭Antigravity进程 - 使用sysinfo库实现跨平台统一处理
-pub fn kill_antigravity_processes() -> Result<String, String> {
+///
+/// 没有匹配的进程时返回 [`CoreError::NotFound`]（调用方可用 `is_not_found` 判断并跳过）
+pub fn kill_antigravity_processes() -> CoreResult<String> {
+    #[cfg(feature = "simulation")]
+    if crate::simulation::is_enabled() {
+        return crate::simulation::kill();
+    }
+
     tracing::info!("🔍 开始搜索并关闭 Antigravity 进程");
 
     // 使用sysinfo库获取所有进程
@@ -41,7 +50,7 @@ pub fn kill_antigravity_processes() -> Result<String, String> {
     if killed_processes.is_empty() {
         tracing::info!("ℹ️ 未找到匹配的 Antigravity 进程");
         tracing::info!("🔍 搜索的进程模式: {:?}", process_patterns);
-        Err("未找到Antigravity进程".to_string())
+        Err(CoreError::not_found("Antigravity 进程"))
     } else {
         let success_msg = format!("已成功关闭Antigravity进程: {}", killed_processes.join(", "));
         tracing::info!("🎉 {}", success_msg);
@@ -51,6 +60,11 @@ pub fn kill_antigravity_processes() -> Result<String, String> {
 
 /// 检查 Antigravity 进程是否正在运行（使用 sysinfo）
 pub fn is_antigravity_running() -> bool {
+    #[cfg(feature = "simulation")]
+    if crate::simulation::is_enabled() {
+        return crate::simulation::is_running();
+    }
+
     tracing::debug!("🔍 检查 Antigravity 进程是否运行");
 
     let mut system = sysinfo::System::new_all();
@@ -0,0 +1,275 @@
+//! 插件脚本引擎
+//! 从配置目录下的 `plugins/` 加载 Rhai 脚本（`*.rhai`），每个脚本即一个插件，文件名为插件名：
+//! - `fn on_account_changed(data)` / `fn on_backup_created(data)` / `fn on_antigravity_stopped(data)`：
+//!   事件处理函数，事件数据与钩子脚本相同
+//! - `fn command_<名称>(args)`：自定义命令，前端通过 `run_plugin_command` 调用，返回值转为 JSON
+//!
+//! 脚本只能使用受限的 API，不提供文件、网络与进程访问，并限制运算次数、调用深度与数据大小：
+//! - `accounts()`：备份账户的只读元数据（`account`、`email`、`plan`、`token_expiry`）
+//! - `notify(body)`：发送系统通知
+//! - `log(message)`：写入诊断日志
+
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::hooks::HookEvent;
+
+/// 插件脚本扩展名
+const PLUGIN_EXTENSION: &str = "rhai";
+
+/// 自定义命令函数名前缀
+const COMMAND_PREFIX: &str = "command_";
+
+/// 事件处理函数名前缀
+const HANDLER_PREFIX: &str = "on_";
+
+// 沙箱限制
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+const MAX_COLLECTION_SIZE: usize = 10_000;
+
+/// 插件信息
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct PluginInfo {
+    /// 插件名（脚本文件名，不含扩展名）
+    pub name: String,
+    pub path: String,
+    /// 自定义命令名（不含 `command_` 前缀）
+    pub commands: Vec<String>,
+    /// 处理的事件
+    pub handlers: Vec<HookEvent>,
+    /// 编译失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 已加载的插件
+struct LoadedPlugin {
+    info: PluginInfo,
+    ast: Option<AST>,
+}
+
+static PLUGINS: Mutex<Vec<LoadedPlugin>> = Mutex::new(Vec::new());
+
+/// 插件目录
+pub fn plugins_directory() -> PathBuf {
+    crate::directories::get_config_directory().join("plugins")
+}
+
+fn handler_name(event: HookEvent) -> &'static str {
+    match event {
+        HookEvent::AccountChanged => "on_account_changed",
+        HookEvent::BackupCreated => "on_backup_created",
+        HookEvent::AntigravityStopped => "on_antigravity_stopped",
+    }
+}
+
+const EVENTS: [HookEvent; 3] = [
+    HookEvent::AccountChanged,
+    HookEvent::BackupCreated,
+    HookEvent::AntigravityStopped,
+];
+
+/// 只读账户元数据
+#[derive(Serialize)]
+struct AccountMetadata {
+    account: String,
+    email: String,
+    plan: String,
+    token_expiry: Option<String>,
+}
+
+fn accounts() -> Result<Dynamic, Box<rhai::EvalAltResult>> {
+    let accounts: Vec<AccountMetadata> = crate::antigravity::report::build()?
+        .into_iter()
+        .map(|row| AccountMetadata {
+            account: row.account,
+            email: row.email,
+            plan: row.plan,
+            token_expiry: row.token_expiry,
+        })
+        .collect();
+    rhai::serde::to_dynamic(accounts)
+}
+
+/// 构造受限的脚本引擎，`app_handle` 为空时（仅编译）不注册需要应用的函数
+fn engine(app_handle: Option<&AppHandle>, plugin: &str) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        .disable_symbol("eval");
+
+    let name = plugin.to_string();
+    engine.on_print(
+        move |message| tracing::info!(target: "plugins::script", plugin = %name, "{}", message),
+    );
+    let name = plugin.to_string();
+    engine.on_debug(move |message, _, _| {
+        tracing::debug!(target: "plugins::script", plugin = %name, "{}", message)
+    });
+    let name = plugin.to_string();
+    engine.register_fn("log", move |message: &str| {
+        tracing::info!(target: "plugins::script", plugin = %name, "{}", message)
+    });
+    engine.register_fn("accounts", accounts);
+
+    if let Some(app_handle) = app_handle {
+        let app_handle = app_handle.clone();
+        engine.register_fn("notify", move |body: &str| {
+            crate::notifications::notify(
+                &app_handle,
+                crate::notifications::NotificationKind::Plugin,
+                body,
+            )
+        });
+    }
+    engine
+}
+
+fn compile(path: &std::path::Path, name: &str) -> LoadedPlugin {
+    let mut info = PluginInfo {
+        name: name.to_string(),
+        path: path.display().to_string(),
+        commands: Vec::new(),
+        handlers: Vec::new(),
+        error: None,
+    };
+
+    match engine(None, name).compile_file(path.to_path_buf()) {
+        Ok(ast) => {
+            for function in ast.iter_functions() {
+                if let Some(command) = function.name.strip_prefix(COMMAND_PREFIX) {
+                    info.commands.push(command.to_string());
+                } else if function.name.starts_with(HANDLER_PREFIX) {
+                    if let Some(event) = EVENTS
+                        .into_iter()
+                        .find(|event| handler_name(*event) == function.name)
+                    {
+                        info.handlers.push(event);
+                    }
+                }
+            }
+            info.commands.sort();
+            LoadedPlugin {
+                info,
+                ast: Some(ast),
+            }
+        }
+        Err(e) => {
+            tracing::warn!(target: "plugins::load", plugin = %name, error = %e, "插件编译失败");
+            info.error = Some(e.to_string());
+            LoadedPlugin { info, ast: None }
+        }
+    }
+}
+
+/// 重新加载插件目录中的所有脚本，返回插件列表
+pub fn load() -> Vec<PluginInfo> {
+    let dir = plugins_directory();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.is_file() && path.extension().is_some_and(|ext| ext == PLUGIN_EXTENSION)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    let loaded: Vec<LoadedPlugin> = paths
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some(compile(path, &name))
+        })
+        .collect();
+    let infos = loaded.iter().map(|plugin| plugin.info.clone()).collect();
+
+    tracing::info!(target: "plugins::load", count = loaded.len(), dir = %dir.display(), "🧩 插件已加载");
+    if let Ok(mut plugins) = PLUGINS.lock() {
+        *plugins = loaded;
+    }
+    infos
+}
+
+/// 当前已加载的插件
+pub fn list() -> Vec<PluginInfo> {
+    PLUGINS
+        .lock()
+        .map(|plugins| plugins.iter().map(|plugin| plugin.info.clone()).collect())
+        .unwrap_or_default()
+}
+
+fn call(
+    app_handle: &AppHandle,
+    plugin: &str,
+    ast: &AST,
+    function: &str,
+    data: &Value,
+) -> Result<Value, String> {
+    let argument = rhai::serde::to_dynamic(data).map_err(|e| e.to_string())?;
+    let result: Dynamic = engine(Some(app_handle), plugin)
+        .call_fn(&mut Scope::new(), ast, function, (argument,))
+        .map_err(|e| format!("插件 {} 执行 {} 失败: {}", plugin, function, e))?;
+    rhai::serde::from_dynamic(&result).map_err(|e| format!("插件返回值无法转换为 JSON: {}", e))
+}
+
+/// 查找定义了指定函数的插件，返回 (插件名, AST)
+fn find(plugin: Option<&str>, function: &str) -> Vec<(String, AST)> {
+    PLUGINS
+        .lock()
+        .map(|plugins| {
+            plugins
+                .iter()
+                .filter(|loaded| plugin.is_none_or(|plugin| loaded.info.name == plugin))
+                .filter_map(|loaded| {
+                    let ast = loaded.ast.as_ref()?;
+                    ast.iter_functions()
+                        .any(|f| f.name == function)
+                        .then(|| (loaded.info.name.clone(), ast.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 运行插件的自定义命令
+pub fn run_command(
+    app_handle: &AppHandle,
+    plugin: &str,
+    command: &str,
+    args: &Value,
+) -> Result<Value, String> {
+    let function = format!("{}{}", COMMAND_PREFIX, command);
+    let (name, ast) = find(Some(plugin), &function)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("插件 {} 没有命令 {}", plugin, command))?;
+    call(app_handle, &name, &ast, &function, args)
+}
+
+/// 调用所有处理该事件的插件（后台执行，不阻塞调用方）
+pub fn dispatch(app_handle: &AppHandle, event: HookEvent, data: &Value) {
+    let function = handler_name(event);
+    for (name, ast) in find(None, function) {
+        let app_handle = app_handle.clone();
+        let data = data.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = call(&app_handle, &name, &ast, function, &data) {
+                tracing::warn!(target: "plugins::event", plugin = %name, handler = function, error = %e, "插件事件处理失败");
+            }
+        });
+    }
+}
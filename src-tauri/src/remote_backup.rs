@@ -0,0 +1,588 @@
+//! 远程备份（S3 兼容对象存储）
+//! 把账户备份推送到 S3 / MinIO 等兼容对象存储的指定桶与前缀下、从远程拉取到本地备份目录，
+//! 并对比列出两边各有哪些备份。请求使用 AWS Signature V4 签名，只允许 HTTPS 端点；
+//! Secret Access Key 保存在系统钥匙串中，设置文件只保存端点、桶、前缀与 Access Key ID。
+//!
+//! 上传的是自包含的备份：钥匙串中的登录状态会先组装回去，再按本机的压缩与加密设置封装，
+//! 启用备份加密时远程只保存密文（拉取到其他机器时需要相同的备份密钥）
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+use crate::antigravity::backup_archive::{
+    ArchiveImportAction, ArchiveImportItem, ArchiveImportReport, ConflictStrategy,
+};
+use crate::cancellation::CancellationToken;
+use crate::utils::blocking;
+
+/// 钥匙串服务名与 Secret Access Key 条目名
+const KEYCHAIN_SERVICE: &str = "antigravity-agent";
+const KEYCHAIN_USER: &str = "s3-secret-access-key";
+
+/// 参与签名的请求头（按字母顺序）
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// 单次列举返回的对象数上限（S3 默认值）
+const LIST_PAGE_SIZE: &str = "1000";
+
+/// 单个远程备份的大小上限
+const MAX_OBJECT_BYTES: usize = 8 * 1024 * 1024;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// S3 远程备份配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct S3BackupSettings {
+    /// 端点（如 `https://s3.us-east-1.amazonaws.com` 或 MinIO 地址），只允许 HTTPS
+    pub endpoint: String,
+    /// 区域（MinIO 一般为 `us-east-1`）
+    #[serde(default = "default_region")]
+    pub region: String,
+    pub bucket: String,
+    /// 对象键前缀（如 `antigravity/`），为空时放在桶根目录
+    #[serde(default)]
+    pub prefix: String,
+    pub access_key_id: String,
+    /// 使用路径风格地址（`<端点>/<桶>`，MinIO 通常需要）；否则使用虚拟主机风格（`<桶>.<端点>`）
+    #[serde(default)]
+    pub path_style: bool,
+}
+
+/// 远程备份配置状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct S3BackupStatus {
+    pub settings: Option<S3BackupSettings>,
+    /// 钥匙串中是否已保存 Secret Access Key
+    pub secret_stored: bool,
+}
+
+/// 本地与远程备份对比中的一项
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RemoteBackupEntry {
+    /// 备份名（账户邮箱）
+    pub name: String,
+    /// 本地是否存在
+    pub local: bool,
+    /// 远程是否存在
+    pub remote: bool,
+    /// 本地备份修改时间（RFC 3339）
+    pub local_modified: Option<String>,
+    /// 远程对象修改时间（RFC 3339）
+    pub remote_modified: Option<String>,
+    /// 远程对象大小（字节）
+    pub remote_size: Option<u64>,
+}
+
+/// 推送失败的备份
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RemotePushFailure {
+    pub name: String,
+    pub error: String,
+}
+
+/// 推送结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RemotePushReport {
+    pub uploaded: usize,
+    pub failed: Vec<RemotePushFailure>,
+}
+
+/// 远程对象
+struct RemoteObject {
+    name: String,
+    size: u64,
+    last_modified: String,
+}
+
+/// 校验配置
+pub fn validate(settings: &S3BackupSettings) -> Result<(), String> {
+    let url =
+        reqwest::Url::parse(&settings.endpoint).map_err(|e| format!("端点地址无效: {}", e))?;
+    if url.scheme() != "https" {
+        return Err("端点必须使用 HTTPS".to_string());
+    }
+    if url.host_str().is_none() || url.path().trim_matches('/') != "" || url.query().is_some() {
+        return Err("端点只能包含协议、主机与端口".to_string());
+    }
+    if settings.region.trim().is_empty() {
+        return Err("区域不能为空".to_string());
+    }
+    let bucket = &settings.bucket;
+    if bucket.len() < 3
+        || bucket.len() > 63
+        || !bucket
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+    {
+        return Err(format!("桶名称无效: {}", bucket));
+    }
+    if settings.access_key_id.trim().is_empty() {
+        return Err("Access Key ID 不能为空".to_string());
+    }
+    Ok(())
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+        .map_err(|e| format!("打开钥匙串条目失败: {}", e))
+}
+
+/// 把 Secret Access Key 保存到钥匙串
+pub fn store_secret(secret: &str) -> Result<(), String> {
+    keychain_entry()?
+        .set_password(secret)
+        .map_err(|e| format!("写入钥匙串失败: {}", e))
+}
+
+/// 钥匙串中是否已保存 Secret Access Key
+pub fn has_secret() -> bool {
+    keychain_entry().is_ok_and(|entry| entry.get_password().is_ok())
+}
+
+/// 删除钥匙串中的 Secret Access Key（条目不存在时忽略）
+pub fn delete_secret() {
+    if let Ok(entry) = keychain_entry() {
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => {
+                tracing::warn!(target: "backup::remote", error = %e, "删除钥匙串中的 S3 密钥失败");
+            }
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hmac(key: &[u8], data: &str) -> Result<Vec<u8>, String> {
+    let mut mac = HmacSha256::new_from_slice(key).map_err(|e| format!("初始化签名失败: {}", e))?;
+    mac.update(data.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// SigV4 的 URI 编码：只保留非保留字符，`encode_slash` 为 false 时保留路径分隔符
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// 取出 XML 中第一个指定标签的文本（S3 响应结构简单，不需要完整的 XML 解析）
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    Some(
+        xml[start..end]
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// 规范化前缀：去掉开头的 `/`，非空时以 `/` 结尾
+fn normalized_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim().trim_start_matches('/');
+    if prefix.is_empty() || prefix.ends_with('/') {
+        prefix.to_string()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+/// 已签名请求的 S3 客户端
+struct S3Client {
+    settings: S3BackupSettings,
+    secret: Zeroizing<String>,
+    http: reqwest::Client,
+    /// 请求使用的主机（含非默认端口）
+    host: String,
+    /// 桶的路径（路径风格为 `/<桶>`，虚拟主机风格为空）
+    bucket_path: String,
+    prefix: String,
+}
+
+impl S3Client {
+    fn new(settings: &S3BackupSettings) -> Result<Self, String> {
+        validate(settings)?;
+        let secret = Zeroizing::new(
+            keychain_entry()?
+                .get_password()
+                .map_err(|_| "未保存 Secret Access Key，请先在远程备份设置中填写".to_string())?,
+        );
+        let url =
+            reqwest::Url::parse(&settings.endpoint).map_err(|e| format!("端点地址无效: {}", e))?;
+        let mut host = url.host_str().unwrap_or_default().to_string();
+        if let Some(port) = url.port() {
+            host = format!("{}:{}", host, port);
+        }
+        let (host, bucket_path) = if settings.path_style {
+            (host, format!("/{}", uri_encode(&settings.bucket, true)))
+        } else {
+            (format!("{}.{}", settings.bucket, host), String::new())
+        };
+
+        Ok(Self {
+            settings: settings.clone(),
+            secret,
+            http: crate::http_client::shared()?,
+            host,
+            bucket_path,
+            prefix: normalized_prefix(&settings.prefix),
+        })
+    }
+
+    fn object_key(&self, name: &str) -> String {
+        format!("{}{}.json", self.prefix, name)
+    }
+
+    /// 发送签名请求，非 2xx 响应转换为错误
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: Option<&str>,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, String> {
+        let path = format!(
+            "{}/{}",
+            self.bucket_path,
+            key.map(|key| uri_encode(key, false)).unwrap_or_default()
+        );
+        let mut params: Vec<(String, String)> = query
+            .iter()
+            .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+            .collect();
+        params.sort();
+        let query = params
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, query, self.host, payload_hash, amz_date, SIGNED_HEADERS, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.settings.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let mut signing_key = hmac(format!("AWS4{}", self.secret.as_str()).as_bytes(), &date)?;
+        for part in [self.settings.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac(&signing_key, part)?;
+        }
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.settings.access_key_id,
+            scope,
+            SIGNED_HEADERS,
+            hex(&hmac(&signing_key, &string_to_sign)?)
+        );
+
+        let mut url = format!("https://{}{}", self.host, path);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        let response = self
+            .http
+            .request(method, &url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("请求对象存储失败: {}", e))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let reason = xml_tag(&text, "Message")
+            .or_else(|| xml_tag(&text, "Code"))
+            .unwrap_or_else(|| status.to_string());
+        Err(format!(
+            "对象存储返回错误 ({}): {}",
+            status.as_u16(),
+            reason
+        ))
+    }
+
+    /// 列出前缀下的备份对象（不含子目录中的对象）
+    async fn list(&self, token: &CancellationToken) -> Result<Vec<RemoteObject>, String> {
+        let mut objects = Vec::new();
+        let mut continuation = None;
+        loop {
+            token.check()?;
+            let mut query = vec![
+                ("list-type", "2"),
+                ("max-keys", LIST_PAGE_SIZE),
+                ("prefix", self.prefix.as_str()),
+            ];
+            if let Some(continuation) = continuation.as_deref() {
+                query.push(("continuation-token", continuation));
+            }
+            let xml = self
+                .send(reqwest::Method::GET, None, &query, Vec::new())
+                .await?
+                .text()
+                .await
+                .map_err(|e| format!("读取对象列表失败: {}", e))?;
+
+            for contents in xml.split("<Contents>").skip(1) {
+                let contents = contents.split("</Contents>").next().unwrap_or_default();
+                let Some(key) = xml_tag(contents, "Key") else {
+                    continue;
+                };
+                let Some(name) = key
+                    .strip_prefix(&self.prefix)
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .filter(|name| !name.is_empty() && !name.contains('/'))
+                else {
+                    continue;
+                };
+                objects.push(RemoteObject {
+                    name: name.to_string(),
+                    size: xml_tag(contents, "Size")
+                        .and_then(|size| size.parse().ok())
+                        .unwrap_or(0),
+                    last_modified: xml_tag(contents, "LastModified").unwrap_or_default(),
+                });
+            }
+
+            continuation = xml_tag(&xml, "NextContinuationToken");
+            if xml_tag(&xml, "IsTruncated").as_deref() != Some("true") || continuation.is_none() {
+                return Ok(objects);
+            }
+        }
+    }
+
+    async fn put(&self, name: &str, body: Vec<u8>) -> Result<(), String> {
+        self.send(
+            reqwest::Method::PUT,
+            Some(&self.object_key(name)),
+            &[],
+            body,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        let bytes = self
+            .send(
+                reqwest::Method::GET,
+                Some(&self.object_key(name)),
+                &[],
+                Vec::new(),
+            )
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| format!("下载备份失败: {}", e))?;
+        if bytes.len() > MAX_OBJECT_BYTES {
+            return Err(format!("远程备份过大（{} 字节）", bytes.len()));
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+/// 本地备份名 -> 修改时间（RFC 3339）
+async fn local_backups() -> Result<BTreeMap<String, String>, String> {
+    blocking::run("列出本地备份", || {
+        let backups = crate::antigravity::backup::list_backup_files()?
+            .into_iter()
+            .map(|entry| {
+                let modified: chrono::DateTime<Utc> = entry.modified.into();
+                (entry.name, modified.to_rfc3339())
+            })
+            .collect();
+        Ok::<_, String>(backups)
+    })
+    .await
+}
+
+/// 对比列出本地与远程的备份（按名称排序）
+pub async fn list(
+    settings: &S3BackupSettings,
+    token: &CancellationToken,
+) -> Result<Vec<RemoteBackupEntry>, String> {
+    let client = S3Client::new(settings)?;
+    let remote = client.list(token).await?;
+    let mut entries: BTreeMap<String, RemoteBackupEntry> = local_backups()
+        .await?
+        .into_iter()
+        .map(|(name, modified)| {
+            let entry = RemoteBackupEntry {
+                name: name.clone(),
+                local: true,
+                remote: false,
+                local_modified: Some(modified),
+                remote_modified: None,
+                remote_size: None,
+            };
+            (name, entry)
+        })
+        .collect();
+    for object in remote {
+        let entry = entries
+            .entry(object.name.clone())
+            .or_insert_with(|| RemoteBackupEntry {
+                name: object.name,
+                local: false,
+                remote: false,
+                local_modified: None,
+                remote_modified: None,
+                remote_size: None,
+            });
+        entry.remote = true;
+        entry.remote_modified = Some(object.last_modified);
+        entry.remote_size = Some(object.size);
+    }
+    Ok(entries.into_values().collect())
+}
+
+/// 推送本地备份到远程（`names` 为空时推送全部），同名对象直接覆盖
+///
+/// 每上传一个备份前检查 `token`，已取消时返回取消错误（已上传的保留）
+pub async fn push(
+    settings: &S3BackupSettings,
+    names: Option<Vec<String>>,
+    token: &CancellationToken,
+) -> Result<RemotePushReport, String> {
+    let client = S3Client::new(settings)?;
+    // 先在阻塞线程池中读取并封装备份，组装钥匙串中的登录状态
+    let contents = blocking::run("读取备份", move || {
+        let mut contents = Vec::new();
+        for entry in crate::antigravity::backup::list_backup_files()? {
+            if names
+                .as_ref()
+                .is_some_and(|names| !names.contains(&entry.name))
+            {
+                continue;
+            }
+            let content = crate::antigravity::keychain::read_backup(&entry.path)
+                .and_then(|data| crate::antigravity::vault::seal_backup(&data));
+            contents.push((entry.name, content));
+        }
+        Ok::<_, String>(contents)
+    })
+    .await?;
+
+    let mut report = RemotePushReport {
+        uploaded: 0,
+        failed: Vec::new(),
+    };
+    for (name, content) in contents {
+        token.check()?;
+        match content {
+            Ok(content) => match client.put(&name, content.into_bytes()).await {
+                Ok(()) => report.uploaded += 1,
+                Err(error) => report.failed.push(RemotePushFailure { name, error }),
+            },
+            Err(error) => report.failed.push(RemotePushFailure { name, error }),
+        }
+    }
+
+    tracing::info!(target: "backup::remote", uploaded = report.uploaded, failed = report.failed.len(), "☁️ 已推送备份到对象存储");
+    Ok(report)
+}
+
+/// 从远程拉取备份到本地备份目录（`names` 为空时拉取全部），同名时按 `strategy` 处理
+///
+/// 每下载一个备份前检查 `token`，已取消时返回取消错误（已写入的备份保留）
+pub async fn pull(
+    settings: &S3BackupSettings,
+    names: Option<Vec<String>>,
+    strategy: ConflictStrategy,
+    token: &CancellationToken,
+) -> Result<ArchiveImportReport, String> {
+    let client = S3Client::new(settings)?;
+    let mut downloads = Vec::new();
+    for object in client.list(token).await? {
+        if names
+            .as_ref()
+            .is_some_and(|names| !names.contains(&object.name))
+        {
+            continue;
+        }
+        token.check()?;
+        let content = client.get(&object.name).await;
+        downloads.push((object.name, content));
+    }
+
+    let report = blocking::run("写入备份", move || {
+        let accounts_dir = crate::directories::get_accounts_directory();
+        let items = downloads
+            .into_iter()
+            .map(|(name, content)| {
+                let filename = format!("{}.json", name);
+                // 远程保存的是封装后的备份，先解开加密与压缩
+                let plaintext = content.and_then(|bytes| {
+                    let text = String::from_utf8(bytes)
+                        .map_err(|_| "备份内容不是有效的文本".to_string())?;
+                    let data = crate::antigravity::vault::open(&text)?;
+                    serde_json::to_vec(&data)
+                        .map(Zeroizing::new)
+                        .map_err(|e| format!("序列化备份失败: {}", e))
+                });
+                match plaintext {
+                    Ok(bytes) => crate::antigravity::backup_archive::import_backup(
+                        &accounts_dir,
+                        filename,
+                        &bytes,
+                        strategy,
+                    ),
+                    Err(error) => ArchiveImportItem {
+                        filename,
+                        action: ArchiveImportAction::Failed,
+                        target: None,
+                        error: Some(error),
+                    },
+                }
+            })
+            .collect();
+        Ok::<_, String>(crate::antigravity::backup_archive::summarize(items))
+    })
+    .await?;
+
+    tracing::info!(
+        target: "backup::remote",
+        strategy = ?strategy,
+        restored = report.restored_count,
+        skipped = report.skipped_count,
+        failed = report.failed_count,
+        "☁️ 已从对象存储拉取备份"
+    );
+    Ok(report)
+}
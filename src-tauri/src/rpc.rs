@@ -0,0 +1,221 @@
+//! JSON-RPC over stdio 自动化模式
+//! 以 `antigravity-agent --rpc` 启动时，应用照常初始化但不显示主窗口，并在 stdin/stdout 上
+//! 按行收发 JSON-RPC 2.0 消息，供编辑器插件、测试脚本等无需 HTTP 即可驱动 Agent。
+//! 方法名与 Tauri 命令同名，参数对象使用 snake_case 字段名，例如：
+//! `{"jsonrpc":"2.0","id":1,"method":"switch_to_antigravity_account","params":{"account_name":"work@example.com"}}`
+//!
+//! 此模式下控制台日志改写到 stderr，stdout 只输出协议消息；stdin 关闭且进行中的调用完成后应用退出
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+use crate::error::AgentError;
+
+/// 启动自动化模式的命令行参数
+pub const RPC_ARG: &str = "--rpc";
+
+// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// 命令执行失败，`data` 为命令错误（`{ code, message, context }`）
+const COMMAND_ERROR: i64 = -32000;
+
+/// 是否以自动化模式启动
+pub fn is_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::args().any(|arg| arg == RPC_ARG))
+}
+
+/// 调用失败
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl From<AgentError> for RpcError {
+    fn from(error: AgentError) -> Self {
+        Self {
+            code: COMMAND_ERROR,
+            message: error.to_string(),
+            data: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+fn params<T: DeserializeOwned>(params: &Value) -> Result<T, RpcError> {
+    let params = if params.is_null() {
+        json!({})
+    } else {
+        params.clone()
+    };
+    serde_json::from_value(params).map_err(|e| RpcError {
+        code: INVALID_PARAMS,
+        message: format!("参数无效: {}", e),
+        data: None,
+    })
+}
+
+fn result<T: Serialize, E: Into<AgentError>>(result: Result<T, E>) -> Result<Value, RpcError> {
+    let value = result.map_err(|e| RpcError::from(e.into()))?;
+    serde_json::to_value(value).map_err(|e| RpcError::from(AgentError::decode(e)))
+}
+
+#[derive(Deserialize)]
+struct AccountParams {
+    account_name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ListAccountsParams {
+    sanitize: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct DecodedSessionParams {
+    redact_secrets: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AuditLogParams {
+    limit: Option<usize>,
+}
+
+/// 将方法映射到对应的命令实现
+async fn call(app: &AppHandle, method: &str, raw: &Value) -> Result<Value, RpcError> {
+    use crate::commands;
+
+    match method {
+        "get_antigravity_accounts" => {
+            let p: ListAccountsParams = params(raw)?;
+            result(commands::get_antigravity_accounts(app.state(), p.sanitize).await)
+        }
+        "get_current_antigravity_account_info" => {
+            result(commands::get_current_antigravity_account_info().await)
+        }
+        "get_decoded_session_state" => {
+            let p: DecodedSessionParams = params(raw)?;
+            result(commands::get_decoded_session_state(p.redact_secrets).await)
+        }
+        "get_available_models" => result(commands::get_available_models().await),
+        "save_antigravity_current_account" => {
+            result(commands::save_antigravity_current_account(app.clone()).await)
+        }
+        "switch_to_antigravity_account" => {
+            let p: AccountParams = params(raw)?;
+            result(commands::switch_to_antigravity_account(app.clone(), p.account_name).await)
+        }
+        "restore_antigravity_account" => {
+            let p: AccountParams = params(raw)?;
+            result(commands::restore_antigravity_account(app.clone(), p.account_name).await)
+        }
+        "preview_restore" => {
+            let p: AccountParams = params(raw)?;
+            result(commands::preview_restore(p.account_name).await)
+        }
+        "is_antigravity_running" => result(Ok::<_, AgentError>(
+            commands::is_antigravity_running().await,
+        )),
+        "get_app_lock_status" => result(commands::get_app_lock_status().await),
+        "get_audit_log" => {
+            let p: AuditLogParams = params(raw)?;
+            result(commands::get_audit_log(p.limit).await)
+        }
+        other => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("不支持的方法: {}", other),
+            data: None,
+        }),
+    }
+}
+
+/// 处理一行输入，通知（无 id）不返回响应
+async fn handle(app: &AppHandle, line: &str) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => {
+            return Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": PARSE_ERROR, "message": format!("JSON 解析失败: {}", e) },
+            }))
+        }
+    };
+
+    let id = message.get("id").cloned();
+    let response = match message["method"].as_str() {
+        Some(method) => {
+            tracing::info!(target: "rpc::call", method = method, "📨 收到 RPC 调用");
+            call(app, method, &message["params"]).await
+        }
+        None => Err(RpcError {
+            code: INVALID_REQUEST,
+            message: "缺少 method".to_string(),
+            data: None,
+        }),
+    };
+
+    let id = id?;
+    Some(match response {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => {
+            let mut body = json!({ "code": error.code, "message": error.message });
+            if let Some(data) = error.data {
+                body["data"] = data;
+            }
+            json!({ "jsonrpc": "2.0", "id": id, "error": body })
+        }
+    })
+}
+
+fn write_line(value: &Value) {
+    // 持有 stdout 锁写完整行，避免并发响应的内容交错
+    let mut stdout = std::io::stdout().lock();
+    if writeln!(stdout, "{}", value)
+        .and_then(|_| stdout.flush())
+        .is_err()
+    {
+        tracing::warn!(target: "rpc::write", "写入 stdout 失败");
+    }
+}
+
+/// 开始读取 stdin（每个请求并发执行，响应顺序可能与请求顺序不同，按 id 对应）
+pub fn start(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        tracing::info!(target: "rpc::start", "🔌 JSON-RPC 自动化模式已启动");
+        let mut pending = Vec::new();
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let app_handle = app_handle.clone();
+            pending.push(tauri::async_runtime::spawn(async move {
+                if let Some(response) = handle(&app_handle, &line).await {
+                    write_line(&response);
+                }
+            }));
+            pending.retain(|task| !task.inner().is_finished());
+        }
+
+        // 等待进行中的调用写出响应后再退出
+        tauri::async_runtime::block_on(async {
+            for task in pending {
+                let _ = task.await;
+            }
+        });
+        tracing::info!(target: "rpc::start", "stdin 已关闭，退出应用");
+        app_handle.exit(0);
+    });
+}
@@ -0,0 +1,12 @@
+//! 运行 ID
+//! 每次启动生成一个 UUID，写入每一行文件日志并附加到发往前端的事件负载（见 `events::emit`），
+//! 便于区分多次运行交错在同一日志文件中的记录
+
+use std::sync::OnceLock;
+
+static RUN_ID: OnceLock<String> = OnceLock::new();
+
+/// 本次运行的 ID（首次调用时生成）
+pub fn run_id() -> &'static str {
+    RUN_ID.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
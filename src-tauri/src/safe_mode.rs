@@ -0,0 +1,32 @@
+//! 安全模式
+//! 开启后清除 Antigravity 数据、删除/清空备份以及直接写入数据库键值等破坏性命令一律拒绝执行，
+//! 适合把电脑交给他人使用或测试时防止误删数据
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 更新启用状态
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 破坏性命令执行前调用：安全模式下返回拒绝错误
+pub fn ensure_allowed(operation: &str) -> AgentResult<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    tracing::warn!(target: "app::safe_mode", operation = %operation, "🛡️ 安全模式已开启，拒绝执行破坏性命令");
+    Err(AgentError::SafeMode {
+        operation: operation.to_string(),
+    })
+}
@@ -0,0 +1,134 @@
+//! 计划任务
+//! 汇总后台按固定间隔运行的自动任务及其下一次运行时间，并导出为 iCalendar（`.ics`）文件，
+//! 方便在日历应用中查看。目前只有日志保留清理，新增的定时任务需在 `upcoming` 中登记
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+
+/// 日历条目的时长（分钟）
+const EVENT_DURATION_MINUTES: i64 = 5;
+
+/// iCalendar 单行最大字节数（超出需折行）
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 计划任务
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ScheduledJob {
+    /// 稳定的任务 ID（用作日历条目 UID）
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// 下一次运行时间（RFC 3339）
+    pub next_run: String,
+    /// 运行间隔（秒）
+    pub interval_secs: u64,
+}
+
+/// 所有已调度任务的下一次运行
+pub fn upcoming() -> Vec<ScheduledJob> {
+    let mut jobs = Vec::new();
+
+    if let Some(next_run) = crate::utils::log_retention::next_run() {
+        jobs.push(ScheduledJob {
+            id: "log-retention".to_string(),
+            name: "日志保留清理".to_string(),
+            description: "按最长保留天数与总大小上限删除过期的日志分段".to_string(),
+            next_run: next_run.to_rfc3339(),
+            interval_secs: crate::utils::log_retention::CLEANUP_INTERVAL.as_secs(),
+        });
+    }
+    jobs
+}
+
+/// 转义 TEXT 值中的特殊字符
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// 按 75 字节折行（不拆分 UTF-8 字符），续行以空格开头
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// 将间隔转换为重复规则
+fn recurrence_rule(interval_secs: u64) -> Option<String> {
+    let (freq, unit) = match interval_secs {
+        0 => return None,
+        secs if secs % 86400 == 0 => ("DAILY", 86400),
+        secs if secs % 3600 == 0 => ("HOURLY", 3600),
+        secs if secs % 60 == 0 => ("MINUTELY", 60),
+        _ => ("SECONDLY", 1),
+    };
+    Some(format!(
+        "RRULE:FREQ={};INTERVAL={}",
+        freq,
+        interval_secs / unit
+    ))
+}
+
+fn ics_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// 生成 iCalendar 文本
+pub fn to_ics(jobs: &[ScheduledJob]) -> String {
+    let now = ics_time(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!(
+            "PRODID:-//Antigravity Agent//Schedule {}//ZH",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Antigravity Agent".to_string(),
+    ];
+
+    for job in jobs {
+        let Ok(start) = DateTime::parse_from_rfc3339(&job.next_run) else {
+            continue;
+        };
+        let start = start.with_timezone(&Utc);
+        let end = start + chrono::Duration::minutes(EVENT_DURATION_MINUTES);
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@antigravity-agent", job.id));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("DTSTART:{}", ics_time(start)));
+        lines.push(format!("DTEND:{}", ics_time(end)));
+        lines.extend(recurrence_rule(job.interval_secs));
+        lines.push(format!("SUMMARY:{}", escape_text(&job.name)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&job.description)));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in &lines {
+        fold_line(line, &mut ics);
+    }
+    ics
+}
+
+/// 导出计划任务到 `.ics` 文件，返回导出的任务数
+pub fn export_ics(path: &Path) -> Result<usize, String> {
+    let jobs = upcoming();
+    std::fs::write(path, to_ics(&jobs))
+        .map_err(|e| format!("写入日历文件失败 ({}): {}", path.display(), e))?;
+    tracing::info!(target: "schedule::export", jobs = jobs.len(), path = %path.display(), "📅 已导出计划任务日历");
+    Ok(jobs.len())
+}
@@ -0,0 +1,179 @@
+//! 启动自检
+//! 逐项检查配置目录、Antigravity 数据库、可执行文件、备份目录和 jetski 状态解码，
+//! 返回每项的通过 / 失败 / 跳过结果，供引导页与故障排查页展示
+
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::constants::database;
+use crate::utils::network_fs;
+
+/// 单项检查结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    Passed,
+    Failed,
+    /// 前置条件不满足，无法检查（例如数据库不存在时无法解码状态）
+    Skipped,
+}
+
+/// 检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestCheckId {
+    ConfigDirWritable,
+    DatabaseReadable,
+    ExecutableLaunchable,
+    BackupDirWritable,
+    JetskiStateDecodable,
+}
+
+impl SelfTestCheckId {
+    /// 检查项名称
+    fn label(self) -> &'static str {
+        match self {
+            SelfTestCheckId::ConfigDirWritable => "配置目录可写",
+            SelfTestCheckId::DatabaseReadable => "Antigravity 数据库可读",
+            SelfTestCheckId::ExecutableLaunchable => "Antigravity 可执行文件可启动",
+            SelfTestCheckId::BackupDirWritable => "备份目录可写",
+            SelfTestCheckId::JetskiStateDecodable => "登录状态可解码",
+        }
+    }
+}
+
+/// 单项检查
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct SelfTestCheck {
+    pub id: SelfTestCheckId,
+    pub label: &'static str,
+    pub status: SelfTestStatus,
+    /// 结果说明或失败原因
+    pub message: String,
+}
+
+/// 自检报告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct SelfTestReport {
+    /// 所有检查均未失败（跳过不算失败）
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+fn check(id: SelfTestCheckId, status: SelfTestStatus, message: impl Into<String>) -> SelfTestCheck {
+    SelfTestCheck {
+        id,
+        label: id.label(),
+        status,
+        message: message.into(),
+    }
+}
+
+fn from_result(id: SelfTestCheckId, result: Result<String, String>) -> SelfTestCheck {
+    match result {
+        Ok(message) => check(id, SelfTestStatus::Passed, message),
+        Err(message) => check(id, SelfTestStatus::Failed, message),
+    }
+}
+
+/// 写入并删除一个探测文件，确认目录可写
+pub(crate) fn probe_writable(dir: &Path) -> Result<String, String> {
+    let probe = dir.join(".self-test");
+    network_fs::write(&probe, b"ok").map_err(|e| format!("无法写入 {}: {}", dir.display(), e))?;
+    fs::remove_file(&probe).map_err(|e| format!("无法删除探测文件 {}: {}", probe.display(), e))?;
+    Ok(dir.display().to_string())
+}
+
+/// 确认可执行文件存在且可执行（不实际启动）
+fn check_executable() -> Result<String, String> {
+    let custom = crate::antigravity::path_config::get_custom_executable_path()?;
+    let path = match custom {
+        Some(path) => path,
+        None => crate::antigravity::starter::detect_antigravity_executable()
+            .map(|p| p.to_string_lossy().to_string())
+            .ok_or_else(|| "未检测到 Antigravity 可执行文件，请在设置中手动指定".to_string())?,
+    };
+
+    if crate::antigravity::path_config::validate_executable_path(&path) {
+        Ok(path)
+    } else {
+        Err(format!("文件 '{}' 不存在或不是可执行文件", path))
+    }
+}
+
+/// 执行全部检查
+pub fn run() -> SelfTestReport {
+    let mut checks = vec![from_result(
+        SelfTestCheckId::ConfigDirWritable,
+        probe_writable(&crate::directories::get_config_directory()),
+    )];
+
+    // 数据库可读后才能检查状态解码
+    let jetski_state = match crate::platform::get_antigravity_db_path().filter(|p| p.exists()) {
+        None => {
+            checks.push(check(
+                SelfTestCheckId::DatabaseReadable,
+                SelfTestStatus::Failed,
+                "未找到 Antigravity 状态数据库",
+            ));
+            None
+        }
+        Some(db_path) => {
+            let state = network_fs::open_connection(&db_path)
+                .and_then(|conn| {
+                    conn.query_row(
+                        "SELECT value FROM ItemTable WHERE key = ?",
+                        [database::AGENT_STATE],
+                        |row| row.get::<_, String>(0),
+                    )
+                    .optional()
+                })
+                .map_err(|e| format!("读取数据库失败 ({}): {}", db_path.display(), e));
+
+            checks.push(from_result(
+                SelfTestCheckId::DatabaseReadable,
+                state
+                    .as_ref()
+                    .map(|_| db_path.display().to_string())
+                    .map_err(String::clone),
+            ));
+            state.ok()
+        }
+    };
+
+    checks.push(from_result(
+        SelfTestCheckId::ExecutableLaunchable,
+        check_executable(),
+    ));
+    checks.push(from_result(
+        SelfTestCheckId::BackupDirWritable,
+        probe_writable(&crate::directories::get_accounts_directory()),
+    ));
+
+    checks.push(match jetski_state {
+        None => check(
+            SelfTestCheckId::JetskiStateDecodable,
+            SelfTestStatus::Skipped,
+            "数据库不可读，跳过",
+        ),
+        Some(None) => check(
+            SelfTestCheckId::JetskiStateDecodable,
+            SelfTestStatus::Skipped,
+            "Antigravity 当前未登录，跳过",
+        ),
+        Some(Some(state)) => from_result(
+            SelfTestCheckId::JetskiStateDecodable,
+            crate::antigravity::account::decode_jetski_state_proto(&state)
+                .map(|_| "解码成功".to_string()),
+        ),
+    });
+
+    let passed = checks.iter().all(|c| c.status != SelfTestStatus::Failed);
+    for c in &checks {
+        tracing::info!(target: "app::self_test", check = ?c.id, status = ?c.status, message = %c.message, "自检项");
+    }
+
+    SelfTestReport { passed, checks }
+}
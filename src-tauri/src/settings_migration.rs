@@ -0,0 +1,100 @@
+//! 设置文件版本迁移
+//! 设置文件带有 `schema_version` 字段，加载时按顺序执行从旧版本到当前版本的迁移步骤，
+//! 迁移前把原文件复制为 `app_settings.json.v<旧版本>.bak`。
+//! 迁移后仍无法解析的单个字段只回退该字段的默认值，不再因一个字段导致整份设置被重置
+
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+use crate::app_settings::AppSettings;
+
+/// 设置文件中的版本字段
+pub const VERSION_KEY: &str = "schema_version";
+
+/// 当前设置版本
+pub const CURRENT_VERSION: u32 = 1;
+
+/// 迁移步骤：把版本 `from` 的设置改写为版本 `from + 1`
+struct Migration {
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// 所有迁移步骤（按 `from` 递增；修改字段名或字段含义时在末尾追加，并提升 `CURRENT_VERSION`）
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "补齐早期版本缺少的必填字段",
+    apply: |settings| {
+        for key in ["system_tray_enabled", "silent_start_enabled"] {
+            settings.entry(key).or_insert(Value::Bool(false));
+        }
+    },
+}];
+
+/// 设置内容中的版本号（没有版本字段的旧文件为 0）
+pub fn version_of(value: &Value) -> u32 {
+    value
+        .get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32)
+}
+
+/// 执行迁移，返回迁移前的版本（无需迁移时返回 `None`）
+///
+/// 比当前版本更新的文件（由新版本 Agent 写入）原样保留，不做降级
+pub fn migrate(value: &mut Value) -> Option<u32> {
+    let from = version_of(value);
+    if from >= CURRENT_VERSION {
+        return None;
+    }
+    let Value::Object(settings) = value else {
+        return None;
+    };
+
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= from) {
+        (migration.apply)(settings);
+        tracing::info!(
+            target: "app_settings::migrate",
+            from = migration.from,
+            to = migration.from + 1,
+            "⬆️ 设置迁移: {}",
+            migration.description
+        );
+    }
+    settings.insert(VERSION_KEY.to_string(), Value::from(CURRENT_VERSION));
+    Some(from)
+}
+
+/// 解析设置；整体解析失败时逐个字段合并到默认设置，丢弃无法解析的字段
+pub fn deserialize(value: Value) -> AppSettings {
+    let Value::Object(loaded) = value else {
+        return AppSettings::default();
+    };
+    if let Ok(settings) = serde_json::from_value(Value::Object(loaded.clone())) {
+        return settings;
+    }
+
+    let Ok(Value::Object(mut merged)) = serde_json::to_value(AppSettings::default()) else {
+        return AppSettings::default();
+    };
+    for (key, field) in loaded {
+        let previous = merged.insert(key.clone(), field);
+        if serde_json::from_value::<AppSettings>(Value::Object(merged.clone())).is_err() {
+            tracing::warn!(target: "app_settings::migrate", field = %key, "设置字段无法解析，已使用默认值");
+            match previous {
+                Some(previous) => merged.insert(key, previous),
+                None => merged.remove(&key),
+            };
+        }
+    }
+    serde_json::from_value(Value::Object(merged)).unwrap_or_default()
+}
+
+/// 迁移前备份原文件，返回备份路径
+pub fn backup(config_path: &Path, from: u32) -> Result<PathBuf, String> {
+    let backup = config_path.with_extension(format!("json.v{}.bak", from));
+    std::fs::copy(config_path, &backup).map_err(|e| format!("备份设置文件失败: {}", e))?;
+    tracing::info!(target: "app_settings::migrate", backup = %backup.display(), "已备份迁移前的设置文件");
+    Ok(backup)
+}
@@ -0,0 +1,255 @@
+//! 设置热重载
+//! 定时检查 `app_settings.json` 与 `antigravity_path.json` 是否被外部编辑，重新加载后发出
+//! `settings-changed` 事件；应用内修改设置时同样发出该事件（`source` 为 `app`）。
+//! 应用内的保存命令会直接让设置生效，订阅方只需处理 `source` 为 `external` 的事件
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+use crate::app_settings::{AppSettings, AppSettingsManager};
+
+/// 检查间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 被修改的配置文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsFile {
+    /// app_settings.json
+    AppSettings,
+    /// antigravity_path.json
+    AntigravityPath,
+}
+
+/// 修改来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsChangeSource {
+    /// 应用内的设置命令
+    App,
+    /// 外部编辑配置文件
+    External,
+}
+
+/// `settings-changed` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsChangedPayload {
+    pub file: SettingsFile,
+    pub source: SettingsChangeSource,
+    /// 发生变化的顶层字段（不含字段值）
+    pub changed_keys: Vec<String>,
+}
+
+impl SettingsChangedPayload {
+    /// 外部编辑了 app_settings.json 中的指定字段
+    pub fn external_change(&self, key: &str) -> bool {
+        self.file == SettingsFile::AppSettings
+            && self.source == SettingsChangeSource::External
+            && self.changed_keys.iter().any(|changed| changed == key)
+    }
+}
+
+/// 比较两份设置，返回发生变化的顶层字段
+pub fn changed_keys(old: &AppSettings, new: &AppSettings) -> Vec<String> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// 发出 `settings-changed` 事件（没有字段变化时不发出）
+pub fn notify(
+    app_handle: &AppHandle,
+    file: SettingsFile,
+    source: SettingsChangeSource,
+    changed_keys: Vec<String>,
+) {
+    if changed_keys.is_empty() {
+        return;
+    }
+    tracing::info!(target: "settings::watch", file = ?file, source = ?source, keys = ?changed_keys, "⚙️ 设置已变化");
+    let payload = SettingsChangedPayload {
+        file,
+        source,
+        changed_keys,
+    };
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "settings::watch", error = %e, "发射设置变化事件失败");
+    }
+}
+
+/// 文件指纹（修改时间与大小）
+fn fingerprint(path: &PathBuf) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// 重新加载 app_settings.json
+fn reload_app_settings(app_handle: &AppHandle, path: &PathBuf) {
+    match app_handle.state::<AppSettingsManager>().reload() {
+        Ok(changed_keys) => {
+            // 应用自己写入的文件重新加载后没有变化，不视为外部编辑
+            if changed_keys.is_empty() {
+                return;
+            }
+            // 接受外部编辑，避免下次启动时被完整性检查当作损坏而回滚
+            crate::config_integrity::record(path);
+            notify(
+                app_handle,
+                SettingsFile::AppSettings,
+                SettingsChangeSource::External,
+                changed_keys,
+            );
+        }
+        Err(e) => {
+            tracing::warn!(target: "settings::watch", error = %e, "外部修改的设置文件无法加载，保留当前设置")
+        }
+    }
+}
+
+/// 开始监视配置文件
+pub fn start(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let settings_path = crate::directories::get_app_settings_file();
+        let path_config_path = crate::directories::get_antigravity_path_file();
+        let mut settings_fingerprint = fingerprint(&settings_path);
+        let mut path_config_fingerprint = fingerprint(&path_config_path);
+
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let current = fingerprint(&settings_path);
+            if current != settings_fingerprint {
+                settings_fingerprint = current;
+                if current.is_some() {
+                    reload_app_settings(&app_handle, &settings_path);
+                }
+            }
+
+            // 路径配置每次使用时都从文件读取，这里只需通知
+            let current = fingerprint(&path_config_path);
+            if current != path_config_fingerprint {
+                path_config_fingerprint = current;
+                crate::config_integrity::record(&path_config_path);
+                notify(
+                    &app_handle,
+                    SettingsFile::AntigravityPath,
+                    SettingsChangeSource::External,
+                    vec!["custom_executable_path".to_string()],
+                );
+            }
+        }
+    });
+    tracing::info!(target: "settings::watch", "👀 已开始监视配置文件");
+}
+
+/// 外部编辑后让各开关类设置立即生效（托盘与日志清理在各自模块中订阅）
+pub fn register_listener(app_handle: &AppHandle) {
+    let app = app_handle.clone();
+    crate::events::listen(app_handle, move |change: SettingsChangedPayload| {
+        if change.file != SettingsFile::AppSettings
+            || change.source != SettingsChangeSource::External
+        {
+            return;
+        }
+        let settings = app.state::<AppSettingsManager>().get_settings();
+        for key in &change.changed_keys {
+            apply(&app, key, &settings);
+        }
+    });
+}
+
+/// 应用单个字段
+fn apply(app_handle: &AppHandle, key: &str, settings: &AppSettings) {
+    match key {
+        "slow_operation_threshold_ms" => {
+            crate::slow_operation::set_threshold_ms(settings.slow_operation_threshold_ms)
+        }
+        "error_reports_enabled" => crate::error_report::set_enabled(settings.error_reports_enabled),
+        "secure_delete_enabled" => {
+            crate::utils::secure_delete::set_enabled(settings.secure_delete_enabled)
+        }
+        "state_journal_enabled" => {
+            crate::state_journal::set_enabled(settings.state_journal_enabled);
+            if !settings.state_journal_enabled {
+                if let Err(e) = crate::state_journal::clear() {
+                    tracing::warn!(target: "settings::watch", error = %e, "删除登录状态变更日志失败");
+                }
+            }
+        }
+        "backup_compression" => {
+            let mode = settings.backup_compression;
+            tauri::async_runtime::spawn_blocking(move || {
+                crate::antigravity::compression::set_mode(mode);
+                if let Err(e) = crate::antigravity::compression::migrate_backups() {
+                    tracing::error!(target: "settings::watch", error = %e, "按外部修改重写备份压缩格式失败");
+                }
+            });
+        }
+        "http_proxy" => crate::http_client::set_proxy(settings.http_proxy.clone()),
+        "privacy_mode" => {
+            crate::utils::privacy::set_enabled(settings.privacy_mode);
+            crate::system_tray::refresh_tray_menu(app_handle);
+        }
+        "safe_mode" => crate::safe_mode::set_enabled(settings.safe_mode),
+        "locale" => crate::i18n::set_locale(settings.locale),
+        "require_os_auth" => crate::os_auth::set_enabled(settings.require_os_auth),
+        "app_lock" => crate::app_lock::apply_settings(&settings.app_lock),
+        "kdf" => {
+            if let Some(kdf) = settings.kdf {
+                crate::utils::kdf::set_params(kdf);
+            }
+        }
+        "theme_preference" => {
+            crate::window::theme::apply_preference(app_handle, settings.theme_preference)
+        }
+        "keychain_enabled" => {
+            let enabled = settings.keychain_enabled;
+            tauri::async_runtime::spawn_blocking(move || {
+                crate::antigravity::keychain::set_enabled(enabled);
+                if let Err(e) = crate::antigravity::keychain::migrate_backups() {
+                    tracing::error!(target: "settings::watch", error = %e, "按外部修改迁移备份钥匙串存储失败");
+                }
+            });
+        }
+        "local_api" => {
+            let app_handle = app_handle.clone();
+            let local_api = settings.local_api.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::local_api::apply(&app_handle, &local_api).await {
+                    tracing::warn!(target: "settings::watch", error = %e, "重新启动本地 HTTP 接口失败");
+                }
+            });
+        }
+        "browser_bridge" => {
+            let app_handle = app_handle.clone();
+            let browser_bridge = settings.browser_bridge.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::browser_bridge::apply(&app_handle, &browser_bridge).await {
+                    tracing::warn!(target: "settings::watch", error = %e, "重新启动浏览器桥接失败");
+                }
+            });
+        }
+        "peer_discovery" => {
+            let app_handle = app_handle.clone();
+            let peer_discovery = settings.peer_discovery.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = crate::peer_discovery::apply(&app_handle, &peer_discovery) {
+                    tracing::warn!(target: "settings::watch", error = %e, "重新启动局域网对等发现失败");
+                }
+            });
+        }
+        // 通知、Webhook、钩子等在使用时读取设置，无需处理
+        _ => {}
+    }
+}
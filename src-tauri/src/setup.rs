@@ -1,16 +1,212 @@
-use crate::{app_settings, db_monitor, system_tray, window};
+use crate::startup::{self, StartupStep, StartupStepStatus};
+use crate::{app_settings, db_monitor, deep_link, system_tray, window};
 use std::sync::Arc;
 use tauri::{App, Manager};
 
+/// 开机自启动时传入的命令行参数
+pub const AUTOSTART_ARG: &str = "--autostart";
+
 pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
     tracing::info!(target: "app::setup", "开始应用程序设置");
 
+    // 启动进度记录（需最先注册，后续步骤依赖它发出事件）
+    app.manage(startup::StartupProgress::new());
+
+    // 检查配置文件完整性（损坏时先从正常副本恢复，再加载设置）
+    crate::config_integrity::verify_all();
+
     // 初始化应用设置管理器
     let app_handle = app.handle();
     app.manage(app_settings::AppSettingsManager::new(app_handle));
 
-    // 初始化系统托盘管理器
+    // 破坏性操作互斥与限流
+    app.manage(crate::operation_guard::OperationGuard::default());
+    startup::report(
+        app.handle(),
+        StartupStep::SettingsLoaded,
+        StartupStepStatus::Completed,
+        None,
+    );
+
+    // 慢操作检测：注册事件发送句柄并应用阈值设置
+    crate::slow_operation::init(
+        app.handle(),
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .slow_operation_threshold_ms,
+    );
+
+    // 离线错误报告（默认关闭）
+    crate::error_report::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .error_reports_enabled,
+    );
+
+    // 备份凭据存储位置（系统钥匙串 / 备份文件）
+    crate::antigravity::keychain::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .keychain_enabled,
+    );
+
+    // 备份压缩格式
+    crate::antigravity::compression::set_mode(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .backup_compression,
+    );
+
+    // 删除备份时是否先覆盖文件内容
+    crate::utils::secure_delete::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .secure_delete_enabled,
+    );
+
+    // 是否记录登录状态变更日志
+    crate::state_journal::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .state_journal_enabled,
+    );
+
+    // 远程功能的代理设置
+    crate::http_client::set_proxy(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .http_proxy,
+    );
+
+    // 隐私模式
+    crate::utils::privacy::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .privacy_mode,
+    );
+
+    // 后端消息语言
+    crate::i18n::set_locale(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .locale,
+    );
+
+    // 安全模式
+    crate::safe_mode::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .safe_mode,
+    );
+
+    // 敏感操作前的系统身份验证
+    crate::os_auth::set_enabled(
+        app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .require_os_auth,
+    );
+
+    // 应用锁：设置了密码时以锁定状态启动，空闲超时自动锁定
+    crate::app_lock::init(
+        app.handle(),
+        &app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .app_lock,
+    );
+
+    // 本地 HTTP 控制接口（默认关闭）
+    let local_api = app
+        .state::<app_settings::AppSettingsManager>()
+        .get_settings()
+        .local_api;
+    if local_api.enabled {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::local_api::apply(&app_handle, &local_api).await {
+                tracing::warn!(target: "app::setup", error = %e, "启动本地 HTTP 接口失败");
+            }
+        });
+    }
+
+    // 浏览器登录桥接（默认关闭）
+    let browser_bridge = app
+        .state::<app_settings::AppSettingsManager>()
+        .get_settings()
+        .browser_bridge;
+    if browser_bridge.enabled {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::browser_bridge::apply(&app_handle, &browser_bridge).await {
+                tracing::warn!(target: "app::setup", error = %e, "启动浏览器桥接失败");
+            }
+        });
+    }
+
+    // 局域网对等发现（默认关闭）
+    let peer_discovery = app
+        .state::<app_settings::AppSettingsManager>()
+        .get_settings()
+        .peer_discovery;
+    if peer_discovery.enabled {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = crate::peer_discovery::apply(&app_handle, &peer_discovery) {
+                tracing::warn!(target: "app::setup", error = %e, "启动局域网对等发现失败");
+            }
+        });
+    }
+
+    // 插件脚本
+    crate::plugins::load();
+
+    // 钥匙串模式的备份目录加密在启动时自动解锁
+    crate::antigravity::vault::auto_unlock();
+
+    // Argon2 参数：首次运行时在本机测量并保存
+    match app
+        .state::<app_settings::AppSettingsManager>()
+        .get_settings()
+        .kdf
+    {
+        Some(kdf) => crate::utils::kdf::set_params(kdf),
+        None => {
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || match crate::utils::kdf::benchmark() {
+                Ok(kdf) => {
+                    crate::utils::kdf::set_params(kdf);
+                    if let Err(e) = app_handle
+                        .state::<app_settings::AppSettingsManager>()
+                        .update_settings(|settings| settings.kdf = Some(kdf))
+                    {
+                        tracing::warn!(target: "app::setup", error = %e, "保存 Argon2 参数失败");
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(target: "app::setup", error = %e, "Argon2 参数测量失败，使用默认参数");
+                }
+            });
+        }
+    }
+
+    // 配置目录保存着登录令牌，启动时收紧为仅当前用户可访问
+    tauri::async_runtime::spawn_blocking(|| {
+        crate::utils::dir_permissions::harden(&crate::directories::get_config_directory(), true)
+    });
+
+    // 启动时及之后每天按保留策略清理日志
+    crate::utils::log_retention::start_scheduler(app.handle());
+
+    // 监视配置文件的外部编辑，重新加载后让设置立即生效
+    crate::settings_watcher::register_listener(app.handle());
+    crate::settings_watcher::start(app.handle());
+
+    // 初始化系统托盘管理器，并订阅账户/进程事件以刷新托盘提示
     app.manage(system_tray::SystemTrayManager::new());
+    app.state::<system_tray::SystemTrayManager>()
+        .register_status_listeners(app.handle());
+
+    // 在任务栏 / Dock 上显示切换、备份、恢复的进度
+    window::taskbar::register_progress_listener(app.handle());
 
     // Tracing 日志记录器已在 main 函数中初始化，这里跳过
 
@@ -32,30 +228,10 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
 
     tracing::info!(target: "app::setup::db_monitor", "数据库监控器初始化完成");
 
-    // 初始化窗口事件处理器
-    if let Err(e) = window::init_window_event_handler(app) {
-        tracing::error!(target: "app::setup::window", error = %e, "窗口事件处理器初始化失败");
-    } else {
-        tracing::info!(target: "app::setup::window", "窗口事件处理器初始化完成");
-    }
-
     // 检查静默启动设置
     let settings_manager = app.state::<app_settings::AppSettingsManager>();
     let settings = settings_manager.get_settings();
 
-    // 根据设置决定是否创建系统托盘
-    if settings.system_tray_enabled {
-        tracing::info!(target: "app::setup::tray", "系统托盘已启用，正在创建托盘");
-        let system_tray = app.state::<system_tray::SystemTrayManager>();
-        if let Err(e) = system_tray.enable(app.handle()) {
-            tracing::error!(target: "app::setup::tray", error = %e, "启动时创建系统托盘失败");
-        } else {
-            tracing::info!(target: "app::setup::tray", "系统托盘已创建");
-        }
-    } else {
-        tracing::info!(target: "app::setup::tray", "系统托盘已禁用，跳过创建");
-    }
-
     // 双重检查：如果静默启动但未启用系统托盘，这是不允许的
     if settings.silent_start_enabled && !settings.system_tray_enabled {
         tracing::warn!(
@@ -75,35 +251,105 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
         }
 
         tracing::info!(target: "app::setup::silent_start", "已禁用静默启动，正常显示窗口");
-    } else if settings.silent_start_enabled && settings.system_tray_enabled {
-        tracing::info!(target: "app::setup::silent_start", "静默启动模式已启用（系统托盘已启用），准备隐藏主窗口");
-
-        // 延迟执行静默启动，确保在窗口状态恢复完成后隐藏窗口
-        let app_handle_for_silent = app.handle().clone();
+    }
 
-        tauri::async_runtime::spawn(async move {
-            // 等待1.5秒，确保窗口状态恢复和其他初始化都完成
-            tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+    // 静默启动或由开机自启动拉起时，直接隐藏在托盘中启动（没有托盘时始终显示窗口）
+    let launched_by_autostart = std::env::args().any(|arg| arg == AUTOSTART_ARG);
+    // 自动化模式由调用方通过 stdin 驱动，始终不显示窗口
+    let start_hidden = crate::rpc::is_enabled()
+        || (settings.system_tray_enabled
+            && (settings.silent_start_enabled || launched_by_autostart));
 
-            tracing::debug!(target: "app::setup::silent_start", "执行静默启动窗口隐藏操作");
+    if start_hidden {
+        tracing::info!(
+            target: "app::setup::silent_start",
+            silent_start = settings.silent_start_enabled,
+            autostart = launched_by_autostart,
+            "静默启动：主窗口保持隐藏，可通过系统托盘图标访问应用"
+        );
+    } else {
+        tracing::debug!(target: "app::setup::silent_start", "静默启动未启用，窗口状态恢复后显示主窗口");
+    }
 
-            if let Some(main_window) = app_handle_for_silent.get_webview_window("main") {
-                // 隐藏窗口
-                match main_window.hide() {
-                    Ok(()) => {
-                        tracing::info!(target: "app::setup::silent_start", "静默启动：窗口已隐藏");
-                        tracing::info!(target: "app::setup::silent_start", "可通过系统托盘图标访问应用");
-                    }
-                    Err(e) => {
-                        tracing::error!(target: "app::setup::silent_start", error = %e, "静默启动隐藏窗口失败");
-                    }
-                }
-            } else {
-                tracing::error!(target: "app::setup::silent_start", "无法获取主窗口进行静默启动");
+    // 初始化窗口事件处理器（主窗口初始为隐藏，恢复位置后再按需显示，避免跳动）
+    if let Err(e) = window::init_window_event_handler(app, !start_hidden) {
+        tracing::error!(target: "app::setup::window", error = %e, "窗口事件处理器初始化失败");
+        if !start_hidden {
+            if let Some(main_window) = app.get_webview_window("main") {
+                let _ = main_window.show();
             }
-        });
+        }
     } else {
-        tracing::debug!(target: "app::setup::silent_start", "静默启动未启用，正常显示窗口");
+        tracing::info!(target: "app::setup::window", "窗口事件处理器初始化完成");
+    }
+
+    // 应用主题偏好并监听系统主题变化
+    if let Err(e) = window::theme::init(app.handle()) {
+        tracing::warn!(target: "app::setup::theme", error = %e, "主题同步初始化失败");
+    }
+
+    // 注册 antigravity-agent:// 深度链接处理
+    if let Err(e) = deep_link::init(app) {
+        tracing::error!(target: "app::setup::deep_link", error = %e, "深度链接初始化失败");
+    }
+
+    // 注册快速切换窗口的全局快捷键
+    if let Err(e) = window::quick_switcher::register_shortcut(app.handle()) {
+        tracing::warn!(target: "app::setup::quick_switcher", error = %e, "快速切换快捷键注册失败");
+    }
+
+    // 根据设置决定是否创建系统托盘
+    if settings.system_tray_enabled {
+        tracing::info!(target: "app::setup::tray", "系统托盘已启用，正在创建托盘");
+        let system_tray = app.state::<system_tray::SystemTrayManager>();
+        if let Err(e) = system_tray.enable(app.handle()) {
+            tracing::error!(target: "app::setup::tray", error = %e, "启动时创建系统托盘失败");
+            startup::report(
+                app.handle(),
+                StartupStep::TrayInitialized,
+                StartupStepStatus::Failed,
+                Some(e),
+            );
+        } else {
+            tracing::info!(target: "app::setup::tray", "系统托盘已创建");
+            startup::report(
+                app.handle(),
+                StartupStep::TrayInitialized,
+                StartupStepStatus::Completed,
+                None,
+            );
+        }
+    } else {
+        tracing::info!(target: "app::setup::tray", "系统托盘已禁用，跳过创建");
+        startup::report(
+            app.handle(),
+            StartupStep::TrayInitialized,
+            StartupStepStatus::Skipped,
+            Some("系统托盘已禁用".to_string()),
+        );
+    }
+
+    // 检测 Antigravity 数据路径
+    match crate::platform::get_antigravity_db_path() {
+        Some(db_path) if db_path.exists() => startup::report(
+            app.handle(),
+            StartupStep::PathsDetected,
+            StartupStepStatus::Completed,
+            None,
+        ),
+        _ => startup::report(
+            app.handle(),
+            StartupStep::PathsDetected,
+            StartupStepStatus::Failed,
+            Some("未找到 Antigravity 状态数据库，请确认已安装并登录过 Antigravity".to_string()),
+        ),
+    }
+
+    crate::config_integrity::emit_warnings(app.handle());
+
+    // JSON-RPC 自动化模式：开始读取 stdin
+    if crate::rpc::is_enabled() {
+        crate::rpc::start(app.handle());
     }
 
     tracing::info!(target: "app::setup", "应用程序设置完成");
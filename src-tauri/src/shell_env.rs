@@ -0,0 +1,115 @@
+//! Shell 环境变量导出
+//! 将 Agent 检测到的 Antigravity 路径与当前登录账户生成为 `ANTIGRAVITY_*` 环境变量语句，
+//! 外部脚本 `eval` / `Invoke-Expression` 后即可使用与 Agent 相同的安装位置
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellFormat {
+    /// bash / zsh 等 POSIX shell：`export NAME='value'`
+    Posix,
+    /// fish：`set -gx NAME 'value'`
+    Fish,
+    /// PowerShell：`$env:NAME = 'value'`
+    #[serde(rename = "powershell")]
+    PowerShell,
+    /// Windows cmd：`set "NAME=value"`
+    Cmd,
+}
+
+/// 生成单条赋值语句
+fn statement(format: ShellFormat, name: &str, value: &str) -> String {
+    match format {
+        ShellFormat::Posix => format!("export {}='{}'", name, value.replace('\'', "'\\''")),
+        ShellFormat::Fish => format!(
+            "set -gx {} '{}'",
+            name,
+            value.replace('\\', "\\\\").replace('\'', "\\'")
+        ),
+        ShellFormat::PowerShell => format!("$env:{} = '{}'", name, value.replace('\'', "''")),
+        ShellFormat::Cmd => format!("set \"{}={}\"", name, value),
+    }
+}
+
+/// 注释行（未检测到的变量以注释说明）
+fn comment(format: ShellFormat, text: &str) -> String {
+    match format {
+        ShellFormat::Cmd => format!("rem {}", text),
+        _ => format!("# {}", text),
+    }
+}
+
+/// Antigravity 可执行文件：优先使用有效的自定义路径，否则自动检测
+fn executable_path() -> Option<String> {
+    crate::antigravity::path_config::get_custom_executable_path()
+        .ok()
+        .flatten()
+        .filter(|path| crate::antigravity::path_config::validate_executable_path(path))
+        .or_else(|| {
+            crate::antigravity::starter::detect_antigravity_executable()
+                .map(|path| path.display().to_string())
+        })
+}
+
+/// 收集变量（值为 `None` 表示未检测到）
+fn variables(account: Option<&Value>) -> Vec<(&'static str, Option<String>)> {
+    let display = |path: std::path::PathBuf| path.display().to_string();
+    let context = account.and_then(|account| account.get("context"));
+    let field = |key: &str| {
+        context
+            .and_then(|context| context.get(key))
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    vec![
+        ("ANTIGRAVITY_EXECUTABLE", executable_path()),
+        (
+            "ANTIGRAVITY_DATA_DIR",
+            crate::platform::get_antigravity_data_dir().map(display),
+        ),
+        (
+            "ANTIGRAVITY_STATE_DB",
+            crate::platform::get_antigravity_db_path().map(display),
+        ),
+        (
+            "ANTIGRAVITY_AGENT_CONFIG_DIR",
+            Some(display(crate::directories::get_config_directory())),
+        ),
+        (
+            "ANTIGRAVITY_AGENT_ACCOUNTS_DIR",
+            Some(display(crate::directories::get_accounts_directory())),
+        ),
+        ("ANTIGRAVITY_ACCOUNT_EMAIL", field("email")),
+        ("ANTIGRAVITY_ACCOUNT_PLAN", field("plan_name")),
+    ]
+}
+
+/// 生成环境变量脚本，`account` 为解码后的当前登录状态（未登录时为 `None`）
+pub fn render(format: ShellFormat, account: Option<&Value>) -> String {
+    let mut lines = vec![comment(
+        format,
+        &format!(
+            "Antigravity Agent {} 检测到的环境",
+            env!("CARGO_PKG_VERSION")
+        ),
+    )];
+    for (name, value) in variables(account) {
+        lines.push(match value {
+            Some(value) => statement(format, name, &value),
+            None => comment(format, &format!("{} 未检测到", name)),
+        });
+    }
+
+    let newline = match format {
+        ShellFormat::Cmd | ShellFormat::PowerShell => "\r\n",
+        _ => "\n",
+    };
+    let mut script = lines.join(newline);
+    script.push_str(newline);
+    script
+}
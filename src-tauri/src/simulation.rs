@@ -0,0 +1,253 @@
+//! 模拟模式（`simulation` 特性）
+//! 以 `--simulation` 启动或设置环境变量 `ANTIGRAVITY_AGENT_SIMULATION=1` 时，不再访问真实的
+//! Antigravity：数据目录、可执行文件与进程操作都改由这里提供的合成环境承担，
+//! 便于在 CI 中驱动备份、恢复与数据库监控流程，或在没有安装 Antigravity 的机器上演示。
+//!
+//! 合成环境位于 `ANTIGRAVITY_AGENT_SIMULATION_DIR`（默认为用户主目录下的
+//! `.antigravity-agent-simulation`）：
+//! - `config/`：本应用的配置目录（与真实配置隔离，不做旧目录迁移）
+//! - `Antigravity/User/globalStorage/state.vscdb`：带有登录状态等常见键的状态数据库
+//! - `Antigravity/User/globalStorage/storage.json`、`Antigravity/machineid`：设备标识
+//! - `bin/antigravity`：可执行文件替身，启动与关闭只切换内存中的运行状态
+
+use rusqlite::params;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use base64::Engine;
+
+use crate::constants::database;
+use crate::error::{CoreError, CoreResult};
+
+/// 启用模拟模式的命令行参数
+pub const SIMULATION_ARG: &str = "--simulation";
+
+/// 启用模拟模式的环境变量（值为 `1` 时启用）
+const SIMULATION_ENV: &str = "ANTIGRAVITY_AGENT_SIMULATION";
+
+/// 指定合成环境根目录的环境变量
+const SIMULATION_DIR_ENV: &str = "ANTIGRAVITY_AGENT_SIMULATION_DIR";
+
+/// 初始登录的账户
+const SIGNED_IN_ACCOUNT: &str = "alice@example.com";
+
+/// 首次创建时预置为备份的其他账户（便于直接演示切换）
+const BACKUP_ACCOUNTS: [&str; 2] = ["bob@example.com", "carol@example.com"];
+
+/// 模拟的 Antigravity 是否在运行（启动时视为已打开）
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+/// 是否启用模拟模式
+pub fn is_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        std::env::args().any(|arg| arg == SIMULATION_ARG)
+            || std::env::var(SIMULATION_ENV).is_ok_and(|value| value == "1")
+    })
+}
+
+/// 合成环境根目录
+fn root_dir() -> PathBuf {
+    match std::env::var_os(SIMULATION_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()
+            .expect("Home directory not found")
+            .join(".antigravity-agent-simulation"),
+    }
+}
+
+/// 模拟模式下本应用的配置目录
+pub fn config_dir() -> PathBuf {
+    root_dir().join("config")
+}
+
+/// 模拟的 Antigravity 安装根目录（`machineid` 所在目录）
+pub fn antigravity_root() -> PathBuf {
+    root_dir().join("Antigravity")
+}
+
+/// 模拟的 Antigravity 数据目录（`User/globalStorage`）
+pub fn data_dir() -> PathBuf {
+    antigravity_root().join("User").join("globalStorage")
+}
+
+/// 可执行文件替身路径
+pub fn executable_path() -> PathBuf {
+    let name = if cfg!(windows) {
+        "antigravity.cmd"
+    } else {
+        "antigravity"
+    };
+    root_dir().join("bin").join(name)
+}
+
+/// 创建合成环境（已存在的文件保持不变，可重复调用）
+pub fn prepare() -> CoreResult<()> {
+    let data_dir = data_dir();
+    fs::create_dir_all(&data_dir).map_err(|e| CoreError::io(&data_dir, e))?;
+
+    let db_path = data_dir.join("state.vscdb");
+    if !db_path.exists() {
+        create_database(&db_path)?;
+        seed_backups()?;
+    }
+
+    write_if_missing(
+        &data_dir.join("storage.json"),
+        &serde_json::to_string_pretty(&serde_json::json!({
+            "telemetry.machineId": random_hex(32),
+            "telemetry.macMachineId": random_hex(32),
+            "telemetry.devDeviceId": uuid::Uuid::new_v4().to_string(),
+            "telemetry.sqmId": format!("{{{}}}", uuid::Uuid::new_v4().to_string().to_uppercase()),
+        }))
+        .map_err(|e| CoreError::parse("storage.json", e))?,
+    )?;
+    write_if_missing(
+        &antigravity_root().join("machineid"),
+        &uuid::Uuid::new_v4().to_string(),
+    )?;
+    create_executable_stub()?;
+
+    tracing::info!(target: "app::simulation", root = %root_dir().display(), "🧪 模拟模式已启用");
+    Ok(())
+}
+
+/// 创建状态数据库：ItemTable 结构与 Antigravity（VS Code）一致，并写入常见键
+fn create_database(db_path: &Path) -> CoreResult<()> {
+    let conn = rusqlite::Connection::open(db_path).map_err(|e| CoreError::sqlite(db_path, e))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB);",
+    )
+    .map_err(|e| CoreError::sqlite(db_path, e))?;
+
+    let auth_status = serde_json::json!({
+        "name": "Alice",
+        "email": SIGNED_IN_ACCOUNT,
+        "apiKey": format!("sim-{}", random_hex(16)),
+    });
+    let rows = [
+        (
+            database::AGENT_STATE.to_string(),
+            session_state(SIGNED_IN_ACCOUNT)?,
+        ),
+        (database::AUTH_STATUS.to_string(), auth_status.to_string()),
+        ("antigravityOnboarding".to_string(), "true".to_string()),
+        (
+            "storage.serviceMachineId".to_string(),
+            uuid::Uuid::new_v4().to_string(),
+        ),
+        (
+            "workbench.panel.markers.hidden".to_string(),
+            "false".to_string(),
+        ),
+        (
+            "colorThemeData".to_string(),
+            r#"{"id":"vs-dark"}"#.to_string(),
+        ),
+    ];
+    for (key, value) in rows {
+        conn.execute(
+            "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+            params![key, value],
+        )
+        .map_err(|e| CoreError::sqlite(db_path, e))?;
+    }
+    Ok(())
+}
+
+/// 在账户备份目录中预置其他账户的备份
+fn seed_backups() -> CoreResult<()> {
+    let accounts_dir = crate::directories::get_accounts_directory();
+    for email in BACKUP_ACCOUNTS {
+        let content = serde_json::json!({ (database::AGENT_STATE): session_state(email)? });
+        let serialized =
+            serde_json::to_string_pretty(&content).map_err(|e| CoreError::parse("账户备份", e))?;
+        write_if_missing(&accounts_dir.join(format!("{}.json", email)), &serialized)?;
+    }
+    Ok(())
+}
+
+/// 创建可执行文件替身（什么都不做，直接退出）
+fn create_executable_stub() -> CoreResult<()> {
+    let path = executable_path();
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| CoreError::io(parent, e))?;
+    }
+
+    let script = if cfg!(windows) {
+        "@exit /b 0\r\n"
+    } else {
+        "#!/bin/sh\nexit 0\n"
+    };
+    fs::write(&path, script).map_err(|e| CoreError::io(&path, e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| CoreError::io(&path, e))?;
+    }
+    Ok(())
+}
+
+/// 用合成令牌构造登录状态（`id_token` 为未签名的 JWT，30 天后到期）
+fn session_state(email: &str) -> CoreResult<String> {
+    let encode = |value: serde_json::Value| {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value.to_string())
+    };
+    let expiry = chrono::Utc::now().timestamp() + 30 * 24 * 60 * 60;
+    let id_token = format!(
+        "{}.{}.simulation",
+        encode(serde_json::json!({ "alg": "none", "typ": "JWT" })),
+        encode(serde_json::json!({ "email": email, "exp": expiry })),
+    );
+    let access_token = format!("ya29.simulation-{}", random_hex(24));
+    crate::antigravity::account::build_session_state(email, &access_token, &id_token)
+        .map_err(CoreError::Other)
+}
+
+fn write_if_missing(path: &Path, content: &str) -> CoreResult<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| CoreError::io(parent, e))?;
+    }
+    fs::write(path, content).map_err(|e| CoreError::io(path, e))
+}
+
+/// 随机十六进制字符串（`len` 个字符，最多 64 个）
+fn random_hex(len: usize) -> String {
+    let hex = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    hex[..len.min(hex.len())].to_string()
+}
+
+/// 模拟的 Antigravity 是否在运行
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// 模拟启动 Antigravity
+pub fn start() -> String {
+    RUNNING.store(true, Ordering::SeqCst);
+    tracing::info!(target: "app::simulation", "🧪 模拟启动 Antigravity");
+    "Antigravity 已启动（模拟）".to_string()
+}
+
+/// 模拟关闭 Antigravity；未运行时返回 [`CoreError::NotFound`]（与真实进程操作一致）
+pub fn kill() -> CoreResult<String> {
+    if !RUNNING.swap(false, Ordering::SeqCst) {
+        return Err(CoreError::not_found("Antigravity 进程"));
+    }
+    tracing::info!(target: "app::simulation", "🧪 模拟关闭 Antigravity");
+    Ok("已成功关闭Antigravity进程（模拟）".to_string())
+}
@@ -0,0 +1,92 @@
+//! 慢操作检测
+//! 流水线阶段与数据库读取完成后检查耗时，超过阈值时记录警告并发出 `slow-operation` 事件，
+//! 帮助用户定位杀毒软件扫描或网络驱动器导致的卡顿
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tauri::AppHandle;
+
+/// 默认阈值（毫秒）
+pub const DEFAULT_THRESHOLD_MS: u64 = 2000;
+
+/// 当前阈值（毫秒）
+static THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_THRESHOLD_MS);
+
+/// 用于发出事件的应用句柄（数据库读取等位置拿不到 AppHandle）
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// `slow-operation` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowOperationPayload {
+    /// 操作名，例如 `pipeline::switch`、`db::open`
+    pub operation: String,
+    pub duration_ms: u64,
+    pub threshold_ms: u64,
+}
+
+/// 注册应用句柄并应用设置中的阈值（`None` 使用默认值）
+pub fn init(app_handle: &AppHandle, threshold_ms: Option<u64>) {
+    let _ = APP_HANDLE.set(app_handle.clone());
+    set_threshold_ms(threshold_ms);
+}
+
+/// 更新阈值（`None` 恢复默认值）
+pub fn set_threshold_ms(threshold_ms: Option<u64>) {
+    THRESHOLD_MS.store(
+        threshold_ms.unwrap_or(DEFAULT_THRESHOLD_MS),
+        Ordering::Relaxed,
+    );
+}
+
+/// 当前阈值（毫秒）
+pub fn threshold_ms() -> u64 {
+    THRESHOLD_MS.load(Ordering::Relaxed)
+}
+
+/// 检查从 `started` 到现在的耗时，超过阈值时发出警告
+pub fn check(operation: &str, started: Instant) {
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let threshold_ms = threshold_ms();
+    if duration_ms <= threshold_ms {
+        return;
+    }
+
+    tracing::warn!(
+        target: "app::slow_operation",
+        operation = %operation,
+        duration_ms = duration_ms,
+        threshold_ms = threshold_ms,
+        "🐢 操作耗时超过阈值"
+    );
+
+    let Some(app_handle) = APP_HANDLE.get() else {
+        return;
+    };
+    let payload = SlowOperationPayload {
+        operation: operation.to_string(),
+        duration_ms,
+        threshold_ms,
+    };
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "app::slow_operation", operation = %operation, error = %e, "发射慢操作事件失败");
+    }
+}
+
+/// 计时执行同步操作
+pub fn measure<T>(operation: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    check(operation, started);
+    result
+}
+
+/// 计时执行异步操作
+pub async fn measure_async<T>(operation: &str, future: impl Future<Output = T>) -> T {
+    let started = Instant::now();
+    let result = future.await;
+    check(operation, started);
+    result
+}
@@ -0,0 +1,112 @@
+//! 启动进度模块
+//! 在 `setup` 各初始化步骤完成或失败时发出 `startup-progress` 事件，
+//! 同时记录已完成的步骤，前端加载完成后可通过命令补取错过的事件
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// 启动步骤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStep {
+    /// 应用设置已加载
+    SettingsLoaded,
+    /// 系统托盘已初始化
+    TrayInitialized,
+    /// Antigravity 路径已检测
+    PathsDetected,
+    /// 数据库监控已启动
+    MonitorStarted,
+}
+
+impl StartupStep {
+    /// 全部启动步骤
+    pub const ALL: [StartupStep; 4] = [
+        StartupStep::SettingsLoaded,
+        StartupStep::TrayInitialized,
+        StartupStep::PathsDetected,
+        StartupStep::MonitorStarted,
+    ];
+}
+
+/// 步骤结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStepStatus {
+    Completed,
+    Skipped,
+    Failed,
+}
+
+/// `startup-progress` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct StartupProgressPayload {
+    pub step: StartupStep,
+    pub status: StartupStepStatus,
+    /// 步骤说明或失败原因
+    pub message: Option<String>,
+    /// 已结束（完成、跳过或失败）的步骤数
+    pub finished: usize,
+    pub total: usize,
+}
+
+/// 启动进度记录
+#[derive(Default)]
+pub struct StartupProgress {
+    steps: Mutex<Vec<StartupProgressPayload>>,
+}
+
+impl StartupProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取已记录的步骤
+    pub fn snapshot(&self) -> Vec<StartupProgressPayload> {
+        self.steps.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+/// 记录一个启动步骤的结果并发出事件
+pub fn report(
+    app_handle: &AppHandle,
+    step: StartupStep,
+    status: StartupStepStatus,
+    message: Option<String>,
+) {
+    let Some(progress) = app_handle.try_state::<StartupProgress>() else {
+        return;
+    };
+
+    let payload = {
+        let Ok(mut steps) = progress.steps.lock() else {
+            return;
+        };
+        steps.retain(|s| s.step != step);
+        let payload = StartupProgressPayload {
+            step,
+            status,
+            message: message
+                .as_deref()
+                .map(crate::utils::log_sanitizer::redact_secrets),
+            finished: steps.len() + 1,
+            total: StartupStep::ALL.len(),
+        };
+        steps.push(payload.clone());
+        payload
+    };
+
+    match status {
+        StartupStepStatus::Failed => {
+            tracing::error!(target: "app::startup::progress", step = ?step, message = ?payload.message, "启动步骤失败")
+        }
+        _ => {
+            tracing::info!(target: "app::startup::progress", step = ?step, status = ?status, finished = payload.finished, total = payload.total, "启动步骤完成")
+        }
+    }
+
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "app::startup::progress", error = %e, "发射启动进度事件失败");
+    }
+}
@@ -1,9 +1,23 @@
+//! 应用状态
+//! 配置档案、账户信息与当前账户保存在配置目录的 `app_state.json` 中，启动时加载，
+//! 每次修改后立即写回（先写临时文件再替换）。文件带有 `version` 字段。
+//! 旧版本配置目录中的状态文件由 `ConfigManager::migrate_legacy_dirs` 在启动时移入
+
 use crate::directories;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 状态文件中的版本字段
+const VERSION_KEY: &str = "version";
+
+/// 当前状态文件版本
+const CURRENT_VERSION: u32 = 1;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ProfileInfo {
     pub name: String,
     pub source_path: String,
@@ -13,7 +27,7 @@ pub struct ProfileInfo {
 }
 
 // Antigravity 账户信息结构
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AntigravityAccount {
     pub id: String,
     pub name: String,
@@ -25,24 +39,186 @@ pub struct AntigravityAccount {
     pub last_switched: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AppState {
+/// 需要持久化的状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersistedState {
     pub profiles: HashMap<String, ProfileInfo>,
-    pub config_dir: PathBuf,
     pub antigravity_accounts: HashMap<String, AntigravityAccount>,
     pub current_account_id: Option<String>,
 }
 
+/// 提供给前端的状态（不含账户凭据）
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct AppStateSummary {
+    /// 配置档案（按名称排序）
+    pub profiles: Vec<ProfileInfo>,
+    /// 最近一次切换到的账户
+    pub current_account_id: Option<String>,
+}
+
+pub struct AppState {
+    pub config_dir: PathBuf,
+    /// 状态文件路径
+    state_file: PathBuf,
+    /// 持久化状态（修改与写文件在同一把锁内完成）
+    data: Mutex<PersistedState>,
+}
+
 impl Default for AppState {
     fn default() -> Self {
+        Self::load()
+    }
+}
+
+/// 解析状态文件内容；没有版本字段的文件视为版本 0，字段缺失时使用默认值
+fn parse(content: &str) -> Result<PersistedState, String> {
+    let mut value: Value =
+        serde_json::from_str(content).map_err(|e| format!("状态文件格式无效: {}", e))?;
+    let version = value.get(VERSION_KEY).and_then(Value::as_u64).unwrap_or(0);
+    if version > CURRENT_VERSION as u64 {
+        tracing::warn!(target: "app::state", version, "状态文件由更新版本写入，按当前版本读取");
+    }
+    if let Value::Object(map) = &mut value {
+        map.remove(VERSION_KEY);
+    }
+    serde_json::from_value(value).map_err(|e| format!("状态文件内容无效: {}", e))
+}
+
+/// 读取状态文件；不存在或无法解析时返回 `None`
+fn read(state_file: &Path) -> Option<PersistedState> {
+    if !state_file.exists() {
+        return None;
+    }
+    match fs::read_to_string(state_file)
+        .map_err(|e| e.to_string())
+        .and_then(|c| parse(&c))
+    {
+        Ok(state) => Some(state),
+        Err(e) => {
+            tracing::error!(target: "app::state", error = %e, "读取状态文件失败，使用空状态");
+            None
+        }
+    }
+}
+
+/// 写入状态文件（带版本号，先写临时文件再替换）
+fn write(state_file: &Path, state: &PersistedState) -> Result<(), String> {
+    let mut value = serde_json::to_value(state).map_err(|e| format!("序列化状态失败: {}", e))?;
+    if let Value::Object(map) = &mut value {
+        map.insert(VERSION_KEY.to_string(), Value::from(CURRENT_VERSION));
+    }
+    let content =
+        serde_json::to_string_pretty(&value).map_err(|e| format!("序列化状态失败: {}", e))?;
+
+    let temp = state_file.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("写入状态文件失败: {}", e))?;
+    fs::rename(&temp, state_file).map_err(|e| format!("替换状态文件失败: {}", e))?;
+    crate::config_integrity::record(state_file);
+    Ok(())
+}
+
+impl AppState {
+    /// 从配置目录加载状态（必要时从旧位置迁移并立即写回）
+    pub fn load() -> Self {
         // 使用统一的配置目录
         let config_dir = directories::get_config_directory();
+        let state_file = directories::get_app_state_file();
+
+        let migrated = !state_file.exists();
+        let data = match read(&state_file) {
+            Some(data) => {
+                if migrated {
+                    if let Err(e) = write(&state_file, &data) {
+                        tracing::error!(target: "app::state", error = %e, "写入迁移后的状态文件失败");
+                    }
+                }
+                data
+            }
+            None => PersistedState::default(),
+        };
+        tracing::info!(
+            target: "app::state",
+            profiles = data.profiles.len(),
+            has_current_account = data.current_account_id.is_some(),
+            "已加载应用状态"
+        );
 
         Self {
-            profiles: HashMap::new(),
             config_dir,
-            antigravity_accounts: HashMap::new(),
-            current_account_id: None,
+            state_file,
+            data: Mutex::new(data),
+        }
+    }
+
+    /// 当前状态摘要
+    pub fn summary(&self) -> AppStateSummary {
+        let data = self.data.lock().unwrap();
+        let mut profiles: Vec<ProfileInfo> = data.profiles.values().cloned().collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        AppStateSummary {
+            profiles,
+            current_account_id: data.current_account_id.clone(),
+        }
+    }
+
+    /// 修改状态并立即写回文件；写入失败时恢复修改前的状态
+    pub fn update<F>(&self, update_fn: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut PersistedState),
+    {
+        let mut data = self.data.lock().unwrap();
+        let before = data.clone();
+        update_fn(&mut data);
+        if let Err(e) = write(&self.state_file, &data) {
+            *data = before;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// 记录备份对应的配置档案（保留首次创建时间）
+    pub fn record_profile(&self, name: &str, source_path: &Path, backup_path: &Path) {
+        let now = chrono::Local::now().to_rfc3339();
+        let result = self.update(|data| {
+            let created_at = data
+                .profiles
+                .get(name)
+                .map_or_else(|| now.clone(), |profile| profile.created_at.clone());
+            data.profiles.insert(
+                name.to_string(),
+                ProfileInfo {
+                    name: name.to_string(),
+                    source_path: source_path.display().to_string(),
+                    backup_path: backup_path.display().to_string(),
+                    created_at,
+                    last_updated: now.clone(),
+                },
+            );
+        });
+        if let Err(e) = result {
+            tracing::warn!(target: "app::state", error = %e, "保存配置档案失败");
+        }
+    }
+
+    /// 移除配置档案（备份被删除时调用；`None` 表示全部移除）
+    pub fn remove_profiles(&self, name: Option<&str>) {
+        let result = self.update(|data| match name {
+            Some(name) => {
+                data.profiles.remove(name);
+            }
+            None => data.profiles.clear(),
+        });
+        if let Err(e) = result {
+            tracing::warn!(target: "app::state", error = %e, "移除配置档案失败");
+        }
+    }
+
+    /// 记录当前账户（切换成功后调用）
+    pub fn set_current_account(&self, account: &str) {
+        let result = self.update(|data| data.current_account_id = Some(account.to_string()));
+        if let Err(e) = result {
+            tracing::warn!(target: "app::state", error = %e, "保存当前账户失败");
         }
     }
 }
@@ -0,0 +1,512 @@
+//! 登录状态变更日志（默认关闭，在设置中开启）
+//! 数据库监控每次读取 Antigravity 状态数据库后，把关键 ItemTable 键（登录状态、认证状态、
+//! 引导标记、服务设备标识）的原始值变化追加到配置目录的 `state_journal.jsonl`：每次运行、
+//! 每隔一段时间或一定数量的变更写入一条完整快照作为重放基准，其余只记录发生变化的键。
+//!
+//! [`reconstruct_at`] 从不晚于指定时间的最近快照开始重放变更，得到该时刻这些键的值，
+//! 可以只查看，也可以写回数据库（例如回到昨晚登出之前的登录状态）。
+//!
+//! 日志与备份受同样的保护：每行按备份的压缩与加密设置封装，启用备份加密时只保存密文；
+//! 启用钥匙串存储但未启用加密时，含凭据的键只保存摘要（此时无法写回这些键）。
+//! 启用或关闭备份加密、切换钥匙串存储时日志随备份一起重写，重写与删除遵循安全删除设置
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::antigravity::{keychain, vault};
+use crate::constants::database;
+use crate::error::{CoreError, CoreResult, ResultExt};
+
+/// 记录变化的 ItemTable 键
+const TRACKED_KEYS: [&str; 4] = [
+    database::AGENT_STATE,
+    database::AUTH_STATUS,
+    "antigravityOnboarding",
+    "storage.serviceMachineId",
+];
+
+/// 含凭据的键：查看时只返回是否存在与长度，不返回内容
+const SECRET_KEYS: [&str; 2] = [database::AGENT_STATE, database::AUTH_STATUS];
+
+/// 日志保留天数（写入快照时清理更早的条目）
+const RETENTION_DAYS: i64 = 30;
+
+/// 距上次快照超过该时长时写入新快照，保证过期条目可以被清理
+const SNAPSHOT_INTERVAL_HOURS: i64 = 24;
+
+/// 距上次快照的变更超过该数量时写入新快照
+const SNAPSHOT_EVERY_CHANGES: usize = 200;
+
+/// 日志最多保留的条目数（超出时从更晚的快照开始保留）
+const MAX_ENTRIES: usize = 2000;
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 各键的原始值（`None` 表示数据库中没有该键）
+type KeyValues = BTreeMap<String, Option<String>>;
+
+/// 日志中保存的值
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredValue {
+    /// ItemTable 中的原始字符串
+    Raw(String),
+    /// 只保存摘要（不允许保存凭据原文时）
+    Digest { sha256: String, length: usize },
+}
+
+impl StoredValue {
+    fn length(&self) -> usize {
+        match self {
+            StoredValue::Raw(value) => value.chars().count(),
+            StoredValue::Digest { length, .. } => *length,
+        }
+    }
+}
+
+/// 日志条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EntryKind {
+    /// 所有记录键的完整值（重放基准）
+    Snapshot,
+    /// 仅包含发生变化的键
+    Change,
+}
+
+/// 日志条目（每行一个按备份设置封装的 JSON 对象）
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    timestamp: DateTime<Utc>,
+    kind: EntryKind,
+    values: BTreeMap<String, Option<StoredValue>>,
+}
+
+/// 最近一次写入日志的状态
+struct LastWrite {
+    values: KeyValues,
+    snapshot_at: DateTime<Utc>,
+    changes_since_snapshot: usize,
+}
+
+/// 最近一次写入日志后的各键值；本次运行尚未写入时为 `None`（下一次记录写入快照）
+static LAST: Mutex<Option<LastWrite>> = Mutex::new(None);
+
+/// 指定时刻单个键的状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ReconstructedKey {
+    pub key: String,
+    /// 该时刻数据库中是否有这个键
+    pub present: bool,
+    /// 值的长度（字符数）
+    pub length: usize,
+    /// 值内容（含凭据的键不返回）
+    pub value: Option<String>,
+}
+
+/// 指定时刻的关键键状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ReconstructedState {
+    /// 请求的时间（RFC 3339）
+    pub timestamp: String,
+    /// 作为重放基准的快照时间
+    pub base_snapshot_at: String,
+    /// 重放的变更条数
+    pub replayed_changes: usize,
+    /// 该时刻之前最后一次变化的时间（没有变更时为快照时间）
+    pub last_change_at: String,
+    /// 该时刻登录的账户邮箱
+    pub email: Option<String>,
+    pub keys: Vec<ReconstructedKey>,
+    /// 写回结果说明（只查看时为 `None`）
+    pub restore_message: Option<String>,
+}
+
+/// 更新启用状态（关闭后下次开启时重新写入快照）
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        if let Ok(mut last) = LAST.lock() {
+            *last = None;
+        }
+    }
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 是否可以保存凭据原文：启用备份加密时以密文保存；启用钥匙串存储但未加密时
+/// 备份文件中没有明文凭据，日志也只保存摘要
+fn store_secrets() -> bool {
+    vault::is_enabled() || !keychain::is_enabled()
+}
+
+fn digest(value: &str) -> StoredValue {
+    StoredValue::Digest {
+        sha256: Sha256::digest(value.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect(),
+        length: value.chars().count(),
+    }
+}
+
+/// 按当前保护级别转换要保存的值
+fn stored(values: &KeyValues) -> BTreeMap<String, Option<StoredValue>> {
+    let store_secrets = store_secrets();
+    values
+        .iter()
+        .map(|(key, value)| {
+            let value = value.as_deref().map(|value| {
+                if store_secrets || !SECRET_KEYS.contains(&key.as_str()) {
+                    StoredValue::Raw(value.to_string())
+                } else {
+                    digest(value)
+                }
+            });
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// 记录数据库监控读取到的 ItemTable 原始行中关键键的变化
+///
+/// 未启用时不做任何事；本次运行的首次调用写入快照，之后只在有变化时写入变更。
+/// 启用了备份加密但尚未解锁时跳过（解锁后的下一次读取补记）；写入失败只记录警告
+pub fn record(rows: &[(String, String)]) {
+    if !is_enabled() {
+        return;
+    }
+    if vault::is_enabled() && !vault::is_unlocked() {
+        tracing::debug!(target: "state_journal::write", "备份目录未解锁，暂不记录登录状态变化");
+        return;
+    }
+
+    let current: KeyValues = TRACKED_KEYS
+        .iter()
+        .map(|key| {
+            let value = rows
+                .iter()
+                .find(|(row_key, _)| row_key == key)
+                .map(|(_, value)| value.clone());
+            (key.to_string(), value)
+        })
+        .collect();
+
+    let Ok(mut last) = LAST.lock() else {
+        return;
+    };
+    let now = Utc::now();
+    let entry = match last.as_ref() {
+        Some(previous) => {
+            let changed: KeyValues = current
+                .iter()
+                .filter(|(key, value)| previous.values.get(*key) != Some(*value))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            if changed.is_empty() {
+                return;
+            }
+            let snapshot_due = now - previous.snapshot_at
+                >= Duration::hours(SNAPSHOT_INTERVAL_HOURS)
+                || previous.changes_since_snapshot >= SNAPSHOT_EVERY_CHANGES;
+            if snapshot_due {
+                JournalEntry {
+                    timestamp: now,
+                    kind: EntryKind::Snapshot,
+                    values: stored(&current),
+                }
+            } else {
+                JournalEntry {
+                    timestamp: now,
+                    kind: EntryKind::Change,
+                    values: stored(&changed),
+                }
+            }
+        }
+        None => JournalEntry {
+            timestamp: now,
+            kind: EntryKind::Snapshot,
+            values: stored(&current),
+        },
+    };
+
+    if let Err(e) = append(&entry) {
+        tracing::warn!(target: "state_journal::write", error = %e, "写入登录状态变更日志失败");
+        return;
+    }
+    tracing::debug!(target: "state_journal::write", kind = ?entry.kind, keys = entry.values.len(), "📝 已记录登录状态变化");
+    *last = Some(match (entry.kind, last.take()) {
+        (EntryKind::Change, Some(previous)) => LastWrite {
+            values: current,
+            snapshot_at: previous.snapshot_at,
+            changes_since_snapshot: previous.changes_since_snapshot + 1,
+        },
+        _ => LastWrite {
+            values: current,
+            snapshot_at: entry.timestamp,
+            changes_since_snapshot: 0,
+        },
+    });
+
+    // 清理需要读取（并解密）整个日志，只在写入快照时进行；快照至少每天或每
+    // SNAPSHOT_EVERY_CHANGES 条变更写入一次，日志大小仍然有界
+    if entry.kind == EntryKind::Snapshot {
+        if let Err(e) = prune() {
+            tracing::warn!(target: "state_journal::prune", error = %e, "清理登录状态变更日志失败");
+        }
+    }
+}
+
+/// 按备份的压缩与加密设置封装一条日志（单行）
+fn seal_line(entry: &JournalEntry) -> CoreResult<String> {
+    let value = serde_json::to_value(entry).map_err(|e| CoreError::parse("变更日志条目", e))?;
+    let sealed = vault::seal_backup(&value).map_err(CoreError::Other)?;
+    // 封装结果是格式化的 JSON，压缩为一行
+    let sealed: Value =
+        serde_json::from_str(&sealed).map_err(|e| CoreError::parse("变更日志条目", e))?;
+    Ok(sealed.to_string())
+}
+
+fn append(entry: &JournalEntry) -> CoreResult<()> {
+    let path = crate::directories::get_state_journal_file();
+    let line = seal_line(entry)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| CoreError::io(&path, e))?;
+    writeln!(file, "{}", line).map_err(|e| CoreError::io(&path, e))
+}
+
+/// 读取日志中的条目（按写入顺序）
+///
+/// 无法解析的行跳过；加密的行无法解密（未解锁或密钥不符）时返回错误，避免把部分日志当作全部
+fn load() -> CoreResult<Vec<JournalEntry>> {
+    let path = crate::directories::get_state_journal_file();
+    if !path.exists() {
+        return Err(CoreError::not_found("登录状态变更日志"));
+    }
+    let content = fs::read_to_string(&path).map_err(|e| CoreError::io(&path, e))?;
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let opened = match vault::open(line) {
+            Ok(opened) => opened,
+            Err(e) if vault::is_sealed(&value) => {
+                return Err(CoreError::Other(format!("读取登录状态变更日志失败: {}", e)));
+            }
+            Err(_) => continue,
+        };
+        if let Ok(entry) = serde_json::from_value(opened) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// 用给定条目替换日志文件（旧文件按安全删除设置删除）
+fn write_entries(entries: &[JournalEntry]) -> CoreResult<()> {
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&seal_line(entry)?);
+        content.push('\n');
+    }
+    let path = crate::directories::get_state_journal_file();
+    let temp = path.with_extension("jsonl.tmp");
+    fs::write(&temp, content).map_err(|e| CoreError::io(&temp, e))?;
+    if path.exists() {
+        crate::utils::secure_delete::remove_file(&path).map_err(|e| CoreError::io(&path, e))?;
+    }
+    fs::rename(&temp, &path).map_err(|e| CoreError::io(&path, e))
+}
+
+/// 删除保留期之前的条目（写入快照后调用）：保留期开始前的最近一个快照及其之后的条目仍然保留，保证可以重放；
+/// 条目数超过上限时从满足上限的最早快照开始保留
+fn prune() -> CoreResult<()> {
+    let cutoff = Utc::now() - Duration::days(RETENTION_DAYS);
+    let entries = load()?;
+    let is_snapshot = |entry: &&JournalEntry| entry.kind == EntryKind::Snapshot;
+
+    let mut start = entries
+        .iter()
+        .rposition(|entry| is_snapshot(&entry) && entry.timestamp <= cutoff)
+        .unwrap_or(0);
+    if entries.len() - start > MAX_ENTRIES {
+        if let Some(later) = entries
+            .iter()
+            .enumerate()
+            .skip(start + 1)
+            .find(|(index, entry)| is_snapshot(entry) && entries.len() - index <= MAX_ENTRIES)
+            .map(|(index, _)| index)
+        {
+            start = later;
+        }
+    }
+    if start == 0 {
+        return Ok(());
+    }
+
+    write_entries(&entries[start..])?;
+    tracing::info!(target: "state_journal::prune", removed = start, "🧹 已清理过期的登录状态变更日志");
+    Ok(())
+}
+
+/// 按当前的加密与钥匙串设置重写整个日志（启用或关闭备份加密、切换钥匙串存储后调用），
+/// 返回重写的条目数；日志不存在时返回 0
+///
+/// 不再允许保存凭据原文时，已有的凭据原文替换为摘要
+pub fn reseal() -> CoreResult<usize> {
+    // 持有记录锁，避免重写期间追加的条目丢失
+    let _last = LAST
+        .lock()
+        .map_err(|e| CoreError::Other(format!("获取变更日志锁失败: {}", e)))?;
+    let mut entries = match load() {
+        Ok(entries) => entries,
+        Err(e) if e.is_not_found() => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    if !store_secrets() {
+        for entry in &mut entries {
+            for (key, value) in entry.values.iter_mut() {
+                if let Some(StoredValue::Raw(raw)) = value.as_ref() {
+                    if SECRET_KEYS.contains(&key.as_str()) {
+                        *value = Some(digest(raw));
+                    }
+                }
+            }
+        }
+    }
+    write_entries(&entries)?;
+    tracing::info!(target: "state_journal::write", entries = entries.len(), "🔐 已按当前备份保护设置重写登录状态变更日志");
+    Ok(entries.len())
+}
+
+/// 删除整个日志（按安全删除设置覆盖文件内容），下一次记录重新写入快照
+pub fn clear() -> CoreResult<()> {
+    let mut last = LAST
+        .lock()
+        .map_err(|e| CoreError::Other(format!("获取变更日志锁失败: {}", e)))?;
+    let path = crate::directories::get_state_journal_file();
+    if path.exists() {
+        crate::utils::secure_delete::remove_file(&path).map_err(|e| CoreError::io(&path, e))?;
+        tracing::info!(target: "state_journal::write", "🗑️ 已删除登录状态变更日志");
+    }
+    *last = None;
+    Ok(())
+}
+
+/// 重建指定时刻关键键的值；`restore` 为 true 时写回 Antigravity 数据库
+///
+/// `timestamp` 为 RFC 3339 时间。日志中没有不晚于该时间的快照时返回 [`CoreError::NotFound`]；
+/// 要写回的凭据只保存了摘要时拒绝写回
+pub fn reconstruct_at(timestamp: &str, restore: bool) -> CoreResult<ReconstructedState> {
+    let at = DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|e| CoreError::parse("时间", e))?
+        .with_timezone(&Utc);
+
+    let mut state: Option<BTreeMap<String, Option<StoredValue>>> = None;
+    let mut base_snapshot_at = at;
+    let mut last_change_at = at;
+    let mut replayed_changes = 0;
+    // 日志按时间追加，遇到晚于目标时间的条目即可停止
+    for entry in load()?
+        .into_iter()
+        .take_while(|entry| entry.timestamp <= at)
+    {
+        match entry.kind {
+            EntryKind::Snapshot => {
+                base_snapshot_at = entry.timestamp;
+                replayed_changes = 0;
+                // 快照与上一状态相同时不算一次变化
+                if state.as_ref() != Some(&entry.values) {
+                    last_change_at = entry.timestamp;
+                }
+                state = Some(entry.values);
+            }
+            EntryKind::Change => {
+                // 没有基准快照的变更无法重放
+                let Some(state) = state.as_mut() else {
+                    continue;
+                };
+                state.extend(entry.values);
+                replayed_changes += 1;
+                last_change_at = entry.timestamp;
+            }
+        }
+    }
+    let values =
+        state.ok_or_else(|| CoreError::not_found(format!("{} 之前的状态快照", timestamp)))?;
+
+    let raw = |key: &str| match values.get(key) {
+        Some(Some(StoredValue::Raw(value))) => Some(value.clone()),
+        _ => None,
+    };
+    let email = raw(database::AGENT_STATE)
+        .and_then(|state| crate::antigravity::account::decode_jetski_state_proto(&state).ok())
+        .and_then(|session| session["context"]["email"].as_str().map(str::to_string));
+    let keys = values
+        .iter()
+        .map(|(key, value)| ReconstructedKey {
+            key: key.clone(),
+            present: value.is_some(),
+            length: value.as_ref().map_or(0, StoredValue::length),
+            value: raw(key).filter(|_| !SECRET_KEYS.contains(&key.as_str())),
+        })
+        .collect();
+
+    let restore_message = if restore {
+        let mut writes = KeyValues::new();
+        for (key, value) in &values {
+            let value = match value {
+                None => None,
+                Some(StoredValue::Raw(value)) => Some(value.clone()),
+                Some(StoredValue::Digest { .. }) => {
+                    return Err(CoreError::Other(format!(
+                        "该时刻的 {} 只保存了摘要（启用了钥匙串存储但未启用备份加密），无法写回",
+                        key
+                    )));
+                }
+            };
+            writes.insert(key.clone(), value);
+        }
+        let message =
+            crate::antigravity::restore::write_item_values(&writes).context("写回数据库失败")?;
+        // 写回的值会被数据库监控读到，以快照形式重新记录，避免与回滚前的值混在同一段变更中
+        if let Ok(mut last) = LAST.lock() {
+            *last = None;
+        }
+        Some(message)
+    } else {
+        None
+    };
+
+    tracing::info!(
+        target: "state_journal::reconstruct",
+        timestamp = %timestamp,
+        replayed_changes,
+        restored = restore,
+        "⏪ 已重建指定时刻的登录状态"
+    );
+    Ok(ReconstructedState {
+        timestamp: at.to_rfc3339(),
+        base_snapshot_at: base_snapshot_at.to_rfc3339(),
+        replayed_changes,
+        last_change_at: last_change_at.to_rfc3339(),
+        email,
+        keys,
+        restore_message,
+    })
+}
@@ -0,0 +1,104 @@
+//! 托盘图标状态模块
+//!
+//! 在应用默认图标右下角叠加状态标记，生成运行 / 停止 / 切换中 / 错误四种托盘图标；
+//! macOS 额外生成单色模板图（template image），由系统根据菜单栏主题着色
+
+use tauri::image::Image;
+
+/// 托盘图标状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayIconState {
+    /// Antigravity 正在运行
+    Running,
+    /// Antigravity 未运行
+    Stopped,
+    /// 正在执行切换 / 备份 / 恢复流水线
+    Switching,
+    /// 最近一次流水线失败
+    Error,
+}
+
+impl TrayIconState {
+    /// 状态标记颜色（RGB）
+    fn badge_color(self) -> [u8; 3] {
+        match self {
+            TrayIconState::Running => [0x22, 0xc5, 0x5e],
+            TrayIconState::Stopped => [0x9c, 0xa3, 0xaf],
+            TrayIconState::Switching => [0xf5, 0x9e, 0x0b],
+            TrayIconState::Error => [0xef, 0x44, 0x44],
+        }
+    }
+}
+
+/// 根据基础图标生成指定状态的托盘图标
+///
+/// `template` 为 true 时生成单色模板图：图标变为黑色剪影，
+/// 停止状态整体变淡，切换中显示空心圆环，错误显示实心圆点
+pub fn render_state_icon(base: &Image<'_>, state: TrayIconState, template: bool) -> Image<'static> {
+    let width = base.width();
+    let height = base.height();
+    let mut rgba = base.rgba().to_vec();
+
+    if template {
+        let alpha_scale = if state == TrayIconState::Stopped {
+            0.4
+        } else {
+            1.0
+        };
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel[0] = 0;
+            pixel[1] = 0;
+            pixel[2] = 0;
+            pixel[3] = (pixel[3] as f32 * alpha_scale) as u8;
+        }
+    }
+
+    // 状态标记：右下角圆点，直径约为图标尺寸的 40%
+    let size = width.min(height) as f32;
+    let radius = size * 0.2;
+    let border = (size * 0.05).max(1.0);
+    let center_x = width as f32 - radius - border;
+    let center_y = height as f32 - radius - border;
+
+    let draw_badge =
+        !(template && matches!(state, TrayIconState::Running | TrayIconState::Stopped));
+
+    if draw_badge {
+        for y in 0..height {
+            for x in 0..width {
+                let dx = x as f32 + 0.5 - center_x;
+                let dy = y as f32 + 0.5 - center_y;
+                let distance = (dx * dx + dy * dy).sqrt();
+                let index = ((y * width + x) * 4) as usize;
+
+                if distance > radius + border {
+                    continue;
+                }
+
+                if template {
+                    // 模板图：先挖空边框区域，再绘制圆环或实心圆点
+                    let ring_only = state == TrayIconState::Switching;
+                    let inside = if ring_only {
+                        distance <= radius && distance >= radius - border * 1.5
+                    } else {
+                        distance <= radius
+                    };
+                    rgba[index..index + 4].copy_from_slice(&[
+                        0,
+                        0,
+                        0,
+                        if inside { 255 } else { 0 },
+                    ]);
+                } else if distance > radius {
+                    // 白色描边，保证在任意图标颜色上都清晰可见
+                    rgba[index..index + 4].copy_from_slice(&[255, 255, 255, 255]);
+                } else {
+                    let [r, g, b] = state.badge_color();
+                    rgba[index..index + 4].copy_from_slice(&[r, g, b, 255]);
+                }
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
@@ -1,14 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
 use tauri::{AppHandle, Manager};
 
 use crate::app_settings::AppSettingsManager;
+use crate::db_monitor::{AccountChangedPayload, ProcessChangedPayload};
+use crate::pipeline::{PipelineStage, PipelineStatusPayload};
+use crate::settings_watcher::SettingsChangedPayload;
+use crate::system_tray::icon::{render_state_icon, TrayIconState};
+
+/// 托盘图标左键单击行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayLeftClickAction {
+    /// 显示托盘菜单（含快速切换账户子菜单）
+    #[default]
+    QuickSwitchMenu,
+    /// 显示 / 隐藏主窗口
+    ToggleWindow,
+    /// 启动 / 关闭 Antigravity
+    ToggleAntigravity,
+}
+
+/// 托盘提示与图标所反映的当前状态
+#[derive(Debug, Clone, Default)]
+pub struct TrayStatus {
+    /// 当前登录的账户邮箱
+    pub email: Option<String>,
+    /// 当前账户的订阅计划
+    pub plan: Option<String>,
+    /// Antigravity 是否正在运行
+    pub antigravity_running: bool,
+    /// 正在执行的流水线数量（切换内部会嵌套恢复）
+    pub active_pipelines: u32,
+    /// 最近一次流水线是否失败
+    pub last_pipeline_failed: bool,
+}
+
+impl TrayStatus {
+    /// 根据当前状态计算托盘图标
+    pub fn icon_state(&self) -> TrayIconState {
+        if self.active_pipelines > 0 {
+            TrayIconState::Switching
+        } else if self.last_pipeline_failed {
+            TrayIconState::Error
+        } else if self.antigravity_running {
+            TrayIconState::Running
+        } else {
+            TrayIconState::Stopped
+        }
+    }
+}
 
 /// 系统托盘管理器
-pub struct SystemTrayManager;
+pub struct SystemTrayManager {
+    status: Mutex<TrayStatus>,
+}
 
 impl SystemTrayManager {
     /// 创建新的管理器
     pub fn new() -> Self {
-        Self
+        Self {
+            status: Mutex::new(TrayStatus::default()),
+        }
+    }
+
+    /// 订阅账户变化、进程状态与流水线事件，自动刷新托盘提示和图标
+    pub fn register_status_listeners(&self, app_handle: &AppHandle) {
+        let app = app_handle.clone();
+        crate::events::listen(app_handle, move |account: AccountChangedPayload| {
+            let system_tray = app.state::<SystemTrayManager>();
+            system_tray.update_status(&app, |s| {
+                s.email = account.email;
+                s.plan = account.plan;
+            });
+        });
+
+        let app = app_handle.clone();
+        crate::events::listen(app_handle, move |process: ProcessChangedPayload| {
+            let system_tray = app.state::<SystemTrayManager>();
+            system_tray.update_status(&app, |s| {
+                s.antigravity_running = process.running;
+            });
+        });
+
+        let app = app_handle.clone();
+        crate::events::listen(app_handle, move |pipeline: PipelineStatusPayload| {
+            let system_tray = app.state::<SystemTrayManager>();
+            system_tray.update_status(&app, |s| match pipeline.stage {
+                PipelineStage::Started => {
+                    s.active_pipelines += 1;
+                    s.last_pipeline_failed = false;
+                }
+                PipelineStage::Progress => {}
+                PipelineStage::Succeeded => {
+                    s.active_pipelines = s.active_pipelines.saturating_sub(1);
+                }
+                PipelineStage::Failed => {
+                    s.active_pipelines = s.active_pipelines.saturating_sub(1);
+                    s.last_pipeline_failed = true;
+                }
+            });
+        });
+
+        // 外部编辑设置文件后按新设置显示或隐藏托盘、刷新置顶账户
+        let app = app_handle.clone();
+        crate::events::listen(app_handle, move |change: SettingsChangedPayload| {
+            let system_tray = app.state::<SystemTrayManager>();
+            let result = if change.external_change("system_tray_enabled") {
+                if system_tray.is_enabled_setting(&app) {
+                    system_tray.enable(&app)
+                } else {
+                    system_tray.disable(&app)
+                }
+            } else if change.external_change("pinned_accounts") {
+                system_tray.refresh_menu(&app)
+            } else {
+                Ok(())
+            };
+            if let Err(e) = result {
+                tracing::warn!("按外部修改的设置更新托盘失败: {e}");
+            }
+        });
+    }
+
+    /// 更新托盘状态并刷新提示文字与图标
+    pub fn update_status<F>(&self, app_handle: &AppHandle, update_fn: F)
+    where
+        F: FnOnce(&mut TrayStatus),
+    {
+        if let Ok(mut status) = self.status.lock() {
+            update_fn(&mut status);
+        }
+        self.apply_status(app_handle);
+    }
+
+    /// 将当前状态写入托盘提示与图标
+    fn apply_status(&self, app_handle: &AppHandle) {
+        let Some(tray) = app_handle.tray_by_id("main") else {
+            return;
+        };
+        let Ok(status) = self.status.lock() else {
+            return;
+        };
+
+        // 状态图标（macOS 使用单色模板图，跟随菜单栏主题）
+        if let Some(base_icon) = app_handle.default_window_icon() {
+            let use_template = cfg!(target_os = "macos");
+            let icon = render_state_icon(base_icon, status.icon_state(), use_template);
+            if let Err(e) = tray.set_icon_with_as_template(Some(icon), use_template) {
+                tracing::warn!("更新托盘图标失败: {e}");
+            }
+        }
+
+        let account_line = match (&status.email, &status.plan) {
+            (Some(email), Some(plan)) => {
+                format!(
+                    "账户: {} ({})",
+                    crate::system_tray::tray::mask_email(email),
+                    plan
+                )
+            }
+            (Some(email), None) => format!("账户: {}", crate::system_tray::tray::mask_email(email)),
+            (None, _) => "账户: 未登录".to_string(),
+        };
+        let process_line = if status.antigravity_running {
+            "Antigravity: 运行中"
+        } else {
+            "Antigravity: 未运行"
+        };
+        let tooltip = format!("Antigravity Agent\n{account_line}\n{process_line}");
+
+        if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+            tracing::warn!("更新托盘提示失败: {e}");
+        }
     }
 
     /// 启用系统托盘
@@ -32,9 +197,120 @@ impl SystemTrayManager {
             tracing::info!("系统托盘已创建");
         }
 
+        // 3. 同步托盘提示与状态图标
+        self.apply_status(app_handle);
+
+        // 4. 填充快速切换菜单
+        if let Err(e) = self.refresh_menu(app_handle) {
+            tracing::warn!("填充托盘快速切换菜单失败: {e}");
+        }
+
+        Ok(())
+    }
+
+    /// 处理托盘图标鼠标事件（左键单击按设置执行对应操作）
+    pub fn handle_tray_icon_event(&self, app_handle: &AppHandle, event: &TrayIconEvent) {
+        let TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        } = event
+        else {
+            return;
+        };
+
+        let action = app_handle
+            .state::<AppSettingsManager>()
+            .get_settings()
+            .tray_left_click_action;
+
+        match action {
+            // 菜单由托盘自身在左键时弹出
+            TrayLeftClickAction::QuickSwitchMenu => {}
+            TrayLeftClickAction::ToggleWindow => {
+                let visible = app_handle
+                    .get_webview_window("main")
+                    .and_then(|window| window.is_visible().ok())
+                    .unwrap_or(false);
+                let result = if visible {
+                    self.minimize_to_tray(app_handle)
+                } else {
+                    self.restore_from_tray(app_handle)
+                };
+                if let Err(e) = result {
+                    tracing::error!("托盘左键切换窗口失败: {e}");
+                }
+            }
+            TrayLeftClickAction::ToggleAntigravity => {
+                tauri::async_runtime::spawn_blocking(|| {
+                    let result = if crate::platform::is_antigravity_running() {
+                        crate::platform::kill_antigravity_processes().map_err(String::from)
+                    } else {
+                        crate::antigravity::starter::start_antigravity()
+                    };
+                    match result {
+                        Ok(message) => {
+                            tracing::info!("托盘左键切换 Antigravity 运行状态: {message}")
+                        }
+                        Err(e) => tracing::error!("托盘左键切换 Antigravity 运行状态失败: {e}"),
+                    }
+                });
+            }
+        }
+    }
+
+    /// 设置托盘图标左键单击行为
+    pub fn set_left_click_action(
+        &self,
+        app_handle: &AppHandle,
+        action: TrayLeftClickAction,
+    ) -> Result<(), String> {
+        app_handle
+            .state::<AppSettingsManager>()
+            .update_settings(|s| s.tray_left_click_action = action)?;
+
+        if let Some(tray) = app_handle.tray_by_id("main") {
+            tray.set_show_menu_on_left_click(action == TrayLeftClickAction::QuickSwitchMenu)
+                .map_err(|e| format!("更新托盘左键行为失败: {e}"))?;
+        }
+
         Ok(())
     }
 
+    /// 根据备份目录重建托盘菜单（置顶账户优先，其余按最近备份时间排序）
+    ///
+    /// 在备份新增、删除、导入或置顶状态变化后调用
+    pub fn refresh_menu(&self, app_handle: &AppHandle) -> Result<(), String> {
+        if !self.is_enabled_setting(app_handle) {
+            return Ok(());
+        }
+
+        let accounts = crate::antigravity::backup::list_backup_files()?
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+
+        crate::system_tray::update_tray_menu(app_handle, accounts)
+    }
+
+    /// 设置账户在快速切换菜单中是否置顶
+    pub fn set_account_pinned(
+        &self,
+        app_handle: &AppHandle,
+        email: &str,
+        pinned: bool,
+    ) -> Result<(), String> {
+        let settings_manager = app_handle.state::<AppSettingsManager>();
+        settings_manager.update_settings(|s| {
+            s.pinned_accounts.retain(|e| e != email);
+            if pinned {
+                s.pinned_accounts.push(email.to_string());
+            }
+        })?;
+
+        self.refresh_menu(app_handle)
+    }
+
     /// 禁用系统托盘
     pub fn disable(&self, app_handle: &AppHandle) -> Result<(), String> {
         // 1. 更新设置
@@ -88,9 +364,19 @@ impl SystemTrayManager {
     /// 从托盘恢复窗口
     pub fn restore_from_tray(&self, app_handle: &AppHandle) -> Result<(), String> {
         if let Some(window) = app_handle.get_webview_window("main") {
+            // 通过最小化隐藏到托盘的窗口需要先取消最小化
+            window.unminimize().map_err(|e| e.to_string())?;
             window.show().map_err(|e| e.to_string())?;
             window.set_focus().map_err(|e| e.to_string())?;
         }
         Ok(())
     }
 }
+
+/// 备份列表变化后刷新托盘菜单（失败只记录日志，不影响调用方）
+pub fn refresh_tray_menu(app_handle: &AppHandle) {
+    let system_tray = app_handle.state::<SystemTrayManager>();
+    if let Err(e) = system_tray.refresh_menu(app_handle) {
+        tracing::warn!("刷新托盘菜单失败: {e}");
+    }
+}
@@ -3,20 +3,37 @@
 //! 使用 Tauri 2.9 内置的 tray API 实现后端控制托盘
 
 use crate::app_settings::AppSettingsManager;
-use tauri::menu::{Menu, MenuBuilder, MenuItem};
+use tauri::menu::{Menu, MenuBuilder, MenuItem, SubmenuBuilder};
 use tauri::tray::{TrayIcon, TrayIconBuilder};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
+
+/// 托盘“最近备份”子菜单显示的条目数
+const RECENT_BACKUPS_LIMIT: usize = 5;
 
 /// 创建系统托盘（返回托盘实例）
 pub fn create_tray_with_return(app: &AppHandle) -> Result<TrayIcon, String> {
     // 创建基础菜单（账户列表将由前端动态更新）
     let menu = create_basic_menu(app)?;
 
+    // 左键行为由设置决定：显示菜单，或交给托盘管理器处理
+    let left_click_action = app
+        .state::<AppSettingsManager>()
+        .get_settings()
+        .tray_left_click_action;
+
     // 构建托盘图标
     let tray = TrayIconBuilder::with_id("main")
         .menu(&menu)
         .on_menu_event(handle_tray_menu_event)
-        .show_menu_on_left_click(true)
+        .on_tray_icon_event(|tray, event| {
+            let app = tray.app_handle();
+            app.state::<crate::system_tray::SystemTrayManager>()
+                .handle_tray_icon_event(app, &event);
+        })
+        .show_menu_on_left_click(
+            left_click_action == crate::system_tray::TrayLeftClickAction::QuickSwitchMenu,
+        )
+        .tooltip("Antigravity Agent")
         .build(app)
         .map_err(|e| format!("创建系统托盘失败: {e}"))?;
 
@@ -29,6 +46,15 @@ pub fn create_tray_with_return(app: &AppHandle) -> Result<TrayIcon, String> {
     Ok(tray)
 }
 
+/// 退出菜单文字（已推迟安装更新时提示退出后会安装）
+fn quit_label() -> &'static str {
+    if crate::updater::will_install_on_exit() {
+        "退出并安装更新"
+    } else {
+        "退出应用"
+    }
+}
+
 /// 创建基础菜单（不含账户列表）
 fn create_basic_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, String> {
     MenuBuilder::new(app)
@@ -38,7 +64,7 @@ fn create_basic_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, String> {
         )
         .separator()
         .item(
-            &MenuItem::with_id(app, "quit", "退出应用", true, None::<&str>)
+            &MenuItem::with_id(app, "quit", quit_label(), true, None::<&str>)
                 .map_err(|e| format!("创建退出菜单失败: {e}"))?,
         )
         .build()
@@ -59,17 +85,86 @@ fn handle_tray_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
         }
         "quit" => {
             tracing::info!("退出应用");
-            app.exit(0);
+            crate::window::exit_guard::exit_or_confirm(app);
         }
-        // 账户切换事件
+        // 账户切换事件：直接在后端执行完整的切换流程
         account_id if account_id.starts_with("account_") => {
-            let account_email = account_id.strip_prefix("account_").unwrap_or("");
+            let account_email = account_id
+                .strip_prefix("account_")
+                .unwrap_or("")
+                .to_string();
             tracing::info!("请求切换到账户: {account_email}");
 
-            // 发射事件到前端
-            if let Err(e) = app.emit("tray-switch-account", account_email) {
-                tracing::error!("发射账户切换事件失败: {e}");
-            }
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = crate::commands::switch_to_antigravity_account(
+                    app.clone(),
+                    account_email.clone(),
+                )
+                .await;
+
+                let payload = match &result {
+                    Ok(message) => crate::events::TraySwitchResultPayload {
+                        email: account_email,
+                        success: true,
+                        message: message.clone(),
+                        code: None,
+                    },
+                    Err(e) => {
+                        tracing::error!("托盘切换账户失败: {e}");
+                        crate::events::TraySwitchResultPayload {
+                            email: account_email,
+                            success: false,
+                            message: e.to_string(),
+                            code: Some(e.code().to_string()),
+                        }
+                    }
+                };
+
+                // 通知前端切换结果
+                if let Err(e) = crate::events::emit(&app, &payload) {
+                    tracing::error!("发射账户切换结果事件失败: {e}");
+                }
+            });
+        }
+        // 最近备份事件：先创建安全快照，再恢复所选备份
+        backup_id if backup_id.starts_with("recent_backup_") => {
+            let backup_name = backup_id
+                .strip_prefix("recent_backup_")
+                .unwrap_or("")
+                .to_string();
+            tracing::info!("请求恢复最近备份: {backup_name}");
+
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = crate::commands::restore_backup_with_safety_snapshot(
+                    app.clone(),
+                    backup_name.clone(),
+                )
+                .await;
+
+                let payload = match &result {
+                    Ok(message) => crate::events::TraySwitchResultPayload {
+                        email: backup_name,
+                        success: true,
+                        message: message.clone(),
+                        code: None,
+                    },
+                    Err(e) => {
+                        tracing::error!("托盘恢复备份失败: {e}");
+                        crate::events::TraySwitchResultPayload {
+                            email: backup_name,
+                            success: false,
+                            message: e.to_string(),
+                            code: Some(e.code().to_string()),
+                        }
+                    }
+                };
+
+                if let Err(e) = crate::events::emit(&app, &payload) {
+                    tracing::error!("发射备份恢复结果事件失败: {e}");
+                }
+            });
         }
         _ => {
             tracing::warn!("未处理的菜单事件: {}", event.id.0);
@@ -101,28 +196,70 @@ pub fn update_tray_menu(app: &AppHandle, accounts: Vec<String>) -> Result<(), St
             .map_err(|e| format!("创建显示主窗口菜单失败: {e}"))?,
     );
 
-    // 添加账户列表
-    if !accounts.is_empty() {
-        menu_builder = menu_builder.separator();
-
-        for account in &accounts {
-            let masked_email = mask_email(account);
-            menu_builder = menu_builder.item(
-                &MenuItem::with_id(
-                    app,
-                    format!("account_{}", account),
-                    &masked_email,
-                    true,
-                    None::<&str>,
-                )
-                .map_err(|e| format!("创建账户菜单失败: {e}"))?,
-            );
+    // 添加“切换账户”子菜单（置顶账户在前，其余保持传入顺序）
+    let accounts = order_pinned_first(accounts, &settings.pinned_accounts);
+    let mut switch_menu =
+        SubmenuBuilder::with_id(app, "switch_account", "切换账户").enabled(!accounts.is_empty());
+
+    for account in &accounts {
+        let mut label = mask_email(account);
+        if settings.pinned_accounts.contains(account) {
+            label = format!("📌 {label}");
         }
+        switch_menu = switch_menu.item(
+            &MenuItem::with_id(
+                app,
+                format!("account_{}", account),
+                &label,
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| format!("创建账户菜单失败: {e}"))?,
+        );
+    }
+
+    menu_builder = menu_builder.separator().item(
+        &switch_menu
+            .build()
+            .map_err(|e| format!("创建切换账户子菜单失败: {e}"))?,
+    );
+
+    // 添加“最近备份”子菜单（按备份时间倒序，最多 5 条）
+    let recent_backups = crate::antigravity::backup::list_backup_files().unwrap_or_else(|e| {
+        tracing::warn!("读取最近备份失败: {e}");
+        Vec::new()
+    });
+    let mut recent_menu = SubmenuBuilder::with_id(app, "recent_backups", "最近备份")
+        .enabled(!recent_backups.is_empty());
+
+    for backup in recent_backups.iter().take(RECENT_BACKUPS_LIMIT) {
+        let modified: chrono::DateTime<chrono::Local> = backup.modified.into();
+        let label = format!(
+            "{}  ({})",
+            mask_email(&backup.name),
+            modified.format("%m-%d %H:%M")
+        );
+        recent_menu = recent_menu.item(
+            &MenuItem::with_id(
+                app,
+                format!("recent_backup_{}", backup.name),
+                &label,
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| format!("创建最近备份菜单失败: {e}"))?,
+        );
     }
 
+    menu_builder = menu_builder.item(
+        &recent_menu
+            .build()
+            .map_err(|e| format!("创建最近备份子菜单失败: {e}"))?,
+    );
+
     // 退出应用
     menu_builder = menu_builder.separator().item(
-        &MenuItem::with_id(app, "quit", "退出应用", true, None::<&str>)
+        &MenuItem::with_id(app, "quit", quit_label(), true, None::<&str>)
             .map_err(|e| format!("创建退出菜单失败: {e}"))?,
     );
 
@@ -138,8 +275,23 @@ pub fn update_tray_menu(app: &AppHandle, accounts: Vec<String>) -> Result<(), St
     Ok(())
 }
 
-/// 邮箱打码函数
-fn mask_email(email: &str) -> String {
+/// 将置顶账户排到最前（按置顶顺序），其余账户保持原有顺序
+fn order_pinned_first(accounts: Vec<String>, pinned: &[String]) -> Vec<String> {
+    let mut ordered: Vec<String> = pinned
+        .iter()
+        .filter(|p| accounts.contains(p))
+        .cloned()
+        .collect();
+    ordered.extend(accounts.into_iter().filter(|a| !pinned.contains(a)));
+    ordered
+}
+
+/// 邮箱打码函数（隐私模式下返回哈希标识）
+pub(crate) fn mask_email(email: &str) -> String {
+    if crate::utils::privacy::is_enabled() {
+        return crate::utils::privacy::hash(email);
+    }
+
     let parts: Vec<&str> = email.split('@').collect();
     if parts.len() != 2 {
         return email.to_string();
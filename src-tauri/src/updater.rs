@@ -0,0 +1,331 @@
+//! 应用更新
+//! 基于 `tauri_plugin_updater` 检查并安装更新。更新通道（稳定版 / 测试版）决定检查的端点，
+//! 下载进度通过 `update-download-progress` 事件发出。下载完成后可立即安装并重启，
+//! 也可推迟到应用退出时安装（开启 `install_update_later` 时默认推迟）。
+//! 账户切换、恢复等操作进行中时不会安装更新：立即安装会等待操作结束，退出时则跳过
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// 稳定版更新清单
+const STABLE_ENDPOINT: &str =
+    "https://github.com/MonchiLin/antigravity-agent/releases/latest/download/latest.json";
+
+/// 测试版更新清单（固定发布在 `beta` 标签下）
+const BETA_ENDPOINT: &str =
+    "https://github.com/MonchiLin/antigravity-agent/releases/download/beta/latest.json";
+
+/// 更新通道
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// 稳定版
+    #[default]
+    Stable,
+    /// 测试版
+    Beta,
+}
+
+impl UpdateChannel {
+    /// 该通道的更新清单地址
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            Self::Stable => STABLE_ENDPOINT,
+            Self::Beta => BETA_ENDPOINT,
+        }
+    }
+}
+
+/// 更新设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(default)]
+pub struct UpdateSettings {
+    /// 更新通道
+    pub channel: UpdateChannel,
+    /// 已下载但未手动安装的更新在应用退出时自动安装
+    pub install_update_later: bool,
+}
+
+/// 可用更新
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct UpdateInfo {
+    /// 新版本号
+    pub version: String,
+    /// 当前版本号
+    pub current_version: String,
+    /// 发布时间（RFC 3339）
+    pub date: Option<String>,
+    /// 更新说明
+    pub notes: Option<String>,
+    /// 安装包大小（字节），服务器未返回长度时为 `None`
+    pub size: Option<u64>,
+    /// 检查时使用的通道
+    pub channel: UpdateChannel,
+}
+
+/// 下载进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 等待进行中的账户操作结束时的检查间隔
+const PIPELINE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `update-download-progress` 事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDownloadProgressPayload {
+    /// 正在下载的版本
+    pub version: String,
+    /// 已下载字节数
+    pub downloaded: u64,
+    /// 总字节数（服务器未返回长度时为 `None`）
+    pub total: Option<u64>,
+    /// 是否已下载完成
+    pub finished: bool,
+}
+
+/// 更新状态
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct UpdateStatus {
+    /// 已检查到、尚未下载的版本
+    pub available: Option<String>,
+    /// 已下载、尚未安装的版本
+    pub downloaded: Option<String>,
+    /// 已下载的更新是否会在应用退出时安装
+    pub install_on_exit: bool,
+}
+
+/// 最近一次检查到的更新
+static PENDING: Mutex<Option<Update>> = Mutex::new(None);
+
+/// 已下载、等待安装的更新
+static DOWNLOADED: Mutex<Option<(Update, Vec<u8>)>> = Mutex::new(None);
+
+/// 已下载的更新是否推迟到退出时安装
+static INSTALL_ON_EXIT: AtomicBool = AtomicBool::new(false);
+
+/// 当前的更新设置
+pub fn settings(app_handle: &AppHandle) -> UpdateSettings {
+    app_handle
+        .state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .update
+}
+
+/// 按 `HEAD` 请求的 `Content-Length` 获取安装包大小
+async fn package_size(url: &Url) -> Option<u64> {
+    let client = crate::http_client::shared().ok()?;
+    let response = client.head(url.as_str()).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// 检查当前通道是否有可用更新
+pub async fn check(app_handle: &AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let channel = settings(app_handle).channel;
+    let endpoint = Url::parse(channel.endpoint()).map_err(|e| format!("更新地址无效: {}", e))?;
+
+    let mut builder = app_handle
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|e| format!("配置更新地址失败: {}", e))?;
+    if let Some(proxy) = crate::http_client::proxy() {
+        let proxy = Url::parse(&proxy).map_err(|e| format!("代理地址无效: {}", e))?;
+        builder = builder.proxy(proxy);
+    }
+    let updater = builder
+        .build()
+        .map_err(|e| format!("创建更新检查器失败: {}", e))?;
+
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| format!("检查更新失败: {}", e))?;
+    let Some(update) = update else {
+        tracing::info!(target: "updater::check", channel = ?channel, "当前已是最新版本");
+        *PENDING.lock().unwrap() = None;
+        return Ok(None);
+    };
+
+    let info = UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        date: update
+            .date
+            .and_then(|date| chrono::DateTime::from_timestamp(date.unix_timestamp(), 0))
+            .map(|date| date.to_rfc3339()),
+        notes: update.body.clone(),
+        size: package_size(&update.download_url).await,
+        channel,
+    };
+    tracing::info!(
+        target: "updater::check",
+        channel = ?channel,
+        version = %info.version,
+        current = %info.current_version,
+        "🆕 发现新版本"
+    );
+    *PENDING.lock().unwrap() = Some(update);
+    Ok(Some(info))
+}
+
+/// 发出下载进度事件
+fn emit_progress(app_handle: &AppHandle, payload: UpdateDownloadProgressPayload) {
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::warn!(target: "updater::download", error = %e, "发射下载进度事件失败");
+    }
+}
+
+/// 下载最近一次检查到的更新，返回是否会在退出时自动安装
+pub async fn download(app_handle: &AppHandle) -> Result<bool, String> {
+    let update = PENDING
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "没有待下载的更新，请先检查更新".to_string())?;
+
+    tracing::info!(target: "updater::download", version = %update.version, "⬇️ 开始下载更新");
+    let version = update.version.clone();
+    let mut downloaded = 0u64;
+    let mut last_emitted: Option<Instant> = None;
+    let result = update
+        .download(
+            |chunk_length, total| {
+                downloaded += chunk_length as u64;
+                // 限制事件频率，避免大量小数据块刷屏
+                if last_emitted.is_some_and(|last| last.elapsed() < PROGRESS_INTERVAL) {
+                    return;
+                }
+                last_emitted = Some(Instant::now());
+                emit_progress(
+                    app_handle,
+                    UpdateDownloadProgressPayload {
+                        version: version.clone(),
+                        downloaded,
+                        total,
+                        finished: false,
+                    },
+                );
+            },
+            || {},
+        )
+        .await;
+    let bytes = match result {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            // 保留待下载的更新，允许重试
+            *PENDING.lock().unwrap() = Some(update);
+            return Err(format!("下载更新失败: {}", e));
+        }
+    };
+    emit_progress(
+        app_handle,
+        UpdateDownloadProgressPayload {
+            version: version.clone(),
+            downloaded: bytes.len() as u64,
+            total: Some(bytes.len() as u64),
+            finished: true,
+        },
+    );
+    tracing::info!(target: "updater::download", version = %version, size = bytes.len(), "✅ 更新下载完成");
+    *DOWNLOADED.lock().unwrap() = Some((update, bytes));
+
+    let install_on_exit = settings(app_handle).install_update_later;
+    INSTALL_ON_EXIT.store(install_on_exit, Ordering::SeqCst);
+    crate::system_tray::refresh_tray_menu(app_handle);
+    Ok(install_on_exit)
+}
+
+/// 当前的更新状态
+pub fn status() -> UpdateStatus {
+    let available = PENDING
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|update| update.version.clone());
+    let downloaded = DOWNLOADED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|(update, _)| update.version.clone());
+    UpdateStatus {
+        available,
+        install_on_exit: downloaded.is_some() && INSTALL_ON_EXIT.load(Ordering::SeqCst),
+        downloaded,
+    }
+}
+
+/// 退出时是否会安装已下载的更新
+pub fn will_install_on_exit() -> bool {
+    status().install_on_exit
+}
+
+/// 推迟或取消推迟已下载更新的安装（推迟后在应用退出时安装）
+pub fn set_install_on_exit(app_handle: &AppHandle, enabled: bool) -> Result<UpdateStatus, String> {
+    if enabled && DOWNLOADED.lock().unwrap().is_none() {
+        return Err("没有已下载的更新".to_string());
+    }
+    INSTALL_ON_EXIT.store(enabled, Ordering::SeqCst);
+    tracing::info!(target: "updater::install", enabled, "⏳ 已更新退出时安装选项");
+    crate::system_tray::refresh_tray_menu(app_handle);
+    Ok(status())
+}
+
+/// 立即安装已下载的更新并重启应用；有进行中的账户操作时等待其结束
+pub async fn install_now(app_handle: &AppHandle) -> Result<(), String> {
+    if crate::pipeline::is_any_running() {
+        tracing::info!(target: "updater::install", "账户操作进行中，等待其结束后再安装更新");
+        while crate::pipeline::is_any_running() {
+            tokio::time::sleep(PIPELINE_POLL_INTERVAL).await;
+        }
+    }
+
+    let (update, bytes) = DOWNLOADED
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "没有已下载的更新".to_string())?;
+
+    if let Err(e) = update.install(&bytes) {
+        *DOWNLOADED.lock().unwrap() = Some((update, bytes));
+        return Err(format!("安装更新失败: {}", e));
+    }
+    tracing::info!(target: "updater::install", version = %update.version, "✅ 更新已安装，即将重启");
+    app_handle.restart();
+}
+
+/// 应用退出时安装已推迟的更新（账户操作仍在进行时跳过，避免破坏其数据）
+pub fn install_on_exit() {
+    if !will_install_on_exit() {
+        return;
+    }
+    if crate::pipeline::is_any_running() {
+        tracing::warn!(target: "updater::install", "账户操作仍在进行，本次退出不安装更新");
+        return;
+    }
+    let Some((update, bytes)) = DOWNLOADED
+        .lock()
+        .ok()
+        .and_then(|mut downloaded| downloaded.take())
+    else {
+        return;
+    };
+    match update.install(&bytes) {
+        Ok(()) => {
+            tracing::info!(target: "updater::install", version = %update.version, "✅ 已在退出时安装更新")
+        }
+        Err(e) => {
+            tracing::error!(target: "updater::install", version = %update.version, error = %e, "退出时安装更新失败")
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! 阻塞 IO 辅助模块
+//! 备份、恢复与配置文件的读写（包括 SQLite 访问）都是阻塞调用，在异步命令中统一交给
+//! tokio 的阻塞线程池执行，避免写入数 MB 的登录状态时占住异步运行时的工作线程
+
+use crate::error::{CoreError, CoreResult};
+use crate::utils::network_fs;
+use std::path::PathBuf;
+
+/// 在阻塞线程池中执行 `task`
+///
+/// `what` 描述任务内容，仅在任务异常退出（panic 或被取消）时用于错误信息
+pub async fn run<T, E, F>(what: &str, task: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    T: Send + 'static,
+    E: From<CoreError> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(task).await {
+        Ok(result) => result,
+        Err(e) => Err(CoreError::Other(format!("{}任务失败: {}", what, e)).into()),
+    }
+}
+
+/// 读取文本文件（网络路径按重试策略读取）
+pub async fn read_to_string(path: PathBuf) -> CoreResult<String> {
+    run("读取文件", move || {
+        network_fs::read_to_string(&path).map_err(|e| CoreError::io(&path, e))
+    })
+    .await
+}
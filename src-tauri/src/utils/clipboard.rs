@@ -0,0 +1,81 @@
+//! 敏感内容剪贴板
+//! 复制令牌等敏感内容后在指定时间自动清空剪贴板；清空前确认剪贴板内容仍是我们写入的值，
+//! 用户之后复制的其他内容不会被清除。内存中只保存内容的哈希
+
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 默认自动清空时间（秒）
+pub const DEFAULT_TTL_SECS: u64 = 30;
+
+/// 自动清空时间上限（秒）
+const MAX_TTL_SECS: u64 = 600;
+
+/// 每次复制递增，只有最近一次复制安排的清空任务生效
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 保持剪贴板实例存活（Linux 上实例释放后写入的内容可能随之丢失）
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+fn with_clipboard<T>(
+    f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+) -> Result<T, String> {
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .map_err(|e| format!("获取剪贴板锁失败: {}", e))?;
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new().map_err(|e| format!("打开剪贴板失败: {}", e))?);
+    }
+    let clipboard = clipboard.as_mut().expect("剪贴板已初始化");
+    f(clipboard).map_err(|e| format!("访问剪贴板失败: {}", e))
+}
+
+fn digest(value: &str) -> [u8; 32] {
+    Sha256::digest(value.as_bytes()).into()
+}
+
+/// 复制敏感内容，`ttl_secs` 秒后若剪贴板内容未变则清空
+pub fn copy_secret(value: String, ttl_secs: Option<u64>) -> Result<(), String> {
+    let ttl = ttl_secs.unwrap_or(DEFAULT_TTL_SECS).clamp(1, MAX_TTL_SECS);
+    let expected = digest(&value);
+
+    with_clipboard(|clipboard| clipboard.set_text(value))?;
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    tracing::info!(target: "app::clipboard", ttl_secs = ttl, "📋 已复制敏感内容，将自动清空");
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(ttl)).await;
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let cleared = tokio::task::spawn_blocking(move || clear_if_unchanged(&expected)).await;
+        match cleared {
+            Ok(Ok(true)) => {
+                tracing::info!(target: "app::clipboard", "🧹 剪贴板中的敏感内容已清空")
+            }
+            Ok(Ok(false)) => {
+                tracing::debug!(target: "app::clipboard", "剪贴板内容已变化，跳过清空")
+            }
+            Ok(Err(e)) => tracing::warn!(target: "app::clipboard", error = %e, "清空剪贴板失败"),
+            Err(e) => tracing::warn!(target: "app::clipboard", error = %e, "清空剪贴板任务失败"),
+        }
+    });
+    Ok(())
+}
+
+/// 剪贴板内容仍是复制的值时清空，返回是否已清空
+fn clear_if_unchanged(expected: &[u8; 32]) -> Result<bool, String> {
+    with_clipboard(|clipboard| {
+        let unchanged = clipboard
+            .get_text()
+            .map(|current| digest(&current) == *expected)
+            .unwrap_or(false);
+        if unchanged {
+            clipboard.clear()?;
+        }
+        Ok(unchanged)
+    })
+}
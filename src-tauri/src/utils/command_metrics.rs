@@ -0,0 +1,103 @@
+//! 命令性能指标
+//! `log_async_command!` / `log_user_command!` 在命令结束时记录耗时，
+//! 按命令名汇总调用次数、失败次数、平均耗时与 p95，用于性能排查
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// 每个命令保留的最近耗时样本数（用于计算 p95）
+const MAX_SAMPLES: usize = 512;
+
+/// 单个命令的累计统计
+#[derive(Debug, Default)]
+struct CommandStats {
+    count: u64,
+    error_count: u64,
+    total_ms: f64,
+    max_ms: f64,
+    /// 最近的耗时样本（毫秒）
+    samples: VecDeque<f64>,
+}
+
+/// 单个命令的性能指标
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct CommandMetrics {
+    pub command: String,
+    /// 调用次数
+    pub count: u64,
+    /// 失败次数
+    pub error_count: u64,
+    /// 平均耗时（毫秒）
+    pub avg_ms: f64,
+    /// 最近样本的 p95 耗时（毫秒）
+    pub p95_ms: f64,
+    /// 最大耗时（毫秒）
+    pub max_ms: f64,
+}
+
+fn registry() -> &'static Mutex<HashMap<String, CommandStats>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, CommandStats>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 记录一次命令执行
+pub fn record(command: &str, duration: Duration, success: bool) {
+    let Ok(mut registry) = registry().lock() else {
+        return;
+    };
+
+    let elapsed_ms = duration.as_secs_f64() * 1000.0;
+    let stats = registry.entry(command.to_string()).or_default();
+    stats.count += 1;
+    if !success {
+        stats.error_count += 1;
+    }
+    stats.total_ms += elapsed_ms;
+    stats.max_ms = stats.max_ms.max(elapsed_ms);
+
+    if stats.samples.len() == MAX_SAMPLES {
+        stats.samples.pop_front();
+    }
+    stats.samples.push_back(elapsed_ms);
+}
+
+/// 计算样本的 p95（最近邻取整）
+fn percentile_95(samples: &VecDeque<f64>) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let mut sorted: Vec<f64> = samples.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let rank = ((sorted.len() as f64) * 0.95).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+/// 所有命令的指标快照，按平均耗时倒序
+pub fn snapshot() -> Vec<CommandMetrics> {
+    let Ok(registry) = registry().lock() else {
+        return Vec::new();
+    };
+
+    let mut metrics: Vec<CommandMetrics> = registry
+        .iter()
+        .map(|(command, stats)| CommandMetrics {
+            command: command.clone(),
+            count: stats.count,
+            error_count: stats.error_count,
+            avg_ms: stats.total_ms / stats.count.max(1) as f64,
+            p95_ms: percentile_95(&stats.samples),
+            max_ms: stats.max_ms,
+        })
+        .collect();
+    metrics.sort_by(|a, b| b.avg_ms.total_cmp(&a.avg_ms));
+    metrics
+}
+
+/// 清空所有指标
+pub fn reset() {
+    if let Ok(mut registry) = registry().lock() {
+        registry.clear();
+    }
+}
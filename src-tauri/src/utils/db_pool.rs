@@ -0,0 +1,72 @@
+//! SQLite 只读连接池
+//! 数据库监控、读取当前账户与备份时只需读取 Antigravity 的状态数据库。按数据库路径缓存少量
+//! 只读连接复用，避免每次调用都重新打开连接、反复争用实时数据库的文件锁。
+//! 空闲超过 [`IDLE_TIMEOUT`] 的连接会被关闭，不会长期占用数据库文件
+
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::utils::network_fs;
+
+/// 每个数据库路径最多保留的空闲连接数
+const MAX_IDLE_PER_PATH: usize = 2;
+
+/// 空闲连接的最长保留时间
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 空闲连接
+struct IdleConnection {
+    path: PathBuf,
+    conn: Connection,
+    last_used: Instant,
+}
+
+/// 所有空闲连接（数量很少，线性查找即可）
+static IDLE: Mutex<Vec<IdleConnection>> = Mutex::new(Vec::new());
+
+/// 关闭超时的空闲连接
+fn prune(idle: &mut Vec<IdleConnection>) {
+    idle.retain(|entry| entry.last_used.elapsed() < IDLE_TIMEOUT);
+}
+
+/// 取出该路径的空闲连接
+fn checkout(path: &Path) -> Option<Connection> {
+    let mut idle = IDLE.lock().unwrap();
+    prune(&mut idle);
+    let index = idle.iter().rposition(|entry| entry.path == path)?;
+    Some(idle.swap_remove(index).conn)
+}
+
+/// 归还连接；该路径的空闲连接已满时直接关闭
+fn checkin(path: &Path, conn: Connection) {
+    let mut idle = IDLE.lock().unwrap();
+    prune(&mut idle);
+    if idle.iter().filter(|entry| entry.path == path).count() >= MAX_IDLE_PER_PATH {
+        return;
+    }
+    idle.push(IdleConnection {
+        path: path.to_path_buf(),
+        conn,
+        last_used: Instant::now(),
+    });
+}
+
+/// 使用该数据库的只读连接执行 `f`，优先复用空闲连接
+///
+/// `f` 返回错误时连接不再放回池中，下次调用重新打开
+pub fn with_read_connection<T, F>(path: &Path, f: F) -> rusqlite::Result<T>
+where
+    F: FnOnce(&Connection) -> rusqlite::Result<T>,
+{
+    let conn = match checkout(path) {
+        Some(conn) => conn,
+        None => network_fs::open_read_only_connection(path)?,
+    };
+    let result = f(&conn);
+    if result.is_ok() {
+        checkin(path, conn);
+    }
+    result
+}
@@ -0,0 +1,190 @@
+//! 配置目录权限加固
+//! `.antigravity-agent` 中保存着登录令牌，启动时与用户手动触发时检查并收紧权限：
+//! Unix 上目录 0700、文件 0600；Windows 上移除继承权限，只保留当前用户的完全控制
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// 权限检查与修复结果
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+pub struct HardenReport {
+    /// 检查的目录
+    pub path: PathBuf,
+    /// 发现的问题（权限过宽的路径及原因）
+    pub issues: Vec<String>,
+    /// 已修复的路径数
+    pub repaired: usize,
+    /// 修复失败的路径及原因
+    pub errors: Vec<String>,
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::HardenReport;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    const DIR_MODE: u32 = 0o700;
+    const FILE_MODE: u32 = 0o600;
+
+    fn visit(path: &Path, repair: bool, report: &mut HardenReport) {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return;
+        };
+        // 不跟随符号链接，避免修改目录之外的文件
+        if metadata.file_type().is_symlink() {
+            return;
+        }
+
+        let expected = if metadata.is_dir() {
+            DIR_MODE
+        } else {
+            FILE_MODE
+        };
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            report
+                .issues
+                .push(format!("{} 权限过宽 ({:o})", path.display(), mode));
+            if repair {
+                match fs::set_permissions(path, fs::Permissions::from_mode(expected)) {
+                    Ok(()) => report.repaired += 1,
+                    Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+                }
+            }
+        }
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    visit(&entry.path(), repair, report);
+                }
+            }
+        }
+    }
+
+    pub fn harden(dir: &Path, repair: bool, report: &mut HardenReport) {
+        visit(dir, repair, report);
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::HardenReport;
+    use std::os::windows::process::CommandExt;
+    use std::path::Path;
+    use std::process::Command;
+
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    /// 允许出现在 ACL 中的主体
+    const ALLOWED_PRINCIPALS: &[&str] = &["NT AUTHORITY\\SYSTEM", "BUILTIN\\Administrators"];
+
+    fn icacls(args: &[&str]) -> std::io::Result<std::process::Output> {
+        Command::new("icacls")
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+    }
+
+    /// 列出 ACL 中不属于当前用户、SYSTEM 与管理员组的条目
+    fn foreign_entries(dir: &Path, user: &str) -> Result<Vec<String>, String> {
+        let output =
+            icacls(&[&dir.to_string_lossy()]).map_err(|e| format!("执行 icacls 失败: {}", e))?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let dir_text = dir.to_string_lossy();
+
+        Ok(text
+            .lines()
+            .map(|line| line.trim_start_matches(dir_text.as_ref()).trim())
+            .filter(|line| line.contains(":("))
+            .filter(|line| {
+                let principal = line.split(":(").next().unwrap_or_default();
+                let name = principal.rsplit('\\').next().unwrap_or(principal);
+                !name.eq_ignore_ascii_case(user)
+                    && !ALLOWED_PRINCIPALS
+                        .iter()
+                        .any(|allowed| principal.eq_ignore_ascii_case(allowed))
+            })
+            .map(str::to_string)
+            .collect())
+    }
+
+    pub fn harden(dir: &Path, repair: bool, report: &mut HardenReport) {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        if user.is_empty() {
+            report.errors.push("无法获取当前用户名".to_string());
+            return;
+        }
+
+        match foreign_entries(dir, &user) {
+            Ok(entries) => report.issues.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| format!("{} 存在其他用户的权限: {}", dir.display(), entry)),
+            ),
+            Err(e) => {
+                report.errors.push(e);
+                return;
+            }
+        }
+
+        if !repair || report.issues.is_empty() {
+            return;
+        }
+
+        // 移除继承的权限，只授予当前用户完全控制（子目录与文件继承）
+        let grant = format!("{}:(OI)(CI)F", user);
+        let dir_text = dir.to_string_lossy();
+        let result = icacls(&[
+            &dir_text,
+            "/inheritance:r",
+            "/grant:r",
+            &grant,
+            "/T",
+            "/C",
+            "/Q",
+        ]);
+        match result {
+            Ok(output) if output.status.success() => report.repaired += 1,
+            Ok(output) => report.errors.push(format!(
+                "icacls 执行失败: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+            Err(e) => report.errors.push(format!("执行 icacls 失败: {}", e)),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use super::HardenReport;
+    use std::path::Path;
+
+    pub fn harden(_dir: &Path, _repair: bool, _report: &mut HardenReport) {}
+}
+
+/// 检查目录权限，`repair` 为 true 时同时收紧过宽的权限
+pub fn harden(dir: &Path, repair: bool) -> HardenReport {
+    let mut report = HardenReport {
+        path: dir.to_path_buf(),
+        ..Default::default()
+    };
+    if !dir.exists() {
+        return report;
+    }
+
+    imp::harden(dir, repair, &mut report);
+
+    if !report.issues.is_empty() {
+        tracing::warn!(
+            target: "app::permissions",
+            issues = report.issues.len(),
+            repaired = report.repaired,
+            errors = report.errors.len(),
+            "🔐 配置目录权限过宽"
+        );
+    }
+    report
+}
@@ -0,0 +1,28 @@
+//! 目录大小统计
+//! 递归累加目录下所有文件的字节数，不跟随符号链接；无法读取的条目按 0 计
+
+use std::fs;
+use std::path::Path;
+
+/// 文件或目录占用的字节数
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        };
+    }
+
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
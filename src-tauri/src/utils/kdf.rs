@@ -0,0 +1,158 @@
+//! Argon2id 参数
+//! 首次运行时在本机测量，选出单次派生约 250ms 的参数并保存到设置；
+//! 备份加密主密码与应用锁密码的派生都使用这里的参数（已保存的哈希/密文自带参数，不受影响）
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 目标派生耗时
+const TARGET: Duration = Duration::from_millis(250);
+
+/// 内存参数下限（KiB，OWASP 推荐的最低值）与上限
+const MIN_M_COST: u32 = 19 * 1024;
+const MAX_M_COST: u32 = 1024 * 1024;
+
+/// 迭代次数上限
+const MAX_T_COST: u32 = 10;
+
+/// 并行度上限
+const MAX_P_COST: u32 = 4;
+
+/// 测量时使用的初始参数
+const PROBE: KdfSettings = KdfSettings {
+    m_cost: 32 * 1024,
+    t_cost: 2,
+    p_cost: 1,
+};
+
+/// Argon2id 参数（内存 KiB、迭代次数、并行度）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct KdfSettings {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfSettings {
+    fn default() -> Self {
+        Self {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+        }
+    }
+}
+
+/// 当前使用的参数（未测量前为默认值）
+static CURRENT: Mutex<Option<KdfSettings>> = Mutex::new(None);
+
+/// 应用保存的参数
+pub fn set_params(settings: KdfSettings) {
+    if let Ok(mut current) = CURRENT.lock() {
+        *current = Some(settings);
+    }
+}
+
+/// 当前参数
+pub fn params() -> KdfSettings {
+    CURRENT
+        .lock()
+        .ok()
+        .and_then(|current| *current)
+        .unwrap_or_default()
+}
+
+/// 以给定参数构造 Argon2id 实例，`output_len` 为派生密钥长度
+pub fn argon2(
+    settings: &KdfSettings,
+    output_len: Option<usize>,
+) -> Result<Argon2<'static>, String> {
+    let params = Params::new(
+        settings.m_cost,
+        settings.t_cost,
+        settings.p_cost,
+        output_len,
+    )
+    .map_err(|e| format!("Argon2 参数无效: {}", e))?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+/// 检查来自文件等不可信来源的参数是否在允许范围内
+///
+/// 必须在派生前调用：参数直接决定派生占用的内存与耗时，超限的参数可能让应用分配数 GB 内存
+pub fn check_limits(settings: &KdfSettings) -> Result<(), String> {
+    if !(MIN_M_COST..=MAX_M_COST).contains(&settings.m_cost) {
+        return Err(format!(
+            "Argon2 内存参数超出范围（{} KiB，允许 {}..={}）",
+            settings.m_cost, MIN_M_COST, MAX_M_COST
+        ));
+    }
+    if !(1..=MAX_T_COST).contains(&settings.t_cost) {
+        return Err(format!(
+            "Argon2 迭代次数超出范围（{}，允许 1..={}）",
+            settings.t_cost, MAX_T_COST
+        ));
+    }
+    if !(1..=MAX_P_COST).contains(&settings.p_cost) {
+        return Err(format!(
+            "Argon2 并行度超出范围（{}，允许 1..={}）",
+            settings.p_cost, MAX_P_COST
+        ));
+    }
+    Ok(())
+}
+
+/// 以当前参数构造 Argon2id 实例
+pub fn current(output_len: Option<usize>) -> Result<Argon2<'static>, String> {
+    argon2(&params(), output_len)
+}
+
+/// 测量一次派生耗时
+fn measure(settings: &KdfSettings) -> Result<Duration, String> {
+    let argon2 = argon2(settings, Some(32))?;
+    let mut output = [0u8; 32];
+    let started = Instant::now();
+    argon2
+        .hash_password_into(
+            b"antigravity-agent-benchmark",
+            b"benchmark-salt",
+            &mut output,
+        )
+        .map_err(|e| format!("Argon2 测量失败: {}", e))?;
+    Ok(started.elapsed())
+}
+
+/// 在本机测量并选出单次派生约 250ms 的参数
+///
+/// 先按耗时比例调整内存参数，内存达到上限仍不够时再增加迭代次数
+pub fn benchmark() -> Result<KdfSettings, String> {
+    let probe = measure(&PROBE)?.max(Duration::from_millis(1));
+    let factor = TARGET.as_secs_f64() / probe.as_secs_f64();
+
+    let wanted_m = PROBE.m_cost as f64 * factor;
+    let m_cost = (wanted_m as u32).clamp(MIN_M_COST, MAX_M_COST);
+    let t_cost = if wanted_m > MAX_M_COST as f64 {
+        ((PROBE.t_cost as f64 * wanted_m / MAX_M_COST as f64).round() as u32)
+            .clamp(PROBE.t_cost, MAX_T_COST)
+    } else {
+        PROBE.t_cost
+    };
+
+    let settings = KdfSettings {
+        m_cost,
+        t_cost,
+        p_cost: PROBE.p_cost,
+    };
+    let elapsed = measure(&settings)?;
+    tracing::info!(
+        target: "app::kdf",
+        m_cost = settings.m_cost,
+        t_cost = settings.t_cost,
+        p_cost = settings.p_cost,
+        elapsed_ms = elapsed.as_millis() as u64,
+        "⏱️ Argon2 参数测量完成"
+    );
+    Ok(settings)
+}
@@ -1,21 +1,88 @@
 //! 日志装饰器工具
 //! 使用 tracing 提供命令执行的自动日志记录功能，包含智能脱敏
 
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// 参数中单个字符串值保留的最大字符数
+const MAX_ARG_CHARS: usize = 64;
+
+/// 参数摘要的最大字符数
+const MAX_SUMMARY_CHARS: usize = 512;
+
+/// 截断过长的字符串值，只保留开头与总长度
+fn truncate_strings(value: &mut Value) {
+    match value {
+        Value::String(text) if text.chars().count() > MAX_ARG_CHARS => {
+            let total = text.chars().count();
+            let head: String = text.chars().take(MAX_ARG_CHARS).collect();
+            *text = format!("{}…({} 字符)", head, total);
+        }
+        Value::Object(map) => map.values_mut().for_each(truncate_strings),
+        Value::Array(items) => items.iter_mut().for_each(truncate_strings),
+        _ => {}
+    }
+}
+
+/// 生成命令参数摘要：凭据字段遮盖，长字符串截断
+pub fn summarize_args(args: Vec<(&str, Value)>) -> String {
+    let mut value = Value::Object(
+        args.into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<Map<_, _>>(),
+    );
+    crate::utils::redacted_json::redact(&mut value);
+    truncate_strings(&mut value);
+
+    let summary = value.to_string();
+    if summary.chars().count() > MAX_SUMMARY_CHARS {
+        let head: String = summary.chars().take(MAX_SUMMARY_CHARS).collect();
+        format!("{}…", head)
+    } else {
+        summary
+    }
+}
+
+/// 序列化单个参数（无法序列化时记为 null）
+pub fn arg_value<T: Serialize + ?Sized>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// 命令返回值序列化后的字节数
+pub fn result_size<T: Serialize + ?Sized>(value: &T) -> usize {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0)
+}
+
 /// 替代原来的 log_async_command! 宏（带脱敏）
 /// 使用简洁的实现来避免类型推断问题
+///
+/// - `log_async_command!("name", future)`
+/// - `log_async_command!("name", args = (a, b), future)`：记录脱敏后的参数摘要
+/// - `log_async_command!("name", window = window.label(), args = (a), future)`：同时记录调用窗口
 #[macro_export]
 macro_rules! log_async_command {
-    ($command_name:expr, $future:expr) => {{
+    (@run $command_name:expr, $window:expr, [$($arg:ident),*], $future:expr) => {{
         let start_time = std::time::Instant::now();
+        let window: Option<&str> = $window;
+        let args = $crate::utils::log_decorator::summarize_args(vec![
+            $((stringify!($arg), $crate::utils::log_decorator::arg_value(&$arg))),*
+        ]);
         tracing::info!(
             target: "command::start",
             command = $command_name,
+            window = window.unwrap_or("-"),
+            args = %args,
             "🔧 开始执行命令"
         );
 
         // 直接处理future，避免类型推断问题
-        let (result, duration) = match $future.await {
-            Ok(r) => (Ok(r), start_time.elapsed()),
+        let (result, duration, result_bytes) = match $future.await {
+            Ok(r) => {
+                let result_bytes = $crate::utils::log_decorator::result_size(&r);
+                (Ok(r), start_time.elapsed(), result_bytes)
+            }
             Err(e) => {
                 let duration = start_time.elapsed();
                 // 简化错误处理，避免字符串操作的类型推断
@@ -23,18 +90,27 @@ macro_rules! log_async_command {
                 tracing::error!(
                     target: "command::error",
                     command = $command_name,
+                    window = window.unwrap_or("-"),
+                    args = %args,
+                    result = "err",
                     duration_ms = duration.as_millis(),
                     error = %e,
                     "❌ 命令失败: {}", error_msg
                 );
-                (Err(e), duration)
+                $crate::error_report::capture_command_error($command_name, &e.to_string());
+                (Err(e), duration, 0)
             }
         };
 
+        $crate::utils::command_metrics::record($command_name, duration, result.is_ok());
+
         if result.is_ok() {
             tracing::info!(
                 target: "command::success",
                 command = $command_name,
+                window = window.unwrap_or("-"),
+                result = "ok",
+                result_bytes = result_bytes,
                 duration_ms = duration.as_millis(),
                 "✅ 命令完成"
             );
@@ -42,6 +118,15 @@ macro_rules! log_async_command {
 
         result
     }};
+    ($command_name:expr, window = $window:expr, args = ($($arg:ident),* $(,)?), $future:expr) => {
+        $crate::log_async_command!(@run $command_name, Some($window), [$($arg),*], $future)
+    };
+    ($command_name:expr, args = ($($arg:ident),* $(,)?), $future:expr) => {
+        $crate::log_async_command!(@run $command_name, None::<&str>, [$($arg),*], $future)
+    };
+    ($command_name:expr, $future:expr) => {
+        $crate::log_async_command!(@run $command_name, None::<&str>, [], $future)
+    };
 }
 
 /// 带用户上下文的日志记录（带脱敏）
@@ -50,7 +135,11 @@ macro_rules! log_user_command {
     ($command_name:expr, $user_email:expr, $future:expr) => {{
         let start_time = std::time::Instant::now();
         let sanitizer = $crate::utils::log_sanitizer::LogSanitizer::new();
-        let masked_email = sanitizer.sanitize_email($user_email);
+        let masked_email = if $crate::utils::privacy::is_enabled() {
+            $crate::utils::privacy::hash($user_email)
+        } else {
+            sanitizer.sanitize_email($user_email)
+        };
         tracing::info!(
             target: "user_command::start",
             command = $command_name,
@@ -61,6 +150,7 @@ macro_rules! log_user_command {
         match $future.await {
             Ok(result) => {
                 let duration = start_time.elapsed();
+                $crate::utils::command_metrics::record($command_name, duration, true);
                 tracing::info!(
                     target: "user_command::success",
                     command = $command_name,
@@ -71,6 +161,7 @@ macro_rules! log_user_command {
             }
             Err(e) => {
                 let duration = start_time.elapsed();
+                $crate::utils::command_metrics::record($command_name, duration, false);
                 let error_msg = format!("用户操作失败");
                 tracing::error!(
                     target: "user_command::error",
@@ -0,0 +1,312 @@
+//! 日志查询模块
+//! 读取 JSON lines 格式的日志文件，按级别、目标模块、时间范围和关键字过滤，
+//! 返回分页后的结构化日志条目，供前端日志查看器使用
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::log_sanitizer::redact_secrets;
+
+/// 日志文件名前缀（与 `SanitizingFileWriter` 保持一致）
+pub const LOG_FILE_PREFIX: &str = "antigravity-agent";
+
+/// 默认每页条数
+const DEFAULT_LIMIT: usize = 200;
+/// 单页最大条数
+const MAX_LIMIT: usize = 1000;
+
+/// 日志查询条件（所有字段均可省略）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(default)]
+pub struct LogQueryFilter {
+    /// 最低日志级别（trace / debug / info / warn / error）
+    pub level: Option<String>,
+    /// 目标模块前缀，例如 `window` 匹配 `window::save`
+    pub target: Option<String>,
+    /// 起始时间（毫秒时间戳，含）
+    pub since_ms: Option<i64>,
+    /// 结束时间（毫秒时间戳，含）
+    pub until_ms: Option<i64>,
+    /// 关键字（不区分大小写，匹配消息与字段）
+    pub text: Option<String>,
+    /// 运行 ID（精确匹配）
+    pub run_id: Option<String>,
+    /// 跳过的条数（按时间倒序）
+    pub offset: usize,
+    /// 返回的条数
+    pub limit: Option<usize>,
+}
+
+/// 结构化日志条目
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct LogEntry {
+    /// RFC 3339 格式的时间
+    pub timestamp: String,
+    /// 毫秒时间戳
+    pub timestamp_ms: i64,
+    /// 日志级别（小写）
+    pub level: String,
+    /// 目标模块
+    pub target: String,
+    /// 日志消息
+    pub message: String,
+    /// 其余结构化字段
+    pub fields: Map<String, Value>,
+    /// 写入该条日志的运行 ID（旧版本日志没有）
+    pub run_id: Option<String>,
+}
+
+/// 日志查询结果
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct LogQueryResult {
+    /// 当前页的条目（时间倒序）
+    pub entries: Vec<LogEntry>,
+    /// 符合条件的总条数
+    pub total: usize,
+    /// 是否还有更多条目
+    pub has_more: bool,
+}
+
+/// 日志级别排序值，数值越大越严重
+fn level_rank(level: &str) -> u8 {
+    match level.to_ascii_lowercase().as_str() {
+        "trace" => 0,
+        "debug" => 1,
+        "info" => 2,
+        "warn" | "warning" => 3,
+        "error" => 4,
+        _ => 2,
+    }
+}
+
+/// 解析单行 JSON 日志；非 JSON 行（旧版纯文本日志）返回 None
+pub fn parse_line(line: &str) -> Option<LogEntry> {
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    let object = value.as_object()?;
+
+    let timestamp = object.get("timestamp")?.as_str()?.to_string();
+    let timestamp_ms = DateTime::parse_from_rfc3339(&timestamp)
+        .ok()?
+        .with_timezone(&Utc)
+        .timestamp_millis();
+
+    let level = object
+        .get("level")
+        .and_then(Value::as_str)
+        .unwrap_or("INFO")
+        .to_ascii_lowercase();
+    let target = object
+        .get("target")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    let run_id = object
+        .get("run_id")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let mut fields = object
+        .get("fields")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let message = match fields.remove("message") {
+        Some(Value::String(message)) => message,
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+
+    // 写入时已脱敏，这里再移除一次凭据，覆盖旧版本写入的日志
+    let message = redact_secrets(&message);
+    for value in fields.values_mut() {
+        if let Value::String(text) = value {
+            *text = redact_secrets(text);
+        }
+    }
+
+    Some(LogEntry {
+        timestamp,
+        timestamp_ms,
+        level,
+        target,
+        message,
+        fields,
+        run_id,
+    })
+}
+
+impl LogQueryFilter {
+    /// 条目是否满足过滤条件
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(level) = &self.level {
+            if level_rank(&entry.level) < level_rank(level) {
+                return false;
+            }
+        }
+
+        if let Some(target) = self.target.as_deref().filter(|t| !t.is_empty()) {
+            if !entry.target.starts_with(target) {
+                return false;
+            }
+        }
+
+        if let Some(run_id) = self.run_id.as_deref().filter(|r| !r.is_empty()) {
+            if entry.run_id.as_deref() != Some(run_id) {
+                return false;
+            }
+        }
+
+        if self
+            .since_ms
+            .is_some_and(|since| entry.timestamp_ms < since)
+        {
+            return false;
+        }
+        if self
+            .until_ms
+            .is_some_and(|until| entry.timestamp_ms > until)
+        {
+            return false;
+        }
+
+        if let Some(text) = self.text.as_deref().filter(|t| !t.is_empty()) {
+            let needle = text.to_lowercase();
+            let in_message = entry.message.to_lowercase().contains(&needle);
+            let in_fields = entry
+                .fields
+                .values()
+                .any(|value| value.to_string().to_lowercase().contains(&needle));
+            if !in_message && !in_fields {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// 日志文件是否可能包含时间范围内的条目（按文件名中的日期判断）
+    fn may_contain(&self, file_date: Option<NaiveDate>) -> bool {
+        let Some(date) = file_date else {
+            return true;
+        };
+        let day_start = date
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        let day_end = day_start + 24 * 60 * 60 * 1000;
+
+        // 留一天余量，避免本地时区与 UTC 的日期差异
+        let margin = 24 * 60 * 60 * 1000;
+        if self.since_ms.is_some_and(|since| day_end + margin < since) {
+            return false;
+        }
+        if self
+            .until_ms
+            .is_some_and(|until| day_start - margin > until)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// 从日志文件名中提取日期（`antigravity-agent.2024-01-15`）
+pub fn file_date(path: &Path) -> Option<NaiveDate> {
+    let name = path.file_name()?.to_str()?;
+    let date = name.strip_prefix(LOG_FILE_PREFIX)?.trim_start_matches('.');
+    NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// 列出日志目录下的日志文件，按文件名倒序（最新在前）
+pub fn list_log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(log_dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// 收集所有符合条件的条目（时间倒序，不分页）
+fn collect_matching(log_dir: &Path, filter: &LogQueryFilter) -> Vec<LogEntry> {
+    let mut matched = Vec::new();
+    for path in list_log_files(log_dir) {
+        if !filter.may_contain(file_date(&path)) {
+            continue;
+        }
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!(target: "logging::query", error = %e, "读取日志文件失败: {}", path.display());
+                continue;
+            }
+        };
+
+        matched.extend(
+            content
+                .lines()
+                .filter_map(parse_line)
+                .filter(|entry| filter.matches(entry)),
+        );
+    }
+
+    // 时间倒序，最新的日志在前
+    matched.sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+    matched
+}
+
+/// 在日志目录中执行查询
+pub fn query(log_dir: &Path, filter: &LogQueryFilter) -> Result<LogQueryResult, String> {
+    let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let matched = collect_matching(log_dir, filter);
+    let total = matched.len();
+    let entries: Vec<LogEntry> = matched
+        .into_iter()
+        .skip(filter.offset)
+        .take(limit)
+        .collect();
+    let has_more = filter.offset + entries.len() < total;
+
+    Ok(LogQueryResult {
+        entries,
+        total,
+        has_more,
+    })
+}
+
+/// 将所有符合条件的条目导出为 JSON lines 文件（忽略分页，按时间正序），返回导出条数
+pub fn export(log_dir: &Path, filter: &LogQueryFilter, output: &Path) -> Result<usize, String> {
+    let mut entries = collect_matching(log_dir, filter);
+    entries.reverse();
+
+    let mut content = String::new();
+    for entry in &entries {
+        let line =
+            serde_json::to_string(entry).map_err(|e| format!("序列化日志条目失败: {}", e))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    fs::write(output, content).map_err(|e| format!("写入导出文件失败: {}", e))?;
+    Ok(entries.len())
+}
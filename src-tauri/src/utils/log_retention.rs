@@ -0,0 +1,181 @@
+//! 日志保留策略
+//! 按最长保留天数与总大小上限删除过期的日志分段，启动时和之后每天自动执行一次；
+//! 当天正在写入的分段始终保留
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::utils::log_query::{file_date, list_log_files};
+
+/// 自动清理的执行间隔
+pub const CLEANUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 最近一次自动清理的时间
+static LAST_RUN: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
+
+/// 日志保留设置（字段为空表示不限制）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(default)]
+pub struct LogRetentionSettings {
+    /// 最长保留天数
+    pub max_age_days: Option<u32>,
+    /// 日志总大小上限（MB）
+    pub max_total_size_mb: Option<u64>,
+}
+
+impl Default for LogRetentionSettings {
+    fn default() -> Self {
+        Self {
+            max_age_days: Some(14),
+            max_total_size_mb: Some(100),
+        }
+    }
+}
+
+/// 清理结果
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+pub struct LogCleanupResult {
+    /// 删除的分段数
+    pub deleted_files: usize,
+    /// 释放的字节数
+    pub freed_bytes: u64,
+}
+
+/// 日志分段
+struct LogSegment {
+    path: PathBuf,
+    date: Option<NaiveDate>,
+    size: u64,
+}
+
+/// 列出可删除的分段（最新在前），排除当天正在写入的分段
+fn removable_segments(log_dir: &Path, today: NaiveDate) -> Vec<LogSegment> {
+    list_log_files(log_dir)
+        .into_iter()
+        .map(|path| LogSegment {
+            date: file_date(&path),
+            size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path,
+        })
+        .filter(|segment| segment.date != Some(today))
+        .collect()
+}
+
+/// 删除指定分段
+fn delete_segments<'a>(segments: impl IntoIterator<Item = &'a LogSegment>) -> LogCleanupResult {
+    let mut result = LogCleanupResult::default();
+    for segment in segments {
+        match fs::remove_file(&segment.path) {
+            Ok(()) => {
+                result.deleted_files += 1;
+                result.freed_bytes += segment.size;
+            }
+            Err(e) => {
+                tracing::warn!(target: "logging::retention", error = %e, "删除日志文件失败: {}", segment.path.display());
+            }
+        }
+    }
+    result
+}
+
+/// 删除除当天分段以外的所有日志
+pub fn clear(log_dir: &Path) -> LogCleanupResult {
+    let today = Local::now().date_naive();
+    delete_segments(&removable_segments(log_dir, today))
+}
+
+/// 按保留策略删除过期分段
+pub fn enforce(log_dir: &Path, settings: &LogRetentionSettings) -> LogCleanupResult {
+    let today = Local::now().date_naive();
+    let segments = removable_segments(log_dir, today);
+
+    // 当天分段计入总大小，但不会被删除
+    let active_size: u64 = list_log_files(log_dir)
+        .iter()
+        .filter(|path| file_date(path) == Some(today))
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|m| m.len())
+        .sum();
+    let mut total_size = active_size + segments.iter().map(|s| s.size).sum::<u64>();
+    let size_limit = settings
+        .max_total_size_mb
+        .map(|mb| mb.saturating_mul(1024 * 1024));
+
+    // 从最旧的分段开始，超过天数或总大小超限的都删除
+    let mut expired = Vec::new();
+    for segment in segments.iter().rev() {
+        let too_old = match (settings.max_age_days, segment.date) {
+            (Some(days), Some(date)) => (today - date).num_days() > i64::from(days),
+            _ => false,
+        };
+        let over_size = size_limit.is_some_and(|limit| total_size > limit);
+        if !too_old && !over_size {
+            break;
+        }
+        total_size = total_size.saturating_sub(segment.size);
+        expired.push(segment);
+    }
+
+    let result = delete_segments(expired);
+    if result.deleted_files > 0 {
+        tracing::info!(
+            target: "logging::retention",
+            deleted_files = result.deleted_files,
+            freed_bytes = result.freed_bytes,
+            "🧹 已清理过期日志"
+        );
+    }
+    result
+}
+
+/// 启动时执行一次清理，之后每天执行一次（每次读取最新的保留设置）
+pub fn start_scheduler(app_handle: &AppHandle) {
+    // 外部编辑保留策略后立即按新策略清理一次
+    let app = app_handle.clone();
+    crate::events::listen(
+        app_handle,
+        move |change: crate::settings_watcher::SettingsChangedPayload| {
+            if !change.external_change("log_retention") {
+                return;
+            }
+            let settings = app
+                .state::<crate::app_settings::AppSettingsManager>()
+                .get_settings()
+                .log_retention;
+            let log_dir = crate::directories::get_log_directory();
+            tauri::async_runtime::spawn_blocking(move || enforce(&log_dir, &settings));
+        },
+    );
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Ok(mut last_run) = LAST_RUN.lock() {
+                *last_run = Some(Local::now());
+            }
+
+            let settings = app_handle
+                .state::<crate::app_settings::AppSettingsManager>()
+                .get_settings()
+                .log_retention;
+            let log_dir = crate::directories::get_log_directory();
+            if let Err(e) = tokio::task::spawn_blocking(move || enforce(&log_dir, &settings)).await
+            {
+                tracing::warn!(target: "logging::retention", error = %e, "日志清理任务失败");
+            }
+        }
+    });
+}
+
+/// 下一次自动清理的时间（调度尚未启动时为 `None`）
+pub fn next_run() -> Option<DateTime<Local>> {
+    let last_run = (*LAST_RUN.lock().ok()?)?;
+    Some(last_run + chrono::Duration::from_std(CLEANUP_INTERVAL).ok()?)
+}
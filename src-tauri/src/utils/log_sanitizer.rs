@@ -1,7 +1,26 @@
 //! 日志脱敏模块
 //! 对敏感信息进行智能遮盖，保护用户隐私的同时保留调试价值
+//!
+//! 访问令牌、id_token、API 密钥等凭据会被完全替换为 [`REDACTED`]，
+//! 既作用于日志文件与控制台输出，也作用于事件负载与日志查询结果
 
 use regex::Regex;
+use std::sync::OnceLock;
+
+/// 凭据被替换后的占位文本
+pub const REDACTED: &str = "[REDACTED]";
+
+/// 已知的凭据字段名（匹配时忽略大小写与 `_` / `-` 分隔）
+pub(crate) const SECRET_KEY_NAMES: &[&str] = &[
+    "access_token",
+    "id_token",
+    "refresh_token",
+    "api_key",
+    "client_secret",
+    "authorization",
+    "password",
+    "secret",
+];
 
 /// 日志脱敏器
 pub struct LogSanitizer {
@@ -13,15 +32,31 @@ pub struct LogSanitizer {
     user_home_regex: Regex,
     /// Windows用户目录正则表达式
     windows_user_regex: Regex,
+    /// 已知凭据字段名后的值（`"access_token": "..."`、`id_token=...`）
+    secret_field_regex: Regex,
+    /// 无字段名也能识别的凭据格式（JWT、Google OAuth 令牌、Bearer 等）
+    secret_value_regex: Regex,
 }
 
 impl Default for LogSanitizer {
     fn default() -> Self {
         Self {
             email_regex: Regex::new(r"(?i)[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}").unwrap(),
-            api_key_regex: Regex::new(r"(?i)(?P<prefix>key|token|secret|api[-_]?key|access[-_]?token)[\s=:]+(?P<key>[a-zA-Z0-9+/=_-]{20,})").unwrap(),
+            api_key_regex: Regex::new(r"(?i)(?P<prefix>key|token|secret|api[-_]?key|access[-_]?token)(?P<sep>[\s=:]+)(?P<key>[a-zA-Z0-9+/=_-]{20,})").unwrap(),
             user_home_regex: Regex::new(r"(?P<prefix>/home/[^/]+)").unwrap(),
             windows_user_regex: Regex::new(r"C:\\\\Users\\\\[^\\\\]+").unwrap(),
+            secret_field_regex: Regex::new(&secret_field_pattern()).unwrap(),
+            secret_value_regex: Regex::new(
+                r"(?x)
+                eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]*  # JWT（id_token）
+                | ya29\.[A-Za-z0-9_-]{10,}                                # Google 访问令牌
+                | 1//[A-Za-z0-9_-]{20,}                                    # Google 刷新令牌
+                | AIza[A-Za-z0-9_-]{35}                                    # Google API 密钥
+                | sk-[A-Za-z0-9_-]{20,}                                    # 常见 API 密钥前缀
+                | (?i:bearer)\s+[A-Za-z0-9._~+/=-]{16,}                    # Authorization 头
+                ",
+            )
+            .unwrap(),
         }
     }
 }
@@ -34,10 +69,15 @@ impl LogSanitizer {
 
     /// 对字符串进行脱敏处理
     pub fn sanitize(&self, input: &str) -> String {
-        let mut result = input.to_string();
+        // 0. 先移除凭据，避免后续规则只做部分遮盖
+        let mut result = self.redact_secrets(input);
 
-        // 1. 脱敏邮箱地址
-        result = self.sanitize_email(&result);
+        // 1. 脱敏邮箱地址（隐私模式下替换为哈希）
+        result = if crate::utils::privacy::is_enabled() {
+            crate::utils::privacy::scrub(&result)
+        } else {
+            self.sanitize_email(&result)
+        };
 
         // 2. 使用基础的路径脱敏
         result = self.sanitize_paths(&result);
@@ -135,24 +175,67 @@ impl LogSanitizer {
         self.api_key_regex
             .replace_all(input, |caps: &regex::Captures| {
                 let prefix = &caps["prefix"];
+                let separator = &caps["sep"];
                 let key = &caps["key"];
                 let visible_len = std::cmp::min(4, key.len());
                 let masked_len = key.len().saturating_sub(visible_len);
 
                 if key.len() <= 4 {
-                    format!("{}{}", prefix, key)
+                    format!("{}{}{}", prefix, separator, key)
                 } else {
                     let visible_part = &key[..visible_len];
                     let masked_part = "*".repeat(masked_len);
-                    format!("{}{}{}", prefix, visible_part, masked_part)
+                    format!("{}{}{}{}", prefix, separator, visible_part, masked_part)
                 }
             })
             .to_string()
     }
+
+    /// 凭据脱敏 - 将已知字段的值与可识别的令牌格式整体替换为 `[REDACTED]`
+    ///
+    /// 只替换值本身，JSON 的引号与分隔符保持不变，输出仍是合法 JSON
+    ///
+    /// # 示例
+    /// ```
+    /// "{\"access_token\":\"ya29.a0Af...\"}" → "{\"access_token\":\"[REDACTED]\"}"
+    /// "Authorization: Bearer abc.def.ghi" → "Authorization: [REDACTED]"
+    /// ```
+    pub fn redact_secrets(&self, input: &str) -> String {
+        // 先替换可识别格式，`Bearer xxx` 这类带空格的值才能被整体移除
+        let result = self.secret_value_regex.replace_all(input, REDACTED);
+        self.secret_field_regex
+            .replace_all(&result, |caps: &regex::Captures| {
+                format!("{}{}", &caps["prefix"], REDACTED)
+            })
+            .to_string()
+    }
+}
+
+/// 构造凭据字段正则：字段名 + 可选引号 + 分隔符 + 可选引号，值到引号 / 空白 / 逗号为止
+fn secret_field_pattern() -> String {
+    let names = SECRET_KEY_NAMES
+        .iter()
+        .map(|name| name.replace('_', "[_-]?"))
+        .collect::<Vec<_>>()
+        .join("|");
+    format!(
+        r#"(?i)(?P<prefix>\b(?:{})\\?"?\s*[:=]\s*\\?"?)(?P<value>[^"\\\s,;&}}]{{6,}})"#,
+        names
+    )
+}
+
+/// 全局共享的脱敏器，避免每条日志都重新编译正则
+fn shared() -> &'static LogSanitizer {
+    static SANITIZER: OnceLock<LogSanitizer> = OnceLock::new();
+    SANITIZER.get_or_init(LogSanitizer::new)
+}
+
+/// 仅移除凭据（保留邮箱与路径），用于控制台输出、事件负载等场景
+pub fn redact_secrets(message: &str) -> String {
+    shared().redact_secrets(message)
 }
 
 /// 对日志消息进行脱敏处理的便捷函数
 pub fn sanitize_log_message(message: &str) -> String {
-    let sanitizer = LogSanitizer::new();
-    sanitizer.sanitize(message)
+    shared().sanitize(message)
 }
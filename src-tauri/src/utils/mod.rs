@@ -1,6 +1,19 @@
 //! 工具模块
 
+pub mod blocking;
+pub mod clipboard;
+pub mod command_metrics;
+pub mod db_pool;
+pub mod dir_permissions;
+pub mod dir_size;
+pub mod kdf;
 pub mod log_decorator;
+pub mod log_query;
+pub mod log_retention;
 pub mod log_sanitizer;
+pub mod network_fs;
+pub mod privacy;
+pub mod redacted_json;
 pub mod sanitizing_layer;
+pub mod secure_delete;
 pub mod tracing_config;
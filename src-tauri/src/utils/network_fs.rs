@@ -0,0 +1,298 @@
+//! 网络路径支持模块
+//! 检测位于 UNC 共享或网络挂载点上的路径，并为文件读写和数据库连接提供重试与更长的超时
+
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// 本地路径的 SQLite 忙等待超时
+const LOCAL_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 网络路径的 SQLite 忙等待超时
+const NETWORK_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 被视为网络文件系统的挂载类型（Linux / macOS）
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb",
+    "smbfs",
+    "smb3",
+    "afpfs",
+    "webdav",
+    "sshfs",
+    "fuse.sshfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "fuse.glusterfs",
+    "davfs",
+    "fuse.rclone",
+];
+
+/// 目录 -> 是否位于网络挂载点（挂载表的读取代价较高，每个目录只检测一次）
+static MOUNT_CACHE: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+
+/// 重试策略
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（包含首次）
+    pub attempts: u32,
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// 本地路径：不重试
+    pub const LOCAL: RetryPolicy = RetryPolicy {
+        attempts: 1,
+        base_delay: Duration::from_millis(0),
+    };
+
+    /// 网络路径：最多 5 次，200ms 起指数退避
+    pub const NETWORK: RetryPolicy = RetryPolicy {
+        attempts: 5,
+        base_delay: Duration::from_millis(200),
+    };
+
+    /// 根据路径类型选择重试策略
+    pub fn for_path(path: &Path) -> Self {
+        if is_network_path(path) {
+            Self::NETWORK
+        } else {
+            Self::LOCAL
+        }
+    }
+
+    /// 第 `attempt` 次失败后的等待时间（从 1 开始）
+    fn delay_after(&self, attempt: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+}
+
+/// 判断路径是否位于网络共享上
+///
+/// - Windows：`\\server\share\...` 与 `\\?\UNC\server\share\...`
+/// - Linux：根据 `/proc/self/mounts` 中最长匹配挂载点的文件系统类型判断
+/// - macOS：根据 `mount` 命令输出中最长匹配挂载点的文件系统类型判断
+///
+/// 挂载点的检测结果按所在目录缓存，同一目录下的文件读写不会重复读取挂载表
+pub fn is_network_path(path: &Path) -> bool {
+    if let Some(Component::Prefix(prefix)) = path.components().next() {
+        if matches!(prefix.kind(), Prefix::UNC(..) | Prefix::VerbatimUNC(..)) {
+            return true;
+        }
+    }
+
+    let path_str = path.to_string_lossy();
+    if path_str.starts_with("\\\\") || path_str.starts_with("//") {
+        return true;
+    }
+
+    is_network_mount_cached(path)
+}
+
+/// 按所在目录缓存挂载点检测结果
+fn is_network_mount_cached(path: &Path) -> bool {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => path,
+    };
+    let cache = MOUNT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(cached) = cache.lock().ok().and_then(|cache| cache.get(dir).copied()) {
+        return cached;
+    }
+
+    // 检测时不持有锁：macOS 上需要运行 `mount` 进程
+    let is_network = is_network_mount_impl(dir);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(dir.to_path_buf(), is_network);
+    }
+    is_network
+}
+
+#[cfg(target_os = "linux")]
+fn is_network_mount_impl(path: &Path) -> bool {
+    let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") else {
+        return false;
+    };
+
+    let mounts = mounts.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let _source = fields.next()?;
+        let mount_point = decode_mount_escapes(fields.next()?);
+        let fs_type = fields.next()?.to_string();
+        Some((mount_point, fs_type))
+    });
+
+    longest_mount_is_network(path, mounts)
+}
+
+#[cfg(target_os = "macos")]
+fn is_network_mount_impl(path: &Path) -> bool {
+    // 输出格式: //user@server/share on /Volumes/share (smbfs, nodev, nosuid, mounted by user)
+    let Ok(output) = std::process::Command::new("mount").output() else {
+        return false;
+    };
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let mounts = output.lines().filter_map(|line| {
+        let (_, rest) = line.split_once(" on ")?;
+        let (mount_point, options) = rest.rsplit_once(" (")?;
+        let fs_type = options.split([',', ')']).next()?.trim().to_string();
+        Some((mount_point.to_string(), fs_type))
+    });
+
+    longest_mount_is_network(path, mounts)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn is_network_mount_impl(_path: &Path) -> bool {
+    false
+}
+
+/// 在挂载表中找到包含该路径的最长挂载点，并判断其是否为网络文件系统
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn longest_mount_is_network(path: &Path, mounts: impl Iterator<Item = (String, String)>) -> bool {
+    mounts
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .map(|(_, fs_type)| NETWORK_FS_TYPES.contains(&fs_type.as_str()))
+        .unwrap_or(false)
+}
+
+/// 解码 `/proc/self/mounts` 中的八进制转义（如 `\040` 表示空格）
+#[cfg(target_os = "linux")]
+fn decode_mount_escapes(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 4 <= bytes.len() {
+            let digits = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(digits, 8) {
+                decoded.push(value);
+                i += 4;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// 判断 IO 错误是否值得重试
+fn is_retryable(error: &io::Error) -> bool {
+    !matches!(
+        error.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied | io::ErrorKind::InvalidInput
+    )
+}
+
+/// 按路径对应的重试策略执行 IO 操作
+pub fn with_retry<T, F>(path: &Path, operation: &str, mut op: F) -> io::Result<T>
+where
+    F: FnMut() -> io::Result<T>,
+{
+    let policy = RetryPolicy::for_path(path);
+    let mut attempt = 1;
+
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.attempts && is_retryable(&e) => {
+                let delay = policy.delay_after(attempt);
+                tracing::warn!(
+                    target: "network_fs::retry",
+                    operation = operation,
+                    attempt = attempt,
+                    delay_ms = delay.as_millis(),
+                    error = %e,
+                    "网络路径操作失败，准备重试"
+                );
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 读取文件内容（网络路径自动重试）
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    with_retry(path, "read", || std::fs::read_to_string(path))
+}
+
+/// 写入文件内容（网络路径自动重试）
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let contents = contents.as_ref();
+    with_retry(path, "write", || std::fs::write(path, contents))
+}
+
+/// 打开 SQLite 数据库连接
+///
+/// 网络路径使用更长的忙等待超时，并在打开失败时重试
+pub fn open_connection(path: &Path) -> rusqlite::Result<Connection> {
+    crate::slow_operation::measure("db::open", || {
+        open_connection_with_retry(path, OpenFlags::default())
+    })
+}
+
+/// 以只读方式打开 SQLite 数据库连接（超时与重试同 [`open_connection`]）
+pub fn open_read_only_connection(path: &Path) -> rusqlite::Result<Connection> {
+    crate::slow_operation::measure("db::open", || {
+        open_connection_with_retry(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+    })
+}
+
+fn open_connection_with_retry(path: &Path, flags: OpenFlags) -> rusqlite::Result<Connection> {
+    let is_network = is_network_path(path);
+    let policy = if is_network {
+        RetryPolicy::NETWORK
+    } else {
+        RetryPolicy::LOCAL
+    };
+    let busy_timeout = if is_network {
+        NETWORK_BUSY_TIMEOUT
+    } else {
+        LOCAL_BUSY_TIMEOUT
+    };
+
+    let mut attempt = 1;
+    loop {
+        match Connection::open_with_flags(path, flags) {
+            Ok(conn) => {
+                conn.busy_timeout(busy_timeout)?;
+                return Ok(conn);
+            }
+            Err(e) if attempt < policy.attempts => {
+                let delay = policy.delay_after(attempt);
+                tracing::warn!(
+                    target: "network_fs::retry",
+                    operation = "sqlite_open",
+                    attempt = attempt,
+                    delay_ms = delay.as_millis(),
+                    error = %e,
+                    "打开网络路径上的数据库失败，准备重试"
+                );
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
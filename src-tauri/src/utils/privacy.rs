@@ -0,0 +1,111 @@
+//! 隐私模式
+//! 开启后，日志、发往前端的事件与审计日志中的邮箱/账户名替换为稳定的加盐哈希
+//! （同一账户始终得到同一标识，便于对照排查），完整值只保存在备份中，方便录屏或分享截图
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use hmac::{Hmac, Mac};
+use regex::Regex;
+use serde_json::Value;
+use sha2::Sha256;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// 哈希标识前缀
+const HASH_PREFIX: &str = "acct-";
+
+/// 保留的哈希字节数（显示为两倍长度的十六进制）
+const HASH_BYTES: usize = 6;
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 更新启用状态（启用前先加载盐值，避免在日志写入过程中首次读写盐值文件）
+pub fn set_enabled(enabled: bool) {
+    if enabled {
+        salt();
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn email_regex() -> &'static Regex {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    EMAIL.get_or_init(|| Regex::new(r"(?i)[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}").unwrap())
+}
+
+/// 本机盐值：首次使用时随机生成并保存在配置目录，保证哈希跨重启稳定
+fn salt() -> &'static [u8] {
+    static SALT: OnceLock<Vec<u8>> = OnceLock::new();
+    SALT.get_or_init(|| {
+        let path = crate::directories::get_privacy_salt_file();
+        if let Some(salt) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| BASE64.decode(text.trim()).ok())
+            .filter(|salt| !salt.is_empty())
+        {
+            return salt;
+        }
+
+        let mut salt = vec![0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        if let Err(e) = fs::write(&path, BASE64.encode(&salt)) {
+            tracing::warn!(target: "app::privacy", error = %e, "保存隐私模式盐值失败，重启后哈希将变化");
+        }
+        salt
+    })
+}
+
+/// 计算标识的加盐哈希（忽略大小写），如 `acct-3f9a1c0b7d2e`
+pub fn hash(value: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt()).expect("HMAC 支持任意长度的密钥");
+    mac.update(value.trim().to_lowercase().as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let short: String = digest[..HASH_BYTES]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("{}{}", HASH_PREFIX, short)
+}
+
+/// 隐私模式下将账户名整体替换为哈希，否则原样返回
+pub fn account(name: &str) -> String {
+    if is_enabled() {
+        hash(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// 隐私模式下将文本中的邮箱替换为哈希，否则原样返回
+pub fn scrub(text: &str) -> String {
+    if !is_enabled() {
+        return text.to_string();
+    }
+    email_regex()
+        .replace_all(text, |caps: &regex::Captures| hash(&caps[0]))
+        .to_string()
+}
+
+/// 隐私模式下替换 JSON 中所有字符串值里的邮箱
+pub fn scrub_value(value: &mut Value) {
+    if !is_enabled() {
+        return;
+    }
+    match value {
+        Value::String(text) => {
+            if email_regex().is_match(text) {
+                *text = scrub(text);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub_value),
+        Value::Object(map) => map.values_mut().for_each(scrub_value),
+        _ => {}
+    }
+}
@@ -0,0 +1,80 @@
+//! 脱敏 JSON 序列化
+//! 序列化为 `serde_json::Value` 后遮盖已知凭据字段（令牌、api_key、id_token 等）的值，
+//! 保留对象与数组结构，用于分享备份内容、诊断信息等需要交给他人查看的数据
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::utils::log_sanitizer::{redact_secrets, REDACTED, SECRET_KEY_NAMES};
+
+/// 去掉 `_` / `-` 并转小写，`accessToken`、`access-token`、`access_token` 视为同一字段
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 字段名是否表示凭据
+fn is_sensitive_key(key: &str) -> bool {
+    // Antigravity 登录状态整体包含 OAuth 令牌
+    if key == crate::constants::database::AGENT_STATE {
+        return true;
+    }
+
+    let key = normalize_key(key);
+    key.ends_with("token")
+        || key.ends_with("tokens")
+        || key.ends_with("password")
+        || SECRET_KEY_NAMES
+            .iter()
+            .any(|name| key == normalize_key(name))
+}
+
+/// 遮盖值中的所有标量，保留结构
+fn mask(value: &mut Value) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(mask),
+        Value::Array(items) => items.iter_mut().for_each(mask),
+        Value::Null => {}
+        other => *other = Value::String(REDACTED.to_string()),
+    }
+}
+
+/// 原地脱敏：凭据字段的值整体遮盖，其余字符串移除可识别的令牌格式
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    mask(value);
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        Value::String(text) => *text = redact_secrets(text),
+        _ => {}
+    }
+}
+
+/// 原地移除凭据字段（连同键一起删除），用于公开分享时不保留任何凭据痕迹
+pub fn strip(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !is_sensitive_key(key));
+            map.values_mut().for_each(strip);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip),
+        Value::String(text) => *text = redact_secrets(text),
+        _ => {}
+    }
+}
+
+/// 序列化并脱敏
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(value)?;
+    redact(&mut value);
+    Ok(value)
+}
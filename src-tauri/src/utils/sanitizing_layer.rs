@@ -1,5 +1,5 @@
-/// 自定义日志写入器，仅对文件输出进行脱敏
-/// 控制台输出保持原始内容
+/// 自定义日志写入器，文件输出完整脱敏
+/// 控制台输出只移除凭据，保留其余原始内容
 use std::io::{self, Write};
 use tracing_appender::rolling::RollingFileAppender;
 use tracing_subscriber::fmt::writer::MakeWriter;
@@ -15,7 +15,8 @@ impl SanitizingFileWriter {
 
         // 创建按日期滚动的日志文件
         // 文件名格式: antigravity-agent.2024-01-15.log
-        let appender = tracing_appender::rolling::daily(&log_dir, "antigravity-agent");
+        let appender =
+            tracing_appender::rolling::daily(&log_dir, crate::utils::log_query::LOG_FILE_PREFIX);
 
         Ok(Self { appender })
     }
@@ -26,7 +27,12 @@ impl Write for SanitizingFileWriter {
         // 将字节转换为字符串进行脱敏处理
         let msg = String::from_utf8_lossy(buf);
         let sanitized = crate::utils::log_sanitizer::sanitize_log_message(&msg);
-        self.appender.write_all(sanitized.as_bytes())?;
+        // 每行 JSON 日志开头注入本次运行 ID
+        let tagged = match sanitized.strip_prefix('{') {
+            Some(rest) => format!("{{\"run_id\":\"{}\",{}", crate::run_id::run_id(), rest),
+            None => sanitized,
+        };
+        self.appender.write_all(tagged.as_bytes())?;
         Ok(buf.len())
     }
 
@@ -42,3 +48,38 @@ impl<'a> MakeWriter<'a> for SanitizingFileWriter {
         SanitizingFileWriter::new().expect("Failed to create sanitizing file writer")
     }
 }
+
+/// 控制台写入器：只移除凭据，邮箱与路径保持原样便于本地调试（隐私模式下邮箱替换为哈希）
+///
+/// JSON-RPC 自动化模式下 stdout 专用于协议消息，改写到 stderr
+pub struct RedactingStdoutWriter;
+
+impl Write for RedactingStdoutWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let msg = String::from_utf8_lossy(buf);
+        let redacted =
+            crate::utils::privacy::scrub(&crate::utils::log_sanitizer::redact_secrets(&msg));
+        if crate::rpc::is_enabled() {
+            io::stderr().write_all(redacted.as_bytes())?;
+        } else {
+            io::stdout().write_all(redacted.as_bytes())?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if crate::rpc::is_enabled() {
+            io::stderr().flush()
+        } else {
+            io::stdout().flush()
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RedactingStdoutWriter {
+    type Writer = RedactingStdoutWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingStdoutWriter
+    }
+}
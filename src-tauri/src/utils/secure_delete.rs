@@ -0,0 +1,56 @@
+//! 备份文件安全删除
+//! 开启后删除备份前先用随机数据覆盖文件内容并刷盘，再截断、删除，
+//! 降低令牌从磁盘残留中被恢复的可能；SSD 的磨损均衡与写时复制文件系统下只能尽力而为
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 每次覆盖写入的块大小
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// 是否启用（默认关闭，由设置决定）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 更新启用状态
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否已启用
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 用随机数据覆盖整个文件并截断
+fn overwrite(path: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let len = file.metadata()?.len();
+
+    file.seek(SeekFrom::Start(0))?;
+    let mut block = vec![0u8; BLOCK_SIZE];
+    let mut remaining = len;
+    while remaining > 0 {
+        let size = remaining.min(BLOCK_SIZE as u64) as usize;
+        OsRng.fill_bytes(&mut block[..size]);
+        file.write_all(&block[..size])?;
+        remaining -= size as u64;
+    }
+    file.sync_all()?;
+
+    file.set_len(0)?;
+    file.sync_all()
+}
+
+/// 删除文件；启用安全删除时先覆盖内容（覆盖失败仍会删除）
+pub fn remove_file(path: &Path) -> io::Result<()> {
+    if is_enabled() {
+        if let Err(e) = overwrite(path) {
+            tracing::warn!(target: "backup::secure_delete", file = %path.display(), error = %e, "覆盖文件失败，直接删除");
+        }
+    }
+    fs::remove_file(path)
+}
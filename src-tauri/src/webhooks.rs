@@ -0,0 +1,195 @@
+//! Webhook 通知
+//! 账户切换完成、备份创建、监控检测到登出时，向设置中配置的 URL POST JSON 负载，
+//! 便于接入 Slack、家庭自动化等。每个 Webhook 可选择订阅的事件并配置签名密钥：
+//! - `X-Agent-Event`：事件名
+//! - `X-Agent-Timestamp`：Unix 时间戳（秒）
+//! - `X-Agent-Signature`：`sha256=<hex>`，为 HMAC-SHA256(密钥, "<timestamp>.<body>")
+//!
+//! 通过共享 HTTP 客户端发送（只允许 HTTPS，遵循代理设置），失败时按指数退避重试
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 最多尝试次数（含首次）
+const MAX_ATTEMPTS: u32 = 4;
+
+/// 首次重试前的等待时间，之后每次翻 4 倍
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// 单次请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook 事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// 账户切换完成
+    SwitchComplete,
+    /// 备份已创建
+    BackupCreated,
+    /// 监控检测到 Antigravity 已登出
+    LoggedOut,
+}
+
+impl WebhookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::SwitchComplete => "switch_complete",
+            WebhookEvent::BackupCreated => "backup_created",
+            WebhookEvent::LoggedOut => "logged_out",
+        }
+    }
+}
+
+/// 单个 Webhook 配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct WebhookConfig {
+    /// 接收地址（HTTPS）
+    pub url: String,
+    /// 签名密钥，为空时不签名
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// 订阅的事件，为空表示全部事件
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl WebhookConfig {
+    fn subscribes(&self, event: WebhookEvent) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.contains(&event))
+    }
+}
+
+/// 校验 Webhook 配置
+pub fn validate(webhook: &WebhookConfig) -> Result<(), String> {
+    let url = tauri::Url::parse(webhook.url.trim())
+        .map_err(|e| format!("Webhook 地址无效 ({}): {}", webhook.url, e))?;
+    if url.scheme() != "https" {
+        return Err(format!("Webhook 地址必须使用 HTTPS: {}", webhook.url));
+    }
+    Ok(())
+}
+
+/// 计算签名
+fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC 支持任意长度的密钥");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// 发送一次请求
+async fn send(webhook: &WebhookConfig, event: &str, body: &[u8]) -> Result<(), (String, bool)> {
+    let client = crate::http_client::shared().map_err(|e| (e, false))?;
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let mut request = client
+        .post(webhook.url.trim())
+        .timeout(REQUEST_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Agent-Event", event)
+        .header("X-Agent-Timestamp", timestamp.to_string())
+        .body(body.to_vec());
+    if let Some(secret) = webhook
+        .secret
+        .as_deref()
+        .filter(|secret| !secret.is_empty())
+    {
+        request = request.header("X-Agent-Signature", signature(secret, timestamp, body));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| (format!("请求失败: {}", e), true))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    // 4xx（429 除外）说明配置有误，重试没有意义
+    let retryable = status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    Err((format!("接收端返回 {}", status), retryable))
+}
+
+/// 发送并在失败时重试
+async fn deliver(webhook: WebhookConfig, event: &'static str, body: Vec<u8>) {
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=MAX_ATTEMPTS {
+        match send(&webhook, event, &body).await {
+            Ok(()) => {
+                tracing::info!(target: "webhooks::deliver", event = event, attempt = attempt, "📨 Webhook 已送达");
+                return;
+            }
+            Err((e, retryable)) if retryable && attempt < MAX_ATTEMPTS => {
+                tracing::warn!(target: "webhooks::deliver", event = event, attempt = attempt, error = %e, "Webhook 发送失败，稍后重试");
+                tokio::time::sleep(backoff).await;
+                backoff *= 4;
+            }
+            Err((e, _)) => {
+                tracing::error!(target: "webhooks::deliver", event = event, attempt = attempt, error = %e, "❌ Webhook 发送失败");
+                return;
+            }
+        }
+    }
+}
+
+/// 构造请求体（隐私模式下邮箱替换为哈希）
+fn payload(event: &str, mut data: Value) -> Vec<u8> {
+    crate::utils::privacy::scrub_value(&mut data);
+    json!({
+        "event": event,
+        "timestamp": chrono::Local::now().to_rfc3339(),
+        "run_id": crate::run_id::run_id(),
+        "data": data,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+/// 向订阅了该事件的所有 Webhook 发送通知（后台执行，不阻塞调用方）
+pub fn dispatch(app_handle: &AppHandle, event: WebhookEvent, data: Value) {
+    let webhooks: Vec<WebhookConfig> = app_handle
+        .try_state::<crate::app_settings::AppSettingsManager>()
+        .map(|manager| manager.get_settings().webhooks)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|webhook| webhook.subscribes(event))
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let body = payload(event.name(), data);
+    for webhook in webhooks {
+        tauri::async_runtime::spawn(deliver(webhook, event.name(), body.clone()));
+    }
+}
+
+/// 发送一次测试事件（不重试），返回发送结果
+pub async fn test(webhook: &WebhookConfig) -> Result<(), String> {
+    validate(webhook)?;
+    let body = payload(
+        "test",
+        json!({ "message": "Antigravity Agent Webhook 测试" }),
+    );
+    send(webhook, "test", &body).await.map_err(|(e, _)| e)
+}
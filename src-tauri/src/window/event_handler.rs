@@ -1,20 +1,31 @@
 // 窗口事件处理模块
 // 负责在应用启动时恢复窗口状态
 
-use super::state_manager::{load_window_state, save_window_state, WindowState};
-use std::sync::{Arc, Mutex};
+use super::state_manager::{
+    current_zoom, load_window_state, save_window_state, set_current_zoom, WindowState,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use tauri::Manager;
+use tokio::sync::mpsc;
+
+/// 防抖延迟时间：最后一次窗口变化后等待该时间再保存
+const DEBOUNCE_DURATION: Duration = Duration::from_secs(2);
 
 /// 初始化窗口事件处理器
-pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// 主窗口在配置中初始为隐藏；`show_after_restore` 为 true 时在恢复位置和大小后再显示，
+/// 静默启动时保持隐藏
+pub fn init_window_event_handler(
+    app: &tauri::App,
+    show_after_restore: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // 获取主窗口
     let main_window = app.get_webview_window("main").ok_or("无法获取主窗口")?;
 
-    // 创建保存状态的共享状态，用于防抖和恢复标志
-    let is_restoring = Arc::new(Mutex::new(true)); // 恢复标志，防止保存状态
-    let debounce_timer = Arc::new(Mutex::new(None::<tauri::async_runtime::JoinHandle<()>>)); // 防抖定时器句柄
-    const DEBOUNCE_DURATION: Duration = Duration::from_secs(2); // 防抖延迟时间
+    // 恢复标志，恢复期间不保存状态
+    let is_restoring = Arc::new(AtomicBool::new(true));
 
     // 应用启动时，尝试恢复上次保存的窗口状态
     let window_clone = main_window.clone();
@@ -22,6 +33,9 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
     tauri::async_runtime::spawn(async move {
         match load_window_state().await {
             Ok(saved_state) => {
+                // 校验保存的位置在当前显示器布局下是否可见（显示器可能已被拔掉）
+                let saved_state = fit_to_monitors(&window_clone, saved_state);
+
                 tracing::debug!(
                     target: "window::restore",
                     x = %saved_state.x,
@@ -53,95 +67,82 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
                 // 如果之前是最大化状态，则恢复最大化
                 if saved_state.maximized {
                     if let Err(e) = window_clone.maximize() {
-                        eprintln!("⚠️ 恢复窗口最大化状态失败: {}", e);
+                        tracing::warn!(target: "window::restore", error = %e, "⚠️ 恢复窗口最大化状态失败");
                     } else {
-                        println!("✅ 窗口状态恢复完成（包含最大化）");
+                        tracing::info!(target: "window::restore", "✅ 窗口状态恢复完成（包含最大化）");
                     }
                 } else {
-                    println!("✅ 窗口状态恢复完成");
+                    tracing::info!(target: "window::restore", "✅ 窗口状态恢复完成");
+                }
+
+                // 恢复全屏状态
+                if saved_state.fullscreen {
+                    if let Err(e) = window_clone.set_fullscreen(true) {
+                        tracing::warn!(target: "window::restore", error = %e, "恢复全屏状态失败");
+                    }
+                }
+
+                // 恢复网页缩放比例
+                if (saved_state.zoom - 1.0).abs() > f64::EPSILON {
+                    match window_clone.set_zoom(saved_state.zoom) {
+                        Ok(()) => set_current_zoom(saved_state.zoom),
+                        Err(e) => {
+                            tracing::warn!(target: "window::restore", error = %e, "恢复缩放比例失败");
+                        }
+                    }
                 }
             }
             Err(e) => {
-                eprintln!("⚠️ 加载窗口状态失败: {}，将使用默认状态", e);
-                println!("✅ 使用默认窗口状态");
+                tracing::warn!(target: "window::restore", error = %e, "⚠️ 加载窗口状态失败，将使用默认状态");
             }
         }
 
-        // 恢复完成后，等待一小段时间确保所有窗口事件都处理完毕，然后清除恢复标志
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        // 安全的锁获取，避免毒化锁 panic
-        match is_restoring_clone.lock() {
-            Ok(mut flag) => {
-                *flag = false;
-                println!("✅ 窗口状态恢复标志已清除，开始响应窗口变化事件");
-            }
-            Err(_) => {
-                eprintln!("⚠️ 恢复标志锁中毒，无法清除标志");
+        if show_after_restore {
+            if let Err(e) = window_clone.show() {
+                tracing::error!(target: "window::restore", error = %e, "显示主窗口失败");
             }
         }
-    });
-
-    // 防抖保存函数 - 更简单的实现，避免复杂的借用
-    let window_for_save = main_window.clone();
-    let is_restoring_for_save = is_restoring.clone();
-    let timer_for_save = debounce_timer.clone();
-
-    let schedule_save = move || {
-        // 取消之前的定时器
-        let timer = timer_for_save.clone();
-        {
-            if let Ok(mut timer_guard) = timer.try_lock() {
-                if let Some(handle) = timer_guard.take() {
-                    handle.abort();
-                }
-            }
-        } // 锁在这里自动释放
-
-        // 克隆异步任务需要的变量
-        let window = window_for_save.clone();
-        let restoring = is_restoring_for_save.clone();
-        let timer_clone = timer_for_save.clone();
-
-        // 启动新的延迟保存任务
-        let handle = tauri::async_runtime::spawn(async move {
-            tokio::time::sleep(DEBOUNCE_DURATION).await;
-
-            // 检查是否正在恢复状态
-            let should_save = match restoring.try_lock() {
-                Ok(is_restoring_flag) => !*is_restoring_flag,
-                Err(_) => {
-                    tracing::warn!(target: "window::event", "恢复标志锁被占用，跳过保存");
-                    false
-                }
-            };
 
-            if should_save {
-                save_current_window_state(&window).await;
-                tracing::debug!(target: "window::event", "窗口状态已保存（防抖延迟后）");
-            }
-
-            // 清除定时器
-            if let Ok(mut timer_guard) = timer_clone.try_lock() {
-                *timer_guard = None;
-            }
-        });
+        // 恢复完成后，等待一小段时间确保所有窗口事件都处理完毕，然后清除恢复标志
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        is_restoring_clone.store(false, Ordering::SeqCst);
+        tracing::debug!(target: "window::restore", "窗口状态恢复标志已清除，开始响应窗口变化事件");
+    });
 
-        // 保存定时器句柄
-        if let Ok(mut timer_guard) = timer_for_save.try_lock() {
-            *timer_guard = Some(handle);
-        }
-    };
+    // 防抖保存：窗口事件只向通道发送通知，由单个任务在最后一次变化后保存
+    let (save_tx, save_rx) = mpsc::unbounded_channel::<()>();
+    tauri::async_runtime::spawn(run_debounced_saver(
+        main_window.clone(),
+        is_restoring.clone(),
+        save_rx,
+    ));
 
     // 监听窗口事件，包括大小变化、移动和关闭
     let window_for_events = main_window.clone();
-    let schedule_save_clone = schedule_save.clone();
 
     window_for_events.clone().on_window_event(move |event| {
         match event {
             // 窗口大小变化或移动时，使用防抖机制延迟保存
             tauri::WindowEvent::Resized { .. } | tauri::WindowEvent::Moved { .. } => {
+                // 最小化也会触发 Resized：按设置隐藏到托盘，且不保存最小化时的位置
+                if window_for_events.is_minimized().unwrap_or(false) {
+                    let app_handle = window_for_events.app_handle();
+                    let settings = app_handle
+                        .state::<crate::app_settings::AppSettingsManager>()
+                        .get_settings();
+                    let system_tray = app_handle.state::<crate::system_tray::SystemTrayManager>();
+
+                    if settings.minimize_to_tray_enabled && system_tray.is_enabled_setting(app_handle) {
+                        tracing::info!(target: "window::event", "窗口已最小化，隐藏到托盘");
+                        if let Err(e) = system_tray.minimize_to_tray(app_handle) {
+                            tracing::error!(target: "window::event", error = %e, "最小化到托盘失败");
+                        }
+                    }
+                    return;
+                }
+
                 tracing::debug!(target: "window::event", "检测到窗口变化，启动防抖保存");
-                schedule_save_clone();
+                let _ = save_tx.send(());
             }
             // 注意：Tauri 2.x 中没有 Maximized/Unmaximized 事件
             // 最大化/还原状态会在 Resized 事件中捕获和处理
@@ -171,6 +172,13 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
                     return;
                 }
 
+                // 有进行中的账户操作时，交由前端确认后再退出
+                if super::exit_guard::should_confirm_exit(app_handle) {
+                    api.prevent_close();
+                    super::exit_guard::request_confirmation(app_handle);
+                    return;
+                }
+
                 tracing::info!(target: "window::event", "系统托盘未启用，立即保存状态并允许关闭");
 
                 // 如果系统托盘未启用，立即保存状态并允许关闭（不需要防抖）
@@ -187,23 +195,138 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// 防抖保存任务
+///
+/// 收到通知后开始计时，计时期间每收到新通知就重新计时；计时结束后保存一次。
+/// 通道关闭时若仍有未保存的变化，会在退出前保存，保证最终状态总能写入
+async fn run_debounced_saver(
+    window: tauri::WebviewWindow,
+    is_restoring: Arc<AtomicBool>,
+    mut save_rx: mpsc::UnboundedReceiver<()>,
+) {
+    while save_rx.recv().await.is_some() {
+        let mut channel_closed = false;
+
+        loop {
+            tokio::select! {
+                message = save_rx.recv() => {
+                    if message.is_none() {
+                        channel_closed = true;
+                        break;
+                    }
+                }
+                _ = tokio::time::sleep(DEBOUNCE_DURATION) => break,
+            }
+        }
+
+        if is_restoring.load(Ordering::SeqCst) {
+            tracing::debug!(target: "window::event", "窗口状态恢复中，跳过保存");
+        } else {
+            save_current_window_state(&window).await;
+            tracing::debug!(target: "window::event", "窗口状态已保存（防抖延迟后）");
+        }
+
+        if channel_closed {
+            break;
+        }
+    }
+}
+
+/// 标题栏判定高度（物理像素），用于判断窗口是否仍可被拖动
+const TITLE_BAR_HEIGHT: i64 = 40;
+
+/// 标题栏至少需要可见的宽度（物理像素）
+const MIN_VISIBLE_WIDTH: i64 = 100;
+
+/// 根据当前显示器布局校正保存的窗口状态
+///
+/// 标题栏与任一显示器工作区的交集不足时，视为窗口不可见：
+/// 将窗口尺寸限制在主显示器工作区内并居中
+fn fit_to_monitors(window: &tauri::WebviewWindow, state: WindowState) -> WindowState {
+    let monitors = match window.available_monitors() {
+        Ok(monitors) if !monitors.is_empty() => monitors,
+        Ok(_) => return state,
+        Err(e) => {
+            tracing::warn!(target: "window::restore", error = %e, "获取显示器列表失败，跳过位置校验");
+            return state;
+        }
+    };
+
+    let title_bar_visible = monitors.iter().any(|monitor| {
+        let area = monitor.work_area();
+        let left = (state.x as i64).max(area.position.x as i64);
+        let right = (state.x as i64 + state.width as i64)
+            .min(area.position.x as i64 + area.size.width as i64);
+        let top = (state.y as i64).max(area.position.y as i64);
+        let bottom = (state.y as i64 + TITLE_BAR_HEIGHT)
+            .min(area.position.y as i64 + area.size.height as i64);
+        right - left >= MIN_VISIBLE_WIDTH && bottom > top
+    });
+
+    if title_bar_visible {
+        return state;
+    }
+
+    let target = window
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| monitors[0].clone());
+    let area = target.work_area();
+
+    let width = state.width.min(area.size.width as f64);
+    let height = state.height.min(area.size.height as f64);
+    let x = area.position.x as f64 + (area.size.width as f64 - width) / 2.0;
+    let y = area.position.y as f64 + (area.size.height as f64 - height) / 2.0;
+
+    tracing::info!(
+        target: "window::restore",
+        saved_x = %state.x,
+        saved_y = %state.y,
+        x = %x,
+        y = %y,
+        "保存的窗口位置在当前显示器上不可见，已移动到主显示器中央"
+    );
+
+    WindowState {
+        x,
+        y,
+        width,
+        height,
+        ..state
+    }
+}
+
 /// 保存当前窗口状态的辅助函数
-async fn save_current_window_state(window: &tauri::WebviewWindow) {
+pub(crate) async fn save_current_window_state(window: &tauri::WebviewWindow) {
     if let (Ok(outer_position), Ok(outer_size), Ok(is_maximized)) = (
         window.outer_position(),
         window.outer_size(),
         window.is_maximized(),
     ) {
-        let current_state = WindowState {
-            x: outer_position.x as f64,
-            y: outer_position.y as f64,
-            width: outer_size.width as f64,
-            height: outer_size.height as f64,
-            maximized: is_maximized,
+        let zoom = current_zoom();
+        let current_state = if window.is_fullscreen().unwrap_or(false) {
+            // 全屏时的位置和大小是整个屏幕，保留之前窗口化时的位置和大小
+            let previous = load_window_state().await.unwrap_or_default();
+            WindowState {
+                fullscreen: true,
+                zoom,
+                ..previous
+            }
+        } else {
+            WindowState {
+                x: outer_position.x as f64,
+                y: outer_position.y as f64,
+                width: outer_size.width as f64,
+                height: outer_size.height as f64,
+                maximized: is_maximized,
+                fullscreen: false,
+                zoom,
+            }
         };
 
         if let Err(e) = save_window_state(current_state).await {
-            eprintln!("保存窗口状态失败: {}", e);
+            tracing::error!(target: "window::save", error = %e, "保存窗口状态失败");
         }
     }
 }
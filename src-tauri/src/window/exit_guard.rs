@@ -0,0 +1,51 @@
+//! 退出确认模块
+//! 账户切换 / 恢复等操作进行中时，按设置拦截退出请求并交由前端确认，避免在恢复中途退出
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// `close-confirmation-requested` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct CloseConfirmationPayload {
+    pub message: String,
+}
+
+/// 是否需要在退出前确认（有正在执行的流水线操作，且设置开启或退出时要安装更新）
+pub fn should_confirm_exit(app_handle: &AppHandle) -> bool {
+    let confirm_enabled = app_handle
+        .state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .confirm_exit_during_operations;
+
+    (confirm_enabled || crate::updater::will_install_on_exit()) && crate::pipeline::is_any_running()
+}
+
+/// 显示主窗口并向前端发出退出确认请求
+pub fn request_confirmation(app_handle: &AppHandle) {
+    tracing::info!(target: "window::exit_guard", "存在进行中的账户操作，请求用户确认退出");
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let mut message =
+        "账户切换或恢复操作正在进行，现在退出可能导致 Antigravity 数据不完整。".to_string();
+    if crate::updater::will_install_on_exit() {
+        message.push_str("现在退出也不会安装已下载的更新。");
+    }
+    let payload = CloseConfirmationPayload { message };
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "window::exit_guard", error = %e, "发射退出确认事件失败");
+    }
+}
+
+/// 退出应用；需要确认时改为发出确认请求
+pub fn exit_or_confirm(app_handle: &AppHandle) {
+    if should_confirm_exit(app_handle) {
+        request_confirmation(app_handle);
+    } else {
+        app_handle.exit(0);
+    }
+}
@@ -2,7 +2,11 @@
 //! Handles window state persistence and event handling
 
 pub mod event_handler;
+pub mod exit_guard;
+pub mod quick_switcher;
 pub mod state_manager;
+pub mod taskbar;
+pub mod theme;
 
 // Re-export commonly used functions
 pub use event_handler::init_window_event_handler;
@@ -0,0 +1,80 @@
+//! 快速切换窗口模块
+//! 通过全局快捷键打开 / 关闭一个置顶的小窗口，无需打开完整管理界面即可切换账户
+
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// 快速切换窗口标签
+pub const SWITCHER_LABEL: &str = "switcher";
+
+/// 打开 / 关闭快速切换窗口的全局快捷键
+pub const SWITCHER_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+/// 注册快速切换窗口的全局快捷键
+pub fn register_shortcut(app_handle: &AppHandle) -> Result<(), String> {
+    app_handle
+        .global_shortcut()
+        .on_shortcut(SWITCHER_SHORTCUT, |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                if let Err(e) = toggle(app) {
+                    tracing::error!(target: "window::quick_switcher", error = %e, "切换快速切换窗口失败");
+                }
+            }
+        })
+        .map_err(|e| format!("注册快捷键 {} 失败: {}", SWITCHER_SHORTCUT, e))?;
+
+    tracing::info!(target: "window::quick_switcher", shortcut = SWITCHER_SHORTCUT, "快速切换快捷键已注册");
+    Ok(())
+}
+
+/// 快速切换窗口存在时关闭，否则创建
+pub fn toggle(app_handle: &AppHandle) -> Result<(), String> {
+    if app_handle.get_webview_window(SWITCHER_LABEL).is_some() {
+        close(app_handle)
+    } else {
+        open(app_handle)
+    }
+}
+
+/// 创建快速切换窗口
+pub fn open(app_handle: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(SWITCHER_LABEL) {
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        SWITCHER_LABEL,
+        WebviewUrl::App("index.html#/switcher".into()),
+    )
+    .title("快速切换账户")
+    .inner_size(360.0, 420.0)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .focused(true)
+    .build()
+    .map_err(|e| format!("创建快速切换窗口失败: {}", e))?;
+
+    // 失去焦点时自动关闭，行为与命令面板一致
+    let app = app_handle.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Focused(false) = event {
+            let _ = close(&app);
+        }
+    });
+
+    tracing::debug!(target: "window::quick_switcher", "快速切换窗口已打开");
+    Ok(())
+}
+
+/// 关闭并销毁快速切换窗口
+pub fn close(app_handle: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(SWITCHER_LABEL) {
+        window.destroy().map_err(|e| e.to_string())?;
+        tracing::debug!(target: "window::quick_switcher", "快速切换窗口已关闭");
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::config_manager::ConfigManager;
 
@@ -14,6 +15,32 @@ pub struct WindowState {
     pub width: f64,
     pub height: f64,
     pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// 网页缩放比例
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+}
+
+/// 缩放比例允许范围
+pub const MIN_ZOOM: f64 = 0.5;
+pub const MAX_ZOOM: f64 = 3.0;
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+/// 当前缩放比例（Tauri 不提供读取缩放比例的接口，由设置缩放的入口维护）
+static CURRENT_ZOOM: AtomicU64 = AtomicU64::new(0x3FF0_0000_0000_0000); // 1.0f64
+
+/// 获取当前缩放比例
+pub fn current_zoom() -> f64 {
+    f64::from_bits(CURRENT_ZOOM.load(Ordering::Relaxed))
+}
+
+/// 记录当前缩放比例
+pub fn set_current_zoom(zoom: f64) {
+    CURRENT_ZOOM.store(zoom.to_bits(), Ordering::Relaxed);
 }
 
 impl Default for WindowState {
@@ -24,6 +51,8 @@ impl Default for WindowState {
             width: 800.0,
             height: 600.0,
             maximized: false,
+            fullscreen: false,
+            zoom: default_zoom(),
         }
     }
 }
@@ -54,7 +83,8 @@ impl WindowState {
 pub async fn save_window_state(state: WindowState) -> Result<(), String> {
     // 验证窗口状态是否有效，拒绝保存异常值
     if !state.is_valid() {
-        println!(
+        tracing::warn!(
+            target: "window::save",
             "⚠️ 检测到无效的窗口状态，跳过保存: 位置({:.1}, {:.1}), 大小({:.1}x{:.1})",
             state.x, state.y, state.width, state.height
         );
@@ -70,9 +100,10 @@ pub async fn save_window_state(state: WindowState) -> Result<(), String> {
 
     fs::write(state_file, json_content).map_err(|e| format!("保存窗口状态失败: {}", e))?;
 
-    println!(
-        "💾 窗口状态已保存: 位置({:.1}, {:.1}), 大小({:.1}x{:.1}), 最大化:{}",
-        state.x, state.y, state.width, state.height, state.maximized
+    tracing::info!(
+        target: "window::save",
+        "💾 窗口状态已保存: 位置({:.1}, {:.1}), 大小({:.1}x{:.1}), 最大化:{}, 全屏:{}, 缩放:{:.2}",
+        state.x, state.y, state.width, state.height, state.maximized, state.fullscreen, state.zoom
     );
 
     Ok(())
@@ -93,14 +124,18 @@ pub async fn load_window_state() -> Result<WindowState, String> {
 
         // 验证加载的状态是否有效
         if !state.is_valid() {
-            println!(
+            tracing::warn!(
+                target: "window::restore",
                 "⚠️ 加载的窗口状态无效（位置({:.1}, {:.1}), 大小({:.1}x{:.1})），使用默认状态",
                 state.x, state.y, state.width, state.height
             );
             return Ok(WindowState::default());
         }
 
-        Ok(state)
+        Ok(WindowState {
+            zoom: state.zoom.clamp(MIN_ZOOM, MAX_ZOOM),
+            ..state
+        })
     } else {
         Ok(WindowState::default())
     }
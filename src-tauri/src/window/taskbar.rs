@@ -0,0 +1,80 @@
+//! 任务栏 / Dock 进度模块
+//! 订阅 `pipeline-status` 事件（与前端相同的事件源），在 Windows 任务栏进度条与 macOS Dock 上显示长耗时操作进度
+
+use crate::pipeline::{PipelineStage, PipelineStatusPayload};
+use std::time::Duration;
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Manager};
+
+/// 失败状态在任务栏上停留的时间
+const ERROR_DISPLAY_DURATION: Duration = Duration::from_secs(3);
+
+/// 注册任务栏进度监听
+pub fn register_progress_listener(app_handle: &AppHandle) {
+    let app = app_handle.clone();
+    crate::events::listen(app_handle, move |payload: PipelineStatusPayload| {
+        handle_pipeline_status(&app, &payload)
+    });
+}
+
+fn handle_pipeline_status(app_handle: &AppHandle, payload: &PipelineStatusPayload) {
+    // 嵌套流水线（如切换中的恢复步骤）不改变外层操作的进度显示
+    let nested = crate::pipeline::is_any_running();
+
+    match payload.stage {
+        PipelineStage::Started if !nested => {
+            set_progress(app_handle, ProgressBarStatus::Indeterminate, None);
+            set_badge(app_handle, Some("…".to_string()));
+        }
+        PipelineStage::Progress => {
+            let progress = payload.progress.unwrap_or(0);
+            set_progress(app_handle, ProgressBarStatus::Normal, Some(progress as u64));
+            set_badge(app_handle, Some(format!("{progress}%")));
+        }
+        PipelineStage::Succeeded if !nested => {
+            set_progress(app_handle, ProgressBarStatus::None, None);
+            set_badge(app_handle, None);
+        }
+        PipelineStage::Failed if !nested => {
+            set_progress(app_handle, ProgressBarStatus::Error, Some(100));
+            set_badge(app_handle, Some("!".to_string()));
+
+            let app = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(ERROR_DISPLAY_DURATION).await;
+                if !crate::pipeline::is_any_running() {
+                    set_progress(&app, ProgressBarStatus::None, None);
+                    set_badge(&app, None);
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+fn set_progress(app_handle: &AppHandle, status: ProgressBarStatus, progress: Option<u64>) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+
+    if let Err(e) = window.set_progress_bar(ProgressBarState {
+        status: Some(status),
+        progress,
+    }) {
+        tracing::debug!(target: "window::taskbar", error = %e, "设置任务栏进度失败");
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn set_badge(app_handle: &AppHandle, label: Option<String>) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+
+    if let Err(e) = window.set_badge_label(label) {
+        tracing::debug!(target: "window::taskbar", error = %e, "设置 Dock 徽标失败");
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_badge(_app_handle: &AppHandle, _label: Option<String>) {}
@@ -0,0 +1,98 @@
+//! 主题同步模块
+//! 监听系统深色 / 浅色主题变化并推送 `theme-changed` 事件，同时应用用户保存的主题偏好
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Theme};
+
+/// 用户主题偏好
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    /// 跟随系统
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// `theme-changed` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct ThemeChangedPayload {
+    /// 当前生效的主题（light / dark）
+    pub theme: String,
+    pub preference: ThemePreference,
+}
+
+/// 将主题转换为字符串
+fn theme_name(theme: Theme) -> String {
+    match theme {
+        Theme::Dark => "dark".to_string(),
+        _ => "light".to_string(),
+    }
+}
+
+/// 获取主窗口当前生效的主题（偏好为跟随系统时即系统主题）
+pub fn current_theme(app_handle: &AppHandle) -> Result<String, String> {
+    let window = app_handle
+        .get_webview_window("main")
+        .ok_or("无法获取主窗口".to_string())?;
+    let theme = window
+        .theme()
+        .map_err(|e| format!("获取窗口主题失败: {}", e))?;
+    Ok(theme_name(theme))
+}
+
+/// 应用主题偏好（跟随系统时取消强制主题）
+pub fn apply_preference(app_handle: &AppHandle, preference: ThemePreference) {
+    let theme = match preference {
+        ThemePreference::System => None,
+        ThemePreference::Light => Some(Theme::Light),
+        ThemePreference::Dark => Some(Theme::Dark),
+    };
+    app_handle.set_theme(theme);
+
+    tracing::debug!(target: "window::theme", preference = ?preference, "已应用主题偏好");
+    emit_theme_changed(app_handle, preference);
+}
+
+/// 初始化主题同步：应用保存的偏好，并监听系统主题变化
+pub fn init(app_handle: &AppHandle) -> Result<(), String> {
+    let preference = app_handle
+        .state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .theme_preference;
+    apply_preference(app_handle, preference);
+
+    let window = app_handle
+        .get_webview_window("main")
+        .ok_or("无法获取主窗口".to_string())?;
+
+    let app = app_handle.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::ThemeChanged(theme) = event {
+            tracing::info!(target: "window::theme", theme = %theme_name(*theme), "🎨 系统主题已变化");
+            let preference = app
+                .state::<crate::app_settings::AppSettingsManager>()
+                .get_settings()
+                .theme_preference;
+            emit_theme_changed(&app, preference);
+        }
+    });
+
+    Ok(())
+}
+
+fn emit_theme_changed(app_handle: &AppHandle, preference: ThemePreference) {
+    let theme = match current_theme(app_handle) {
+        Ok(theme) => theme,
+        Err(e) => {
+            tracing::warn!(target: "window::theme", error = %e, "获取当前主题失败");
+            return;
+        }
+    };
+
+    let payload = ThemeChangedPayload { theme, preference };
+    if let Err(e) = crate::events::emit(app_handle, &payload) {
+        tracing::error!(target: "window::theme", error = %e, "发射主题变化事件失败");
+    }
+}
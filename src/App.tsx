@@ -8,6 +8,7 @@ import AppDock from './components/app/AppDock.tsx';
 import {AppContent} from "@/components/app/AppContent.tsx";
 import {AppLoader} from "@/components/app/AppLoader.tsx";
 import {PlatformCommands} from "@/commands/PlatformCommands.ts";
+import {STARTUP_STEP_LABELS, useStartupProgress} from "@/hooks/use-startup-progress.ts";
 
 function App() {
   // ========== 应用状态 ==========
@@ -16,6 +17,9 @@ function App() {
   // ========== Hook 集成 ==========
   useDevToolsShortcut();
 
+  // 启动进度（用于显示失败的初始化步骤）
+  const startupProgress = useStartupProgress();
+
   // 用户管理
   const antigravityAccount = useAntigravityAccount();
 
@@ -75,6 +79,11 @@ function App() {
           <p className="text-gray-500 dark:text-gray-400">
             请稍候，正在查找 Antigravity 数据库路径
           </p>
+          {startupProgress.failedSteps.map((step) => (
+            <p key={step.step} className="mt-2 text-sm text-red-500">
+              {STARTUP_STEP_LABELS[step.step]}失败：{step.message}
+            </p>
+          ))}
         </div>
       </div>
     );
@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import {AntigravityAccount} from "@/commands/types/account.types.ts";
+import {AntigravityAccount, AppStateSummary, AvailableModels, BackupVersion, RestorePreview, SessionPatch} from "@/commands/types/account.types.ts";
 
 /**
  * Antigravity 账户管理命令
@@ -13,12 +13,20 @@ export class AccountCommands {
     return invoke('get_current_antigravity_account_info');
   }
 
+  /**
+   * 获取已保存的应用状态（配置档案与最近一次切换到的账户）
+   */
+  static async getAppState(): Promise<AppStateSummary> {
+    return invoke('get_app_state');
+  }
+
   /**
    * 获取所有已备份的账户列表
+   * @param sanitize 为 true 时移除令牌等凭据，用于公开分享
    * @returns 账户列表
    */
-  static async getAntigravityAccounts(): Promise<AntigravityAccount[]> {
-    return invoke('get_antigravity_accounts');
+  static async getAntigravityAccounts(sanitize = false): Promise<AntigravityAccount[]> {
+    return invoke('get_antigravity_accounts', { sanitize });
   }
 
   /**
@@ -38,6 +46,33 @@ export class AccountCommands {
     return invoke('switch_to_antigravity_account', { accountName: accountName });
   }
 
+  /**
+   * 预览恢复指定账户：列出主库与账户库中将被写入、替换或删除的键，不修改数据库
+   * @param accountName 账户名（邮箱）
+   */
+  static async previewRestore(accountName: string): Promise<RestorePreview> {
+    return invoke('preview_restore', { accountName });
+  }
+
+  /**
+   * 列出账户备份的历史版本（每次备份覆盖前自动保存，每个账户保留最近 10 个）
+   * @param accountName 账户名（邮箱）
+   * @returns 历史版本，最新的在前
+   */
+  static async listBackupVersions(accountName: string): Promise<BackupVersion[]> {
+    return invoke('list_backup_versions', { accountName });
+  }
+
+  /**
+   * 将账户备份的某个历史版本写回 Antigravity 数据库
+   * @param accountName 账户名（邮箱）
+   * @param version 版本 ID
+   * @returns 恢复结果消息
+   */
+  static async restoreBackupVersion(accountName: string, version: string): Promise<string> {
+    return invoke('restore_backup_version', { accountName, version });
+  }
+
   /**
    * 清除所有 Antigravity 数据（注销）
    * @returns 清除结果消息
@@ -45,4 +80,41 @@ export class AccountCommands {
   static async clearAllData(): Promise<string> {
     return invoke('clear_all_antigravity_data');
   }
+
+  /**
+   * 获取当前登录状态的结构化内容（套餐、模型、会话历史），不包含原始 Base64 数据
+   * @param redactSecrets 是否隐藏令牌等凭据，默认隐藏
+   * @returns 解码后的会话状态
+   */
+  static async getDecodedSessionState(redactSecrets = true): Promise<AntigravityAccount> {
+    return invoke('get_decoded_session_state', { redactSecrets });
+  }
+
+  /**
+   * 获取当前账户与每个备份的可用模型
+   * @returns 各账户的套餐、模型与推荐模型
+   */
+  static async getAvailableModels(): Promise<AvailableModels[]> {
+    return invoke('get_available_models');
+  }
+
+  /**
+   * 就地修改当前登录状态中的指定字段（修改前自动创建安全快照）
+   * @param patch 要修改的字段，未提供的字段保持不变
+   * @returns 修改结果消息
+   */
+  static async patchSessionState(patch: SessionPatch): Promise<string> {
+    return invoke('patch_session_state', { patch });
+  }
+
+  /**
+   * 用粘贴的令牌创建账户备份，无需在本机的 Antigravity 中登录（同名备份已存在时拒绝）
+   * @param email 账户邮箱
+   * @param accessToken 访问令牌
+   * @param idToken ID 令牌（其 `exp` 声明作为到期时间）
+   * @returns 创建结果消息
+   */
+  static async createAccountFromToken(email: string, accessToken: string, idToken: string): Promise<string> {
+    return invoke('create_account_from_token', { email, accessToken, idToken });
+  }
 }
@@ -1,16 +1,120 @@
 import {invoke} from '@tauri-apps/api/core';
-import type {BackupData, RestoreResult} from './types/account-manage.types.ts';
+import type {
+  ArchiveImportReport,
+  BackupArchiveSummary,
+  BackupContentsOptions,
+  BackupContentsPage,
+  BackupData,
+  BackupIndexEntry,
+  BackupVaultMode,
+  BackupVaultStatus,
+  ConflictStrategy,
+  ImportDetection,
+  ImportFormat,
+  ImportReport,
+  ReportFormat,
+  RestoreResult,
+  TransferPayload,
+} from './types/account-manage.types.ts';
 
 /**
  * 账户与备份综合命令
  */
 export class AccountManageCommands {
-  static collectAccountContents(): Promise<BackupData[]> {
-    return invoke('collect_account_contents');
+  /**
+   * 分页收集备份文件内容
+   * @param share 为 true 时遮盖凭据，仅用于分享排查
+   * @param sanitize 为 true 时移除令牌与原始登录状态，只保留可公开的元数据
+   * @param options 分页与只取元数据选项
+   */
+  static collectAccountContents(
+    share = false,
+    sanitize = false,
+    options: BackupContentsOptions = {},
+  ): Promise<BackupContentsPage> {
+    return invoke('collect_account_contents', { share, sanitize, ...options });
   }
 
-  static restoreBackupFiles(backups: BackupData[]): Promise<RestoreResult> {
-    return invoke('restore_backup_files', { accountFileData: backups });
+  /**
+   * 按需获取单个备份的完整内容
+   * @param name 备份名（文件名去掉 .json）
+   */
+  static getBackupContent(name: string, share = false, sanitize = false): Promise<BackupData> {
+    return invoke('get_backup_content', { name, share, sanitize });
+  }
+
+  /**
+   * 按元数据索引列出备份（不含登录状态，无需逐个读取备份文件）
+   */
+  static listBackupsIndexed(): Promise<BackupIndexEntry[]> {
+    return invoke('list_backups_indexed');
+  }
+
+  /**
+   * 写入备份文件
+   * @param operationId 操作 ID，写入过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static restoreBackupFiles(backups: BackupData[], operationId?: string): Promise<RestoreResult> {
+    return invoke('restore_backup_files', { accountFileData: backups, operationId });
+  }
+
+  /**
+   * 导入账户配置文件：后端校验签名与内容后才写入，未通过校验的文件会被隔离
+   * @param allowUnsigned 允许导入旧版本导出的未签名文件
+   * @param trustSigner 信任文件的签名设备（不是本机也不在信任列表中时需要用户确认）
+   * @param operationId 操作 ID，导入过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static importConfigArchive(
+    path: string,
+    password: string,
+    allowUnsigned = false,
+    trustSigner = false,
+    operationId?: string,
+  ): Promise<RestoreResult> {
+    return invoke('import_config_archive', { path, password, allowUnsigned, trustSigner, operationId });
+  }
+
+  /**
+   * 将所有账户备份与 Antigravity 路径配置导出为一个加密的 `.agbak` 归档，用于整体迁移到新机器
+   * @param path 归档文件路径
+   * @param password 导出密码，导入时需要
+   * @param operationId 操作 ID，打包过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static exportBackupArchive(
+    path: string,
+    password: string,
+    operationId?: string,
+  ): Promise<BackupArchiveSummary> {
+    return invoke('export_backup_archive', { path, password, operationId });
+  }
+
+  /**
+   * 解密 `.agbak` 归档并合并到备份目录
+   * @param strategy 与已有备份同名时的处理策略
+   * @param allowUnsigned 是否允许导入没有设备签名的旧版归档（需用户确认）
+   * @param trustSigner 是否信任未在信任列表中的签名设备（需用户确认）
+   * @param importExecutablePath 是否导入归档中的自定义 Antigravity 可执行文件路径（需用户确认）
+   * @param operationId 操作 ID，导入过程中可传给 `OperationCommands.cancel` 取消
+   * @returns 每个文件的导入结果
+   */
+  static importBackupArchive(
+    path: string,
+    password: string,
+    strategy: ConflictStrategy = 'skip',
+    allowUnsigned = false,
+    trustSigner = false,
+    importExecutablePath = false,
+    operationId?: string,
+  ): Promise<ArchiveImportReport> {
+    return invoke('import_backup_archive', {
+      path,
+      password,
+      strategy,
+      allowUnsigned,
+      trustSigner,
+      importExecutablePath,
+      operationId,
+    });
   }
 
   static deleteBackup(name: string): Promise<string> {
@@ -21,6 +125,81 @@ export class AccountManageCommands {
     return invoke('clear_all_backups');
   }
 
+  /**
+   * 导出账户报表（邮箱、套餐、令牌到期、最近备份/切换时间、备份大小）
+   * @param format 导出格式
+   * @param path 导出文件路径
+   * @returns 导出的账户数
+   */
+  static exportAccountReport(format: ReportFormat, path: string): Promise<number> {
+    return invoke('export_account_report', { format, path });
+  }
+
+  // ==== 二维码账户传输 ====
+  /**
+   * 为指定账户生成加密分片，每个分片渲染为一个二维码
+   * @param accountName 账户备份名
+   */
+  static createTransferPayload(accountName: string): Promise<TransferPayload> {
+    return invoke('create_transfer_payload', { accountName });
+  }
+
+  /**
+   * 拼装扫描到的分片（顺序任意）并用传输码解密导入
+   * @param chunks 扫描到的分片文本
+   * @param code 传输码
+   */
+  static importTransferPayload(chunks: string[], code: string): Promise<RestoreResult> {
+    return invoke('import_transfer_payload', { chunks, code });
+  }
+
+  // ==== 从其他切换工具导入 ====
+  /**
+   * 识别其他账户切换工具导出文件的格式与记录数
+   * @param path 导出文件路径
+   */
+  static detectImportFormat(path: string): Promise<ImportDetection> {
+    return invoke('detect_import_format', { path });
+  }
+
+  /**
+   * 从其他账户切换工具的导出文件导入账户
+   * @param path 导出文件路径
+   * @param format 预期格式（与识别结果不一致时拒绝导入）
+   */
+  static importFromOtherTool(path: string, format?: ImportFormat): Promise<ImportReport> {
+    return invoke('import_from_other_tool', { path, format });
+  }
+
+  // ==== 备份目录加密 ====
+  static getBackupVaultStatus(): Promise<BackupVaultStatus> {
+    return invoke('get_backup_vault_status');
+  }
+
+  /**
+   * 启用备份目录加密，返回加密的文件数
+   * @param password 主密码模式必填；钥匙串模式忽略
+   */
+  static enableBackupVault(mode: BackupVaultMode, password?: string): Promise<number> {
+    return invoke('enable_backup_vault', { mode, password });
+  }
+
+  static unlockBackupVault(password: string): Promise<void> {
+    return invoke('unlock_backup_vault', { password });
+  }
+
+  static lockBackupVault(): Promise<void> {
+    return invoke('lock_backup_vault');
+  }
+
+  /**
+   * 关闭备份目录加密，返回解密的文件数
+   * @param password 未解锁时需要主密码
+   */
+  static disableBackupVault(password?: string): Promise<number> {
+    return invoke('disable_backup_vault', { password });
+  }
+
   // ==== 配置加解密 ====
   static encryptConfig(jsonData: string, password: string): Promise<string> {
     return invoke('encrypt_config_data', { jsonData: jsonData, password });
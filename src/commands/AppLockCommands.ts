@@ -0,0 +1,46 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AppLockStatus } from './types/app-lock.types.ts';
+
+/**
+ * 应用锁命令
+ * 锁定期间账户相关命令会返回 `APP_LOCKED` 错误，锁定状态变化通过 `app-lock-changed` 事件通知
+ */
+export class AppLockCommands {
+  static async getStatus(): Promise<AppLockStatus> {
+    return invoke('get_app_lock_status');
+  }
+
+  /**
+   * 使用密码解锁
+   */
+  static async unlock(password: string): Promise<void> {
+    return invoke('unlock', { password });
+  }
+
+  static async lock(): Promise<void> {
+    return invoke('lock_app');
+  }
+
+  /**
+   * 上报用户活动，重置空闲计时
+   */
+  static async reportActivity(): Promise<void> {
+    return invoke('report_activity');
+  }
+
+  /**
+   * 设置、修改或清除应用锁密码
+   * @param currentPassword 已设置密码时必填
+   * @param newPassword 为空时清除密码（关闭应用锁）
+   */
+  static async setPassword(currentPassword?: string, newPassword?: string): Promise<AppLockStatus> {
+    return invoke('set_app_lock_password', { currentPassword, newPassword });
+  }
+
+  /**
+   * 保存空闲锁定时间（分钟，0 表示只在启动时锁定）
+   */
+  static async saveIdleMinutes(minutes: number): Promise<AppLockStatus> {
+    return invoke('save_app_lock_idle_minutes', { minutes });
+  }
+}
@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { BackupProblem, BackupVerifyReport } from './types/backup-check.types.ts';
+
+/**
+ * 备份检查命令
+ * 找出无法使用的账户备份、按完整性清单校验备份，并移入回收目录
+ */
+export class BackupCheckCommands {
+  /**
+   * 扫描有问题的备份（备份目录已加密时需先解锁）
+   * @param operationId 操作 ID，扫描过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static async scan(operationId?: string): Promise<BackupProblem[]> {
+    return invoke('scan_backup_problems', { operationId });
+  }
+
+  /**
+   * 按完整性清单（每次写入备份时记录的 SHA-256 与大小）校验所有备份
+   * @param operationId 操作 ID，校验过程中可传给 `OperationCommands.cancel` 取消
+   * @returns 每个备份的校验结果
+   */
+  static async verify(operationId?: string): Promise<BackupVerifyReport> {
+    return invoke('verify_backups', { operationId });
+  }
+
+  /**
+   * 将指定备份移入回收目录（安全模式下拒绝；执行前弹出确认对话框）
+   * @param names 备份名列表
+   * @returns 结果消息
+   */
+  static async trash(names: string[]): Promise<string> {
+    return invoke('trash_backups', { names });
+  }
+}
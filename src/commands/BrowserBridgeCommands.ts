@@ -0,0 +1,32 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { BrowserBridgeStatus } from './types/browser-bridge.types.ts';
+
+/**
+ * 浏览器登录桥接命令
+ * 配套浏览器扩展连接 `ws://127.0.0.1:<port>/v1/bridge?token=<token>`，交来的登录会直接创建为账户备份，
+ * 结果通过 `browser-login-imported` 事件通知
+ */
+export class BrowserBridgeCommands {
+  /**
+   * 获取桥接状态与配对令牌
+   */
+  static async getStatus(): Promise<BrowserBridgeStatus> {
+    return invoke('get_browser_bridge_status');
+  }
+
+  /**
+   * 启用/停用桥接或修改端口，首次启用时生成配对令牌
+   * @param enabled 是否启用
+   * @param port 监听端口（不传则保持当前端口）
+   */
+  static async saveSettings(enabled: boolean, port?: number): Promise<BrowserBridgeStatus> {
+    return invoke('save_browser_bridge_settings', { enabled, port });
+  }
+
+  /**
+   * 重新生成配对令牌，已配对的扩展需要重新填写
+   */
+  static async regenerateToken(): Promise<BrowserBridgeStatus> {
+    return invoke('regenerate_browser_bridge_token');
+  }
+}
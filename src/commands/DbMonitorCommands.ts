@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import type { ReconstructedState } from './types/state-journal.types.ts';
 
 /**
  * 数据库监控命令
@@ -27,4 +28,14 @@ export class DbMonitorCommands {
   static async stop(): Promise<string> {
     return invoke('stop_database_monitoring');
   }
+
+  /**
+   * 根据登录状态变更日志重建指定时刻的关键键值
+   * @param timestamp 时间（RFC 3339）
+   * @param restore 是否写回 Antigravity 数据库（写入前自动创建安全快照）
+   * @returns 该时刻的状态
+   */
+  static async reconstructStateAt(timestamp: string, restore?: boolean): Promise<ReconstructedState> {
+    return invoke('reconstruct_state_at', { timestamp, restore });
+  }
 }
@@ -0,0 +1,37 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { DeviceIdentity, TrustedSigner } from './types/device.types.ts';
+
+/**
+ * 设备身份命令
+ * 设备密钥用于为导出的配置文件签名
+ */
+export class DeviceCommands {
+  /**
+   * 获取本机设备身份（首次调用时生成密钥）
+   */
+  static async getIdentity(): Promise<DeviceIdentity> {
+    return invoke('get_device_identity');
+  }
+
+  /**
+   * 轮换设备密钥，之前导出的文件仍可用旧公钥校验
+   */
+  static async rotateIdentity(): Promise<DeviceIdentity> {
+    return invoke('rotate_device_identity');
+  }
+
+  /**
+   * 列出受信任的签名设备（导入配置文件时接受其签名）
+   */
+  static async listTrustedSigners(): Promise<TrustedSigner[]> {
+    return invoke('list_trusted_signers');
+  }
+
+  /**
+   * 从信任列表移除签名设备
+   * @returns 设备是否在信任列表中
+   */
+  static async removeTrustedSigner(deviceId: string): Promise<boolean> {
+    return invoke('remove_trusted_signer', { deviceId });
+  }
+}
@@ -0,0 +1,14 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { EventType } from './types/event.types.ts';
+
+/**
+ * 事件命令
+ */
+export class EventCommands {
+  /**
+   * 列出后端会发出的所有事件及其负载类型
+   */
+  static async listEventTypes(): Promise<EventType[]> {
+    return invoke('list_event_types');
+  }
+}
@@ -0,0 +1,35 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ItemTableKey, ItemTableValue } from './types/item-table.types.ts';
+
+/**
+ * ItemTable 浏览命令
+ * 查看和修改 Antigravity 状态数据库（state.vscdb）中的任意条目
+ */
+export class ItemTableCommands {
+  /**
+   * 列出匹配模式的键
+   * @param pattern 搜索模式（`*` / `?` 通配，不含通配符时按包含匹配；不传则列出全部）
+   */
+  static async listKeys(pattern?: string): Promise<ItemTableKey[]> {
+    return invoke('db_list_keys', { pattern });
+  }
+
+  /**
+   * 读取指定键的值
+   * @param key 键名
+   * @returns 条目，键不存在时为 null
+   */
+  static async getValue(key: string): Promise<ItemTableValue | null> {
+    return invoke('db_get_value', { key });
+  }
+
+  /**
+   * 写入指定键的值（安全模式下拒绝；写入前弹出确认对话框并创建安全快照）
+   * @param key 键名
+   * @param value 新值
+   * @returns 写入结果消息
+   */
+  static async setValue(key: string, value: string): Promise<string> {
+    return invoke('db_set_value', { key, value });
+  }
+}
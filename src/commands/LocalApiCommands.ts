@@ -0,0 +1,36 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { LocalApiStatus } from './types/local-api.types.ts';
+
+/**
+ * 本地 HTTP 控制接口命令
+ * 接口只监听 127.0.0.1，请求需携带 `Authorization: Bearer <token>`
+ */
+export class LocalApiCommands {
+  /**
+   * 获取接口状态与访问令牌
+   */
+  static async getStatus(): Promise<LocalApiStatus> {
+    return invoke('get_local_api_status');
+  }
+
+  /**
+   * 启用/停用接口、修改端口或切换账户前的确认要求，首次启用时生成访问令牌
+   * @param enabled 是否启用
+   * @param port 监听端口（不传则保持当前端口）
+   * @param requireConfirmation 外部工具切换账户前是否要求在 Agent 中确认（不传则保持当前设置）
+   */
+  static async saveSettings(
+    enabled: boolean,
+    port?: number,
+    requireConfirmation?: boolean,
+  ): Promise<LocalApiStatus> {
+    return invoke('save_local_api_settings', { enabled, port, requireConfirmation });
+  }
+
+  /**
+   * 重新生成访问令牌，旧令牌立即失效
+   */
+  static async regenerateToken(): Promise<LocalApiStatus> {
+    return invoke('regenerate_local_api_token');
+  }
+}
@@ -1,5 +1,13 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { FrontendLogEntry } from './types/logging.types';
+import type {
+  AuditEntry,
+  CommandMetrics,
+  CrashReport,
+  FrontendLogEntry,
+  LogQueryFilter,
+  LogQueryResult,
+} from './types/logging.types';
+import type { LogCleanupResult } from './types/settings.types';
 
 /**
  * 日志和加密命令
@@ -22,4 +30,80 @@ export class LoggingCommands {
   static async writeTextFile(path: string, content: string): Promise<string> {
     return invoke('write_text_file', { path, content });
   }
+
+  /**
+   * 查询日志
+   * @param filter 查询条件
+   * @returns 分页后的结构化日志条目
+   */
+  static async queryLogs(filter: LogQueryFilter = {}): Promise<LogQueryResult> {
+    return invoke('query_logs', { filter });
+  }
+
+  /**
+   * 导出日志（忽略分页，可按运行 ID 过滤）
+   * @param path 导出文件路径
+   * @param filter 过滤条件
+   * @returns 导出的条目数
+   */
+  static async exportLogs(path: string, filter: LogQueryFilter = {}): Promise<number> {
+    return invoke('export_logs', { path, filter });
+  }
+
+  /**
+   * 清空日志（保留当天正在写入的日志文件）
+   * @returns 清理结果
+   */
+  static async clearLogs(): Promise<LogCleanupResult> {
+    return invoke('clear_logs');
+  }
+
+  /**
+   * 导出离线错误报告（Sentry envelope 文件）
+   * @param targetDir 导出目录
+   * @returns 导出的文件数
+   */
+  static async exportErrorReports(targetDir: string): Promise<number> {
+    return invoke('export_error_reports', { targetDir });
+  }
+
+  /**
+   * 获取本次运行 ID
+   * @returns 启动时生成的 UUID
+   */
+  static async getRunId(): Promise<string> {
+    return invoke('get_run_id');
+  }
+
+  /**
+   * 获取崩溃报告
+   * @returns 崩溃报告列表（最新在前）
+   */
+  static async getCrashReports(): Promise<CrashReport[]> {
+    return invoke('get_crash_reports');
+  }
+
+  /**
+   * 获取命令性能指标
+   * @returns 每个命令的调用次数与耗时统计（按平均耗时倒序）
+   */
+  static async getCommandMetrics(): Promise<CommandMetrics[]> {
+    return invoke('get_command_metrics');
+  }
+
+  /**
+   * 清空命令性能指标
+   */
+  static async resetCommandMetrics(): Promise<void> {
+    return invoke('reset_command_metrics');
+  }
+
+  /**
+   * 获取操作审计日志
+   * @param limit 返回条数（默认 500）
+   * @returns 审计条目（最新在前）
+   */
+  static async getAuditLog(limit?: number): Promise<AuditEntry[]> {
+    return invoke('get_audit_log', { limit });
+  }
 }
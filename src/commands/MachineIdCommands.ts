@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { MachineIds } from './types/machine-id.types.ts';
+
+/**
+ * Antigravity 设备标识命令
+ * 查看并重新生成 Antigravity 的遥测设备标识
+ */
+export class MachineIdCommands {
+  /**
+   * 获取当前的设备标识
+   */
+  static async getMachineIds(): Promise<MachineIds> {
+    return invoke('get_machine_ids');
+  }
+
+  /**
+   * 重新生成设备标识（需先关闭 Antigravity；安全模式下拒绝；执行前弹出确认对话框，旧标识会先备份）
+   * @returns 新的设备标识
+   */
+  static async regenerateMachineIds(): Promise<MachineIds> {
+    return invoke('regenerate_machine_ids');
+  }
+}
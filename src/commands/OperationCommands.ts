@@ -0,0 +1,17 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * 长耗时操作命令
+ * 切换、恢复等流水线操作的 ID 见 `pipeline-status` 事件的 `operation_id`；
+ * 备份检查、导入导出可在调用时自行传入 ID
+ */
+export class OperationCommands {
+  /**
+   * 取消正在执行的操作（取消后发出 `operation-cancelled` 事件）
+   * @param operationId 操作 ID
+   * @returns 操作不存在或已经结束时为 false
+   */
+  static async cancel(operationId: string): Promise<boolean> {
+    return invoke('cancel_operation', { operationId });
+  }
+}
@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { PeerDiscoveryStatus } from './types/peer-discovery.types.ts';
+
+/**
+ * 局域网对等发现命令
+ * 通过 mDNS 广播/浏览 `_antigravity-agent._tcp`，发现或失去对端时发出 `peer-discovered` / `peer-lost` 事件
+ */
+export class PeerDiscoveryCommands {
+  /**
+   * 获取对等发现状态与已发现的对端
+   */
+  static async getStatus(): Promise<PeerDiscoveryStatus> {
+    return invoke('get_peer_discovery_status');
+  }
+
+  /**
+   * 启用/停用对等发现或修改广播端口
+   * @param enabled 是否启用
+   * @param port 广播端口（不传则保持当前端口）
+   */
+  static async saveSettings(enabled: boolean, port?: number): Promise<PeerDiscoveryStatus> {
+    return invoke('save_peer_discovery_settings', { enabled, port });
+  }
+}
@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { PlatformInfo, DetectionResult, PathConfig } from './types/platform.types';
+import type { PlatformInfo, DetectionResult, PathConfig, SelfTestReport, BenchmarkReport, OnboardingReport, HardenReport, IntegrityWarning, ShellFormat, DiskUsageReport } from './types/platform.types';
 
 /**
  * 平台工具命令
@@ -62,4 +62,71 @@ export class PlatformCommands {
   static async getCurrentPaths(): Promise<PathConfig> {
     return invoke('get_current_paths');
   }
+
+  /**
+   * 运行自检
+   * @returns 逐项检查结果
+   */
+  static async runSelfTest(): Promise<SelfTestReport> {
+    return invoke('run_self_test');
+  }
+
+  /**
+   * 运行性能基准测试：用合成数据与真实数据测量备份写入、恢复写入、数据库读取与登录状态解码耗时
+   * @returns 逐项耗时及与参考基线的对比
+   */
+  static async runBenchmark(): Promise<BenchmarkReport> {
+    return invoke('run_benchmark');
+  }
+
+  /**
+   * 首次运行引导检测：可执行文件、数据目录、目录权限与当前登录，检测成功的路径会写入路径配置
+   * @returns 检测报告
+   */
+  static async runOnboardingDetection(): Promise<OnboardingReport> {
+    return invoke('run_onboarding_detection');
+  }
+
+  /**
+   * 检查配置目录权限
+   * @param repair 为 true 时收紧为仅当前用户可访问
+   * @returns 检查与修复结果
+   */
+  static async hardenConfigDir(repair = true): Promise<HardenReport> {
+    return invoke('harden_config_dir', { repair });
+  }
+
+  /**
+   * 获取启动时配置文件完整性检查发现的问题
+   * @returns 被外部修改、损坏或丢失的配置文件
+   */
+  static async getConfigIntegrityWarnings(): Promise<IntegrityWarning[]> {
+    return invoke('get_config_integrity_warnings');
+  }
+
+  /**
+   * 复制令牌等敏感内容，到期后若剪贴板内容未变则自动清空
+   * @param value 要复制的内容
+   * @param ttl 自动清空时间（秒），默认 30 秒
+   */
+  static async copySecretToClipboard(value: string, ttl?: number): Promise<void> {
+    return invoke('copy_secret_to_clipboard', { value, ttl });
+  }
+
+  /**
+   * 导出 Antigravity 路径与当前账户为 `ANTIGRAVITY_*` 环境变量语句
+   * @param format 目标 shell
+   * @returns 可直接 `eval` / `Invoke-Expression` 的脚本
+   */
+  static async exportShellEnv(format: ShellFormat): Promise<string> {
+    return invoke('export_shell_env', { format });
+  }
+
+  /**
+   * 获取磁盘占用报告
+   * @returns Antigravity 数据目录与 Agent 配置目录按子目录组织的大小树
+   */
+  static async getDiskUsageReport(): Promise<DiskUsageReport> {
+    return invoke('get_disk_usage_report');
+  }
 }
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { PluginInfo } from './types/plugin.types.ts';
+
+/**
+ * 插件命令
+ * 插件为配置目录 `plugins/` 下的 Rhai 脚本，可注册自定义命令（`fn command_<名称>(args)`）与事件处理函数
+ */
+export class PluginCommands {
+  /**
+   * 获取已加载的插件
+   */
+  static async list(): Promise<PluginInfo[]> {
+    return invoke('list_plugins');
+  }
+
+  /**
+   * 重新加载插件目录中的脚本
+   */
+  static async reload(): Promise<PluginInfo[]> {
+    return invoke('reload_plugins');
+  }
+
+  /**
+   * 运行插件的自定义命令
+   * @param plugin 插件名
+   * @param command 命令名（不含 `command_` 前缀）
+   * @param args 传给脚本的参数
+   * @returns 脚本的返回值
+   */
+  static async run<T = unknown>(plugin: string, command: string, args?: unknown): Promise<T> {
+    return invoke('run_plugin_command', { plugin, command, args });
+  }
+}
@@ -0,0 +1,31 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { QuickSwitchAccount } from './types/quick-switcher.types';
+
+/**
+ * 快速切换窗口命令
+ */
+export class QuickSwitcherCommands {
+  /**
+   * 获取可切换的账户列表
+   * @returns 账户列表（置顶账户在前）
+   */
+  static async listAccounts(): Promise<QuickSwitchAccount[]> {
+    return invoke('list_quick_switch_accounts');
+  }
+
+  /**
+   * 切换到指定账户（会先关闭快速切换窗口）
+   * @param email 账户邮箱
+   * @returns 切换结果消息
+   */
+  static async switchAccount(email: string): Promise<string> {
+    return invoke('quick_switch_account', { email });
+  }
+
+  /**
+   * 关闭快速切换窗口
+   */
+  static async close(): Promise<void> {
+    return invoke('close_quick_switcher');
+  }
+}
@@ -0,0 +1,64 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ArchiveImportReport, ConflictStrategy } from './types/account-manage.types.ts';
+import type {
+  RemoteBackupEntry,
+  RemotePushReport,
+  S3BackupSettings,
+  S3BackupStatus,
+} from './types/remote-backup.types.ts';
+
+/**
+ * 远程备份命令
+ * 把账户备份推送到 S3 / MinIO 等兼容对象存储，或从远程拉取到本地
+ */
+export class RemoteBackupCommands {
+  /**
+   * 获取远程备份配置与密钥保存状态
+   */
+  static async getStatus(): Promise<S3BackupStatus> {
+    return invoke('get_s3_backup_status');
+  }
+
+  /**
+   * 保存远程备份配置
+   * @param settings 配置，传 null 清除配置与钥匙串中的密钥
+   * @param secretAccessKey Secret Access Key，保存到系统钥匙串（不传则保留已保存的密钥）
+   */
+  static async saveSettings(
+    settings: S3BackupSettings | null,
+    secretAccessKey?: string,
+  ): Promise<S3BackupStatus> {
+    return invoke('save_s3_backup_settings', { settings, secretAccessKey });
+  }
+
+  /**
+   * 对比列出本地与远程的备份
+   * @param operationId 操作 ID，列举过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static async list(operationId?: string): Promise<RemoteBackupEntry[]> {
+    return invoke('list_remote_backups', { operationId });
+  }
+
+  /**
+   * 推送本地备份到远程，同名对象直接覆盖
+   * @param names 备份名列表，不传时推送全部
+   * @param operationId 操作 ID，上传过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static async push(names?: string[], operationId?: string): Promise<RemotePushReport> {
+    return invoke('push_remote_backups', { names, operationId });
+  }
+
+  /**
+   * 从远程拉取备份到本地备份目录
+   * @param names 备份名列表，不传时拉取全部
+   * @param strategy 与本地备份同名时的处理策略
+   * @param operationId 操作 ID，下载过程中可传给 `OperationCommands.cancel` 取消
+   */
+  static async pull(
+    names?: string[],
+    strategy: ConflictStrategy = 'skip',
+    operationId?: string,
+  ): Promise<ArchiveImportReport> {
+    return invoke('pull_remote_backups', { names, strategy, operationId });
+  }
+}
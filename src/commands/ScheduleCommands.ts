@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ScheduledJob } from './types/schedule.types.ts';
+
+/**
+ * 计划任务命令
+ */
+export class ScheduleCommands {
+  /**
+   * 获取已调度的自动任务及下一次运行时间
+   */
+  static async getJobs(): Promise<ScheduledJob[]> {
+    return invoke('get_scheduled_jobs');
+  }
+
+  /**
+   * 将自动任务导出为 iCalendar 文件
+   * @param path 目标 `.ics` 文件路径
+   * @returns 导出的任务数
+   */
+  static async exportIcs(path: string): Promise<number> {
+    return invoke('export_schedule_ics', { path });
+  }
+}
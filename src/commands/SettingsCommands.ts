@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { AppSettings } from './types/settings.types';
+import type { AppSettings, BackupCompression, KdfSettings, Locale, LogCleanupResult, LogRetentionSettings, HookConfig, WebhookConfig } from './types/settings.types';
 
 /**
  * 设置管理命令
@@ -23,6 +23,292 @@ export class SettingsCommands {
     return invoke('save_silent_start_state', { enabled });
   }
 
+  /**
+   * 获取最小化到托盘状态
+   * @returns 是否已启用最小化到托盘
+   */
+  static async isMinimizeToTrayEnabled(): Promise<boolean> {
+    return invoke('is_minimize_to_tray_enabled');
+  }
+
+  /**
+   * 保存最小化到托盘状态
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveMinimizeToTrayState(enabled: boolean): Promise<boolean> {
+    return invoke('save_minimize_to_tray_state', { enabled });
+  }
+
+  /**
+   * 获取“操作进行中退出前确认”状态
+   * @returns 是否已启用
+   */
+  static async isConfirmExitEnabled(): Promise<boolean> {
+    return invoke('is_confirm_exit_enabled');
+  }
+
+  /**
+   * 保存“操作进行中退出前确认”状态
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveConfirmExitState(enabled: boolean): Promise<boolean> {
+    return invoke('save_confirm_exit_state', { enabled });
+  }
+
+  /**
+   * 获取慢操作警告阈值
+   * @returns 阈值（毫秒）
+   */
+  static async getSlowOperationThreshold(): Promise<number> {
+    return invoke('get_slow_operation_threshold');
+  }
+
+  /**
+   * 保存慢操作警告阈值
+   * @param thresholdMs 阈值（毫秒），传入 null 恢复默认值
+   * @returns 保存后生效的阈值
+   */
+  static async saveSlowOperationThreshold(thresholdMs: number | null): Promise<number> {
+    return invoke('save_slow_operation_threshold', { thresholdMs });
+  }
+
+  /**
+   * 获取离线错误报告开关
+   * @returns 是否已启用
+   */
+  static async isErrorReportsEnabled(): Promise<boolean> {
+    return invoke('is_error_reports_enabled');
+  }
+
+  /**
+   * 保存离线错误报告开关（报告只保存在本地，不会自动上传）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveErrorReportsEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_error_reports_enabled', { enabled });
+  }
+
+  /**
+   * 获取“备份凭据保存到系统钥匙串”开关
+   * @returns 是否已启用
+   */
+  static async isKeychainEnabled(): Promise<boolean> {
+    return invoke('is_keychain_enabled');
+  }
+
+  /**
+   * 保存“备份凭据保存到系统钥匙串”开关，并迁移已有备份
+   * @param enabled 是否启用
+   * @returns 迁移的备份数
+   */
+  static async saveKeychainEnabled(enabled: boolean): Promise<number> {
+    return invoke('save_keychain_enabled', { enabled });
+  }
+
+  /**
+   * 获取备份压缩格式
+   * @returns 新写入的备份使用的压缩格式
+   */
+  static async getBackupCompression(): Promise<BackupCompression> {
+    return invoke('get_backup_compression');
+  }
+
+  /**
+   * 保存备份压缩格式，并按新格式重写已有备份
+   * @param compression 压缩格式
+   * @returns 重写的备份数
+   */
+  static async saveBackupCompression(compression: BackupCompression): Promise<number> {
+    return invoke('save_backup_compression', { compression });
+  }
+
+  /**
+   * 获取“删除备份前覆盖文件内容”开关
+   * @returns 是否已启用
+   */
+  static async isSecureDeleteEnabled(): Promise<boolean> {
+    return invoke('is_secure_delete_enabled');
+  }
+
+  /**
+   * 保存“删除备份前覆盖文件内容”开关
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveSecureDeleteEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_secure_delete_enabled', { enabled });
+  }
+
+  /**
+   * 获取“记录登录状态变更日志”开关
+   * @returns 是否已启用
+   */
+  static async isStateJournalEnabled(): Promise<boolean> {
+    return invoke('is_state_journal_enabled');
+  }
+
+  /**
+   * 保存“记录登录状态变更日志”开关（关闭时删除已有日志）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveStateJournalEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_state_journal_enabled', { enabled });
+  }
+
+  /**
+   * 获取“敏感操作前要求系统身份验证”开关
+   * @returns 是否已启用
+   */
+  static async isOsAuthRequired(): Promise<boolean> {
+    return invoke('is_os_auth_required');
+  }
+
+  /**
+   * 保存“敏感操作前要求系统身份验证”开关（保存前会弹出一次系统验证）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveOsAuthRequired(enabled: boolean): Promise<boolean> {
+    return invoke('save_os_auth_required', { enabled });
+  }
+
+  /**
+   * 获取安全模式开关
+   * @returns 是否已启用
+   */
+  static async isSafeModeEnabled(): Promise<boolean> {
+    return invoke('is_safe_mode_enabled');
+  }
+
+  /**
+   * 保存安全模式开关（开启后清除数据、删除备份等命令会被拒绝）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveSafeModeEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_safe_mode_enabled', { enabled });
+  }
+
+  /**
+   * 重新测量 Argon2 参数（配置迁移到新设备后使用）
+   * @returns 新的参数
+   */
+  static async rebenchmarkKdf(): Promise<KdfSettings> {
+    return invoke('rebenchmark_kdf');
+  }
+
+  /**
+   * 获取设置文件加密开关
+   * @returns 是否已加密
+   */
+  static async isSettingsEncryptionEnabled(): Promise<boolean> {
+    return invoke('is_settings_encryption_enabled');
+  }
+
+  /**
+   * 保存设置文件加密开关（开启后设置文件使用设备密钥加密保存）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveSettingsEncryptionEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_settings_encryption_enabled', { enabled });
+  }
+
+  /**
+   * 获取远程功能使用的代理地址
+   * @returns 代理地址，未设置时为 null
+   */
+  static async getHttpProxy(): Promise<string | null> {
+    return invoke('get_http_proxy');
+  }
+
+  /**
+   * 保存远程功能使用的代理地址
+   * @param proxy 代理地址（http://、https:// 或 socks5://），为空时使用系统代理环境变量
+   * @returns 保存后的代理地址
+   */
+  static async saveHttpProxy(proxy: string | null): Promise<string | null> {
+    return invoke('save_http_proxy', { proxy });
+  }
+
+  /**
+   * 获取 Webhook 配置
+   */
+  static async getWebhooks(): Promise<WebhookConfig[]> {
+    return invoke('get_webhooks');
+  }
+
+  /**
+   * 保存 Webhook 配置（地址必须为 HTTPS）
+   * @param webhooks 全部 Webhook 配置
+   * @returns 保存后的配置
+   */
+  static async saveWebhooks(webhooks: WebhookConfig[]): Promise<WebhookConfig[]> {
+    return invoke('save_webhooks', { webhooks });
+  }
+
+  /**
+   * 向指定 Webhook 发送一次测试事件
+   * @param webhook Webhook 配置（可以是尚未保存的配置）
+   */
+  static async testWebhook(webhook: WebhookConfig): Promise<void> {
+    return invoke('test_webhook', { webhook });
+  }
+
+  /**
+   * 获取事件钩子配置
+   */
+  static async getHooks(): Promise<HookConfig[]> {
+    return invoke('get_hooks');
+  }
+
+  /**
+   * 保存事件钩子配置
+   * @param hooks 全部钩子配置
+   * @returns 保存后的配置
+   */
+  static async saveHooks(hooks: HookConfig[]): Promise<HookConfig[]> {
+    return invoke('save_hooks', { hooks });
+  }
+
+  /**
+   * 获取隐私模式开关
+   * @returns 是否已启用
+   */
+  static async isPrivacyModeEnabled(): Promise<boolean> {
+    return invoke('is_privacy_mode_enabled');
+  }
+
+  /**
+   * 保存隐私模式开关（开启后日志、事件与审计日志中的邮箱替换为哈希）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async savePrivacyModeEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('save_privacy_mode_enabled', { enabled });
+  }
+
+  /**
+   * 获取日志保留设置
+   * @returns 当前保留策略
+   */
+  static async getLogRetentionSettings(): Promise<LogRetentionSettings> {
+    return invoke('get_log_retention_settings');
+  }
+
+  /**
+   * 保存日志保留设置，并立即按新策略清理一次
+   * @param retention 保留策略
+   * @returns 本次清理结果
+   */
+  static async saveLogRetentionSettings(retention: LogRetentionSettings): Promise<LogCleanupResult> {
+    return invoke('save_log_retention_settings', { retention });
+  }
+
   /**
    * 获取所有应用设置
    * @returns 应用设置对象
@@ -30,4 +316,29 @@ export class SettingsCommands {
   static async getAll(): Promise<AppSettings> {
     return invoke('get_all_settings');
   }
+
+  /**
+   * 获取界面语言
+   */
+  static async getLocale(): Promise<Locale> {
+    return invoke('get_locale');
+  }
+
+  /**
+   * 保存界面语言，后端返回的错误与结果消息随之切换
+   * @param locale 语言
+   * @returns 保存后的语言
+   */
+  static async saveLocale(locale: Locale): Promise<Locale> {
+    return invoke('save_locale', { locale });
+  }
+
+  /**
+   * 获取后端消息目录
+   * @param locale 语言，不传时使用当前语言
+   * @returns 消息 ID → 模板（`{name}` 为参数占位符）
+   */
+  static async getMessageCatalog(locale?: Locale): Promise<Record<string, string>> {
+    return invoke('get_message_catalog', { locale });
+  }
 }
@@ -0,0 +1,67 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { UpdateInfo, UpdateSettings, UpdateStatus } from './types/update.types.ts';
+
+/**
+ * 应用更新命令
+ * 按设置中的更新通道检查、下载并安装更新
+ */
+export class UpdateCommands {
+  /**
+   * 检查更新
+   * @returns 可用更新，已是最新版本时为 null
+   */
+  static async checkForUpdates(): Promise<UpdateInfo | null> {
+    return invoke('check_for_updates');
+  }
+
+  /**
+   * 下载最近一次检查到的更新（进度通过 `update-download-progress` 事件发出）
+   * @returns 是否会在应用退出时自动安装
+   */
+  static async downloadUpdate(): Promise<boolean> {
+    return invoke('download_update');
+  }
+
+  /**
+   * 立即安装已下载的更新并重启应用（账户操作进行中时等待其结束）
+   */
+  static async installUpdate(): Promise<void> {
+    return invoke('install_update');
+  }
+
+  /**
+   * 获取更新状态
+   */
+  static async getUpdateStatus(): Promise<UpdateStatus> {
+    return invoke('get_update_status');
+  }
+
+  /**
+   * 推迟安装已下载的更新，等应用下次退出时再安装
+   */
+  static async deferUpdateInstall(): Promise<UpdateStatus> {
+    return invoke('defer_update_install');
+  }
+
+  /**
+   * 取消退出时安装（已下载的更新保留，仍可手动安装）
+   */
+  static async cancelDeferredUpdateInstall(): Promise<UpdateStatus> {
+    return invoke('cancel_deferred_update_install');
+  }
+
+  /**
+   * 获取更新设置
+   */
+  static async getUpdateSettings(): Promise<UpdateSettings> {
+    return invoke('get_update_settings');
+  }
+
+  /**
+   * 保存更新设置（切换通道后需重新检查更新）
+   * @param update 更新设置
+   */
+  static async saveUpdateSettings(update: UpdateSettings): Promise<UpdateSettings> {
+    return invoke('save_update_settings', { update });
+  }
+}
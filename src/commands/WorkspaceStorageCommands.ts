@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+import type {
+  WorkspaceCleanupResult,
+  WorkspaceStorageEntry,
+} from './types/workspace-storage.types.ts';
+
+/**
+ * 工作区存储命令
+ * 管理 Antigravity 的 User/workspaceStorage 目录
+ */
+export class WorkspaceStorageCommands {
+  /**
+   * 列出工作区状态目录（最近使用的在前）
+   */
+  static async list(): Promise<WorkspaceStorageEntry[]> {
+    return invoke('list_workspace_storage');
+  }
+
+  /**
+   * 删除超过指定天数未使用的工作区状态目录（安全模式下拒绝；执行前弹出确认对话框）
+   * @param olderThanDays 未使用天数（至少为 1）
+   */
+  static async clean(olderThanDays: number): Promise<WorkspaceCleanupResult> {
+    return invoke('clean_workspace_storage', { olderThanDays });
+  }
+}
@@ -27,6 +27,37 @@ export interface BackupData {
   timestamp: number;
 }
 
+/**
+ * 分页的备份内容
+ */
+export interface BackupContentsPage {
+  /** 当前页的备份（按文件名排序）；只取元数据时 `content` 为 null */
+  items: BackupData[];
+
+  /** 备份总数 */
+  total: number;
+
+  /** 下一页的起始位置，没有更多时为 null */
+  nextOffset: number | null;
+}
+
+/**
+ * 备份内容分页选项
+ */
+export interface BackupContentsOptions {
+  /** 起始位置（默认 0） */
+  offset?: number;
+
+  /** 每页数量（不传时返回剩余全部） */
+  limit?: number;
+
+  /** 只返回文件名，不读取备份内容 */
+  metadataOnly?: boolean;
+
+  /** 操作 ID，读取过程中可传给 `OperationCommands.cancel` 取消 */
+  operationId?: string;
+}
+
 /**
  * 恢复操作失败的备份信息
  */
@@ -48,3 +79,182 @@ export interface RestoreResult {
   /** 失败的备份列表 */
   failed: FailedBackup[];
 }
+
+/**
+ * 完整备份归档（.agbak）导出结果
+ */
+export interface BackupArchiveSummary {
+  /** 归档文件路径 */
+  path: string;
+
+  /** 打包的账户备份数 */
+  accounts: number;
+
+  /** 是否包含 Antigravity 路径配置 */
+  path_config: boolean;
+
+  /** 归档文件大小（字节） */
+  size: number;
+}
+
+/**
+ * 导入归档时与已有备份同名的处理策略
+ * - `skip`：保留已有备份
+ * - `overwrite`：用归档中的备份覆盖
+ * - `keep-both`：两者都保留，归档中的备份另存为 `<账户> (n).json`
+ */
+export type ConflictStrategy = 'skip' | 'overwrite' | 'keep-both';
+
+/**
+ * 归档中单个文件的导入结果
+ */
+export type ArchiveImportAction = 'imported' | 'overwritten' | 'renamed' | 'skipped' | 'failed';
+
+export interface ArchiveImportItem {
+  /** 归档中的文件名 */
+  filename: string;
+
+  action: ArchiveImportAction;
+
+  /** 实际写入的文件名（跳过或失败时为 null） */
+  target: string | null;
+
+  /** 失败原因 */
+  error: string | null;
+}
+
+/**
+ * 完整备份归档导入报告
+ */
+export interface ArchiveImportReport {
+  /** 写入的文件数（新增、覆盖与另存） */
+  restored_count: number;
+
+  skipped_count: number;
+
+  failed_count: number;
+
+  /** 每个文件的结果（账户备份在前，路径配置在最后） */
+  items: ArchiveImportItem[];
+}
+
+/**
+ * 备份目录加密的密钥来源
+ */
+export type BackupVaultMode = 'password' | 'keychain';
+
+/**
+ * 备份目录加密状态
+ */
+export interface BackupVaultStatus {
+  /** 是否已启用加密 */
+  enabled: boolean;
+
+  /** 密钥来源（未启用时为 null） */
+  mode: BackupVaultMode | null;
+
+  /** 是否已解锁 */
+  unlocked: boolean;
+}
+
+/**
+ * 账户报表导出格式
+ */
+export type ReportFormat = 'csv' | 'json';
+
+/**
+ * 二维码账户传输载荷
+ */
+export interface TransferPayload {
+  /** 传输 ID，同一组分片相同 */
+  transfer_id: string;
+
+  /** 传输码（形如 `ABCD-EFGH-JKLM-NPQR`），需在接收端手动输入 */
+  code: string;
+
+  /** 按顺序排列的分片，每个渲染为一个二维码 */
+  chunks: string[];
+}
+
+/**
+ * 其他账户切换工具的导入格式
+ * - `state_database`：整份复制的 `state.vscdb`
+ * - `item_table_rows`：ItemTable 行转储
+ * - `profile_list`：按账户保存键值快照的配置列表
+ * - `agent_export`：本应用导出的未加密备份列表
+ * - `agent_backup`：单个备份文件
+ */
+export type ImportFormat =
+  | 'state_database'
+  | 'item_table_rows'
+  | 'profile_list'
+  | 'agent_export'
+  | 'agent_backup';
+
+/**
+ * 导入格式识别结果
+ */
+export interface ImportDetection {
+  /** 识别出的格式 */
+  format: ImportFormat;
+
+  /** 文件中的账户记录数 */
+  records: number;
+}
+
+/**
+ * 单条记录的导入结果
+ */
+export interface ImportRecordResult {
+  /** 记录在源文件中的位置或名称 */
+  source: string;
+
+  /** 导入后的备份名 */
+  account: string | null;
+
+  /** 是否导入成功 */
+  imported: boolean;
+
+  /** 失败原因 */
+  error: string | null;
+}
+
+/**
+ * 从其他工具导入的结果
+ */
+export interface ImportReport {
+  /** 识别出的格式 */
+  format: ImportFormat;
+
+  /** 成功导入的数量 */
+  imported: number;
+
+  /** 失败的数量 */
+  failed: number;
+
+  /** 每条记录的结果 */
+  records: ImportRecordResult[];
+}
+
+/**
+ * 备份元数据索引中的一项
+ */
+export interface BackupIndexEntry {
+  /** 备份文件名 */
+  filename: string;
+
+  /** 登录状态中的邮箱（无法读取或解码时为 null） */
+  email: string | null;
+
+  /** 备份时间（文件修改时间，RFC 3339） */
+  backup_time: string;
+
+  /** 文件大小（字节） */
+  size: number;
+
+  /** 备份中保存的键（不含值；索引过期时为空） */
+  keys: string[];
+
+  /** 索引中没有或已过期：邮箱与键摘要暂缺，正在后台重建 */
+  stale: boolean;
+}
@@ -57,3 +57,98 @@ interface Plan {
   upgrade_msg: string
   upgrade_url: string
 }
+
+/**
+ * 会话状态中可修改的字段（未提供的字段保持不变）
+ */
+export interface SessionPatch {
+  access_token?: string
+  id_token?: string
+  expiry_timestamp?: number
+  email?: string
+  plan_name?: string
+  plan_slug?: string
+}
+
+/**
+ * 账户可用模型
+ */
+export interface AvailableModels {
+  /** 账户（当前登录账户为邮箱，备份为备份名） */
+  account: string
+  /** 是否为当前登录的账户 */
+  current: boolean
+  plan_name: string
+  models: string[]
+  recommended: string[]
+  /** 解码失败时的错误信息 */
+  error: string | null
+}
+
+/**
+ * 备份对应的配置档案
+ */
+export interface ProfileInfo {
+  /** 档案名（邮箱） */
+  name: string
+  /** 备份来源的状态数据库路径 */
+  source_path: string
+  /** 备份文件路径 */
+  backup_path: string
+  /** 首次备份时间（RFC 3339） */
+  created_at: string
+  /** 最近一次备份时间（RFC 3339） */
+  last_updated: string
+}
+
+/**
+ * 已保存的应用状态（重启后保留）
+ */
+export interface AppStateSummary {
+  /** 配置档案（按名称排序） */
+  profiles: ProfileInfo[]
+  /** 最近一次切换到的账户 */
+  current_account_id: string | null
+}
+
+/**
+ * 账户备份的历史版本
+ */
+export interface BackupVersion {
+  /** 版本 ID（创建时间，格式 YYYYMMDD-HHMMSSmmm） */
+  version: string
+  /** 创建时间（RFC 3339） */
+  created_at: string
+  /** 文件大小（字节） */
+  size: number
+}
+
+/**
+ * 恢复时单个键的变化
+ * - insert：数据库中没有该键，将新增
+ * - replace：将替换为备份中的值
+ * - delete：将被删除
+ * - unchanged：与备份中的值相同
+ */
+export type RestoreChangeKind = 'insert' | 'replace' | 'delete' | 'unchanged'
+
+/**
+ * 恢复预览中的一项（只返回值的长度，不返回内容）
+ */
+export interface RestoreChange {
+  /** 数据库名（state.vscdb 或 state.vscdb.backup） */
+  database: string
+  key: string
+  kind: RestoreChangeKind
+  /** 当前值的长度（字符数） */
+  current_length: number | null
+  /** 恢复后值的长度（字符数） */
+  new_length: number | null
+}
+
+/**
+ * 恢复预览
+ */
+export interface RestorePreview {
+  changes: RestoreChange[]
+}
@@ -0,0 +1,27 @@
+/**
+ * 应用锁相关类型定义
+ */
+
+/**
+ * 应用锁状态
+ */
+export interface AppLockStatus {
+  /** 是否已设置密码 */
+  enabled: boolean;
+
+  /** 是否处于锁定状态 */
+  locked: boolean;
+
+  /** 空闲多少分钟后自动锁定（0 表示只在启动时锁定） */
+  idle_minutes: number;
+}
+
+/**
+ * `app-lock-changed` 事件负载
+ */
+export interface AppLockPayload {
+  locked: boolean;
+
+  /** 运行 ID */
+  run_id: string;
+}
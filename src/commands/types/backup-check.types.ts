@@ -0,0 +1,83 @@
+/**
+ * 备份检查相关类型定义
+ */
+
+/**
+ * 备份问题类型
+ * - unreadable: 文件无法读取、解析或解密
+ * - missing_secret: 登录状态引用的钥匙串条目不存在
+ * - missing_state: 缺少登录状态
+ * - invalid_state: 登录状态无法解码
+ * - missing_email: 登录状态中没有邮箱
+ */
+export type BackupProblemKind =
+  | 'unreadable'
+  | 'missing_secret'
+  | 'missing_state'
+  | 'invalid_state'
+  | 'missing_email';
+
+/**
+ * 有问题的备份
+ */
+export interface BackupProblem {
+  /** 备份名（文件名去掉扩展名） */
+  name: string;
+
+  path: string;
+
+  kind: BackupProblemKind;
+
+  message: string;
+}
+
+/**
+ * 备份完整性校验结果
+ * - ok: 与记录一致
+ * - untracked: 清单中没有记录（内容可以解析）
+ * - truncated: 文件比记录时小
+ * - corrupted: 内容与记录不一致且无法解析
+ * - modified: 内容与记录不一致但仍可解析（在 Agent 之外被修改）
+ * - missing: 清单中有记录但文件不存在
+ * - unreadable: 文件无法读取
+ */
+export type BackupIntegrityStatus =
+  | 'ok'
+  | 'untracked'
+  | 'truncated'
+  | 'corrupted'
+  | 'modified'
+  | 'missing'
+  | 'unreadable';
+
+/**
+ * 单个备份的校验结果
+ */
+export interface BackupIntegrity {
+  /** 备份名（文件名去掉扩展名） */
+  name: string;
+
+  status: BackupIntegrityStatus;
+
+  /** 记录的文件大小（字节） */
+  expected_size: number | null;
+
+  /** 实际文件大小（字节） */
+  actual_size: number | null;
+
+  /** 问题说明 */
+  message: string | null;
+}
+
+/**
+ * 备份完整性校验报告
+ */
+export interface BackupVerifyReport {
+  /** 检查的备份数（包括已丢失的） */
+  checked: number;
+
+  /** 有问题的备份数（截断、损坏、被修改、丢失或无法读取） */
+  problems: number;
+
+  results: BackupIntegrity[];
+}
@@ -0,0 +1,46 @@
+/**
+ * 浏览器登录桥接相关类型定义
+ */
+
+/**
+ * 浏览器桥接设置
+ */
+export interface BrowserBridgeSettings {
+  /** 是否启用 */
+  enabled: boolean;
+
+  /** 监听端口 */
+  port: number;
+
+  /** 配对令牌（首次启用时生成，填入浏览器扩展） */
+  token: string | null;
+}
+
+/**
+ * 浏览器桥接状态
+ */
+export interface BrowserBridgeStatus {
+  /** 是否启用 */
+  enabled: boolean;
+
+  /** 服务是否正在监听 */
+  running: boolean;
+
+  /** 监听端口 */
+  port: number;
+
+  /** 配对令牌 */
+  token: string | null;
+}
+
+/**
+ * `browser-login-imported` 事件负载
+ */
+export interface BrowserLoginPayload {
+  account: string;
+
+  success: boolean;
+
+  /** 结果说明或错误信息 */
+  message: string;
+}
@@ -0,0 +1,37 @@
+/**
+ * 设备身份相关类型定义
+ */
+
+/**
+ * 本机设备身份（私钥保存在系统钥匙串中）
+ */
+export interface DeviceIdentity {
+  /** 设备 ID（公钥指纹） */
+  device_id: string;
+
+  /** Ed25519 公钥（Base64） */
+  public_key: string;
+
+  /** 设备名称 */
+  device_name: string;
+
+  /** 密钥创建时间（RFC 3339） */
+  created_at: string;
+}
+
+/**
+ * 受信任的签名设备（导入配置文件时接受其签名）
+ */
+export interface TrustedSigner {
+  /** 设备 ID（公钥指纹） */
+  device_id: string;
+
+  /** Ed25519 公钥（Base64） */
+  public_key: string;
+
+  /** 确认信任时文件中声明的设备名称 */
+  device_name: string;
+
+  /** 加入信任列表的时间（RFC 3339） */
+  trusted_at: string;
+}
@@ -0,0 +1,38 @@
+/**
+ * 事件相关类型定义
+ */
+
+/**
+ * 后端发出的事件名（与 src-tauri/src/events.rs 中的登记保持一致）
+ */
+export type AppEventName =
+  | 'account-changed'
+  | 'antigravity-process-changed'
+  | 'database-changed'
+  | 'pipeline-status'
+  | 'startup-progress'
+  | 'slow-operation'
+  | 'tray-switch-account-result'
+  | 'close-confirmation-requested'
+  | 'theme-changed'
+  | 'app-lock-changed'
+  | 'config-integrity-warning'
+  | 'peer-discovered'
+  | 'peer-lost'
+  | 'browser-login-imported'
+  | 'settings-changed'
+  | 'update-download-progress'
+  | 'operation-cancelled';
+
+/**
+ * 事件类型说明
+ */
+export interface EventType {
+  /** 事件名 */
+  name: AppEventName;
+
+  /** 负载类型名 */
+  payload: string;
+
+  description: string;
+}
@@ -0,0 +1,29 @@
+/**
+ * ItemTable 浏览相关类型定义
+ */
+
+/**
+ * ItemTable 中的键
+ */
+export interface ItemTableKey {
+  key: string;
+
+  /** 值的字节数 */
+  size: number;
+}
+
+/**
+ * ItemTable 中的条目
+ */
+export interface ItemTableValue {
+  key: string;
+
+  /** 值（BLOB 按 UTF-8 解码，无效字节替换为 U+FFFD） */
+  value: string;
+
+  /** 原始值是否为 BLOB */
+  binary: boolean;
+
+  /** 值的字节数 */
+  size: number;
+}
@@ -0,0 +1,40 @@
+/**
+ * 本地 HTTP 控制接口相关类型定义
+ */
+
+/**
+ * 本地接口设置
+ */
+export interface LocalApiSettings {
+  /** 是否启用 */
+  enabled: boolean;
+
+  /** 监听端口 */
+  port: number;
+
+  /** 访问令牌（首次启用时生成） */
+  token: string | null;
+
+  /** 外部工具切换账户前是否要求在 Agent 中确认（默认开启） */
+  require_confirmation: boolean;
+}
+
+/**
+ * 本地接口状态
+ */
+export interface LocalApiStatus {
+  /** 是否启用 */
+  enabled: boolean;
+
+  /** 服务是否正在监听 */
+  running: boolean;
+
+  /** 监听端口 */
+  port: number;
+
+  /** 访问令牌 */
+  token: string | null;
+
+  /** 外部工具切换账户前是否要求在 Agent 中确认 */
+  require_confirmation: boolean;
+}
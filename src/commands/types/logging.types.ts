@@ -38,3 +38,173 @@ export interface FrontendLogEntry {
   /** 会话 ID */
   sessionId?: string;
 }
+
+/**
+ * 日志查询条件（字段均可省略）
+ */
+export interface LogQueryFilter {
+  /** 最低日志级别 */
+  level?: 'trace' | 'debug' | 'info' | 'warn' | 'error';
+
+  /** 目标模块前缀，例如 `window` */
+  target?: string;
+
+  /** 起始时间（毫秒时间戳） */
+  since_ms?: number;
+
+  /** 结束时间（毫秒时间戳） */
+  until_ms?: number;
+
+  /** 关键字（不区分大小写） */
+  text?: string;
+
+  /** 运行 ID（精确匹配） */
+  run_id?: string;
+
+  /** 跳过的条数 */
+  offset?: number;
+
+  /** 每页条数（默认 200，最大 1000） */
+  limit?: number;
+}
+
+/**
+ * 结构化日志条目
+ */
+export interface LogEntry {
+  /** RFC 3339 格式的时间 */
+  timestamp: string;
+
+  /** 毫秒时间戳 */
+  timestamp_ms: number;
+
+  /** 日志级别 */
+  level: string;
+
+  /** 目标模块 */
+  target: string;
+
+  /** 日志消息 */
+  message: string;
+
+  /** 其余结构化字段 */
+  fields: Record<string, unknown>;
+
+  /** 写入该条日志的运行 ID（旧版本日志为 null） */
+  run_id: string | null;
+}
+
+/**
+ * 日志查询结果
+ */
+export interface LogQueryResult {
+  /** 当前页的条目（时间倒序） */
+  entries: LogEntry[];
+
+  /** 符合条件的总条数 */
+  total: number;
+
+  /** 是否还有更多条目 */
+  has_more: boolean;
+}
+
+/**
+ * 崩溃报告
+ */
+export interface CrashReport {
+  /** 报告 ID */
+  id: string;
+
+  /** 崩溃时间（RFC 3339） */
+  created_at: string;
+
+  /** 应用版本 */
+  app_version: string;
+
+  /** 操作系统 */
+  os: string;
+
+  /** CPU 架构 */
+  arch: string;
+
+  /** 发生 panic 的线程名 */
+  thread: string;
+
+  /** panic 信息 */
+  message: string;
+
+  /** 源码位置 */
+  location: string | null;
+
+  /** 回溯 */
+  backtrace: string;
+
+  /** 崩溃前最近的日志行 */
+  recent_logs: string[];
+}
+
+/**
+ * 单个命令的性能指标
+ */
+export interface CommandMetrics {
+  /** 命令名 */
+  command: string;
+
+  /** 调用次数 */
+  count: number;
+
+  /** 失败次数 */
+  error_count: number;
+
+  /** 平均耗时（毫秒） */
+  avg_ms: number;
+
+  /** p95 耗时（毫秒） */
+  p95_ms: number;
+
+  /** 最大耗时（毫秒） */
+  max_ms: number;
+}
+
+/**
+ * 审计的操作类型
+ */
+export type AuditOperation =
+  | 'switch'
+  | 'backup'
+  | 'restore'
+  | 'sign_in'
+  | 'cleanup'
+  | 'delete_backup'
+  | 'clear_backups'
+  | 'import_backups'
+  | 'path_change'
+  | 'hook'
+  | 'item_table_edit'
+  | 'regenerate_machine_ids'
+  | 'workspace_cleanup'
+  | 'trash_backups'
+  | 'state_rollback';
+
+/**
+ * 操作审计日志条目
+ */
+export interface AuditEntry {
+  /** 操作时间（RFC 3339） */
+  timestamp: string;
+
+  /** 操作类型 */
+  operation: AuditOperation;
+
+  /** 操作对象（账户名、路径等） */
+  subject: string | null;
+
+  /** 操作结果 */
+  outcome: 'success' | 'failure';
+
+  /** 耗时（毫秒） */
+  duration_ms: number;
+
+  /** 结果说明或错误信息 */
+  detail: string | null;
+}
@@ -0,0 +1,26 @@
+/**
+ * Antigravity 设备标识相关类型定义
+ */
+
+/**
+ * Antigravity 的设备标识（未找到的为 null）
+ */
+export interface MachineIds {
+  /** storage.json 中的 telemetry.machineId */
+  machine_id: string | null;
+
+  /** storage.json 中的 telemetry.macMachineId */
+  mac_machine_id: string | null;
+
+  /** storage.json 中的 telemetry.devDeviceId */
+  dev_device_id: string | null;
+
+  /** storage.json 中的 telemetry.sqmId */
+  sqm_id: string | null;
+
+  /** 状态数据库中的 storage.serviceMachineId */
+  service_machine_id: string | null;
+
+  /** machineid 文件内容 */
+  machine_id_file: string | null;
+}
@@ -0,0 +1,54 @@
+/**
+ * 局域网对等发现相关类型定义
+ */
+
+/**
+ * 对等发现设置
+ */
+export interface PeerDiscoverySettings {
+  /** 是否启用（mDNS 广播并浏览） */
+  enabled: boolean;
+
+  /** 广播的局域网传输端口 */
+  port: number;
+}
+
+/**
+ * 发现的对端实例
+ */
+export interface Peer {
+  /** 对端设备 ID */
+  device_id: string;
+
+  /** 对端主机名 */
+  device_name: string;
+
+  /** 对端 Agent 版本 */
+  version: string;
+
+  /** 可达地址 */
+  addresses: string[];
+
+  /** 局域网传输端口 */
+  port: number;
+
+  /** 最近一次解析到的时间（RFC 3339） */
+  last_seen: string;
+}
+
+/**
+ * 对等发现状态
+ */
+export interface PeerDiscoveryStatus {
+  /** 是否启用 */
+  enabled: boolean;
+
+  /** mDNS 服务是否正在运行 */
+  running: boolean;
+
+  /** 广播的局域网传输端口 */
+  port: number;
+
+  /** 已发现的对端 */
+  peers: Peer[];
+}
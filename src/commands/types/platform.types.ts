@@ -52,3 +52,249 @@ export interface PathConfig {
   /** 可执行文件路径 */
   executablePath?: string | null;
 }
+
+/**
+ * 自检项结果
+ */
+export type SelfTestStatus = 'passed' | 'failed' | 'skipped';
+
+/**
+ * 单项自检
+ */
+export interface SelfTestCheck {
+  /** 检查项 ID */
+  id:
+    | 'config_dir_writable'
+    | 'database_readable'
+    | 'executable_launchable'
+    | 'backup_dir_writable'
+    | 'jetski_state_decodable';
+
+  /** 检查项名称 */
+  label: string;
+
+  /** 结果 */
+  status: SelfTestStatus;
+
+  /** 结果说明或失败原因 */
+  message: string;
+}
+
+/**
+ * 自检报告
+ */
+export interface SelfTestReport {
+  /** 所有检查均未失败（跳过不算失败） */
+  passed: boolean;
+
+  /** 逐项结果 */
+  checks: SelfTestCheck[];
+}
+
+/**
+ * 性能基准测试结论
+ * - normal: 在基线的 3 倍以内
+ * - slow: 明显慢于基线
+ * - failed: 测试过程中出错
+ * - skipped: 缺少测试数据（例如未登录时没有真实登录状态）
+ */
+export type BenchmarkStatus = 'normal' | 'slow' | 'failed' | 'skipped';
+
+/**
+ * 单项性能基准测试结果
+ */
+export interface BenchmarkResult {
+  /** 测试项 ID */
+  id: 'backup_write' | 'restore_write' | 'database_read' | 'proto_decode';
+
+  /** 测试项名称 */
+  label: string;
+
+  /** 数据来源：固定大小的合成数据 / 当前 Antigravity 数据库中的真实数据 */
+  source: 'synthetic' | 'real';
+
+  status: BenchmarkStatus;
+
+  /** 实际重复次数 */
+  iterations: number;
+
+  /** 数据大小（字节） */
+  bytes: number;
+
+  /** 耗时中位数（毫秒） */
+  median_ms: number | null;
+
+  /** 最长耗时（毫秒） */
+  max_ms: number | null;
+
+  /** 参考基线（毫秒） */
+  baseline_ms: number;
+
+  /** 中位数与基线之比 */
+  ratio: number | null;
+
+  /** 跳过或失败的原因 */
+  message: string | null;
+}
+
+/**
+ * 性能基准测试报告
+ */
+export interface BenchmarkReport {
+  /** 是否有偏慢的测试项 */
+  slow: boolean;
+
+  /** 全部测试的总耗时（毫秒） */
+  total_ms: number;
+
+  /** 逐项结果 */
+  results: BenchmarkResult[];
+}
+
+/**
+ * 可执行文件检测结果
+ */
+export interface ExecutableDetection {
+  /** 可执行文件路径（未找到时为 null） */
+  path: string | null;
+
+  /** 来源：路径配置中已保存 / 本次自动检测 */
+  source: 'configured' | 'detected' | null;
+
+  /** 是否位于网络路径 */
+  is_network_path: boolean;
+
+  /** 结果说明或失败原因 */
+  message: string;
+}
+
+/**
+ * 数据目录检测结果
+ */
+export interface DataDirDetection {
+  /** 包含 state.vscdb 的数据目录（未找到时为 null） */
+  path: string | null;
+
+  /** 是否位于网络路径 */
+  is_network_path: boolean;
+
+  /** 状态数据库能否读取 */
+  database_readable: boolean;
+
+  /** 结果说明或失败原因 */
+  message: string;
+}
+
+/**
+ * 目录权限检查结果
+ */
+export interface PermissionCheck {
+  config_dir_writable: boolean;
+
+  backup_dir_writable: boolean;
+
+  /** 配置目录权限检查（只检查，不修复） */
+  config_dir_permissions: HardenReport;
+
+  /** 不可写的目录及原因 */
+  errors: string[];
+}
+
+/**
+ * 当前登录探测结果
+ */
+export interface LoginProbe {
+  /** Antigravity 当前是否已登录 */
+  logged_in: boolean;
+
+  /** 当前登录的邮箱 */
+  email: string | null;
+
+  /** 探测失败的原因 */
+  error: string | null;
+}
+
+/**
+ * 首次运行检测报告
+ */
+export interface OnboardingReport {
+  executable: ExecutableDetection;
+
+  data_dir: DataDirDetection;
+
+  permissions: PermissionCheck;
+
+  login: LoginProbe;
+
+  /** 检测结果是否已写入路径配置 */
+  persisted: boolean;
+
+  /** 可执行文件与数据目录均已找到且目录可写 */
+  ready: boolean;
+}
+
+/**
+ * 配置目录权限检查与修复结果
+ */
+export interface HardenReport {
+  /** 检查的目录 */
+  path: string;
+
+  /** 发现的问题（权限过宽的路径及原因） */
+  issues: string[];
+
+  /** 已修复的路径数 */
+  repaired: number;
+
+  /** 修复失败的路径及原因 */
+  errors: string[];
+}
+
+/**
+ * 配置文件完整性问题
+ */
+export interface IntegrityWarning {
+  /** 文件名 */
+  file: string;
+
+  /** 被外部修改 / 无法解析 / 丢失 */
+  issue: 'modified' | 'corrupted' | 'missing';
+
+  /** 是否已从最近的正常副本恢复 */
+  restored: boolean;
+}
+
+/**
+ * 环境变量脚本格式
+ */
+export type ShellFormat = 'posix' | 'fish' | 'powershell' | 'cmd';
+
+/**
+ * 磁盘占用树节点
+ */
+export interface DiskUsageNode {
+  name: string;
+
+  /** 路径（汇总文件节点为所在目录） */
+  path: string;
+
+  /** 占用字节数（包含所有子节点） */
+  size: number;
+
+  /** 子节点（按大小降序） */
+  children: DiskUsageNode[];
+}
+
+/**
+ * 磁盘占用报告
+ */
+export interface DiskUsageReport {
+  /** Antigravity 数据目录（未检测到时为 null） */
+  antigravity: DiskUsageNode | null;
+
+  /** Agent 配置目录（备份、日志、配置） */
+  agent: DiskUsageNode;
+
+  /** 合计字节数 */
+  total: number;
+}
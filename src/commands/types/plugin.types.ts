@@ -0,0 +1,25 @@
+/**
+ * 插件相关类型定义
+ */
+
+import type { HookEvent } from './settings.types.ts';
+
+/**
+ * 已加载的插件
+ */
+export interface PluginInfo {
+  /** 插件名（脚本文件名，不含扩展名） */
+  name: string;
+
+  /** 脚本路径 */
+  path: string;
+
+  /** 自定义命令名（不含 `command_` 前缀） */
+  commands: string[];
+
+  /** 处理的事件 */
+  handlers: HookEvent[];
+
+  /** 编译失败时的错误信息 */
+  error: string | null;
+}
@@ -0,0 +1,17 @@
+/**
+ * 快速切换窗口相关类型定义
+ */
+
+/**
+ * 快速切换列表中的账户
+ */
+export interface QuickSwitchAccount {
+  /** 账户邮箱 */
+  email: string;
+
+  /** 是否置顶 */
+  pinned: boolean;
+
+  /** 最近备份时间（毫秒时间戳） */
+  last_backup_ms: number;
+}
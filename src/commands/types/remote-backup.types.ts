@@ -0,0 +1,75 @@
+/**
+ * 远程备份（S3 兼容对象存储）相关类型定义
+ */
+
+/**
+ * S3 远程备份配置
+ */
+export interface S3BackupSettings {
+  /** 端点（如 `https://s3.us-east-1.amazonaws.com` 或 MinIO 地址），只允许 HTTPS */
+  endpoint: string;
+
+  /** 区域（MinIO 一般为 `us-east-1`） */
+  region: string;
+
+  bucket: string;
+
+  /** 对象键前缀（如 `antigravity/`），为空时放在桶根目录 */
+  prefix: string;
+
+  access_key_id: string;
+
+  /** 使用路径风格地址（`<端点>/<桶>`，MinIO 通常需要）；否则使用虚拟主机风格 */
+  path_style: boolean;
+}
+
+/**
+ * 远程备份配置状态
+ */
+export interface S3BackupStatus {
+  settings: S3BackupSettings | null;
+
+  /** 钥匙串中是否已保存 Secret Access Key */
+  secret_stored: boolean;
+}
+
+/**
+ * 本地与远程备份对比中的一项
+ */
+export interface RemoteBackupEntry {
+  /** 备份名（账户邮箱） */
+  name: string;
+
+  /** 本地是否存在 */
+  local: boolean;
+
+  /** 远程是否存在 */
+  remote: boolean;
+
+  /** 本地备份修改时间（RFC 3339） */
+  local_modified: string | null;
+
+  /** 远程对象修改时间（RFC 3339） */
+  remote_modified: string | null;
+
+  /** 远程对象大小（字节） */
+  remote_size: number | null;
+}
+
+/**
+ * 推送失败的备份
+ */
+export interface RemotePushFailure {
+  name: string;
+
+  error: string;
+}
+
+/**
+ * 推送结果
+ */
+export interface RemotePushReport {
+  uploaded: number;
+
+  failed: RemotePushFailure[];
+}
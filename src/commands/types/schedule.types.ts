@@ -0,0 +1,21 @@
+/**
+ * 计划任务相关类型定义
+ */
+
+/**
+ * 计划任务
+ */
+export interface ScheduledJob {
+  /** 稳定的任务 ID（用作日历条目 UID） */
+  id: string;
+
+  name: string;
+
+  description: string;
+
+  /** 下一次运行时间（RFC 3339） */
+  next_run: string;
+
+  /** 运行间隔（秒） */
+  interval_secs: number;
+}
@@ -2,6 +2,12 @@
  * 设置相关类型定义
  */
 
+import type { BrowserBridgeSettings } from './browser-bridge.types.ts';
+import type { LocalApiSettings } from './local-api.types.ts';
+import type { PeerDiscoverySettings } from './peer-discovery.types.ts';
+import type { S3BackupSettings } from './remote-backup.types.ts';
+import type { UpdateSettings } from './update.types.ts';
+
 /**
  * 应用设置
  */
@@ -11,4 +17,194 @@ export interface AppSettings {
 
   /** 静默启动是否启用 */
   silent_start_enabled: boolean;
+
+  /** 最小化时是否隐藏到托盘 */
+  minimize_to_tray_enabled: boolean;
+
+  /** 账户操作进行中退出前是否确认 */
+  confirm_exit_during_operations: boolean;
+
+  /** 慢操作警告阈值（毫秒） */
+  slow_operation_threshold_ms: number;
+
+  /** 日志保留策略 */
+  log_retention: LogRetentionSettings;
+
+  /** 是否在本地保存离线错误报告 */
+  error_reports_enabled: boolean;
+
+  /** 是否将备份中的登录状态保存到系统钥匙串 */
+  keychain_enabled: boolean;
+
+  /** 删除备份前是否先覆盖文件内容 */
+  secure_delete_enabled: boolean;
+
+  /** 是否记录登录状态变更日志 */
+  state_journal_enabled: boolean;
+
+  /** 新写入的备份使用的压缩格式 */
+  backup_compression: BackupCompression;
+
+  /** 敏感操作前是否要求系统身份验证 */
+  require_os_auth: boolean;
+
+  /** 安全模式：拒绝执行破坏性命令 */
+  safe_mode: boolean;
+
+  /** 隐私模式：日志、事件与审计日志中的邮箱/账户名替换为哈希 */
+  privacy_mode: boolean;
+
+  /** 本机测量的 Argon2id 参数（未测量时为 null） */
+  kdf: KdfSettings | null;
+
+  /** 远程功能使用的代理地址（null 表示使用系统代理环境变量） */
+  http_proxy: string | null;
+
+  /** 本地 HTTP 控制接口 */
+  local_api: LocalApiSettings;
+
+  /** Webhook 通知 */
+  webhooks: WebhookConfig[];
+
+  /** 事件钩子脚本 */
+  hooks: HookConfig[];
+
+  /** 局域网对等发现（mDNS） */
+  peer_discovery: PeerDiscoverySettings;
+
+  /** 浏览器登录桥接（WebSocket） */
+  browser_bridge: BrowserBridgeSettings;
+
+  /** 界面语言（后端返回的错误与结果消息使用该语言） */
+  locale: Locale;
+
+  /** 应用更新（通道与安装时机） */
+  update: UpdateSettings;
+
+  /** S3 兼容对象存储远程备份（Secret Access Key 保存在钥匙串中） */
+  s3_backup: S3BackupSettings | null;
+}
+
+/**
+ * 备份压缩格式（none 为普通 JSON；压缩与未压缩的备份可以混用）
+ */
+export type BackupCompression = 'none' | 'gzip' | 'zstd';
+
+/**
+ * 界面语言
+ */
+export type Locale = 'zh' | 'en';
+
+/**
+ * 钩子事件
+ */
+export type HookEvent = 'account-changed' | 'backup-created' | 'antigravity-stopped';
+
+/**
+ * 事件钩子配置
+ */
+export interface HookConfig {
+  /** 触发事件 */
+  event: HookEvent;
+
+  /** 脚本或可执行文件路径 */
+  command: string;
+
+  /** 命令行参数 */
+  args: string[];
+
+  /** 超时（秒），null 时为 30 秒 */
+  timeout_secs: number | null;
+
+  /** 是否启用 */
+  enabled: boolean;
+}
+
+/**
+ * Webhook 事件
+ */
+export type WebhookEvent = 'switch_complete' | 'backup_created' | 'logged_out';
+
+/**
+ * Webhook 配置
+ */
+export interface WebhookConfig {
+  /** 接收地址（HTTPS） */
+  url: string;
+
+  /** 签名密钥（HMAC-SHA256，为 null 时不签名） */
+  secret: string | null;
+
+  /** 订阅的事件，为空表示全部事件 */
+  events: WebhookEvent[];
+
+  /** 是否启用 */
+  enabled: boolean;
+}
+
+/**
+ * Argon2id 参数
+ */
+export interface KdfSettings {
+  /** 内存（KiB） */
+  m_cost: number;
+
+  /** 迭代次数 */
+  t_cost: number;
+
+  /** 并行度 */
+  p_cost: number;
+}
+
+/**
+ * 日志保留设置（字段为 null 表示不限制）
+ */
+export interface LogRetentionSettings {
+  /** 最长保留天数 */
+  max_age_days: number | null;
+
+  /** 日志总大小上限（MB） */
+  max_total_size_mb: number | null;
+}
+
+/**
+ * 日志清理结果
+ */
+export interface LogCleanupResult {
+  /** 删除的分段数 */
+  deleted_files: number;
+
+  /** 释放的字节数 */
+  freed_bytes: number;
+}
+
+/**
+ * `slow-operation` 事件负载
+ */
+export interface SlowOperationPayload {
+  /** 操作名，例如 `pipeline::switch`、`db::open` */
+  operation: string;
+
+  /** 实际耗时（毫秒） */
+  duration_ms: number;
+
+  /** 触发时的阈值（毫秒） */
+  threshold_ms: number;
+
+  /** 运行 ID */
+  run_id: string;
+}
+
+/**
+ * `settings-changed` 事件负载
+ */
+export interface SettingsChangedPayload {
+  /** 被修改的配置文件 */
+  file: 'app_settings' | 'antigravity_path';
+
+  /** 修改来源：应用内命令或外部编辑文件 */
+  source: 'app' | 'external';
+
+  /** 发生变化的顶层字段 */
+  changed_keys: string[];
 }
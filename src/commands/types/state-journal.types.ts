@@ -0,0 +1,44 @@
+/**
+ * 登录状态变更日志相关类型定义
+ */
+
+/**
+ * 指定时刻单个键的状态
+ */
+export interface ReconstructedKey {
+  key: string;
+
+  /** 该时刻数据库中是否有这个键 */
+  present: boolean;
+
+  /** 值的长度（字符数） */
+  length: number;
+
+  /** 值内容（含凭据的键不返回） */
+  value: string | null;
+}
+
+/**
+ * 指定时刻的关键键状态
+ */
+export interface ReconstructedState {
+  /** 请求的时间（RFC 3339） */
+  timestamp: string;
+
+  /** 作为重放基准的快照时间 */
+  base_snapshot_at: string;
+
+  /** 重放的变更条数 */
+  replayed_changes: number;
+
+  /** 该时刻之前最后一次变化的时间 */
+  last_change_at: string;
+
+  /** 该时刻登录的账户邮箱 */
+  email: string | null;
+
+  keys: ReconstructedKey[];
+
+  /** 写回结果说明（只查看时为 null） */
+  restore_message: string | null;
+}
@@ -0,0 +1,73 @@
+/**
+ * 应用更新相关类型定义
+ */
+
+/**
+ * 更新通道
+ */
+export type UpdateChannel = 'stable' | 'beta';
+
+/**
+ * 更新设置
+ */
+export interface UpdateSettings {
+  /** 更新通道（决定检查的更新地址） */
+  channel: UpdateChannel;
+
+  /** 已下载但未手动安装的更新在应用退出时自动安装 */
+  install_update_later: boolean;
+}
+
+/**
+ * 可用更新
+ */
+export interface UpdateInfo {
+  /** 新版本号 */
+  version: string;
+
+  /** 当前版本号 */
+  current_version: string;
+
+  /** 发布时间（RFC 3339） */
+  date: string | null;
+
+  /** 更新说明 */
+  notes: string | null;
+
+  /** 安装包大小（字节），服务器未返回长度时为 null */
+  size: number | null;
+
+  /** 检查时使用的通道 */
+  channel: UpdateChannel;
+}
+
+/**
+ * 更新状态
+ */
+export interface UpdateStatus {
+  /** 已检查到、尚未下载的版本 */
+  available: string | null;
+
+  /** 已下载、尚未安装的版本 */
+  downloaded: string | null;
+
+  /** 已下载的更新是否会在应用退出时安装 */
+  install_on_exit: boolean;
+}
+
+/**
+ * `update-download-progress` 事件负载
+ */
+export interface UpdateDownloadProgressPayload {
+  /** 正在下载的版本 */
+  version: string;
+
+  /** 已下载字节数 */
+  downloaded: number;
+
+  /** 总字节数（服务器未返回长度时为 null） */
+  total: number | null;
+
+  /** 是否已下载完成 */
+  finished: boolean;
+}
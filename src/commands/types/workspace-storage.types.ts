@@ -0,0 +1,39 @@
+/**
+ * 工作区存储相关类型定义
+ */
+
+/**
+ * 工作区状态目录
+ */
+export interface WorkspaceStorageEntry {
+  /** 目录名（工作区哈希） */
+  id: string;
+
+  path: string;
+
+  /** 工作区文件夹或 `.code-workspace` 文件的 URI */
+  uri: string | null;
+
+  /** 本地工作区是否仍存在（远程工作区或无法判断时为 null） */
+  exists: boolean | null;
+
+  /** 最后使用时间（RFC 3339） */
+  last_used: string;
+
+  /** 占用字节数 */
+  size: number;
+}
+
+/**
+ * 工作区存储清理结果
+ */
+export interface WorkspaceCleanupResult {
+  /** 已删除的目录数 */
+  deleted: number;
+
+  /** 释放的字节数 */
+  freed_bytes: number;
+
+  /** 删除失败的目录（目录名: 错误） */
+  failed: string[];
+}
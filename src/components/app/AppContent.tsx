@@ -3,6 +3,7 @@ import BusinessUserDetail from "@/components/business/AccountDetailModal.tsx";
 import {useAntigravityAccount, useCurrentAntigravityAccount} from "@/modules/use-antigravity-account.ts";
 import {useAccountAdditionData, UserTier} from "@/modules/use-account-addition-data.ts";
 import {useTrayMenu} from "@/hooks/use-tray-menu.ts";
+import {useCloseConfirmation} from "@/hooks/use-close-confirmation.ts";
 import {Modal} from 'antd';
 import toast from 'react-hot-toast';
 import {maskEmail} from "@/lib/string-masking.ts";
@@ -32,6 +33,9 @@ export function AppContent() {
   // 初始化托盘菜单更新
   useTrayMenu();
 
+  // 操作进行中退出时弹出确认
+  useCloseConfirmation();
+
   // 组件挂载时获取用户列表
   useEffect(() => {
     const loadUsers = async () => {
@@ -7,6 +7,10 @@ import {logger} from '@/lib/logger.ts';
 import {Modal} from "antd";
 import {AccountSessionListAccountItem} from "@/components/business/AccountSessionList.tsx";
 import {Avatar} from "@/components/ui/avatar.tsx";
+import {PlatformCommands} from "@/commands/PlatformCommands.ts";
+
+// 复制后需要自动清除的敏感字段
+const SECRET_FIELDS = ['apiKey'];
 
 interface BusinessUserDetailProps {
   isOpen: boolean;
@@ -21,10 +25,14 @@ const BusinessUserDetail: React.FC<BusinessUserDetailProps> = ({
 }) => {
   const [copiedField, setCopiedField] = useState<string | null>(null);
 
-  // 复制到剪贴板功能
+  // 复制到剪贴板功能（API Key 等敏感字段到期后自动从剪贴板清除）
   const copyToClipboard = async (text: string, fieldName: string) => {
     try {
-      await navigator.clipboard.writeText(account[fieldName]);
+      if (SECRET_FIELDS.includes(fieldName)) {
+        await PlatformCommands.copySecretToClipboard(account[fieldName]);
+      } else {
+        await navigator.clipboard.writeText(account[fieldName]);
+      }
       setCopiedField(fieldName);
       setTimeout(() => setCopiedField(null), 2000);
     } catch (error) {
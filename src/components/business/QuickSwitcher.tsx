@@ -0,0 +1,114 @@
+import React, {useEffect, useMemo, useState} from 'react';
+import {Pin} from 'lucide-react';
+import {cn} from '@/lib/utils.ts';
+import {maskEmail} from '@/lib/string-masking.ts';
+import {logger} from '@/lib/logger.ts';
+import {getErrorCode, getErrorMessage} from '@/lib/agent-error.ts';
+import {QuickSwitcherCommands} from '@/commands/QuickSwitcherCommands.ts';
+import type {QuickSwitchAccount} from '@/commands/types/quick-switcher.types.ts';
+
+/**
+ * 快速切换窗口：输入过滤账户，方向键选择，回车切换，Esc 关闭
+ */
+const QuickSwitcher: React.FC = () => {
+  const [accounts, setAccounts] = useState<QuickSwitchAccount[]>([]);
+  const [query, setQuery] = useState('');
+  const [selectedIndex, setSelectedIndex] = useState(0);
+
+  useEffect(() => {
+    QuickSwitcherCommands.listAccounts()
+      .then(setAccounts)
+      .catch((error) => {
+        logger.error('加载快速切换账户失败', {
+          module: 'QuickSwitcher',
+          action: 'load_accounts_failed',
+          error: error instanceof Error ? error.message : String(error)
+        });
+      });
+  }, []);
+
+  const filtered = useMemo(() => {
+    const keyword = query.trim().toLowerCase();
+    return keyword
+      ? accounts.filter((account) => account.email.toLowerCase().includes(keyword))
+      : accounts;
+  }, [accounts, query]);
+
+  useEffect(() => {
+    setSelectedIndex(0);
+  }, [query]);
+
+  const handleSwitch = async (account: QuickSwitchAccount | undefined) => {
+    if (!account) return;
+    try {
+      await QuickSwitcherCommands.switchAccount(account.email);
+    } catch (error) {
+      logger.error('快速切换账户失败', {
+        module: 'QuickSwitcher',
+        action: 'switch_failed',
+        code: getErrorCode(error),
+        error: getErrorMessage(error)
+      });
+    }
+  };
+
+  const handleKeyDown = (event: React.KeyboardEvent) => {
+    switch (event.key) {
+      case 'ArrowDown':
+        event.preventDefault();
+        setSelectedIndex((index) => Math.min(index + 1, filtered.length - 1));
+        break;
+      case 'ArrowUp':
+        event.preventDefault();
+        setSelectedIndex((index) => Math.max(index - 1, 0));
+        break;
+      case 'Enter':
+        event.preventDefault();
+        handleSwitch(filtered[selectedIndex]);
+        break;
+      case 'Escape':
+        event.preventDefault();
+        QuickSwitcherCommands.close();
+        break;
+    }
+  };
+
+  return (
+    <div
+      className="h-screen flex flex-col bg-white dark:bg-gray-900 border border-gray-200 dark:border-gray-800 rounded-lg overflow-hidden"
+      onKeyDown={handleKeyDown}
+    >
+      <input
+        autoFocus
+        value={query}
+        onChange={(event) => setQuery(event.target.value)}
+        placeholder="搜索账户..."
+        className="px-4 py-3 text-sm bg-transparent border-b border-gray-100 dark:border-gray-800 outline-none text-gray-900 dark:text-gray-100"
+      />
+      <div className="flex-1 overflow-y-auto p-1">
+        {filtered.length === 0 ? (
+          <div className="p-4 text-center text-xs text-gray-400">没有可切换的账户</div>
+        ) : (
+          filtered.map((account, index) => (
+            <div
+              key={account.email}
+              onMouseEnter={() => setSelectedIndex(index)}
+              onClick={() => handleSwitch(account)}
+              className={cn(
+                "flex items-center justify-between px-3 py-2 rounded-md cursor-pointer text-sm",
+                index === selectedIndex
+                  ? "bg-blue-600 text-white"
+                  : "text-gray-700 dark:text-gray-300"
+              )}
+            >
+              <span className="truncate">{maskEmail(account.email)}</span>
+              {account.pinned && <Pin className="h-3.5 w-3.5 shrink-0"/>}
+            </div>
+          ))
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default QuickSwitcher;
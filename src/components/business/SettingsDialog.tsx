@@ -1,5 +1,5 @@
 import React, {useEffect, useState} from 'react';
-import {FileCode, Monitor, Settings, VolumeX} from 'lucide-react';
+import {FileCode, Minimize2, Monitor, Settings, ShieldAlert, VolumeX} from 'lucide-react';
 import {open} from '@tauri-apps/plugin-dialog';
 import {getVersion} from '@tauri-apps/api/app';
 import {BaseButton} from '@/components/base-ui/BaseButton';
@@ -32,11 +32,21 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
   const [isSilentStartEnabled, setIsSilentStartEnabled] = useState(false);
   const [isSilentStartLoading, setIsSilentStartLoading] = useState(false);
 
+  // 最小化到托盘状态
+  const [isMinimizeToTrayEnabled, setIsMinimizeToTrayEnabled] = useState(false);
+  const [isMinimizeToTrayLoading, setIsMinimizeToTrayLoading] = useState(false);
+
+  // 退出确认状态
+  const [isConfirmExitEnabled, setIsConfirmExitEnabled] = useState(false);
+  const [isConfirmExitLoading, setIsConfirmExitLoading] = useState(false);
+
   useEffect(() => {
     if (isOpen) {
       loadCurrentPaths();
       loadSystemTraySettings();
       loadSilentStartSettings();
+      loadMinimizeToTraySettings();
+      loadConfirmExitSettings();
       loadAppVersion();
     }
   }, [isOpen]);
@@ -115,6 +125,60 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
     }
   };
 
+  const loadMinimizeToTraySettings = async () => {
+    try {
+      const minimizeToTrayEnabled = await SettingsCommands.isMinimizeToTrayEnabled();
+      setIsMinimizeToTrayEnabled(minimizeToTrayEnabled);
+    } catch (error) {
+      setIsMinimizeToTrayEnabled(false);
+    }
+  };
+
+  const handleMinimizeToTrayToggle = async () => {
+    if (!isSystemTrayEnabled) {
+      await handleSystemTrayToggle();
+    }
+
+    setIsMinimizeToTrayLoading(true);
+    try {
+      const result = await SettingsCommands.saveMinimizeToTrayState(!isMinimizeToTrayEnabled);
+      setIsMinimizeToTrayEnabled(result);
+    } catch (error) {
+      logger.error('切换最小化到托盘状态失败', {
+        module: 'SettingsDialog',
+        action: 'toggle_minimize_to_tray_failed',
+        error: error instanceof Error ? error.message : String(error)
+      });
+    } finally {
+      setIsMinimizeToTrayLoading(false);
+    }
+  };
+
+  const loadConfirmExitSettings = async () => {
+    try {
+      const confirmExitEnabled = await SettingsCommands.isConfirmExitEnabled();
+      setIsConfirmExitEnabled(confirmExitEnabled);
+    } catch (error) {
+      setIsConfirmExitEnabled(false);
+    }
+  };
+
+  const handleConfirmExitToggle = async () => {
+    setIsConfirmExitLoading(true);
+    try {
+      const result = await SettingsCommands.saveConfirmExitState(!isConfirmExitEnabled);
+      setIsConfirmExitEnabled(result);
+    } catch (error) {
+      logger.error('切换退出确认状态失败', {
+        module: 'SettingsDialog',
+        action: 'toggle_confirm_exit_failed',
+        error: error instanceof Error ? error.message : String(error)
+      });
+    } finally {
+      setIsConfirmExitLoading(false);
+    }
+  };
+
   
   const handleBrowseExecPath = async () => {
     try {
@@ -202,6 +266,24 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
             onChange={handleSilentStartToggle}
             isLoading={isSilentStartLoading}
           />
+
+          <SettingToggle
+            icon={<Minimize2 className="h-4 w-4 text-green-500"/>}
+            title="最小化到托盘"
+            description="最小化窗口时隐藏到托盘"
+            checked={isMinimizeToTrayEnabled}
+            onChange={handleMinimizeToTrayToggle}
+            isLoading={isMinimizeToTrayLoading}
+          />
+
+          <SettingToggle
+            icon={<ShieldAlert className="h-4 w-4 text-orange-500"/>}
+            title="退出确认"
+            description="账户操作进行中退出时先确认"
+            checked={isConfirmExitEnabled}
+            onChange={handleConfirmExitToggle}
+            isLoading={isConfirmExitLoading}
+          />
         </div>
 
         <div className="h-px bg-gray-100 dark:bg-gray-800"/>
@@ -77,6 +77,7 @@ const UpdateBadge: React.FC<UpdateBadgeProps> = ({
     error: internalError,
     startDownload,
     installAndRelaunch,
+    installOnExit,
     dismissUpdate,
   } = useUpdateChecker(state == null ? autoCheck : false); // 受控时禁用自动检查
 
@@ -112,6 +113,21 @@ const UpdateBadge: React.FC<UpdateBadgeProps> = ({
     }
   };
 
+  // 处理推迟到退出时安装
+  const handleInstallOnExit = async () => {
+    try {
+      await installOnExit();
+      toast.success('更新将在退出应用时安装');
+      setIsUpdateDialogOpen(false);
+    } catch (error) {
+      logger.error('推迟安装失败', {
+        module: 'AppDock',
+        action: 'defer_update_install_failed',
+        error: error instanceof Error ? error.message : String(error)
+      });
+    }
+  };
+
   // 处理安装并重启
   const handleInstallAndRelaunch = async () => {
     try {
@@ -223,6 +239,7 @@ const UpdateBadge: React.FC<UpdateBadgeProps> = ({
         error={updateError}
         onDownload={handleStartDownload}
         onInstall={handleInstallAndRelaunch}
+        onInstallLater={handleInstallOnExit}
         onDismiss={() => {
           dismissUpdate();
           setIsUpdateDialogOpen(false);
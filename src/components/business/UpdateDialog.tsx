@@ -14,6 +14,8 @@ interface BusinessUpdateDialogProps {
   error: string | null;
   onDownload: () => void;
   onInstall: () => void;
+  /** 推迟到应用退出时安装（不传时“稍后”按钮仅关闭对话框） */
+  onInstallLater?: () => void;
   onDismiss: () => void;
 }
 
@@ -26,6 +28,7 @@ const BusinessUpdateDialog: React.FC<BusinessUpdateDialogProps> = ({
   error,
   onDownload,
   onInstall,
+  onInstallLater,
   onDismiss,
 }) => {
   const formatFileSize = (bytes: number): string => {
@@ -149,8 +152,8 @@ const BusinessUpdateDialog: React.FC<BusinessUpdateDialogProps> = ({
           </div>
 
           <div className="flex justify-end gap-3 pt-4 border-t border-gray-200 dark:border-gray-700">
-            <BaseButton variant="outline" onClick={onClose}>
-              稍后重启
+            <BaseButton variant="outline" onClick={onInstallLater ?? onClose}>
+              {onInstallLater ? '退出时安装' : '稍后重启'}
             </BaseButton>
             <BaseButton variant="default" onClick={onInstall} leftIcon={<RotateCw className="w-4 h-4" />}>
               立即重启
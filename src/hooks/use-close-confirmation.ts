@@ -0,0 +1,31 @@
+import {useEffect} from "react";
+import {listen} from "@tauri-apps/api/event";
+import {invoke} from "@tauri-apps/api/core";
+import {Modal} from "antd";
+import {logger} from "../lib/logger.ts";
+
+/**
+ * 退出确认 Hook
+ * 账户操作进行中时后端会拦截退出并发出确认请求，由用户决定是否仍然退出
+ */
+export function useCloseConfirmation() {
+  useEffect(() => {
+    const unlisten = listen("close-confirmation-requested", (event) => {
+      const { message } = event.payload as { message: string };
+      logger.info("收到退出确认请求");
+
+      Modal.confirm({
+        title: "确认退出？",
+        content: message,
+        okText: "仍然退出",
+        cancelText: "取消",
+        okButtonProps: { danger: true },
+        onOk: () => invoke("confirm_exit"),
+      });
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+}
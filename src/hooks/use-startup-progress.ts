@@ -0,0 +1,52 @@
+import {useEffect, useState} from "react";
+import {listen} from "@tauri-apps/api/event";
+import {invoke} from "@tauri-apps/api/core";
+import {logger} from "../lib/logger.ts";
+
+export type StartupStep = 'settings_loaded' | 'tray_initialized' | 'paths_detected' | 'monitor_started';
+
+export interface StartupProgress {
+  step: StartupStep;
+  status: 'completed' | 'skipped' | 'failed';
+  message: string | null;
+  finished: number;
+  total: number;
+}
+
+export const STARTUP_STEP_LABELS: Record<StartupStep, string> = {
+  settings_loaded: '加载设置',
+  tray_initialized: '初始化系统托盘',
+  paths_detected: '检测 Antigravity 路径',
+  monitor_started: '启动数据库监控',
+};
+
+/**
+ * 启动进度 Hook
+ * 先补取页面加载前已完成的步骤，再监听后续的 startup-progress 事件
+ */
+export function useStartupProgress() {
+  const [steps, setSteps] = useState<StartupProgress[]>([]);
+
+  useEffect(() => {
+    const upsert = (progress: StartupProgress) => {
+      setSteps((prev) => [...prev.filter((s) => s.step !== progress.step), progress]);
+      if (progress.status === 'failed') {
+        logger.error("启动步骤失败", { step: progress.step, message: progress.message });
+      }
+    };
+
+    invoke<StartupProgress[]>("get_startup_progress")
+      .then((snapshot) => snapshot.forEach(upsert))
+      .catch((error) => logger.error("获取启动进度失败", error));
+
+    const unlisten = listen<StartupProgress>("startup-progress", (event) => upsert(event.payload));
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  const failedSteps = steps.filter((s) => s.status === 'failed');
+
+  return { steps, failedSteps };
+}
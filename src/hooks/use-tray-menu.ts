@@ -10,7 +10,7 @@ import toast from "react-hot-toast";
  * 负责监听账户变化并更新托盘菜单
  */
 export function useTrayMenu() {
-  const { accounts, switchToAccount } = useAntigravityAccount();
+  const { accounts } = useAntigravityAccount();
 
   // 更新托盘菜单
   const updateTrayMenu = async (accounts: string[]) => {
@@ -26,18 +26,21 @@ export function useTrayMenu() {
     }
   };
 
-  // 监听来自后端的账户切换请求
+  // 监听托盘快速切换的结果（切换流程由后端直接执行）
   useEffect(() => {
-    const unlisten = listen("tray-switch-account", async (event) => {
-      const email = event.payload as string;
-      logger.info("收到托盘账户切换请求", { email });
-
-      try {
-        await switchToAccount(email);
+    const unlisten = listen("tray-switch-account-result", async (event) => {
+      const { email, success, message } = event.payload as {
+        email: string;
+        success: boolean;
+        message: string;
+      };
+      logger.info("收到托盘账户切换结果", { email, success });
+
+      if (success) {
         toast.success(`已切换到账户: ${email}`);
-      } catch (error) {
-        logger.error("托盘账户切换失败", error);
-        toast.error(`切换账户失败: ${error}`);
+      } else {
+        logger.error("托盘账户切换失败", message);
+        toast.error(`切换账户失败: ${message}`);
       }
     });
 
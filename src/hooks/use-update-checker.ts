@@ -10,6 +10,7 @@ export interface UseUpdateCheckerResult {
     checkForUpdates: () => Promise<void>;
     startDownload: () => Promise<void>;
     installAndRelaunch: () => Promise<void>;
+    installOnExit: () => Promise<void>;
     dismissUpdate: () => void;
 }
 
@@ -108,6 +109,25 @@ export function useUpdateChecker(autoCheck: boolean = true): UseUpdateCheckerRes
         }
     }, []);
 
+    /**
+     * 推迟到应用退出时安装
+     */
+    const installOnExit = useCallback(async () => {
+        try {
+            setError(null);
+            await updateService.installOnExit();
+        } catch (err) {
+            const errorMsg = err instanceof Error ? err.message : String(err);
+            logger.error('推迟安装更新失败', {
+            module: 'UpdateChecker',
+            action: 'defer_install_failed',
+            error: errorMsg
+          });
+            setError(errorMsg);
+            throw err;
+        }
+    }, []);
+
     /**
      * 忽略此次更新
      */
@@ -141,6 +161,7 @@ export function useUpdateChecker(autoCheck: boolean = true): UseUpdateCheckerRes
         checkForUpdates,
         startDownload,
         installAndRelaunch,
+        installOnExit,
         dismissUpdate,
     };
 }
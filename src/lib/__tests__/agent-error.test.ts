@@ -0,0 +1,30 @@
+import { describe, it, expect } from "vitest";
+import { getErrorCode, getErrorMessage, isAgentError } from "../agent-error.ts";
+
+describe("agent-error", () => {
+  const backupMissing = {
+    code: "BACKUP_NOT_FOUND",
+    message_id: "error.backup_not_found",
+    message: "账户备份不存在: a@b.com",
+    context: { account: "a@b.com" },
+  };
+
+  it("should recognize structured backend errors", () => {
+    expect(isAgentError(backupMissing)).toBe(true);
+    expect(isAgentError("未找到 Antigravity 安装位置")).toBe(false);
+    expect(isAgentError(null)).toBe(false);
+    expect(isAgentError({ message: "no code" })).toBe(false);
+  });
+
+  it("should return the code of structured errors and INTERNAL otherwise", () => {
+    expect(getErrorCode(backupMissing)).toBe("BACKUP_NOT_FOUND");
+    expect(getErrorCode("plain string")).toBe("INTERNAL");
+    expect(getErrorCode(new Error("boom"))).toBe("INTERNAL");
+  });
+
+  it("should extract a displayable message from any error shape", () => {
+    expect(getErrorMessage(backupMissing)).toBe("账户备份不存在: a@b.com");
+    expect(getErrorMessage(new Error("boom"))).toBe("boom");
+    expect(getErrorMessage("plain string")).toBe("plain string");
+  });
+});
@@ -0,0 +1,70 @@
+/**
+ * 后端命令错误。
+ *
+ * 已迁移到 `AgentError` 的命令以 `{ code, message_id, message, context }` 形式返回错误，
+ * 其余命令仍返回纯字符串；这里的工具函数统一处理两种形式。
+ */
+
+/** 稳定的错误码（与后端 `AgentError::code` 保持一致） */
+export type AgentErrorCode =
+  | "ANTIGRAVITY_NOT_FOUND"
+  | "DATABASE_NOT_FOUND"
+  | "DATABASE_ERROR"
+  | "STATE_MISSING"
+  | "DECODE_FAILED"
+  | "BACKUP_NOT_FOUND"
+  | "IO_ERROR"
+  | "PROCESS_ERROR"
+  | "AUTHENTICATION_FAILED"
+  | "SAFE_MODE"
+  | "BUSY"
+  | "RATE_LIMITED"
+  | "APP_LOCKED"
+  | "CANCELLED"
+  | "INTERNAL";
+
+export interface AgentError {
+  /** 错误码 */
+  code: AgentErrorCode;
+
+  /** 消息目录中的 ID（可用 `get_message_catalog` 自行翻译） */
+  message_id: string;
+
+  /** 按后端当前语言解析的描述 */
+  message: string;
+
+  /** 结构化上下文（路径、账户名等） */
+  context: Record<string, unknown>;
+}
+
+/**
+ * 判断是否为后端返回的结构化错误
+ */
+export function isAgentError(error: unknown): error is AgentError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    typeof (error as AgentError).code === "string" &&
+    typeof (error as AgentError).message === "string"
+  );
+}
+
+/**
+ * 获取错误码；字符串错误与普通异常返回 `INTERNAL`
+ */
+export function getErrorCode(error: unknown): AgentErrorCode {
+  return isAgentError(error) ? error.code : "INTERNAL";
+}
+
+/**
+ * 获取可展示的错误信息
+ */
+export function getErrorMessage(error: unknown): string {
+  if (isAgentError(error)) {
+    return error.message;
+  }
+  if (error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
@@ -1,8 +1,12 @@
 import React from 'react';
 import ReactDOM from 'react-dom/client';
 import App from './App';
+import QuickSwitcher from './components/business/QuickSwitcher';
 import './index.css';
 
+// 快速切换窗口与主窗口共用同一入口，通过 hash 区分
+const isQuickSwitcher = window.location.hash === '#/switcher';
+
 ReactDOM.createRoot(document.getElementById('app')).render(
-    <App />
+    isQuickSwitcher ? <QuickSwitcher /> : <App />
 );
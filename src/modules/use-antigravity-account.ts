@@ -1,5 +1,6 @@
 import {create} from 'zustand';
 import {logger} from '../lib/logger.ts';
+import {getErrorCode, getErrorMessage} from '../lib/agent-error.ts';
 import {AccountCommands} from '@/commands/AccountCommands.ts';
 import type {AntigravityAccount} from '@/commands/types/account.types.ts';
 import {AccountManageCommands} from "@/commands/AccountManageCommands.ts";
@@ -45,7 +46,8 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
       logger.error('用户删除失败', {
         module: 'UserManagement',
         email,
-        error: error instanceof Error ? error.message : String(error)
+        code: getErrorCode(error),
+        error: getErrorMessage(error)
       });
       throw error;
     }
@@ -75,7 +77,8 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
     } catch (error) {
       logger.error('备份当前用户失败', {
         module: 'UserManagement',
-        error: error instanceof Error ? error.message : String(error)
+        code: getErrorCode(error),
+        error: getErrorMessage(error)
       });
       throw error;
     }
@@ -89,7 +92,8 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
       logger.error('切换用户失败', {
         module: 'UserManagement',
         email,
-        error: error instanceof Error ? error.message : String(error)
+        code: getErrorCode(error),
+        error: getErrorMessage(error)
       });
       throw error;
     }
@@ -117,7 +121,8 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
     } catch (error) {
       logger.error('获取用户列表失败', {
         module: 'UserManagement',
-        error: error instanceof Error ? error.message : String(error)
+        code: getErrorCode(error),
+        error: getErrorMessage(error)
       });
       // 如果读取失败，返回当前 store 中的用户
       return get().accounts;
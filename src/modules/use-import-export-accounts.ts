@@ -5,13 +5,18 @@
 
 import {create} from 'zustand';
 import {open, save} from '@tauri-apps/plugin-dialog';
-import {readTextFile} from '@tauri-apps/plugin-fs';
 import {logger} from '@/lib/logger.ts';
 import toast from 'react-hot-toast';
 import {AccountManageCommands} from "@/commands/AccountManageCommands.ts";
-import {BackupData} from "@/commands/types/account-manage.types.ts";
+import {BackupData, RestoreResult} from "@/commands/types/account-manage.types.ts";
 import {LoggingCommands} from "@/commands/LoggingCommands.ts";
 
+/** 与后端 archive::UNSIGNED_ERROR 保持一致 */
+const UNSIGNED_ARCHIVE_ERROR = '配置文件未签名';
+
+/** 与后端 archive::UNTRUSTED_SIGNER_ERROR 保持一致 */
+const UNTRUSTED_SIGNER_ERROR = '配置文件由未受信任的设备签名';
+
 interface EncryptedConfigData {
   version: string;
   backupCount: number;
@@ -105,26 +110,37 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
         try {
           get().closeImportDialog();
           set({ isImporting: true });
-          toast.loading('正在使用 Rust 解密文件...', {duration: 1});
-
-          // 读取文件并解密
-          const encryptedFile = await readTextFile(pendingImportPath);
-          const decryptedJson: string = await AccountManageCommands.decryptConfig(encryptedFile, password);
-          const configData: EncryptedConfigData = JSON.parse(decryptedJson);
-
-          // 验证配置数据格式
-          if (!configData.version || !configData.backups || !Array.isArray(configData.backups)) {
-            throw new Error('配置文件格式无效');
+          toast.loading('正在解密并校验配置文件...', {duration: 1});
+
+          // 后端解密并校验签名与内容，全部通过后才写入
+          let result: RestoreResult | undefined;
+          let allowUnsigned = false;
+          let trustSigner = false;
+          while (!result) {
+            try {
+              result = await AccountManageCommands.importConfigArchive(pendingImportPath, password, allowUnsigned, trustSigner);
+            } catch (error) {
+              const message = String(error);
+              if (!allowUnsigned && message.includes(UNSIGNED_ARCHIVE_ERROR)) {
+                // 旧版本导出的文件没有签名，由用户确认是否信任
+                if (!window.confirm('该配置文件由旧版本导出，没有签名，无法验证来源。确定仍要导入吗？')) {
+                  toast.error('已取消导入未签名的配置文件');
+                  return;
+                }
+                allowUnsigned = true;
+              } else if (!trustSigner && message.includes(UNTRUSTED_SIGNER_ERROR)) {
+                // 其他设备导出的文件，由用户确认是否信任该设备（确认后加入信任列表）
+                if (!window.confirm(`${message}。请确认该文件来自你信任的设备，信任后以后由该设备导出的文件将直接导入。确定信任并导入吗？`)) {
+                  toast.error('已取消导入未受信任设备签名的配置文件');
+                  return;
+                }
+                trustSigner = true;
+              } else {
+                throw error;
+              }
+            }
           }
 
-          logger.info('开始恢复备份数据', {
-            module: 'useImportExportAccount',
-            backupCount: configData.backups.length
-          });
-          toast.loading('正在恢复账户数据...', {duration: 1});
-
-          const result = await AccountManageCommands.restoreBackupFiles(configData.backups);
-
           if (result.failed.length > 0) {
             logger.warn('部分文件恢复失败', {
               module: 'useImportExportAccount',
@@ -167,7 +183,7 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
 
           // 构建配置数据
           const configData: EncryptedConfigData = {
-            version: '1.1.0',
+            version: '1.2.0',
             backupCount: pendingExportData.length,
             backups: pendingExportData
           };
@@ -285,7 +301,7 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
           toast.loading('正在收集账户数据...', {duration: 1});
 
           // ✅ 获取包含完整内容的备份数据
-          const accountContents = await AccountManageCommands.collectAccountContents();
+          const {items: accountContents} = await AccountManageCommands.collectAccountContents();
 
           if (accountContents.length === 0) {
             logger.warn('没有找到账户信息', {
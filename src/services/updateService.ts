@@ -1,5 +1,6 @@
-import { check, Update, DownloadEvent } from '@tauri-apps/plugin-updater';
-import { relaunch } from '@tauri-apps/plugin-process';
+import { listen } from '@tauri-apps/api/event';
+import { UpdateCommands } from '../commands/UpdateCommands.ts';
+import type { UpdateDownloadProgressPayload } from '../commands/types/update.types.ts';
 import { logger } from '../lib/logger.ts';
 
 export interface UpdateInfo {
@@ -7,6 +8,8 @@ export interface UpdateInfo {
     currentVersion: string;
     date: string;
     body: string;
+    /** 安装包大小（字节），未知时为 null */
+    size?: number | null;
 }
 
 export type UpdateState =
@@ -23,14 +26,14 @@ export interface DownloadProgress {
 }
 
 class UpdateService {
-    private pendingUpdate: Update | null = null;
+    private hasPendingUpdate = false;
 
     /**
-     * 检查是否有可用更新
+     * 检查是否有可用更新（按设置中的更新通道）
      */
     async checkForUpdates(): Promise<UpdateInfo | null> {
         try {
-            const update = await check();
+            const update = await UpdateCommands.checkForUpdates();
 
             if (update === null) {
                 logger.info('没有可用更新', {
@@ -40,13 +43,14 @@ class UpdateService {
                 return null;
             }
 
-            this.pendingUpdate = update;
+            this.hasPendingUpdate = true;
 
             return {
                 version: update.version,
-                currentVersion: update.currentVersion,
-                date: update.date,
-                body: update.body || '',
+                currentVersion: update.current_version,
+                date: update.date ?? '',
+                body: update.notes ?? '',
+                size: update.size,
             };
         } catch (error) {
             logger.error('检查更新失败', {
@@ -65,49 +69,31 @@ class UpdateService {
     async downloadUpdate(
         onProgress: (progress: DownloadProgress) => void
     ): Promise<void> {
-        if (!this.pendingUpdate) {
+        if (!this.hasPendingUpdate) {
             throw new Error('没有待下载的更新');
         }
 
-        let downloaded = 0;
-        let total = 0;
+        const unlisten = await listen<UpdateDownloadProgressPayload>('update-download-progress', (event) => {
+            const { downloaded, total, finished } = event.payload;
+            const knownTotal = total ?? 0;
+            const percentage = finished ? 100 : knownTotal > 0 ? Math.round((downloaded / knownTotal) * 100) : 0;
+            onProgress({ downloaded, total: knownTotal, percentage });
+        });
 
         try {
-            await this.pendingUpdate.download((event: DownloadEvent) => {
-                switch (event.event) {
-                    case 'Started':
-                        total = event.data.contentLength || 0;
-                        logger.info('开始下载', {
-                        module: 'UpdateService',
-                        action: 'download_started',
-                        totalBytes: total
-                      });
-                        onProgress({ downloaded: 0, total, percentage: 0 });
-                        break;
-
-                    case 'Progress':
-                        downloaded += event.data.chunkLength;
-                        const percentage = total > 0 ? Math.round((downloaded / total) * 100) : 0;
-                        logger.debug('下载进度', {
-                        module: 'UpdateService',
-                        action: 'download_progress',
-                        downloaded,
-                        total,
-                        percentage
-                      });
-                        onProgress({ downloaded, total, percentage });
-                        break;
-
-                    case 'Finished':
-                        logger.info('下载完成', {
-                        module: 'UpdateService',
-                        action: 'download_completed',
-                        totalBytes: total
-                      });
-                        onProgress({ downloaded: total, total, percentage: 100 });
-                        break;
-                }
-            });
+            logger.info('开始下载', {
+                module: 'UpdateService',
+                action: 'download_started'
+              });
+            onProgress({ downloaded: 0, total: 0, percentage: 0 });
+
+            const installOnExit = await UpdateCommands.downloadUpdate();
+
+            logger.info('下载完成', {
+                module: 'UpdateService',
+                action: 'download_completed',
+                installOnExit
+              });
         } catch (error) {
             logger.error('下载更新失败', {
                 module: 'UpdateService',
@@ -115,6 +101,8 @@ class UpdateService {
                 error: error
               });
             throw new Error(`下载更新失败: ${error}`);
+        } finally {
+            unlisten();
         }
     }
 
@@ -122,7 +110,7 @@ class UpdateService {
      * 安装更新并重启应用
      */
     async installAndRelaunch(): Promise<void> {
-        if (!this.pendingUpdate) {
+        if (!this.hasPendingUpdate) {
             throw new Error('没有待安装的更新');
         }
 
@@ -131,17 +119,8 @@ class UpdateService {
                 module: 'UpdateService',
                 action: 'install_started'
               });
-            await this.pendingUpdate.install();
-
-            logger.info('安装完成，准备重启', {
-                module: 'UpdateService',
-                action: 'install_completed'
-              });
-            // 等待一小段时间确保安装完成
-            await new Promise(resolve => setTimeout(resolve, 500));
-
-            // 重启应用
-            await relaunch();
+            // 后端安装完成后直接重启应用
+            await UpdateCommands.installUpdate();
         } catch (error) {
             logger.error('安装更新失败', {
                 module: 'UpdateService',
@@ -152,11 +131,22 @@ class UpdateService {
         }
     }
 
+    /**
+     * 推迟安装，应用下次退出时再安装已下载的更新
+     */
+    async installOnExit(): Promise<void> {
+        await UpdateCommands.deferUpdateInstall();
+        logger.info('已推迟到退出时安装更新', {
+            module: 'UpdateService',
+            action: 'install_deferred'
+          });
+    }
+
     /**
      * 清除待处理的更新
      */
     clearPendingUpdate(): void {
-        this.pendingUpdate = null;
+        this.hasPendingUpdate = false;
     }
 }
 